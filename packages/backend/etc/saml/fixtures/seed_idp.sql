@@ -0,0 +1,34 @@
+-- Registers the local IdP fixture, responses in this folder are signed with its key
+INSERT INTO saml_identity_providers (
+    id, slug, name, entity_id, sso_url, x509_cert,
+    email_attribute, name_attribute, allow_idp_initiated, is_active
+) VALUES (
+    'b0f1c2d3-0000-4000-8000-5a4d1e000001',
+    'local-idp',
+    'Local IdP fixture',
+    'http://localhost:8081/idp',
+    'http://localhost:8081/idp/sso',
+    '-----BEGIN CERTIFICATE-----
+MIIDGzCCAgOgAwIBAgIUChDAO79+7DrKVpatKk6a+GY4kwUwDQYJKoZIhvcNAQEL
+BQAwHDEaMBgGA1UEAwwRZ2V0bm9yZS1sb2NhbC1pZHAwIBcNMjYxMDE4MTgzMzI3
+WhgPMjEyNjA5MjQxODMzMjdaMBwxGjAYBgNVBAMMEWdldG5vcmUtbG9jYWwtaWRw
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAr1Ek0IctXTvWKjms3lj4
+G/oDZc7S/C6d4vdFseN9SQtMvpKrdKpD5Gwi7VnLeF2LaNu1rIhGrUgdJ6Adtlqu
+dMFpIF03mJ4ssrSKEvGtKJ6AbGwhA+n6m4Kwayr3/p7k98P9sGZqpWzUQ3jxsG5O
+CQkUK63I9j4uX+kA97MsdMiMHn2GS17hCDFwws2Lq6WKXqR8qM5sYVjArRxQfGVf
+gLl8P+m8s5xtRUt/HTbQOv52Hbm3vuZ5Y9yvmnI9HTZvVRLlRriFDPXD5ZMYY5qa
+ty/ckL4qh4cWU/7IxZvqJVZjrj8zvUk4bgFFjRAaPT1Rh0ZNaCUgrxI9ud9jC6p0
+MQIDAQABo1MwUTAdBgNVHQ4EFgQUzQOV1lkiXZOKspMoMq9CikZ3pv8wHwYDVR0j
+BBgwFoAUzQOV1lkiXZOKspMoMq9CikZ3pv8wDwYDVR0TAQH/BAUwAwEB/zANBgkq
+hkiG9w0BAQsFAAOCAQEAPF6Vh/GxL9N6Ajvsir7FOoVVm1aIjxEikwLyxxcBHrIz
+erIOyYddukvzHG6ltDqJ70rqudfDkTEmnn6bULBD/3+P4rMu8fzbPJ1tbAphE17G
+SDrRvoVyLvB9vA5yH/fltGeO2yYPzI5BmgDn7774TCnNwzw0fmWxn9YZ5k/zXAm9
+lAQrRI87f4+S4sQEiqDhk3yWGA7WINriTTVu/EtrmZxnCmzRC5p1tno/MksqHiIe
+Yf2GP2mhptz2ur5+rEcXsVfo5oc+GuEpwLcrfDILmRvzU79YrlbsxtSkxRUr9ARF
+6NFgEDSy4/iWgk94HhsR9TayAvW9zhmSDgUXpuKNhQ==
+-----END CERTIFICATE-----',
+    'email',
+    'displayName',
+    TRUE,
+    TRUE
+) ON CONFLICT (slug) DO NOTHING;
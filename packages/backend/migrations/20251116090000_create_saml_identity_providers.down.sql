@@ -0,0 +1,9 @@
+-- Add down migration script here
+DELETE FROM user_oauth_providers WHERE provider = 'saml';
+
+ALTER TABLE user_oauth_providers DROP CONSTRAINT IF EXISTS user_oauth_providers_provider_check;
+ALTER TABLE user_oauth_providers
+    ADD CONSTRAINT user_oauth_providers_provider_check
+    CHECK (provider IN ('google', 'discord', 'email'));
+
+DROP TABLE IF EXISTS saml_identity_providers;
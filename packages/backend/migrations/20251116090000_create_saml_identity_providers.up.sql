@@ -0,0 +1,21 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS saml_identity_providers (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    slug VARCHAR(255) UNIQUE NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    entity_id VARCHAR(255) UNIQUE NOT NULL,
+    sso_url TEXT NOT NULL,
+    x509_cert TEXT NOT NULL,
+    email_attribute VARCHAR(255),  -- falls back to the NameID when NULL
+    name_attribute VARCHAR(255),
+    allow_idp_initiated BOOLEAN NOT NULL DEFAULT FALSE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMPTZ
+);
+
+ALTER TABLE user_oauth_providers DROP CONSTRAINT IF EXISTS user_oauth_providers_provider_check;
+ALTER TABLE user_oauth_providers
+    ADD CONSTRAINT user_oauth_providers_provider_check
+    CHECK (provider IN ('google', 'discord', 'email', 'saml'));
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE user_sessions DROP COLUMN IF EXISTS authenticated_at;
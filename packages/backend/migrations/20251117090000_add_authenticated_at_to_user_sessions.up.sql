@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE user_sessions
+    ADD COLUMN authenticated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS email_change_requests (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    user_id VARCHAR(255) NOT NULL,
+    old_email VARCHAR(255) NOT NULL,
+    new_email VARCHAR(255) NOT NULL,
+    confirm_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    revert_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'confirmed', 'cancelled', 'reverted')),
+    expires_at TIMESTAMPTZ NOT NULL,         -- confirmation link
+    revert_expires_at TIMESTAMPTZ NOT NULL,  -- revert link sent to the old address
+    confirmed_at TIMESTAMPTZ,
+    reverted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- at most one pending change per user
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_change_requests_pending_user
+    ON email_change_requests(user_id) WHERE status = 'pending';
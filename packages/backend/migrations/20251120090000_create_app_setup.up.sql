@@ -0,0 +1,18 @@
+-- Add up migration script here
+-- single row table, the row exists once the first super admin has been created
+CREATE TABLE IF NOT EXISTS app_setup (
+    id SMALLINT PRIMARY KEY NOT NULL DEFAULT 1 CHECK (id = 1),
+    super_admin_id VARCHAR(255),
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (super_admin_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- instances that were already seeded with the old super key are considered set up
+INSERT INTO app_setup (id, super_admin_id, completed_at)
+SELECT 1, ur.user_id, ur.created_at
+FROM user_roles ur
+JOIN roles r ON r.id = ur.role_id
+WHERE r.name = 'IMMORTAL_USER'
+ORDER BY ur.created_at ASC
+LIMIT 1
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DELETE FROM casbin_rule WHERE ptype = 'p' AND v3 = 'deny';
+UPDATE casbin_rule SET v3 = '' WHERE ptype = 'p' AND v3 = 'allow';
@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- the model now carries an effect column, existing policies were all grants
+UPDATE casbin_rule SET v3 = 'allow' WHERE ptype = 'p' AND (v3 IS NULL OR v3 = '');
@@ -0,0 +1,15 @@
+-- Add down migration script here
+DELETE FROM casbin_rule WHERE ptype = 'p' AND v1 <> '*';
+UPDATE casbin_rule SET v1 = v2, v2 = v3, v3 = v4, v4 = '' WHERE ptype = 'p';
+DELETE FROM casbin_rule WHERE ptype = 'g' AND v2 <> '*';
+UPDATE casbin_rule SET v2 = '' WHERE ptype = 'g';
+
+DELETE FROM user_roles WHERE domain <> '*';
+ALTER TABLE user_roles DROP CONSTRAINT IF EXISTS user_roles_pkey;
+ALTER TABLE user_roles ADD PRIMARY KEY (user_id, role_id);
+ALTER TABLE user_roles DROP COLUMN IF EXISTS domain;
+
+DELETE FROM roles WHERE domain <> '*';
+ALTER TABLE roles DROP CONSTRAINT IF EXISTS roles_name_domain_key;
+ALTER TABLE roles ADD CONSTRAINT roles_name_key UNIQUE (name);
+ALTER TABLE roles DROP COLUMN IF EXISTS domain;
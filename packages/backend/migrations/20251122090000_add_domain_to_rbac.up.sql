@@ -0,0 +1,13 @@
+-- Add up migration script here
+-- '*' is the global domain, roles and assignments there apply in every organization
+ALTER TABLE roles ADD COLUMN IF NOT EXISTS domain VARCHAR(255) NOT NULL DEFAULT '*';
+ALTER TABLE roles DROP CONSTRAINT IF EXISTS roles_name_key;
+ALTER TABLE roles ADD CONSTRAINT roles_name_domain_key UNIQUE (name, domain);
+
+ALTER TABLE user_roles ADD COLUMN IF NOT EXISTS domain VARCHAR(255) NOT NULL DEFAULT '*';
+ALTER TABLE user_roles DROP CONSTRAINT IF EXISTS user_roles_pkey;
+ALTER TABLE user_roles ADD PRIMARY KEY (user_id, role_id, domain);
+
+-- policies move from (sub, obj, act, eft) to (sub, dom, obj, act, eft)
+UPDATE casbin_rule SET v4 = v3, v3 = v2, v2 = v1, v1 = '*' WHERE ptype = 'p';
+UPDATE casbin_rule SET v2 = '*' WHERE ptype = 'g' AND (v2 IS NULL OR v2 = '');
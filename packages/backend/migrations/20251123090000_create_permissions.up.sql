@@ -0,0 +1,41 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS permissions (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    name VARCHAR(128) NOT NULL,       -- resource part of `resource:action`
+    action VARCHAR(128) NOT NULL,
+    description TEXT,
+    group_name VARCHAR(255) NOT NULL DEFAULT 'General',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS permissions_name_action_key
+    ON permissions (name, action) WHERE deleted_at IS NULL;
+
+-- previously listed in etc/permissions.json or checked by handlers
+INSERT INTO permissions (id, name, action, description, group_name) VALUES
+    (gen_random_uuid()::text, 'user-management', 'read', 'View users and invites', 'Administration'),
+    (gen_random_uuid()::text, 'user-management', 'write', 'Manage users and invites', 'Administration'),
+    (gen_random_uuid()::text, 'role-management', 'read', 'View roles and their permissions', 'Administration'),
+    (gen_random_uuid()::text, 'role-management', 'write', 'Create, update and delete roles', 'Administration'),
+    (gen_random_uuid()::text, 'permission-management', 'read', 'View the permission registry', 'Administration'),
+    (gen_random_uuid()::text, 'permission-management', 'write', 'Manage the permission registry', 'Administration'),
+    (gen_random_uuid()::text, 'saml-management', 'read', 'View SAML identity providers', 'Single sign-on'),
+    (gen_random_uuid()::text, 'saml-management', 'write', 'Manage SAML identity providers', 'Single sign-on'),
+    (gen_random_uuid()::text, 'super-admin-management', 'write', 'Grant the super admin role', 'Administration'),
+    (gen_random_uuid()::text, 'projects', 'read', 'View projects', 'Projects'),
+    (gen_random_uuid()::text, 'projects', 'write', 'Create and update projects', 'Projects'),
+    (gen_random_uuid()::text, 'projects', 'delete', 'Delete projects', 'Projects')
+ON CONFLICT DO NOTHING;
+
+-- keep permissions already granted to roles valid
+INSERT INTO permissions (id, name, action)
+SELECT gen_random_uuid()::text, policy.v2, policy.v3
+FROM (
+    SELECT DISTINCT v2, v3 FROM casbin_rule
+    WHERE ptype = 'p' AND v2 IS NOT NULL AND v3 IS NOT NULL AND v2 <> '*' AND v3 <> '*'
+) AS policy
+WHERE NOT EXISTS (
+    SELECT 1 FROM permissions WHERE name = policy.v2 AND action = policy.v3
+);
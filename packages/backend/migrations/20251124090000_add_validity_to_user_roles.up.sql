@@ -0,0 +1,21 @@
+-- Add up migration script here
+ALTER TABLE user_roles ADD COLUMN starts_at TIMESTAMPTZ;
+ALTER TABLE user_roles ADD COLUMN expires_at TIMESTAMPTZ;
+ALTER TABLE user_roles
+    ADD CONSTRAINT user_roles_validity_check
+    CHECK (starts_at IS NULL OR expires_at IS NULL OR expires_at > starts_at);
+
+CREATE INDEX IF NOT EXISTS idx_user_roles_expires_at ON user_roles (expires_at) WHERE expires_at IS NOT NULL;
+
+-- grants removed by the expiry sweeper
+CREATE TABLE IF NOT EXISTS user_role_expirations (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    user_id VARCHAR(255) NOT NULL,
+    role_id VARCHAR(255) NOT NULL,
+    domain VARCHAR(255) NOT NULL,
+    starts_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ NOT NULL,
+    expired_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_role_expirations_user_id ON user_role_expirations (user_id);
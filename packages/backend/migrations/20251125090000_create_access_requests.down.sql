@@ -0,0 +1,6 @@
+-- Add down migration script here
+DELETE FROM permissions WHERE name = 'access-requests' AND action = 'approve';
+
+DROP TABLE IF EXISTS access_request_revocations;
+DROP TABLE IF EXISTS access_request_decisions;
+DROP TABLE IF EXISTS access_requests;
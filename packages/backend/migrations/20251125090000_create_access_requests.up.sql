@@ -0,0 +1,47 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS access_requests (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    requester_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    domain VARCHAR(255) NOT NULL,
+    role_id VARCHAR(255) REFERENCES roles(id) ON DELETE CASCADE,
+    permission VARCHAR(255),              -- `resource:action`
+    justification TEXT NOT NULL,
+    duration_secs BIGINT NOT NULL CHECK (duration_secs > 0),
+    status VARCHAR(32) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'denied', 'cancelled', 'revoked', 'expired')),
+    granted_role_id VARCHAR(255),         -- the requested role, or the role created for a permission
+    starts_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((role_id IS NULL) <> (permission IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_requests_requester_id ON access_requests (requester_id);
+CREATE INDEX IF NOT EXISTS idx_access_requests_domain_status ON access_requests (domain, status);
+CREATE INDEX IF NOT EXISTS idx_access_requests_expires_at ON access_requests (expires_at) WHERE status = 'approved';
+
+CREATE TABLE IF NOT EXISTS access_request_decisions (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    request_id VARCHAR(255) NOT NULL REFERENCES access_requests(id) ON DELETE CASCADE,
+    approver_id VARCHAR(255) NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    decision VARCHAR(32) NOT NULL CHECK (decision IN ('approved', 'denied')),
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_request_decisions_request_id ON access_request_decisions (request_id);
+
+CREATE TABLE IF NOT EXISTS access_request_revocations (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    request_id VARCHAR(255) NOT NULL REFERENCES access_requests(id) ON DELETE CASCADE,
+    revoked_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,  -- NULL when the grant expired
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_request_revocations_request_id ON access_request_revocations (request_id);
+
+INSERT INTO permissions (id, name, action, description, group_name) VALUES
+    (gen_random_uuid()::text, 'access-requests', 'approve', 'Review, approve, deny and revoke access requests', 'Administration')
+ON CONFLICT DO NOTHING;
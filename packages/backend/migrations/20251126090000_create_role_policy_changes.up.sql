@@ -0,0 +1,21 @@
+-- Add up migration script here
+-- one row per change to a role's policies, before/after arrays are NULL when the role did not exist
+CREATE TABLE IF NOT EXISTS role_policy_changes (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    role_id VARCHAR(255) NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    role_name VARCHAR(255) NOT NULL,
+    domain VARCHAR(255) NOT NULL,
+    version INTEGER NOT NULL,
+    action VARCHAR(32) NOT NULL
+        CHECK (action IN ('created', 'updated', 'deleted', 'parent_added', 'parent_removed', 'imported', 'rolled_back')),
+    actor_id VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+    before_permissions TEXT[],
+    before_denied_permissions TEXT[],
+    before_parents TEXT[],            -- parent role ids
+    after_permissions TEXT[],
+    after_denied_permissions TEXT[],
+    after_parents TEXT[],
+    rolled_back_to INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (role_id, version)
+);
@@ -0,0 +1,7 @@
+-- Add down migration script here
+ALTER TABLE role_policy_changes DROP COLUMN IF EXISTS after_conditions;
+ALTER TABLE role_policy_changes DROP COLUMN IF EXISTS before_conditions;
+
+-- conditional allows would become unconditional, they are dropped instead
+DELETE FROM casbin_rule WHERE ptype = 'p' AND v4 = 'allow' AND v5 <> 'true';
+UPDATE casbin_rule SET v5 = '' WHERE ptype = 'p';
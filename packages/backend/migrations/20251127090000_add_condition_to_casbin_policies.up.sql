@@ -0,0 +1,12 @@
+-- Add up migration script here
+-- policies gain a condition evaluated against the request context, `true` when unconditional
+UPDATE casbin_rule SET v5 = 'true' WHERE ptype = 'p' AND (v5 IS NULL OR v5 = '');
+
+-- members with the default role only ever reached their own projects, keep that as an explicit
+-- condition, roles granted on purpose keep their allows on every project
+UPDATE casbin_rule SET v5 = 'r.ctx.owner_id == r.ctx.user_id'
+WHERE ptype = 'p' AND v2 = 'projects' AND v4 = 'allow'
+  AND v0 IN (SELECT id FROM roles WHERE is_default);
+
+ALTER TABLE role_policy_changes ADD COLUMN IF NOT EXISTS before_conditions TEXT[];  -- `permission=condition`
+ALTER TABLE role_policy_changes ADD COLUMN IF NOT EXISTS after_conditions TEXT[];
@@ -0,0 +1,6 @@
+-- Add down migration script here
+UPDATE role_policy_changes SET action = 'imported' WHERE action = 'reconciled';
+
+ALTER TABLE role_policy_changes DROP CONSTRAINT IF EXISTS role_policy_changes_action_check;
+ALTER TABLE role_policy_changes ADD CONSTRAINT role_policy_changes_action_check
+    CHECK (action IN ('created', 'updated', 'deleted', 'parent_added', 'parent_removed', 'imported', 'rolled_back'));
@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- changes written when roles are reconciled with the bootstrap file
+ALTER TABLE role_policy_changes DROP CONSTRAINT IF EXISTS role_policy_changes_action_check;
+ALTER TABLE role_policy_changes ADD CONSTRAINT role_policy_changes_action_check
+    CHECK (action IN ('created', 'updated', 'deleted', 'parent_added', 'parent_removed', 'imported', 'rolled_back', 'reconciled'));
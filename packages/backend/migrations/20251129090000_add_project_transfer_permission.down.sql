@@ -0,0 +1,3 @@
+-- Add down migration script here
+DELETE FROM casbin_rule WHERE ptype = 'p' AND v2 = 'projects' AND v3 = 'transfer';
+DELETE FROM permissions WHERE name = 'projects' AND action = 'transfer';
@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- setting the owner of a project is protected on its own, see CreateOrUpdateProject
+INSERT INTO permissions (id, name, action, description, group_name) VALUES
+    (gen_random_uuid()::text, 'projects', 'transfer', 'Hand projects to another owner', 'Projects')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,8 @@
+-- Add down migration script here
+UPDATE role_policy_changes SET action = 'updated' WHERE action = 'template_synced';
+
+ALTER TABLE role_policy_changes DROP CONSTRAINT IF EXISTS role_policy_changes_action_check;
+ALTER TABLE role_policy_changes ADD CONSTRAINT role_policy_changes_action_check
+    CHECK (action IN ('created', 'updated', 'deleted', 'parent_added', 'parent_removed', 'imported', 'rolled_back', 'reconciled'));
+
+DROP TABLE IF EXISTS role_template_links;
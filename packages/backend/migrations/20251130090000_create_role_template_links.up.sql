@@ -0,0 +1,16 @@
+-- Add up migration script here
+-- roles instantiated from a built-in template, `permissions` is what the template granted at the
+-- last sync so a new version only touches those and leaves the role's own changes alone
+CREATE TABLE IF NOT EXISTS role_template_links (
+    role_id VARCHAR(255) PRIMARY KEY NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    template_key VARCHAR(64) NOT NULL,
+    template_version INTEGER NOT NULL,
+    permissions TEXT[] NOT NULL,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_template_links_template_key ON role_template_links (template_key);
+
+ALTER TABLE role_policy_changes DROP CONSTRAINT IF EXISTS role_policy_changes_action_check;
+ALTER TABLE role_policy_changes ADD CONSTRAINT role_policy_changes_action_check
+    CHECK (action IN ('created', 'updated', 'deleted', 'parent_added', 'parent_removed', 'imported', 'rolled_back', 'reconciled', 'template_synced'));
@@ -0,0 +1,15 @@
+-- Add up migration script here
+-- segregation of duties: a user may hold at most one of the roles of a constraint, directly or
+-- through inheritance. Constraints of `*` apply in every domain.
+CREATE TABLE IF NOT EXISTS role_constraints (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    domain VARCHAR(255) NOT NULL DEFAULT '*',
+    role_ids TEXT[] NOT NULL CHECK (cardinality(role_ids) >= 2),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (name, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_constraints_domain ON role_constraints (domain);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DELETE FROM permissions WHERE name = 'access-reviews' AND action = 'manage';
+
+DROP TABLE IF EXISTS access_review_items;
+DROP TABLE IF EXISTS access_reviews;
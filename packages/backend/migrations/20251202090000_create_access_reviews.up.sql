@@ -0,0 +1,45 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS access_reviews (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    domain VARCHAR(255) NOT NULL DEFAULT '*',
+    role_ids TEXT[] NOT NULL CHECK (cardinality(role_ids) >= 1),
+    reviewer_ids TEXT[] NOT NULL CHECK (cardinality(reviewer_ids) >= 1),
+    -- what happens to assignments nobody reviewed by the deadline
+    default_action VARCHAR(32) NOT NULL CHECK (default_action IN ('confirm', 'revoke')),
+    status VARCHAR(32) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'completed')),
+    deadline TIMESTAMPTZ NOT NULL,
+    created_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_reviews_domain_status ON access_reviews (domain, status);
+CREATE INDEX IF NOT EXISTS idx_access_reviews_deadline ON access_reviews (deadline) WHERE status = 'open';
+
+-- the assignments held when the campaign opened, user and role names are kept for the report
+CREATE TABLE IF NOT EXISTS access_review_items (
+    id VARCHAR(255) PRIMARY KEY NOT NULL,
+    review_id VARCHAR(255) NOT NULL REFERENCES access_reviews(id) ON DELETE CASCADE,
+    user_id VARCHAR(255) NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    role_id VARCHAR(255) NOT NULL,
+    role_name VARCHAR(255) NOT NULL,
+    domain VARCHAR(255) NOT NULL,
+    assigned_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ,
+    decision VARCHAR(32) NOT NULL DEFAULT 'pending' CHECK (decision IN ('pending', 'confirmed', 'revoked')),
+    by_default BOOLEAN NOT NULL DEFAULT FALSE,  -- decided by the default action at the deadline
+    decided_by VARCHAR(255) REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    comment TEXT,
+    UNIQUE (review_id, user_id, role_id, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_review_items_review_id ON access_review_items (review_id, decision);
+
+INSERT INTO permissions (id, name, action, description, group_name) VALUES
+    (gen_random_uuid()::text, 'access-reviews', 'manage', 'Open and complete access review campaigns and export their reports', 'Administration')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use validator::Validate;
+
+// exactly one of role_id or permission (`resource:action`) is requested
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CreateAccessRequestRequest {
+    pub role_id: Option<String>,
+
+    pub permission: Option<String>,
+
+    #[validate(length(min = 10, max = 2000, message = "Justification must be 10 to 2000 characters"))]
+    pub justification: String,
+
+    #[validate(range(min = 5, max = 43200, message = "Access can be requested for 5 minutes to 30 days"))]
+    pub duration_minutes: i64,
+}
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Clone, Debug, Default, Deserialize, Validate)]
+pub struct DecideAccessRequestRequest {
+    #[validate(length(max = 2000))]
+    pub comment: Option<String>,
+
+    // approvers may shorten the requested duration, never extend it
+    #[validate(range(min = 5, max = 43200, message = "Access can be granted for 5 minutes to 30 days"))]
+    pub duration_minutes: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Validate)]
+pub struct RevokeAccessRequestRequest {
+    #[validate(length(max = 2000))]
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct AccessRequestListQuery {
+    pub status: Option<String>,
+}
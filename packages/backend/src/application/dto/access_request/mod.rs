@@ -0,0 +1,2 @@
+pub mod create_access_request_request;
+pub mod decide_access_request_request;
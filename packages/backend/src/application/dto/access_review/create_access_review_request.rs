@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CreateAccessReviewRequest {
+    #[validate(length(min = 1, max = 255, message = "Name is required"))]
+    pub name: String,
+
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, message = "At least one role is required"))]
+    pub role_ids: Vec<String>,
+
+    #[validate(length(min = 1, message = "At least one reviewer is required"))]
+    pub reviewer_ids: Vec<String>,
+
+    pub deadline: chrono::DateTime<chrono::Utc>,
+
+    // `confirm` or `revoke`, ACCESS_REVIEW_DEFAULT_ACTION when missing
+    pub default_action: Option<String>,
+}
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::application::dto::policy::policy_export_query::PolicyFormat;
+
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct DecideAccessReviewItemRequest {
+    // `confirm` or `revoke`
+    pub decision: String,
+
+    #[validate(length(max = 2000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct AccessReviewListQuery {
+    pub status: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct AccessReviewExportQuery {
+    #[serde(default)]
+    pub format: PolicyFormat,
+}
@@ -0,0 +1,2 @@
+pub mod create_access_review_request;
+pub mod decide_access_review_item_request;
@@ -8,6 +8,9 @@ pub struct EmailRegisterRequest {
 
     #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
     pub password: String,
+
+    // required while registration is invite-only
+    pub invite_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Validate)]
@@ -2,5 +2,7 @@ pub mod email_request;
 pub mod jwt_claims;
 pub mod oauth2_request;
 pub mod oauth2_response;
+pub mod reauth_request;
+pub mod super_admin_request;
 pub mod user_settings_dto;
 pub mod token_response;
@@ -27,6 +27,8 @@ pub struct GoogleUserResult {
     pub given_name: Option<String>,
     pub family_name: Option<String>,
     pub email_verified: bool,
+    // Google Workspace domain, absent for consumer accounts
+    pub hd: Option<String>,
 }
 
 impl From<&GoogleUserResult> for User {
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+// email users send their password, OAuth users the code of a fresh consent
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReauthRequest {
+    pub password: Option<String>,
+    pub code: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ReauthResponse {
+    pub authenticated_at: chrono::DateTime<chrono::Utc>,
+    pub reauth_expires_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct GrantSuperAdminRequest {
+    #[validate(length(min = 1, message = "User id is required"))]
+    pub user_id: String,
+}
@@ -1,10 +1,14 @@
 use serde::{Serialize, Deserialize};
+use validator::Validate;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserSettingsDto {
     pub fullname: Option<String>,
     pub email: String,
     pub provider: String,
+    // new address waiting for confirmation, the email above stays in use until then
+    pub pending_email: Option<String>,
+    pub pending_email_expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,3 +18,17 @@ pub struct UserSettingsUpdateDto {
     pub current_password: Option<String>,
     pub new_password: Option<String>,
 }
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EmailChangeTokenQuery {
+    pub token: String,
+}
+
+// the owner replaces the password while undoing the change, whoever made it may know the old one
+#[derive(Deserialize, Debug, Clone, Validate)]
+pub struct RevertEmailChangeRequest {
+    pub token: String,
+
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub new_password: String,
+}
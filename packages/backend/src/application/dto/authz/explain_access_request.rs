@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::domain::entities::access_context::ResourceAttributes;
+
+// the subject is either an existing user or a hypothetical set of roles
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ExplainAccessRequest {
+    pub user_id: Option<String>,
+
+    #[validate(length(min = 1, max = 50, message = "Between 1 and 50 roles can be checked"))]
+    pub role_ids: Option<Vec<String>>,
+
+    #[validate(length(min = 1, max = 128, message = "Object is required"))]
+    pub object: String,
+
+    #[validate(length(min = 1, max = 128, message = "Action is required"))]
+    pub action: String,
+
+    // attributes conditional policies are evaluated against, e.g. the owner of a project
+    pub resource: Option<ResourceAttributes>,
+}
+
+// without objects and actions every registered permission is checked
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct AccessMatrixRequest {
+    pub user_id: Option<String>,
+
+    #[validate(length(min = 1, max = 50, message = "Between 1 and 50 roles can be checked"))]
+    pub role_ids: Option<Vec<String>>,
+
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 objects can be checked"))]
+    pub objects: Option<Vec<String>>,
+
+    #[validate(length(min = 1, max = 20, message = "Between 1 and 20 actions can be checked"))]
+    pub actions: Option<Vec<String>>,
+
+    pub resource: Option<ResourceAttributes>,
+}
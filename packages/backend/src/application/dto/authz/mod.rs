@@ -0,0 +1 @@
+pub mod explain_access_request;
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CreateInviteRequest {
+    #[validate(email)]
+    pub email: String,
+
+    #[validate(range(min = 1, max = 30, message = "Invitations are valid for 1 to 30 days"))]
+    pub valid_days: Option<i64>,
+}
@@ -0,0 +1,42 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::domain::entities::permission::Permission;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreatePermissionRequest {
+    #[validate(length(min = 1, max = 128, message = "Resource name is required"))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 128, message = "Action is required"))]
+    pub action: String,
+
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 255, message = "Group name is required"))]
+    pub group_name: Option<String>,
+}
+
+// resource and action are fixed once created, role policies refer to them
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct UpdatePermissionRequest {
+    pub description: Option<String>,
+
+    #[validate(length(min = 1, max = 255, message = "Group name is required"))]
+    pub group_name: Option<String>,
+}
+
+pub const DEFAULT_PERMISSION_GROUP: &str = "General";
+
+impl From<&CreatePermissionRequest> for Permission {
+    fn from(req: &CreatePermissionRequest) -> Self {
+        Permission::new(
+            req.name.trim().to_string(),
+            req.action.trim().to_string(),
+            req.description.clone(),
+            req.group_name
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PERMISSION_GROUP.to_string()),
+        )
+    }
+}
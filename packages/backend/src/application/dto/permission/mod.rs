@@ -0,0 +1 @@
+pub mod create_update_permission_request;
@@ -0,0 +1,2 @@
+pub mod policy_export_query;
+pub mod policy_reconcile_query;
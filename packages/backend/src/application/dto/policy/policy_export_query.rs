@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PolicyExportQuery {
+    #[serde(default)]
+    pub format: PolicyFormat,
+}
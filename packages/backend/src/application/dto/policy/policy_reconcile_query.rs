@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PolicyReconcileQuery {
+    // overrides ROLE_BOOTSTRAP_NEVER_DELETE for this run
+    #[serde(default)]
+    pub never_delete: Option<bool>,
+}
@@ -1,7 +1,10 @@
 use serde::Deserialize;
 use validator::Validate;
 
-use crate::domain::entities::project::Project;
+use crate::domain::entities::{
+    field_access::{ProtectedField, ProtectedFields},
+    project::Project,
+};
 
 #[derive(Debug, Clone, Deserialize, Validate)]
 pub struct CreateOrUpdateProject {
@@ -9,6 +12,21 @@ pub struct CreateOrUpdateProject {
     pub name: String,
 
     pub description: Option<String>,
+
+    // hands the project to another user, only on updates
+    pub user_id: Option<String>,
+}
+
+impl ProtectedFields for CreateOrUpdateProject {
+    const PROTECTED_FIELDS: &'static [ProtectedField] = &[ProtectedField {
+        name: "user_id",
+        permission: "projects:transfer",
+        owner_readable: false,
+    }];
+
+    fn provided_fields(&self) -> Vec<&'static str> {
+        self.user_id.iter().map(|_| "user_id").collect()
+    }
 }
 
 impl From<&CreateOrUpdateProject> for Project {
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CloneRoleRequest {
+    #[validate(length(min = 1, max = 255, message = "Name is required"))]
+    pub name: String,
+}
@@ -1,7 +1,9 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use validator::Validate;
 
-use crate::domain::entities::role::Role;
+use crate::{domain::entities::role::Role, infra::rbac::GLOBAL_DOMAIN};
 
 #[derive(Debug, Clone, Deserialize, Validate)]
 pub struct CreateOrUpdateRole {
@@ -11,6 +13,42 @@ pub struct CreateOrUpdateRole {
     pub is_default: bool,
 
     pub permissions: Option<Vec<String>>,
+
+    // explicit denies win over permissions granted by any other role of the user
+    pub denied_permissions: Option<Vec<String>>,
+
+    // `resource:action` to an expression over `r.ctx`, e.g. `r.ctx.owner_id == r.ctx.user_id`
+    pub conditions: Option<HashMap<String, String>>,
+}
+
+impl CreateOrUpdateRole {
+    pub fn condition(&self, permission: &str) -> Option<&str> {
+        self.conditions
+            .as_ref()
+            .and_then(|conditions| conditions.get(permission))
+            .map(String::as_str)
+    }
+
+    // conditions can only be set on permissions the role allows or denies
+    pub fn unmatched_conditions(&self) -> Vec<String> {
+        let mut unmatched = self
+            .conditions
+            .iter()
+            .flatten()
+            .map(|(permission, _)| permission)
+            .filter(|permission| {
+                !self
+                    .permissions
+                    .iter()
+                    .flatten()
+                    .chain(self.denied_permissions.iter().flatten())
+                    .any(|granted| granted == *permission)
+            })
+            .cloned()
+            .collect::<Vec<String>>();
+        unmatched.sort();
+        unmatched
+    }
 }
 
 impl From<&CreateOrUpdateRole> for Role {
@@ -19,6 +57,7 @@ impl From<&CreateOrUpdateRole> for Role {
             id: uuid::Uuid::new_v4().to_string(),
             name: req.name.clone(),
             is_default: req.is_default,
+            domain: GLOBAL_DOMAIN.to_string(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
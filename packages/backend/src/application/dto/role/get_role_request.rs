@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::Serialize;
 
 use crate::domain::entities::role::Role;
@@ -6,5 +8,24 @@ use crate::domain::entities::role::Role;
 pub struct RoleWithPermission {
     #[serde(flatten)]
     pub role: Role,
+    // granted or denied on the role itself
     pub permissions: Vec<String>,
+    pub denied_permissions: Vec<String>,
+    // permissions that only apply when their condition holds
+    pub conditions: BTreeMap<String, String>,
+    pub parents: Vec<Role>,
+    pub inherited_permissions: Vec<InheritedPermission>,
+    // direct and inherited together, a deny wins over an allow at check time
+    pub effective_permissions: Vec<String>,
+    pub effective_denied_permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InheritedPermission {
+    pub permission: String,
+    pub effect: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    pub role_id: String,
+    pub role_name: String,
 }
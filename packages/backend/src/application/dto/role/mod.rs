@@ -1,2 +1,5 @@
+pub mod clone_role_request;
 pub mod create_update_role_request;
 pub mod get_role_request;
+pub mod role_template_request;
+pub mod role_template_response;
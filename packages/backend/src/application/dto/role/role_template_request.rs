@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct InstantiateRoleTemplateRequest {
+    // the template's name when not given
+    #[validate(length(min = 1, max = 255, message = "Name is required"))]
+    pub name: Option<String>,
+}
@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use crate::domain::entities::role_template::RoleTemplate;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleTemplateWithInstances {
+    #[serde(flatten)]
+    pub template: RoleTemplate,
+    // roles created from the template that the domain can see
+    pub instances: Vec<RoleTemplateInstance>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleTemplateInstance {
+    pub role_id: String,
+    pub role_name: String,
+    pub domain: String,
+    pub template_version: i32,
+    // synced with an earlier version of the template
+    pub outdated: bool,
+}
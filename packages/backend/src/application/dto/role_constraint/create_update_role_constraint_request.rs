@@ -0,0 +1,14 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateOrUpdateRoleConstraint {
+    #[validate(length(min = 1, max = 255, message = "Name is required"))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    // a user may hold at most one of these
+    #[validate(length(min = 2, message = "At least two roles are required"))]
+    pub role_ids: Vec<String>,
+}
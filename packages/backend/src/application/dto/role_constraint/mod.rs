@@ -0,0 +1 @@
+pub mod create_update_role_constraint_request;
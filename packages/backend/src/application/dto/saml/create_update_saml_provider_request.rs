@@ -0,0 +1,54 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::domain::entities::saml_identity_provider::SamlIdentityProvider;
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateOrUpdateSamlProvider {
+    #[validate(length(min = 1, max = 255, message = "Slug is required"))]
+    pub slug: String,
+
+    #[validate(length(min = 1, max = 255, message = "Name is required"))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 255, message = "Entity ID is required"))]
+    pub entity_id: String,
+
+    #[validate(url(message = "SSO URL must be a valid URL"))]
+    pub sso_url: String,
+
+    #[validate(length(min = 1, message = "IdP signing certificate is required"))]
+    pub x509_cert: String,
+
+    pub email_attribute: Option<String>,
+
+    pub name_attribute: Option<String>,
+
+    #[serde(default)]
+    pub allow_idp_initiated: bool,
+
+    #[serde(default = "default_is_active")]
+    pub is_active: bool,
+}
+
+fn default_is_active() -> bool {
+    true
+}
+
+impl From<&CreateOrUpdateSamlProvider> for SamlIdentityProvider {
+    fn from(req: &CreateOrUpdateSamlProvider) -> Self {
+        let mut provider = SamlIdentityProvider::new(
+            slug::slugify(&req.slug),
+            req.name.clone(),
+            req.entity_id.clone(),
+            req.sso_url.clone(),
+            req.x509_cert.clone(),
+        );
+        provider.email_attribute = req.email_attribute.clone();
+        provider.name_attribute = req.name_attribute.clone();
+        provider.allow_idp_initiated = req.allow_idp_initiated;
+        provider.is_active = req.is_active;
+
+        provider
+    }
+}
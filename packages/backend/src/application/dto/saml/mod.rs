@@ -0,0 +1,2 @@
+pub mod create_update_saml_provider_request;
+pub mod saml_acs_request;
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+// HTTP-POST binding form posted by the IdP to the assertion consumer service
+#[derive(Debug, Clone, Deserialize)]
+pub struct SamlAcsRequest {
+    #[serde(rename = "SAMLResponse")]
+    pub saml_response: String,
+
+    #[serde(rename = "RelayState")]
+    pub relay_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SamlLoginQuery {
+    pub redirect_to: Option<String>,
+}
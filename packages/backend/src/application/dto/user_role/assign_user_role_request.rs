@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+// without a body the grant is permanent
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssignUserRoleRequest {
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1 @@
+pub mod assign_user_role_request;
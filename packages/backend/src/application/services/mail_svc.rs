@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use crate::{domain::repositories::mail_repo::MailRepository, infra::errors::app_error::AppError};
+
+#[derive(Clone)]
+pub struct MailService<M> {
+    mail_repo: Arc<M>,
+}
+
+impl<M> MailService<M>
+where
+    M: MailRepository,
+{
+    pub fn new(mail_repo: Arc<M>) -> Self {
+        Self { mail_repo }
+    }
+
+    pub async fn send_email_change_confirmation(
+        &self,
+        new_email: &str,
+        confirm_url: &str,
+    ) -> Result<(), AppError> {
+        let body = format!(
+            "Someone asked to use this address for their account.\n\n\
+             Confirm the change by opening the link below, it expires in 24 hours:\n{}\n\n\
+             If this was not you, ignore this email and nothing will change.",
+            confirm_url
+        );
+
+        self.mail_repo
+            .send(new_email, "Confirm your new email address", &body)
+            .await
+    }
+
+    pub async fn send_email_change_notice(
+        &self,
+        old_email: &str,
+        new_email: &str,
+        revert_url: &str,
+    ) -> Result<(), AppError> {
+        let body = format!(
+            "A request was made to change the email address of your account to {}.\n\n\
+             If this was not you, open the link below to cancel or undo the change, choose a new password and sign out every session:\n{}\n\n\
+             The link stays valid for 7 days.",
+            new_email, revert_url
+        );
+
+        self.mail_repo
+            .send(old_email, "Your account email is being changed", &body)
+            .await
+    }
+
+    pub async fn send_invite(
+        &self,
+        email: &str,
+        signup_url: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), AppError> {
+        let body = format!(
+            "You have been invited to create an account.\n\n\
+             Sign up with this address using the link below:\n{}\n\n\
+             The invitation expires on {}.",
+            signup_url,
+            expires_at.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        self.mail_repo
+            .send(email, "You have been invited", &body)
+            .await
+    }
+
+    pub async fn send_access_request_pending(
+        &self,
+        approver_email: &str,
+        requester_email: &str,
+        target: &str,
+        domain: &str,
+        justification: &str,
+        review_url: &str,
+    ) -> Result<(), AppError> {
+        let body = format!(
+            "{} requested temporary access to {} in {}.\n\n\
+             Justification:\n{}\n\n\
+             Review the request using the link below:\n{}",
+            requester_email, target, domain, justification, review_url
+        );
+
+        self.mail_repo
+            .send(approver_email, "Access request awaiting your review", &body)
+            .await
+    }
+
+    pub async fn send_access_request_decided(
+        &self,
+        requester_email: &str,
+        target: &str,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        comment: Option<&str>,
+    ) -> Result<(), AppError> {
+        let outcome = match expires_at {
+            Some(expires_at) => format!(
+                "was approved. Access ends on {}.",
+                expires_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            None => "was denied.".to_string(),
+        };
+        let body = format!(
+            "Your request for {} {}\n\n{}",
+            target,
+            outcome,
+            comment.map(|comment| format!("Comment from the approver:\n{}", comment)).unwrap_or_default()
+        );
+
+        self.mail_repo
+            .send(requester_email, "Your access request was reviewed", &body)
+            .await
+    }
+
+    pub async fn send_access_review_opened(
+        &self,
+        reviewer_email: &str,
+        name: &str,
+        domain: &str,
+        assignments: u64,
+        deadline: chrono::DateTime<chrono::Utc>,
+        review_url: &str,
+    ) -> Result<(), AppError> {
+        let body = format!(
+            "You were asked to review who holds which role in {} for the access review \"{}\".\n\n\
+             Confirm or revoke each of the {} assignments before {} using the link below:\n{}\n\n\
+             Assignments left unreviewed get the review's default action.",
+            domain,
+            name,
+            assignments,
+            deadline.format("%Y-%m-%d %H:%M UTC"),
+            review_url
+        );
+
+        self.mail_repo
+            .send(reviewer_email, "Access review awaiting your decisions", &body)
+            .await
+    }
+}
@@ -1,2 +1,5 @@
+pub mod mail_svc;
 pub mod oauth_svc;
 pub mod redis_svc;
+pub mod registration_svc;
+pub mod role_constraint_svc;
@@ -4,8 +4,9 @@ use chrono::TimeZone;
 use tracing::info;
 
 use crate::{
-    application::dto::auth::oauth2_response::{
-        GoogleTokenError, GoogleTokenResponse, GoogleUserResult,
+    application::{
+        dto::auth::oauth2_response::{GoogleTokenError, GoogleTokenResponse, GoogleUserResult},
+        services::registration_svc::{RegistrationService, SignupAttempt},
     },
     domain::{
         entities::{
@@ -23,6 +24,8 @@ use crate::{
         config::AppConfig,
         errors::app_error::AppError,
         oauth2::{constants::GOOGLE_PROVIDER, google::GOOGLE_TOKEN_ENDPOINT},
+        rbac::GLOBAL_DOMAIN,
+        repositories::pg_invite_repo::PgInviteRepository,
     },
 };
 
@@ -33,6 +36,7 @@ pub struct OauthService<U, R, S, O> {
     role_repo: Arc<R>,
     user_session_repo: Arc<S>,
     oauth_provider_repo: Arc<O>,
+    registration_svc: Arc<RegistrationService<PgInviteRepository>>,
 }
 
 impl<U, R, S, O> OauthService<U, R, S, O>
@@ -48,6 +52,7 @@ where
         role_repo: Arc<R>,
         user_session_repo: Arc<S>,
         oauth_provider_repo: Arc<O>,
+        registration_svc: Arc<RegistrationService<PgInviteRepository>>,
     ) -> Self {
         Self {
             cfg,
@@ -55,6 +60,7 @@ where
             role_repo,
             user_session_repo,
             oauth_provider_repo,
+            registration_svc,
         }
     }
 
@@ -62,7 +68,54 @@ where
         &self,
         db_pool: &sqlx::PgPool,
         code: &str,
+        client_ip: &str,
     ) -> Result<GoogleTokenResponse, AppError> {
+        let resp = self.exchange_google_code(code).await?;
+
+        let user_info = self
+            .fetch_google_user(&resp.id_token, &resp.access_token)
+            .await?;
+
+        // if user already registered just return google auth response
+        if let Ok(u) = self.user_repo.find_by_email(&user_info.email).await {
+            // Check if the user has a Google OAuth provider linked
+            match self.oauth_provider_repo.get_by_user_id_and_provider(&u.id, GOOGLE_PROVIDER).await {
+                Ok(_) => {
+                    // User has Google OAuth provider linked, proceed with login
+                    let _session = self
+                        .get_or_create_session(
+                            &u.id,
+                            &resp.access_token,
+                            &resp.refresh_token,
+                            Some(60 * 60 * 24 * 7),
+                        )
+                        .await?;
+                    return Ok(resp);
+                }
+                Err(_) => {
+                    // User exists but doesn't have Google OAuth provider linked
+                    return Err(AppError::AccountAlreadyExistsWithEmail(u.email));
+                }
+            }
+        }
+
+        // register user first & attached role
+        let user_data = self
+            .register_user_from_google(db_pool, &user_info, client_ip)
+            .await?;
+        let _session = self
+            .get_or_create_session(
+                &user_data.id,
+                &resp.access_token,
+                &resp.refresh_token,
+                Some(60 * 60 * 24 * 7),
+            )
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn exchange_google_code(&self, code: &str) -> Result<GoogleTokenResponse, AppError> {
         let mut data = HashMap::new();
 
         data.insert("code".to_string(), code.to_string());
@@ -86,44 +139,6 @@ where
                 if r.status().is_success() {
                     let resp = r.json::<GoogleTokenResponse>().await?;
 
-                    let user_info = self
-                        .fetch_google_user(&resp.id_token, &resp.access_token)
-                        .await?;
-
-                    // if user already registered just return google auth response
-                    if let Ok(u) = self.user_repo.find_by_email(&user_info.email).await {
-                        // Check if the user has a Google OAuth provider linked
-                        match self.oauth_provider_repo.get_by_user_id_and_provider(&u.id, GOOGLE_PROVIDER).await {
-                            Ok(_) => {
-                                // User has Google OAuth provider linked, proceed with login
-                                let _session = self
-                                    .get_or_create_session(
-                                        &u.id,
-                                        &resp.access_token,
-                                        &resp.refresh_token,
-                                        Some(60 * 60 * 24 * 7),
-                                    )
-                                    .await?;
-                                return Ok(resp);
-                            }
-                            Err(_) => {
-                                // User exists but doesn't have Google OAuth provider linked
-                                return Err(AppError::AccountAlreadyExistsWithEmail(u.email));
-                            }
-                        }
-                    }
-
-                    // register user first & attached role
-                    let user_data = self.register_user_from_google(db_pool, &user_info).await?;
-                    let _session = self
-                        .get_or_create_session(
-                            &user_data.id,
-                            &resp.access_token,
-                            &resp.refresh_token,
-                            Some(60 * 60 * 24 * 7),
-                        )
-                        .await?;
-
                     Ok(resp)
                 } else {
                     let err_resp = r.json::<GoogleTokenError>().await?;
@@ -187,7 +202,19 @@ where
         &self,
         db_pool: &sqlx::PgPool,
         google_user_info: &GoogleUserResult,
+        client_ip: &str,
     ) -> Result<User, AppError> {
+        let invite = self
+            .registration_svc
+            .check_signup(&SignupAttempt {
+                email: &google_user_info.email,
+                hosted_domain: google_user_info.hd.as_deref(),
+                invite_token: None,
+                email_verified: google_user_info.email_verified,
+                client_ip,
+            })
+            .await?;
+
         let mut tx = db_pool.begin().await?;
 
         let default_role = self
@@ -207,7 +234,10 @@ where
             GOOGLE_PROVIDER.to_string(),
             google_user_info.sub.clone(),
         );
-        let user_role = UserRole::new(user.id.clone(), default_role.id.clone());
+        let user_role = UserRole::new(user.id.clone(), default_role.id.clone(), GLOBAL_DOMAIN.to_string());
+        self.registration_svc
+            .tx_check_default_role(&mut tx, &user.id, &default_role.id)
+            .await?;
 
         // insert user, user_oauth_provider, user_role
         let (user, _user_oauth_provider, _user_role) = self
@@ -215,6 +245,10 @@ where
             .tx_register_user(&mut tx, &user, &user_oauth_provider, &user_role)
             .await?;
 
+        self.registration_svc
+            .tx_accept_invite(&mut tx, invite, &user.id)
+            .await?;
+
         tx.commit().await?;
 
         Ok(user)
@@ -256,6 +290,13 @@ where
                     .map_err(|err| AppError::ProcessError(err.to_string()))?;
             }
 
+            // every caller is an interactive sign in, so it counts as a fresh authentication
+            exist_session.mark_authenticated();
+            self.user_session_repo
+                .update_authenticated_at(&exist_session)
+                .await
+                .map_err(|err| AppError::ProcessError(err.to_string()))?;
+
             return Ok(exist_session);
         }
 
@@ -297,6 +338,8 @@ where
                 err
             })?;
 
+        self.ensure_active_session(&user.id).await?;
+
         let roles = self
             .role_repo
             .get_roles_by_user_id(&user.id)
@@ -311,6 +354,53 @@ where
         Ok(user_full)
     }
 
+    // for providers whose tokens carry our own user id as subject (e.g. SAML)
+    pub async fn get_current_oauth_user_by_user_id(
+        &self,
+        provider: &str,
+        user_id: &str,
+    ) -> Result<UserFull, AppError> {
+        let oauth_provider = self
+            .oauth_provider_repo
+            .get_by_user_id_and_provider(user_id, provider)
+            .await
+            .map_err(|err| {
+                tracing::error!(" an error occurred when get oauth provider {}", err);
+                err
+            })?;
+
+        let user = self.user_repo.find_by_id(user_id).await.map_err(|err| {
+            tracing::error!(" an error occurred when get user {}", err);
+            err
+        })?;
+
+        self.ensure_active_session(&user.id).await?;
+
+        let roles = self
+            .role_repo
+            .get_roles_by_user_id(&user.id)
+            .await
+            .map_err(|err| {
+                tracing::error!(" an error occurred when get roles {}", err);
+                err
+            })?;
+
+        Ok(UserFull::new(user, oauth_provider, roles))
+    }
+
+    // tokens are stateless, a revoked session only takes effect once the cached user is gone
+    async fn ensure_active_session(&self, user_id: &str) -> Result<(), AppError> {
+        self.user_session_repo
+            .find_by_user_id(user_id)
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::SessionExpired,
+                _ => err,
+            })?;
+
+        Ok(())
+    }
+
     pub async fn google_revoke_token(&self, access_token: &str) -> Result<(), AppError> {
         let client = reqwest::Client::new();
         let url = format!(
@@ -37,6 +37,12 @@ where
         Ok(())
     }
 
+    pub async fn remove_permissions_list(&self) -> Result<(), AppError> {
+        self.redis_repo.delete_value("permissions").await?;
+
+        Ok(())
+    }
+
     pub async fn set_current_user(&self, user: &UserFull) -> Result<(), AppError> {
         let redis_key = format!("current_user_{}", user.user.id);
         let user_json = serde_json::to_string(&user)?;
@@ -61,4 +67,64 @@ where
 
         Ok(())
     }
+
+    pub async fn set_saml_request(&self, request_id: &str, idp_id: &str) -> Result<(), AppError> {
+        let redis_key = format!("saml_request_{}", request_id);
+        self.redis_repo
+            .set_value_with_expiry(&redis_key, idp_id, 600)
+            .await?;
+
+        Ok(())
+    }
+
+    // returns the IdP id the request was sent to, a request can only be answered once
+    pub async fn take_saml_request(&self, request_id: &str) -> Result<String, AppError> {
+        let redis_key = format!("saml_request_{}", request_id);
+        let idp_id = self.redis_repo.get_value(&redis_key).await?;
+        self.redis_repo.delete_value(&redis_key).await?;
+
+        Ok(idp_id)
+    }
+
+    pub async fn mark_saml_assertion_used(
+        &self,
+        assertion_id: &str,
+        expiry: u64,
+    ) -> Result<bool, AppError> {
+        let redis_key = format!("saml_assertion_{}", assertion_id);
+
+        self.redis_repo
+            .set_value_if_absent_with_expiry(&redis_key, "1", expiry)
+            .await
+    }
+
+    // one key per issued token, so the tokens printed by every instance stay valid side by side
+    pub async fn add_bootstrap_token_hash(&self, token_hash: &str, expiry: u64) -> Result<(), AppError> {
+        let redis_key = format!("bootstrap_token_{}", token_hash);
+
+        self.redis_repo
+            .set_value_with_expiry(&redis_key, "1", expiry)
+            .await
+    }
+
+    pub async fn has_bootstrap_token_hash(&self, token_hash: &str) -> Result<bool, AppError> {
+        let redis_key = format!("bootstrap_token_{}", token_hash);
+
+        Ok(self.redis_repo.get_value(&redis_key).await.is_ok())
+    }
+
+    pub async fn remove_bootstrap_token_hash(&self, token_hash: &str) -> Result<(), AppError> {
+        let redis_key = format!("bootstrap_token_{}", token_hash);
+
+        self.redis_repo.delete_value(&redis_key).await
+    }
+
+    // number of signups attempted from this address in the current window
+    pub async fn hit_signup_rate_limit(&self, client_ip: &str, window_secs: i64) -> Result<i64, AppError> {
+        let redis_key = format!("signup_rate_{}", client_ip);
+
+        self.redis_repo
+            .increment_with_expiry(&redis_key, window_secs)
+            .await
+    }
 }
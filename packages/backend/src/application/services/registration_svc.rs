@@ -0,0 +1,212 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    application::services::{redis_svc::RedisService, role_constraint_svc::RoleConstraintService},
+    domain::{entities::invite::Invite, repositories::invite_repo::InviteRepository},
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository, redis_repo_impl::RedisRepositoryImpl,
+        },
+        utils::token::hash_token,
+    },
+};
+
+pub const REGISTRATION_OPEN: &str = "open";
+pub const REGISTRATION_INVITE_ONLY: &str = "invite_only";
+pub const REGISTRATION_CLOSED: &str = "closed";
+
+pub struct SignupAttempt<'a> {
+    pub email: &'a str,
+    // Google Workspace `hd` claim
+    pub hosted_domain: Option<&'a str>,
+    pub invite_token: Option<&'a str>,
+    // the provider proved the address belongs to the caller (Google, SAML)
+    pub email_verified: bool,
+    pub client_ip: &'a str,
+}
+
+/// Single gate every provider goes through before creating an account.
+#[derive(Clone)]
+pub struct RegistrationService<I> {
+    cfg: Arc<AppConfig>,
+    invite_repo: Arc<I>,
+    redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+    allowed_domains: Vec<String>,
+    denied_domains: Vec<String>,
+    disposable_domains: HashSet<String>,
+}
+
+impl<I> RegistrationService<I>
+where
+    I: InviteRepository,
+{
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        invite_repo: Arc<I>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        // a configured blocklist that cannot be read keeps the server from starting
+        let disposable_domains = if cfg.disposable_domains_file.is_empty() {
+            HashSet::new()
+        } else {
+            std::fs::read_to_string(&cfg.disposable_domains_file)
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "Failed to load disposable domains file {}: {}",
+                        cfg.disposable_domains_file, err
+                    )
+                })
+                .lines()
+                .map(|line| line.trim().to_lowercase())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .collect()
+        };
+
+        Self {
+            allowed_domains: parse_domains(&cfg.registration_allowed_domains),
+            denied_domains: parse_domains(&cfg.registration_denied_domains),
+            cfg,
+            invite_repo,
+            redis_svc,
+            role_constraint_svc,
+            disposable_domains,
+        }
+    }
+
+    /// Returns the invite the signup relies on, to be accepted with the new user.
+    pub async fn check_signup(&self, attempt: &SignupAttempt<'_>) -> Result<Option<Invite>, AppError> {
+        let attempts = self
+            .redis_svc
+            .hit_signup_rate_limit(attempt.client_ip, self.cfg.signup_rate_limit_window_secs)
+            .await?;
+        if attempts > self.cfg.signup_rate_limit {
+            return Err(AppError::TooManyRequests);
+        }
+
+        let invite_only = match self.cfg.registration_mode.as_str() {
+            REGISTRATION_OPEN => false,
+            REGISTRATION_INVITE_ONLY => true,
+            // unknown modes fail closed
+            _ => return Err(AppError::RegistrationNotAllowed("registration is closed".to_string())),
+        };
+
+        let email = attempt.email.trim().to_lowercase();
+        let email_domain = email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.to_string())
+            .unwrap_or_default();
+        let domains = [Some(email_domain.clone()), attempt.hosted_domain.map(|hd| hd.to_lowercase())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if domains.iter().any(|domain| matches_any(domain, &self.denied_domains)) {
+            return Err(AppError::RegistrationNotAllowed(
+                "this email domain is not allowed".to_string(),
+            ));
+        }
+
+        if self.is_disposable(&email_domain) {
+            return Err(AppError::RegistrationNotAllowed(
+                "disposable email addresses are not allowed".to_string(),
+            ));
+        }
+
+        let invite = if invite_only {
+            Some(self.find_invite(&email, attempt).await?)
+        } else {
+            None
+        };
+
+        // an invitation is an explicit exception to the allow list
+        if invite.is_none()
+            && !self.allowed_domains.is_empty()
+            && !domains.iter().any(|domain| matches_any(domain, &self.allowed_domains))
+        {
+            return Err(AppError::RegistrationNotAllowed(
+                "this email domain is not allowed".to_string(),
+            ));
+        }
+
+        Ok(invite)
+    }
+
+    // the default role must not combine roles of a segregation-of-duties constraint by itself,
+    // checked in the transaction registering the user with it
+    pub async fn tx_check_default_role(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        role_id: &str,
+    ) -> Result<(), AppError> {
+        self.role_constraint_svc
+            .check_assignment(tx, user_id, role_id, GLOBAL_DOMAIN)
+            .await
+    }
+
+    pub async fn tx_accept_invite(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        invite: Option<Invite>,
+        user_id: &str,
+    ) -> Result<(), AppError> {
+        if let Some(mut invite) = invite {
+            invite.accept(user_id.to_string());
+            self.invite_repo.tx_accept(tx, &invite).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_invite(&self, email: &str, attempt: &SignupAttempt<'_>) -> Result<Invite, AppError> {
+        let invite = match attempt.invite_token {
+            Some(token) => self.invite_repo.find_by_token_hash(&hash_token(token)).await.ok(),
+            // without a token the address itself must be proven by the provider
+            None if attempt.email_verified => self.invite_repo.find_pending_by_email(email).await.ok(),
+            None => None,
+        };
+
+        match invite {
+            Some(invite) if invite.is_pending() && invite.email.to_lowercase() == email => Ok(invite),
+            _ => Err(AppError::RegistrationNotAllowed(
+                "a valid invitation is required".to_string(),
+            )),
+        }
+    }
+
+    fn is_disposable(&self, domain: &str) -> bool {
+        // also catches subdomains of a listed domain
+        let mut candidate = domain;
+        loop {
+            if self.disposable_domains.contains(candidate) {
+                return true;
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => candidate = parent,
+                _ => return false,
+            }
+        }
+    }
+}
+
+fn parse_domains(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|domain| domain.trim().trim_start_matches('@').to_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect()
+}
+
+fn matches_any(domain: &str, list: &[String]) -> bool {
+    list.iter()
+        .any(|entry| domain == entry || domain.ends_with(&format!(".{}", entry)))
+}
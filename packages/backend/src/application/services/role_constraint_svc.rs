@@ -0,0 +1,534 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use casbin::MgmtApi;
+
+use crate::{
+    domain::{
+        entities::{
+            role::Role,
+            role_constraint::{RoleConstraint, RoleConstraintViolation},
+        },
+        repositories::{
+            role_constraint_repo::RoleConstraintRepository, role_repo::RoleRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{Rbac, GLOBAL_DOMAIN},
+    },
+};
+
+// (user id, role id, domain the role is held in)
+type Assignment = (String, String, String);
+
+/// Segregation-of-duties checks every path that hands out roles or changes what they inherit
+/// goes through.
+#[derive(Clone)]
+pub struct RoleConstraintService<C, R, M> {
+    constraint_repo: Arc<C>,
+    role_repo: Arc<R>,
+    user_role_repo: Arc<M>,
+    rbac: Arc<Rbac>,
+}
+
+impl<C, R, M> RoleConstraintService<C, R, M>
+where
+    C: RoleConstraintRepository,
+    R: RoleRepository,
+    M: UserRoleRepository,
+{
+    pub fn new(
+        constraint_repo: Arc<C>,
+        role_repo: Arc<R>,
+        user_role_repo: Arc<M>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            constraint_repo,
+            role_repo,
+            user_role_repo,
+            rbac,
+        }
+    }
+
+    /// Rejects granting the role if the user would then hold roles of a constraint together.
+    /// Conflicts the user already had before are left to the violations report. Grants to the
+    /// user are locked until `tx` ends, so the caller inserts the grant in the same transaction.
+    pub async fn check_assignment(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        role_id: &str,
+        domain: &str,
+    ) -> Result<(), AppError> {
+        // released before the user is locked, approvals lock the user under the write lock
+        let groupings = self.rbac.enforcer.read().await.get_grouping_policy();
+
+        self.check_assignment_with(tx, user_id, role_id, domain, &groupings)
+            .await
+    }
+
+    /// `check_assignment` for callers holding the enforcer write lock, who pass the current
+    /// grouping rules.
+    pub async fn check_assignment_with(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        role_id: &str,
+        domain: &str,
+        groupings: &[Vec<String>],
+    ) -> Result<(), AppError> {
+        let held = self.user_role_repo.tx_lock_by_user_id(tx, user_id).await?;
+
+        let constraints = self.constraint_repo.find_all(GLOBAL_DOMAIN).await?;
+        if constraints.is_empty() {
+            return Ok(());
+        }
+
+        let roles = self.roles_by_id().await?;
+
+        let mut assignments = held
+            .into_iter()
+            .map(|user_role| (user_role.user_id, user_role.role_id, user_role.domain))
+            .collect::<Vec<Assignment>>();
+        let before = user_violations(&constraints, &roles, &assignments, groupings);
+
+        assignments.push((user_id.to_string(), role_id.to_string(), domain.to_string()));
+        let after = user_violations(&constraints, &roles, &assignments, groupings);
+
+        reject_new(&roles, before, after)
+    }
+
+    /// Rejects an inheritance change after which a user or a role would newly combine roles of a
+    /// constraint. Callers hold the enforcer write lock and pass the grouping rules before and
+    /// after the change.
+    pub async fn check_groupings(
+        &self,
+        current: &[Vec<String>],
+        proposed: &[Vec<String>],
+    ) -> Result<(), AppError> {
+        let constraints = self.constraint_repo.find_all(GLOBAL_DOMAIN).await?;
+        if constraints.is_empty() {
+            return Ok(());
+        }
+
+        let roles = self.roles_by_id().await?;
+        let assignments = self.assignments().await?;
+
+        let violations = |groupings: &[Vec<String>]| {
+            let mut violations = user_violations(&constraints, &roles, &assignments, groupings);
+            violations.extend(role_violations(&constraints, &roles, groupings));
+            violations
+        };
+
+        reject_new(&roles, violations(current), violations(proposed))
+    }
+
+    /// Conflicts that exist right now, in the domain and through global grants, everywhere for
+    /// `*`.
+    pub async fn find_violations(
+        &self,
+        domain: &str,
+    ) -> Result<Vec<RoleConstraintViolation>, AppError> {
+        let constraints = self.constraint_repo.find_all(domain).await?;
+        if constraints.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let roles = self.roles_by_id().await?;
+        let assignments = self.assignments().await?;
+        let groupings = self.rbac.enforcer.read().await.get_grouping_policy();
+
+        let mut violations = user_violations(&constraints, &roles, &assignments, &groupings);
+        violations.extend(role_violations(&constraints, &roles, &groupings));
+        violations.retain(|violation| {
+            domain == GLOBAL_DOMAIN || violation.domain == domain || violation.domain == GLOBAL_DOMAIN
+        });
+
+        Ok(violations)
+    }
+
+    async fn roles_by_id(&self) -> Result<HashMap<String, Role>, AppError> {
+        Ok(self
+            .role_repo
+            .find_all_across_domains()
+            .await?
+            .into_iter()
+            .map(|role| (role.id.clone(), role))
+            .collect())
+    }
+
+    async fn assignments(&self) -> Result<Vec<Assignment>, AppError> {
+        Ok(self
+            .user_role_repo
+            .find_unexpired()
+            .await?
+            .into_iter()
+            .map(|user_role| (user_role.user_id, user_role.role_id, user_role.domain))
+            .collect())
+    }
+}
+
+fn reject_new(
+    roles: &HashMap<String, Role>,
+    before: Vec<RoleConstraintViolation>,
+    after: Vec<RoleConstraintViolation>,
+) -> Result<(), AppError> {
+    match after
+        .iter()
+        .find(|violation| !before.iter().any(|existing| existing.is_same(violation)))
+    {
+        Some(violation) => Err(AppError::RoleConstraintViolation(format!(
+            "{} cannot be held together ({}{})",
+            violation.role_names.join(", "),
+            violation.constraint_name,
+            violation
+                .role_id
+                .as_ref()
+                .map(|role_id| {
+                    let name = roles.get(role_id).map_or(role_id.as_str(), |role| role.name.as_str());
+                    format!(", all inherited by role {}", name)
+                })
+                .unwrap_or_default()
+        ))),
+        None => Ok(()),
+    }
+}
+
+// roles of the constraint among the held ones and everything they inherit in the domain
+fn conflict(
+    constraint: &RoleConstraint,
+    held: &[&str],
+    groupings: &[Vec<String>],
+    domain: &str,
+) -> Vec<String> {
+    let mut effective = held.iter().map(|role_id| role_id.to_string()).collect::<BTreeSet<String>>();
+    for role_id in held {
+        effective.extend(Rbac::ancestor_roles(groupings, role_id, domain));
+    }
+
+    let mut conflicting = constraint
+        .role_ids
+        .iter()
+        .filter(|role_id| effective.contains(*role_id))
+        .cloned()
+        .collect::<Vec<String>>();
+    conflicting.sort();
+    conflicting.dedup();
+    conflicting
+}
+
+fn violation(
+    constraint: &RoleConstraint,
+    roles: &HashMap<String, Role>,
+    user_id: Option<&str>,
+    role_id: Option<&str>,
+    domain: &str,
+    role_ids: Vec<String>,
+) -> RoleConstraintViolation {
+    RoleConstraintViolation {
+        constraint_id: constraint.id.clone(),
+        constraint_name: constraint.name.clone(),
+        user_id: user_id.map(str::to_string),
+        role_id: role_id.map(str::to_string),
+        domain: domain.to_string(),
+        role_names: role_ids
+            .iter()
+            .map(|role_id| roles.get(role_id).map_or(role_id.clone(), |role| role.name.clone()))
+            .collect(),
+        role_ids,
+    }
+}
+
+// a global grant counts in every domain, so a global constraint is checked in each domain the
+// user holds roles in. Each conflict is listed once, in the first domain it shows up in.
+fn user_violations(
+    constraints: &[RoleConstraint],
+    roles: &HashMap<String, Role>,
+    assignments: &[Assignment],
+    groupings: &[Vec<String>],
+) -> Vec<RoleConstraintViolation> {
+    let mut by_user: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+    for (user_id, role_id, domain) in assignments {
+        by_user.entry(user_id).or_default().push((role_id, domain));
+    }
+
+    let mut violations: Vec<RoleConstraintViolation> = Vec::new();
+    for (user_id, held) in by_user {
+        let domains = held.iter().map(|(_, domain)| *domain).collect::<BTreeSet<&str>>();
+
+        for constraint in constraints {
+            let scopes = if constraint.domain == GLOBAL_DOMAIN {
+                domains.iter().copied().collect::<Vec<&str>>()
+            } else {
+                vec![constraint.domain.as_str()]
+            };
+
+            for domain in scopes {
+                let held_in_domain = held
+                    .iter()
+                    .filter(|(_, held_domain)| *held_domain == domain || *held_domain == GLOBAL_DOMAIN)
+                    .map(|(role_id, _)| *role_id)
+                    .collect::<Vec<&str>>();
+
+                let role_ids = conflict(constraint, &held_in_domain, groupings, domain);
+                if role_ids.len() < 2 {
+                    continue;
+                }
+
+                let found = violation(constraint, roles, Some(user_id), None, domain, role_ids);
+                if !violations.iter().any(|existing| existing.is_same(&found)) {
+                    violations.push(found);
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+// roles that combine roles of a constraint through inheritance, whoever they are given to
+// ends up in conflict. Roles only known from the grouping rules, like ones an import is about to
+// create, are checked as well.
+fn role_violations(
+    constraints: &[RoleConstraint],
+    roles: &HashMap<String, Role>,
+    groupings: &[Vec<String>],
+) -> Vec<RoleConstraintViolation> {
+    let mut candidates = roles
+        .values()
+        .map(|role| (role.id.as_str(), role.domain.as_str()))
+        .collect::<BTreeSet<(&str, &str)>>();
+    candidates.extend(
+        groupings
+            .iter()
+            .filter(|rule| rule.len() >= 3 && !roles.contains_key(&rule[0]))
+            .map(|rule| (rule[0].as_str(), rule[2].as_str())),
+    );
+
+    let mut violations = Vec::new();
+    for (role_id, role_domain) in candidates {
+        for constraint in constraints.iter().filter(|constraint| {
+            role_domain == GLOBAL_DOMAIN || constraint.applies_in(role_domain)
+        }) {
+            let domain = if constraint.domain == GLOBAL_DOMAIN {
+                role_domain
+            } else {
+                constraint.domain.as_str()
+            };
+
+            let role_ids = conflict(constraint, &[role_id], groupings, domain);
+            if role_ids.len() >= 2 {
+                violations.push(violation(constraint, roles, None, Some(role_id), domain, role_ids));
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles() -> HashMap<String, Role> {
+        ["maker", "checker", "lead", "auditor"]
+            .into_iter()
+            .map(|id| {
+                let name = format!("{} role", id);
+                (id.to_string(), Role::new(id.to_string(), name, false, GLOBAL_DOMAIN.to_string()))
+            })
+            .collect()
+    }
+
+    fn constraint(domain: &str) -> RoleConstraint {
+        let mut constraint = RoleConstraint::new(
+            "maker-checker".to_string(),
+            None,
+            domain.to_string(),
+            vec!["maker".to_string(), "checker".to_string()],
+        );
+        constraint.id = "constraint".to_string();
+        constraint
+    }
+
+    fn assignment(user_id: &str, role_id: &str, domain: &str) -> Assignment {
+        (user_id.to_string(), role_id.to_string(), domain.to_string())
+    }
+
+    fn grouping(role_id: &str, parent_id: &str, domain: &str) -> Vec<String> {
+        vec![role_id.to_string(), parent_id.to_string(), domain.to_string()]
+    }
+
+    #[test]
+    fn user_holding_both_roles_violates() {
+        let assignments = [
+            assignment("alice", "maker", GLOBAL_DOMAIN),
+            assignment("alice", "checker", GLOBAL_DOMAIN),
+            assignment("bob", "maker", GLOBAL_DOMAIN),
+        ];
+
+        let violations = user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &assignments, &[]);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].user_id.as_deref(), Some("alice"));
+        assert_eq!(violations[0].role_ids, vec!["checker", "maker"]);
+        assert_eq!(violations[0].role_names, vec!["checker role", "maker role"]);
+    }
+
+    #[test]
+    fn inherited_roles_count_as_held() {
+        let assignments = [
+            assignment("alice", "maker", GLOBAL_DOMAIN),
+            assignment("alice", "lead", GLOBAL_DOMAIN),
+        ];
+        let groupings = [
+            grouping("lead", "auditor", GLOBAL_DOMAIN),
+            grouping("auditor", "checker", GLOBAL_DOMAIN),
+        ];
+
+        let violations =
+            user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &assignments, &groupings);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].role_ids, vec!["checker", "maker"]);
+    }
+
+    #[test]
+    fn roles_in_different_domains_do_not_conflict() {
+        let assignments = [
+            assignment("alice", "maker", "org-1"),
+            assignment("alice", "checker", "org-2"),
+        ];
+
+        let violations = user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &assignments, &[]);
+
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn global_grants_count_in_every_domain() {
+        let assignments = [
+            assignment("alice", "maker", GLOBAL_DOMAIN),
+            assignment("alice", "checker", "org-1"),
+        ];
+
+        let violations = user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &assignments, &[]);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].domain, "org-1");
+    }
+
+    #[test]
+    fn domain_constraints_only_apply_in_their_domain() {
+        let assignments = [
+            assignment("alice", "maker", "org-2"),
+            assignment("alice", "checker", "org-2"),
+        ];
+
+        assert!(user_violations(&[constraint("org-1")], &roles(), &assignments, &[]).is_empty());
+    }
+
+    #[test]
+    fn role_inheriting_both_roles_violates() {
+        let groupings = [
+            grouping("lead", "maker", GLOBAL_DOMAIN),
+            grouping("lead", "auditor", GLOBAL_DOMAIN),
+            grouping("auditor", "checker", GLOBAL_DOMAIN),
+        ];
+
+        let violations = role_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &groupings);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].role_id.as_deref(), Some("lead"));
+        assert_eq!(violations[0].role_ids, vec!["checker", "maker"]);
+    }
+
+    #[test]
+    fn roles_only_known_from_groupings_are_checked() {
+        let groupings = [
+            grouping("imported", "maker", GLOBAL_DOMAIN),
+            grouping("imported", "checker", GLOBAL_DOMAIN),
+        ];
+
+        let violations = role_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &groupings);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].role_id.as_deref(), Some("imported"));
+    }
+
+    #[test]
+    fn inheritance_cycles_terminate() {
+        let groupings = [
+            grouping("lead", "auditor", GLOBAL_DOMAIN),
+            grouping("auditor", "lead", GLOBAL_DOMAIN),
+        ];
+
+        assert!(role_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &groupings).is_empty());
+    }
+
+    #[test]
+    fn reject_new_ignores_existing_conflicts() {
+        let assignments = vec![
+            assignment("alice", "maker", GLOBAL_DOMAIN),
+            assignment("alice", "checker", GLOBAL_DOMAIN),
+        ];
+        let before = user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &assignments, &[]);
+
+        // the same conflict found again in another domain is not a new one
+        let mut grown = assignments.clone();
+        grown.push(assignment("alice", "auditor", "org-1"));
+        let after = user_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &grown, &[]);
+
+        assert!(reject_new(&roles(), before, after).is_ok());
+    }
+
+    #[test]
+    fn reject_new_rejects_new_conflicts() {
+        let before = user_violations(
+            &[constraint(GLOBAL_DOMAIN)],
+            &roles(),
+            &[assignment("alice", "maker", GLOBAL_DOMAIN)],
+            &[],
+        );
+        let after = user_violations(
+            &[constraint(GLOBAL_DOMAIN)],
+            &roles(),
+            &[
+                assignment("alice", "maker", GLOBAL_DOMAIN),
+                assignment("alice", "checker", GLOBAL_DOMAIN),
+            ],
+            &[],
+        );
+
+        match reject_new(&roles(), before, after) {
+            Err(AppError::RoleConstraintViolation(message)) => {
+                assert_eq!(
+                    message,
+                    "checker role, maker role cannot be held together (maker-checker)"
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reject_new_names_the_inheriting_role() {
+        let groupings = [
+            grouping("lead", "maker", GLOBAL_DOMAIN),
+            grouping("lead", "checker", GLOBAL_DOMAIN),
+        ];
+        let after = role_violations(&[constraint(GLOBAL_DOMAIN)], &roles(), &groupings);
+
+        match reject_new(&roles(), Vec::new(), after) {
+            Err(AppError::RoleConstraintViolation(message)) => {
+                assert!(message.ends_with(", all inherited by role lead role)"), "{}", message);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -4,19 +4,25 @@ use crate::infra::{
     config::AppConfig,
     rbac::Rbac,
     repositories::{
-        pg_oauth_provider::PgOauthProviderRepository, pg_role_repo::PgRoleRepository,
-        pg_user_repo::PgUserRepository, pg_user_session::PgUserSessionRepository,
-        pg_project_repo::PgProjectRepository,
-        redis_repo_impl::RedisRepositoryImpl,
+        pg_access_request_repo::PgAccessRequestRepository, pg_access_review_repo::PgAccessReviewRepository, pg_oauth_provider::PgOauthProviderRepository, pg_role_repo::PgRoleRepository,
+        pg_saml_provider_repo::PgSamlProviderRepository, pg_user_repo::PgUserRepository, pg_user_session::PgUserSessionRepository,
+        pg_project_repo::PgProjectRepository, pg_email_change_repo::PgEmailChangeRepository,
+        pg_invite_repo::PgInviteRepository, pg_app_setup_repo::PgAppSetupRepository,
+        pg_permission_repo::PgPermissionRepository, pg_policy_repo::PgPolicyRepository, pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_policy_change_repo::PgRolePolicyChangeRepository, pg_role_template_repo::PgRoleTemplateRepository, pg_user_role_repo::PgUserRoleRepository,
+        redis_repo_impl::RedisRepositoryImpl, smtp_mail_repo_impl::SmtpMailRepositoryImpl,
     },
     utils::{google_jwt::GoogleJwtMaker, jwt_maker::JwtMaker},
 };
 use bb8_redis::{bb8::Pool, RedisConnectionManager};
+use lettre::{AsyncSmtpTransport, Tokio1Executor};
 use sqlx::PgPool;
 
 use super::{
-    services::{oauth_svc::OauthService, redis_svc::RedisService},
-    usecases::{auth::init::AuthUsecase, role::init::RoleUsecase, project::init::ProjectUsecase, user::init::UserUseCases},
+    services::{
+        mail_svc::MailService, oauth_svc::OauthService, redis_svc::RedisService,
+        registration_svc::RegistrationService, role_constraint_svc::RoleConstraintService,
+    },
+    usecases::{access_request::init::AccessRequestUsecase, access_review::init::AccessReviewUsecase, auth::init::AuthUsecase, authz::init::AuthzUsecase, invite::init::InviteUsecase, permission::init::PermissionUsecase, policy::init::PolicyUsecase, role::init::RoleUsecase, role_constraint::init::RoleConstraintUsecase, saml::init::SamlUsecase, project::init::ProjectUsecase, user::init::UserUseCases, user_role::init::UserRoleUsecase},
 };
 
 #[derive(Clone)]
@@ -33,10 +39,19 @@ pub struct AppState {
 /* Usecases list */
 #[derive(Clone)]
 pub struct Usecase {
+    pub access_request: Arc<AccessRequestUsecase>,
+    pub access_review: Arc<AccessReviewUsecase>,
     pub role: Arc<RoleUsecase>,
+    pub role_constraint: Arc<RoleConstraintUsecase>,
     pub auth: Arc<AuthUsecase>,
+    pub authz: Arc<AuthzUsecase>,
+    pub saml: Arc<SamlUsecase>,
+    pub invite: Arc<InviteUsecase>,
+    pub permission: Arc<PermissionUsecase>,
+    pub policy: Arc<PolicyUsecase>,
     pub project: Arc<ProjectUsecase>,
     pub user: Arc<UserUseCases>,
+    pub user_role: Arc<UserRoleUsecase>,
 }
 
 /* End Usecases list */
@@ -52,6 +67,11 @@ pub struct Service {
         >,
     >,
     pub redis: Arc<RedisService<RedisRepositoryImpl>>,
+    pub mail: Arc<MailService<SmtpMailRepositoryImpl>>,
+    pub registration: Arc<RegistrationService<PgInviteRepository>>,
+    pub role_constraint: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
 }
 
 impl AppState {
@@ -59,12 +79,14 @@ impl AppState {
         cfg: Arc<AppConfig>,
         db_pool: PgPool,
         redis_pool: Pool<RedisConnectionManager>,
+        mail_transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
         rbac: Arc<Rbac>,
     ) -> Self {
         // utils or tooling
         let jwt_maker = Arc::new(JwtMaker::new(cfg.jwt_secret.clone()));
         let google_jwt_maker = Arc::new(GoogleJwtMaker::new(cfg.clone()));
         let redis_repo = Arc::new(RedisRepositoryImpl::new(redis_pool.clone()));
+        let mail_repo = Arc::new(SmtpMailRepositoryImpl::new(cfg.mail_from.clone(), mail_transport));
 
         // repos list
         let role_repo = Arc::new(PgRoleRepository::new(db_pool.clone()));
@@ -72,26 +94,90 @@ impl AppState {
         let project_repo = Arc::new(PgProjectRepository::new(db_pool.clone()));
         let user_session_repo = Arc::new(PgUserSessionRepository::new(db_pool.clone()));
         let oauth_provider_repo = Arc::new(PgOauthProviderRepository::new(db_pool.clone()));
+        let saml_provider_repo = Arc::new(PgSamlProviderRepository::new(db_pool.clone()));
+        let email_change_repo = Arc::new(PgEmailChangeRepository::new(db_pool.clone()));
+        let invite_repo = Arc::new(PgInviteRepository::new(db_pool.clone()));
+        let app_setup_repo = Arc::new(PgAppSetupRepository::new(db_pool.clone()));
+        let permission_repo = Arc::new(PgPermissionRepository::new(db_pool.clone()));
+        let user_role_repo = Arc::new(PgUserRoleRepository::new(db_pool.clone()));
+        let access_request_repo = Arc::new(PgAccessRequestRepository::new(db_pool.clone()));
+        let access_review_repo = Arc::new(PgAccessReviewRepository::new(db_pool.clone()));
+        let policy_repo = Arc::new(PgPolicyRepository::new(db_pool.clone()));
+        let policy_change_repo = Arc::new(PgRolePolicyChangeRepository::new(db_pool.clone()));
+        let role_template_repo = Arc::new(PgRoleTemplateRepository::new(db_pool.clone()));
+        let role_constraint_repo = Arc::new(PgRoleConstraintRepository::new(db_pool.clone()));
 
         // services list
         let redis_svc = Arc::new(RedisService::new(redis_repo.clone()));
+        let mail_svc = Arc::new(MailService::new(mail_repo.clone()));
+        let role_constraint_svc = Arc::new(RoleConstraintService::new(
+            role_constraint_repo.clone(),
+            role_repo.clone(),
+            user_role_repo.clone(),
+            rbac.clone(),
+        ));
+        let registration_svc = Arc::new(RegistrationService::new(
+            cfg.clone(),
+            invite_repo.clone(),
+            redis_svc.clone(),
+            role_constraint_svc.clone(),
+        ));
         let oauth_svc = Arc::new(OauthService::new(
             cfg.clone(),
             user_repo.clone(),
             role_repo.clone(),
             user_session_repo.clone(),
             oauth_provider_repo.clone(),
+            registration_svc.clone(),
         ));
 
         // service registration
         let svc = Arc::new(Service {
             oauth: oauth_svc,
             redis: redis_svc,
+            mail: mail_svc,
+            registration: registration_svc,
+            role_constraint: role_constraint_svc,
         });
 
         // Usecase registration
         let uc = Arc::new(Usecase {
-            role: Arc::new(RoleUsecase::new(role_repo.clone(), rbac.clone())),
+            access_request: Arc::new(AccessRequestUsecase::new(
+                cfg.clone(),
+                access_request_repo.clone(),
+                role_repo.clone(),
+                policy_repo.clone(),
+                permission_repo.clone(),
+                user_repo.clone(),
+                user_role_repo.clone(),
+                rbac.clone(),
+                svc.redis.clone(),
+                svc.mail.clone(),
+                svc.role_constraint.clone(),
+            )),
+            access_review: Arc::new(AccessReviewUsecase::new(
+                cfg.clone(),
+                access_review_repo.clone(),
+                role_repo.clone(),
+                user_repo.clone(),
+                user_role_repo.clone(),
+                svc.redis.clone(),
+                svc.mail.clone(),
+            )),
+            role: Arc::new(RoleUsecase::new(
+                role_repo.clone(),
+                permission_repo.clone(),
+                policy_repo.clone(),
+                policy_change_repo.clone(),
+                role_template_repo.clone(),
+                rbac.clone(),
+                svc.role_constraint.clone(),
+            )),
+            role_constraint: Arc::new(RoleConstraintUsecase::new(
+                role_constraint_repo.clone(),
+                role_repo.clone(),
+                svc.role_constraint.clone(),
+            )),
             auth: Arc::new(AuthUsecase::new(
                 cfg.clone(),
                 svc.oauth.clone(),
@@ -101,12 +187,63 @@ impl AppState {
                 user_session_repo.clone(),
                 jwt_maker.clone(),
                 svc.redis.clone(),
+                svc.registration.clone(),
+                app_setup_repo.clone(),
+                policy_repo.clone(),
+                svc.role_constraint.clone(),
+            )),
+            authz: Arc::new(AuthzUsecase::new(
+                role_repo.clone(),
+                permission_repo.clone(),
+                rbac.clone(),
+            )),
+            saml: Arc::new(SamlUsecase::new(
+                cfg.clone(),
+                svc.oauth.clone(),
+                svc.redis.clone(),
+                user_repo.clone(),
+                role_repo.clone(),
+                oauth_provider_repo.clone(),
+                saml_provider_repo.clone(),
+                jwt_maker.clone(),
+                svc.registration.clone(),
+            )),
+            invite: Arc::new(InviteUsecase::new(
+                cfg.clone(),
+                invite_repo.clone(),
+                user_repo.clone(),
+                svc.mail.clone(),
+            )),
+            permission: Arc::new(PermissionUsecase::new(
+                permission_repo.clone(),
+                svc.redis.clone(),
+            )),
+            policy: Arc::new(PolicyUsecase::new(
+                cfg.clone(),
+                role_repo.clone(),
+                permission_repo.clone(),
+                policy_repo.clone(),
+                policy_change_repo.clone(),
+                rbac.clone(),
+                svc.role_constraint.clone(),
             )),
-            project: Arc::new(ProjectUsecase::new(project_repo.clone())),
+            project: Arc::new(ProjectUsecase::new(project_repo.clone(), rbac.clone())),
             user: Arc::new(UserUseCases::new(
+                cfg.clone(),
                 user_repo.clone(),
+                email_change_repo.clone(),
+                user_session_repo.clone(),
+                svc.mail.clone(),
+                svc.redis.clone(),
                 db_pool.clone(),
             )),
+            user_role: Arc::new(UserRoleUsecase::new(
+                user_repo.clone(),
+                role_repo.clone(),
+                user_role_repo.clone(),
+                svc.redis.clone(),
+                svc.role_constraint.clone(),
+            )),
         });
 
         Self {
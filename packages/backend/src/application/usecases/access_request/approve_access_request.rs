@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use casbin::MgmtApi;
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_request::decide_access_request_request::DecideAccessRequestRequest,
+        services::{
+            mail_svc::MailService, redis_svc::RedisService,
+            role_constraint_svc::RoleConstraintService,
+        },
+    },
+    domain::{
+        entities::{
+            access_request::{AccessRequest, AccessRequestDecision, ACCESS_REQUEST_APPROVED},
+            role::Role,
+            user_role::UserRole,
+        },
+        repositories::{
+            access_request_repo::AccessRequestRepository, policy_repo::PolicyRepository,
+            redis_repo::RedisRepository, role_repo::RoleRepository, user_repo::UserRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, GLOBAL_DOMAIN},
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository, smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        },
+    },
+};
+
+use super::granted_role::{in_scope, jit_role_name};
+
+#[derive(Clone)]
+pub struct ApproveAccessRequest<A, R, Q, U, M, C> {
+    access_request_repo: Arc<A>,
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    user_repo: Arc<U>,
+    user_role_repo: Arc<M>,
+    rbac: Arc<Rbac>,
+    redis_svc: Arc<RedisService<C>>,
+    mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<A, R, Q, U, M, C> ApproveAccessRequest<A, R, Q, U, M, C>
+where
+    A: AccessRequestRepository,
+    R: RoleRepository,
+    Q: PolicyRepository,
+    U: UserRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        access_request_repo: Arc<A>,
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        user_repo: Arc<U>,
+        user_role_repo: Arc<M>,
+        rbac: Arc<Rbac>,
+        redis_svc: Arc<RedisService<C>>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            access_request_repo,
+            role_repo,
+            policy_repo,
+            user_repo,
+            user_role_repo,
+            rbac,
+            redis_svc,
+            mail_svc,
+            role_constraint_svc,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        approver_id: &str,
+        domain: &str,
+        id: &str,
+        req: DecideAccessRequestRequest,
+    ) -> Result<AccessRequest, AppError> {
+        req.validate()?;
+
+        // policy writes take the enforcer lock before the transaction, like revoking and
+        // expiring requests do, so the two can never wait on each other
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let mut tx = db_pool.begin().await?;
+
+        // locked until commit so two approvers cannot grant the same request
+        let mut request = self.access_request_repo.tx_lock(&mut tx, id).await?;
+
+        if !in_scope(&request, domain) {
+            return Err(AppError::ResourceNotFound);
+        }
+        if request.requester_id == approver_id {
+            return Err(AppError::Forbidden);
+        }
+        if !request.is_pending() {
+            return Err(AppError::ProcessError(format!(
+                "The request is already {}",
+                request.status
+            )));
+        }
+
+        let duration_secs = req
+            .duration_minutes
+            .map(|minutes| (minutes * 60).min(request.duration_secs))
+            .unwrap_or(request.duration_secs);
+
+        // a permission request adds the policy of its role in this transaction
+        let mut added_policies = Vec::new();
+        let granted_role_id = match (&request.role_id, &request.permission) {
+            (Some(role_id), _) => {
+                let role = self.role_repo.find_by_id(role_id).await?;
+                if role.domain != GLOBAL_DOMAIN && role.domain != request.domain {
+                    return Err(AppError::ResourceNotFound);
+                }
+
+                // a time-bound grant would replace the permanent one
+                let held_permanently = self
+                    .role_repo
+                    .get_roles_by_user_id(&request.requester_id)
+                    .await?
+                    .iter()
+                    .any(|held| {
+                        held.role.id == role.id
+                            && held.assigned_domain == request.domain
+                            && held.expires_at.is_none()
+                    });
+                if held_permanently {
+                    return Err(AppError::ProcessError(
+                        "The requester already holds this role".to_owned(),
+                    ));
+                }
+
+                self.role_constraint_svc
+                    .check_assignment_with(
+                        &mut tx,
+                        &request.requester_id,
+                        &role.id,
+                        &request.domain,
+                        &enforcer.get_grouping_policy(),
+                    )
+                    .await?;
+
+                role.id
+            }
+            (None, Some(permission)) => {
+                let role = Role::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    jit_role_name(&request),
+                    false,
+                    request.domain.clone(),
+                );
+                let policy = Rbac::permission_policy(
+                    &role.id,
+                    &request.domain,
+                    permission,
+                    EFFECT_ALLOW,
+                )?;
+
+                let role = self.role_repo.tx_create(&mut tx, role).await?;
+                self.policy_repo
+                    .tx_add_rules(&mut tx, "p", std::slice::from_ref(&policy))
+                    .await?;
+                added_policies.push(policy);
+
+                role.id
+            }
+            (None, None) => return Err(AppError::ResourceNotFound),
+        };
+
+        request.approve(granted_role_id, duration_secs);
+
+        self.user_role_repo
+            .tx_create(
+                &mut tx,
+                &UserRole::new(
+                    request.requester_id.clone(),
+                    request.granted_role_id.clone().unwrap_or_default(),
+                    request.domain.clone(),
+                )
+                .with_validity(request.starts_at, request.expires_at),
+            )
+            .await?;
+
+        let request = self.access_request_repo.tx_update(&mut tx, &request).await?;
+        self.access_request_repo
+            .tx_create_decision(
+                &mut tx,
+                &AccessRequestDecision::new(
+                    request.id.clone(),
+                    approver_id.to_string(),
+                    ACCESS_REQUEST_APPROVED,
+                    req.comment.clone(),
+                ),
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        if !added_policies.is_empty() {
+            self.rbac.apply_committed(
+                &mut enforcer,
+                CommittedRules {
+                    added_policies,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+        drop(enforcer);
+
+        self.redis_svc.remove_current_user(&request.requester_id).await?;
+
+        info!(
+            "Access request {} approved by {}, {} granted to {} until {:?}",
+            request.id,
+            approver_id,
+            request.target(),
+            request.requester_id,
+            request.expires_at
+        );
+
+        let requester = self.user_repo.find_by_id(&request.requester_id).await?;
+        if let Err(err) = self
+            .mail_svc
+            .send_access_request_decided(
+                &requester.email,
+                &request.target(),
+                request.expires_at,
+                req.comment.as_deref(),
+            )
+            .await
+        {
+            warn!("Failed to notify requester of access request {}: {}", request.id, err);
+        }
+
+        Ok(request)
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    domain::{
+        entities::access_request::{AccessRequest, ACCESS_REQUEST_CANCELLED},
+        repositories::access_request_repo::AccessRequestRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct CancelAccessRequest<A> {
+    access_request_repo: Arc<A>,
+}
+
+impl<A> CancelAccessRequest<A>
+where
+    A: AccessRequestRepository,
+{
+    pub fn new(access_request_repo: Arc<A>) -> Self {
+        Self { access_request_repo }
+    }
+
+    // only the requester can withdraw a request, and only before it is decided
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        requester_id: &str,
+        id: &str,
+    ) -> Result<AccessRequest, AppError> {
+        let mut tx = db_pool.begin().await?;
+
+        let mut request = self.access_request_repo.tx_lock(&mut tx, id).await?;
+
+        if request.requester_id != requester_id {
+            return Err(AppError::ResourceNotFound);
+        }
+        if !request.is_pending() {
+            return Err(AppError::ProcessError(format!(
+                "The request is already {}",
+                request.status
+            )));
+        }
+
+        request.close(ACCESS_REQUEST_CANCELLED);
+        let request = self.access_request_repo.tx_update(&mut tx, &request).await?;
+
+        tx.commit().await?;
+
+        info!("Access request {} cancelled by its requester", request.id);
+
+        Ok(request)
+    }
+}
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_request::create_access_request_request::CreateAccessRequestRequest,
+        services::mail_svc::MailService,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::{
+            access_context::AccessContext, access_request::AccessRequest, role::AssignedRole,
+            user::UserFull,
+        },
+        repositories::{
+            access_request_repo::AccessRequestRepository, permission_repo::PermissionRepository,
+            role_repo::RoleRepository, user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        rbac::{Rbac, GLOBAL_DOMAIN},
+        repositories::smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+    },
+};
+
+#[derive(Clone)]
+pub struct CreateAccessRequest<A, R, P, M> {
+    cfg: Arc<AppConfig>,
+    access_request_repo: Arc<A>,
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    user_role_repo: Arc<M>,
+    rbac: Arc<Rbac>,
+    mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+}
+
+impl<A, R, P, M> CreateAccessRequest<A, R, P, M>
+where
+    A: AccessRequestRepository,
+    R: RoleRepository,
+    P: PermissionRepository,
+    M: UserRoleRepository,
+{
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        access_request_repo: Arc<A>,
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        user_role_repo: Arc<M>,
+        rbac: Arc<Rbac>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            cfg,
+            access_request_repo,
+            role_repo,
+            permission_repo,
+            user_role_repo,
+            rbac,
+            mail_svc,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        requester: &UserFull,
+        domain: &str,
+        req: CreateAccessRequestRequest,
+    ) -> Result<AccessRequest, AppError> {
+        req.validate()?;
+
+        let (role_id, permission) = match (req.role_id, req.permission) {
+            (Some(role_id), None) => (Some(self.check_role(requester, domain, &role_id).await?), None),
+            (None, Some(permission)) => {
+                (None, Some(self.check_permission(requester, domain, permission.trim()).await?))
+            }
+            _ => {
+                return Err(AppError::ProcessError(
+                    "Request either a role or a permission".to_owned(),
+                ))
+            }
+        };
+
+        let request = AccessRequest::new(
+            requester.user.id.clone(),
+            domain.to_string(),
+            role_id,
+            permission,
+            req.justification.trim().to_string(),
+            req.duration_minutes * 60,
+        );
+
+        if self.access_request_repo.has_open_request(&request).await? {
+            return Err(AppError::ResourceExist(
+                "An open request for this access already exists".to_owned(),
+            ));
+        }
+
+        let request = self.access_request_repo.create(&request).await?;
+
+        info!(
+            "User {} requested {} in {}",
+            request.requester_id,
+            request.target(),
+            request.domain
+        );
+
+        self.notify_approvers(&requester.user.email, &request).await?;
+
+        Ok(request)
+    }
+
+    async fn check_role(
+        &self,
+        requester: &UserFull,
+        domain: &str,
+        role_id: &str,
+    ) -> Result<String, AppError> {
+        let role = self.role_repo.find_by_id(role_id).await?;
+
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&role.name) {
+            return Err(AppError::ProcessError(
+                "Reserved roles cannot be requested".to_owned(),
+            ));
+        }
+
+        let held_permanently = requester.roles.iter().any(|held| {
+            held.role.id == role.id
+                && (held.assigned_domain == GLOBAL_DOMAIN || held.assigned_domain == domain)
+                && held.starts_at.is_none()
+                && held.expires_at.is_none()
+        });
+        if held_permanently {
+            return Err(AppError::ProcessError("You already hold this role".to_owned()));
+        }
+
+        Ok(role.id)
+    }
+
+    async fn check_permission(
+        &self,
+        requester: &UserFull,
+        domain: &str,
+        permission: &str,
+    ) -> Result<String, AppError> {
+        let policy = Rbac::permission_policy("", domain, permission, "")?;
+
+        // wildcards would grant far more than one permission, and super admin management is as
+        // off limits as the super admin role itself
+        if policy[2].contains('*') || policy[3].contains('*') {
+            return Err(AppError::ProcessError(
+                "Wildcard permissions cannot be requested".to_owned(),
+            ));
+        }
+        if policy[2] == "super-admin-management" {
+            return Err(AppError::ProcessError(
+                "Super admin management permissions cannot be requested".to_owned(),
+            ));
+        }
+
+        let unknown = self
+            .permission_repo
+            .find_unknown(&[permission.to_string()])
+            .await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permission {}",
+                permission
+            )));
+        }
+
+        if self
+            .rbac
+            .check_access(
+                &requester.roles,
+                domain,
+                &policy[2],
+                &policy[3],
+                &AccessContext::new(&requester.user.id, None),
+            )
+            .await?
+        {
+            return Err(AppError::ProcessError(
+                "You already have this permission".to_owned(),
+            ));
+        }
+
+        Ok(permission.to_string())
+    }
+
+    // approvers are the holders of any role that allows `access-requests:approve` in the domain
+    async fn notify_approvers(
+        &self,
+        requester_email: &str,
+        request: &AccessRequest,
+    ) -> Result<(), AppError> {
+        let mut approver_role_ids = Vec::new();
+        for role in self.role_repo.find_all(&request.domain).await? {
+            let assigned = AssignedRole {
+                role: role.clone(),
+                assigned_domain: request.domain.clone(),
+                starts_at: None,
+                expires_at: None,
+            };
+            if self
+                .rbac
+                .check_access(
+                    &[assigned],
+                    &request.domain,
+                    "access-requests",
+                    "approve",
+                    &AccessContext::default(),
+                )
+                .await?
+            {
+                approver_role_ids.push(role.id);
+            }
+        }
+
+        if approver_role_ids.is_empty() {
+            warn!("No approver can review access request {}", request.id);
+            return Ok(());
+        }
+
+        let mut emails = self
+            .user_role_repo
+            .find_members_by_role_ids(&approver_role_ids, &request.domain)
+            .await?
+            .into_iter()
+            .filter(|member| member.user_id != request.requester_id)
+            .map(|member| member.email)
+            .collect::<Vec<String>>();
+        emails.sort();
+        emails.dedup();
+
+        let review_url = format!(
+            "{}/access-requests/{}",
+            self.cfg.public_app_url.trim_end_matches('/'),
+            request.id
+        );
+
+        // the request stands even if a notification cannot be delivered
+        for email in emails {
+            if let Err(err) = self
+                .mail_svc
+                .send_access_request_pending(
+                    &email,
+                    requester_email,
+                    &request.target(),
+                    &request.domain,
+                    &request.justification,
+                    &review_url,
+                )
+                .await
+            {
+                warn!("Failed to notify approver {} of access request {}: {}", email, request.id, err);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_request::decide_access_request_request::DecideAccessRequestRequest,
+        services::mail_svc::MailService,
+    },
+    domain::{
+        entities::access_request::{
+            AccessRequest, AccessRequestDecision, ACCESS_REQUEST_DENIED,
+        },
+        repositories::{access_request_repo::AccessRequestRepository, user_repo::UserRepository},
+    },
+    infra::{errors::app_error::AppError, repositories::smtp_mail_repo_impl::SmtpMailRepositoryImpl},
+};
+
+use super::granted_role::in_scope;
+
+#[derive(Clone)]
+pub struct DenyAccessRequest<A, U> {
+    access_request_repo: Arc<A>,
+    user_repo: Arc<U>,
+    mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+}
+
+impl<A, U> DenyAccessRequest<A, U>
+where
+    A: AccessRequestRepository,
+    U: UserRepository,
+{
+    pub fn new(
+        access_request_repo: Arc<A>,
+        user_repo: Arc<U>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            access_request_repo,
+            user_repo,
+            mail_svc,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        approver_id: &str,
+        domain: &str,
+        id: &str,
+        req: DecideAccessRequestRequest,
+    ) -> Result<AccessRequest, AppError> {
+        req.validate()?;
+
+        let mut tx = db_pool.begin().await?;
+
+        let mut request = self.access_request_repo.tx_lock(&mut tx, id).await?;
+
+        if !in_scope(&request, domain) {
+            return Err(AppError::ResourceNotFound);
+        }
+        // requesters withdraw their own requests by cancelling them
+        if request.requester_id == approver_id {
+            return Err(AppError::Forbidden);
+        }
+        if !request.is_pending() {
+            return Err(AppError::ProcessError(format!(
+                "The request is already {}",
+                request.status
+            )));
+        }
+
+        request.close(ACCESS_REQUEST_DENIED);
+        let request = self.access_request_repo.tx_update(&mut tx, &request).await?;
+        self.access_request_repo
+            .tx_create_decision(
+                &mut tx,
+                &AccessRequestDecision::new(
+                    request.id.clone(),
+                    approver_id.to_string(),
+                    ACCESS_REQUEST_DENIED,
+                    req.comment.clone(),
+                ),
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        info!("Access request {} denied by {}", request.id, approver_id);
+
+        let requester = self.user_repo.find_by_id(&request.requester_id).await?;
+        if let Err(err) = self
+            .mail_svc
+            .send_access_request_decided(&requester.email, &request.target(), None, req.comment.as_deref())
+            .await
+        {
+            warn!("Failed to notify requester of access request {}: {}", request.id, err);
+        }
+
+        Ok(request)
+    }
+}
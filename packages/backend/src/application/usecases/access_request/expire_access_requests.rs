@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::{
+        entities::access_request::{AccessRequestRevocation, ACCESS_REQUEST_EXPIRED},
+        repositories::{
+            access_request_repo::AccessRequestRepository, redis_repo::RedisRepository,
+            policy_repo::PolicyRepository, role_repo::RoleRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac},
+    },
+};
+
+use super::granted_role::{tx_end_grant, tx_remove_jit_role};
+
+const EXPIRE_BATCH_SIZE: i64 = 100;
+
+#[derive(Clone)]
+pub struct ExpireAccessRequests<A, R, Q, M, C> {
+    access_request_repo: Arc<A>,
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    user_role_repo: Arc<M>,
+    rbac: Arc<Rbac>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<A, R, Q, M, C> ExpireAccessRequests<A, R, Q, M, C>
+where
+    A: AccessRequestRepository,
+    R: RoleRepository,
+    Q: PolicyRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        access_request_repo: Arc<A>,
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        user_role_repo: Arc<M>,
+        rbac: Arc<Rbac>,
+        redis_svc: Arc<RedisService<C>>,
+    ) -> Self {
+        Self {
+            access_request_repo,
+            role_repo,
+            policy_repo,
+            user_role_repo,
+            rbac,
+            redis_svc,
+        }
+    }
+
+    // closes approved requests whose grant ran out and drops the roles created for them
+    pub async fn execute(&self, db_pool: &sqlx::PgPool) -> Result<usize, AppError> {
+        let expired = self.access_request_repo.find_expired(EXPIRE_BATCH_SIZE).await?;
+        let mut closed = 0;
+
+        for candidate in expired {
+            // taken before the transaction like every policy write, ending a permission grant drops
+            // the role created for it
+            let mut enforcer = self.rbac.enforcer.write().await;
+            let mut tx = db_pool.begin().await?;
+
+            // another instance or a revocation may have closed it meanwhile
+            let request = self.access_request_repo.tx_lock(&mut tx, &candidate.id).await?;
+            if !request.is_granted() {
+                continue;
+            }
+
+            let revocation = AccessRequestRevocation::new(
+                request.id.clone(),
+                None,
+                Some("expired".to_string()),
+            );
+            let request = tx_end_grant(
+                &mut tx,
+                self.access_request_repo.as_ref(),
+                self.user_role_repo.as_ref(),
+                request,
+                ACCESS_REQUEST_EXPIRED,
+                revocation,
+            )
+            .await?;
+
+            let removed_policies = tx_remove_jit_role(
+                &mut tx,
+                self.role_repo.as_ref(),
+                self.policy_repo.as_ref(),
+                &enforcer,
+                &request,
+            )
+            .await?;
+
+            tx.commit().await?;
+
+            if !removed_policies.is_empty() {
+                self.rbac.apply_committed(
+                    &mut enforcer,
+                    CommittedRules {
+                        removed_policies,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            }
+            drop(enforcer);
+
+            self.redis_svc.remove_current_user(&request.requester_id).await?;
+
+            info!("Access request {} expired", request.id);
+            closed += 1;
+        }
+
+        Ok(closed)
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_request::AccessRequestDetail,
+        repositories::access_request_repo::AccessRequestRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::granted_role::in_scope;
+
+#[derive(Clone)]
+pub struct GetAccessRequestById<A> {
+    access_request_repo: Arc<A>,
+}
+
+impl<A> GetAccessRequestById<A>
+where
+    A: AccessRequestRepository,
+{
+    pub fn new(access_request_repo: Arc<A>) -> Self {
+        Self { access_request_repo }
+    }
+
+    // visible to the requester and to approvers of the request's domain
+    pub async fn execute(
+        &self,
+        domain: &str,
+        id: &str,
+        user_id: &str,
+        is_approver: bool,
+    ) -> Result<AccessRequestDetail, AppError> {
+        let request = self.access_request_repo.find_by_id(id).await?;
+
+        if request.requester_id != user_id && !(is_approver && in_scope(&request, domain)) {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        let decisions = self.access_request_repo.find_decisions(&request.id).await?;
+        let revocations = self.access_request_repo.find_revocations(&request.id).await?;
+
+        Ok(AccessRequestDetail {
+            request,
+            decisions,
+            revocations,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_request::{
+            AccessRequest, ACCESS_REQUEST_APPROVED, ACCESS_REQUEST_CANCELLED,
+            ACCESS_REQUEST_DENIED, ACCESS_REQUEST_EXPIRED, ACCESS_REQUEST_PENDING,
+            ACCESS_REQUEST_REVOKED,
+        },
+        repositories::access_request_repo::AccessRequestRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetAccessRequests<A> {
+    access_request_repo: Arc<A>,
+}
+
+impl<A> GetAccessRequests<A>
+where
+    A: AccessRequestRepository,
+{
+    pub fn new(access_request_repo: Arc<A>) -> Self {
+        Self { access_request_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessRequest>, AppError> {
+        let known = [
+            ACCESS_REQUEST_PENDING,
+            ACCESS_REQUEST_APPROVED,
+            ACCESS_REQUEST_DENIED,
+            ACCESS_REQUEST_CANCELLED,
+            ACCESS_REQUEST_REVOKED,
+            ACCESS_REQUEST_EXPIRED,
+        ];
+        if status.is_some_and(|status| !known.contains(&status)) {
+            return Err(AppError::ProcessError(format!(
+                "Unknown status, expected one of {}",
+                known.join(", ")
+            )));
+        }
+
+        self.access_request_repo.find_by_domain(domain, status).await
+    }
+}
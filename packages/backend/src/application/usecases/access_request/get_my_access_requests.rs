@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_request::AccessRequest,
+        repositories::access_request_repo::AccessRequestRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetMyAccessRequests<A> {
+    access_request_repo: Arc<A>,
+}
+
+impl<A> GetMyAccessRequests<A>
+where
+    A: AccessRequestRepository,
+{
+    pub fn new(access_request_repo: Arc<A>) -> Self {
+        Self { access_request_repo }
+    }
+
+    pub async fn execute(&self, requester_id: &str) -> Result<Vec<AccessRequest>, AppError> {
+        self.access_request_repo.find_by_requester(requester_id).await
+    }
+}
@@ -0,0 +1,83 @@
+use casbin::{Enforcer, MgmtApi};
+
+use crate::{
+    domain::{
+        entities::access_request::{AccessRequest, AccessRequestRevocation},
+        repositories::{
+            access_request_repo::AccessRequestRepository, policy_repo::PolicyRepository,
+            role_repo::RoleRepository, user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+    },
+};
+
+pub const JIT_ROLE_PREFIX: &str = "jit:";
+
+// requests for a single permission are granted through a role created for the request
+pub fn jit_role_name(request: &AccessRequest) -> String {
+    format!("{}{}", JIT_ROLE_PREFIX, request.id)
+}
+
+// a request is handled in its own domain, or anywhere by global approvers
+pub fn in_scope(request: &AccessRequest, domain: &str) -> bool {
+    domain == GLOBAL_DOMAIN || request.domain == domain
+}
+
+// drops the role created for a permission request once its grant ended, requested roles stay;
+// written in the transaction ending the grant by a caller holding the enforcer write lock, the
+// returned policies are applied once it commits
+pub async fn tx_remove_jit_role<R, Q>(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    role_repo: &R,
+    policy_repo: &Q,
+    enforcer: &Enforcer,
+    request: &AccessRequest,
+) -> Result<Vec<Vec<String>>, AppError>
+where
+    R: RoleRepository,
+    Q: PolicyRepository,
+{
+    let Some(role_id) = request.granted_role_id.as_deref() else {
+        return Ok(Vec::new());
+    };
+    if request.permission.is_none() {
+        return Ok(Vec::new());
+    }
+
+    role_repo.tx_delete(tx, role_id).await?;
+
+    let policies = enforcer.get_filtered_policy(0, vec![role_id.to_string()]);
+    policy_repo.tx_remove_rules(tx, "p", &policies).await?;
+
+    Ok(policies)
+}
+
+// removes the grant of an approved request and records why it ended
+pub async fn tx_end_grant<A, M>(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    access_request_repo: &A,
+    user_role_repo: &M,
+    mut request: AccessRequest,
+    status: &str,
+    revocation: AccessRequestRevocation,
+) -> Result<AccessRequest, AppError>
+where
+    A: AccessRequestRepository,
+    M: UserRoleRepository,
+{
+    // the grant sweeper may already have removed an expired assignment
+    if let Some(role_id) = request.granted_role_id.as_deref() {
+        user_role_repo
+            .tx_delete(tx, &request.requester_id, role_id, &request.domain)
+            .await?;
+    }
+
+    request.close(status);
+    let request = access_request_repo.tx_update(tx, &request).await?;
+    access_request_repo.tx_create_revocation(tx, &revocation).await?;
+
+    Ok(request)
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::{
+        mail_svc::MailService, redis_svc::RedisService, role_constraint_svc::RoleConstraintService,
+    },
+    infra::{
+        config::AppConfig,
+        rbac::Rbac,
+        repositories::{
+            pg_access_request_repo::PgAccessRequestRepository,
+            pg_permission_repo::PgPermissionRepository, pg_policy_repo::PgPolicyRepository,
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_repo::PgUserRepository, pg_user_role_repo::PgUserRoleRepository,
+            redis_repo_impl::RedisRepositoryImpl, smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        },
+    },
+};
+
+use super::{
+    approve_access_request::ApproveAccessRequest, cancel_access_request::CancelAccessRequest,
+    create_access_request::CreateAccessRequest, deny_access_request::DenyAccessRequest,
+    expire_access_requests::ExpireAccessRequests,
+    get_access_request_by_id::GetAccessRequestById, get_access_requests::GetAccessRequests,
+    get_my_access_requests::GetMyAccessRequests, revoke_access_request::RevokeAccessRequest,
+};
+
+#[derive(Clone)]
+pub struct AccessRequestUsecase {
+    pub create_access_request: Arc<
+        CreateAccessRequest<
+            PgAccessRequestRepository,
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgUserRoleRepository,
+        >,
+    >,
+    pub get_my_access_requests: Arc<GetMyAccessRequests<PgAccessRequestRepository>>,
+    pub get_access_requests: Arc<GetAccessRequests<PgAccessRequestRepository>>,
+    pub get_access_request_by_id: Arc<GetAccessRequestById<PgAccessRequestRepository>>,
+    pub approve_access_request: Arc<
+        ApproveAccessRequest<
+            PgAccessRequestRepository,
+            PgRoleRepository,
+            PgPolicyRepository,
+            PgUserRepository,
+            PgUserRoleRepository,
+            RedisRepositoryImpl,
+        >,
+    >,
+    pub deny_access_request: Arc<DenyAccessRequest<PgAccessRequestRepository, PgUserRepository>>,
+    pub cancel_access_request: Arc<CancelAccessRequest<PgAccessRequestRepository>>,
+    pub revoke_access_request: Arc<
+        RevokeAccessRequest<
+            PgAccessRequestRepository,
+            PgRoleRepository,
+            PgPolicyRepository,
+            PgUserRoleRepository,
+            RedisRepositoryImpl,
+        >,
+    >,
+    pub expire_access_requests: Arc<
+        ExpireAccessRequests<
+            PgAccessRequestRepository,
+            PgRoleRepository,
+            PgPolicyRepository,
+            PgUserRoleRepository,
+            RedisRepositoryImpl,
+        >,
+    >,
+}
+
+impl AccessRequestUsecase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        access_request_repo: Arc<PgAccessRequestRepository>,
+        role_repo: Arc<PgRoleRepository>,
+        policy_repo: Arc<PgPolicyRepository>,
+        permission_repo: Arc<PgPermissionRepository>,
+        user_repo: Arc<PgUserRepository>,
+        user_role_repo: Arc<PgUserRoleRepository>,
+        rbac: Arc<Rbac>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            create_access_request: Arc::new(CreateAccessRequest::new(
+                cfg.clone(),
+                access_request_repo.clone(),
+                role_repo.clone(),
+                permission_repo.clone(),
+                user_role_repo.clone(),
+                rbac.clone(),
+                mail_svc.clone(),
+            )),
+            get_my_access_requests: Arc::new(GetMyAccessRequests::new(access_request_repo.clone())),
+            get_access_requests: Arc::new(GetAccessRequests::new(access_request_repo.clone())),
+            get_access_request_by_id: Arc::new(GetAccessRequestById::new(
+                access_request_repo.clone(),
+            )),
+            approve_access_request: Arc::new(ApproveAccessRequest::new(
+                access_request_repo.clone(),
+                role_repo.clone(),
+                policy_repo.clone(),
+                user_repo.clone(),
+                user_role_repo.clone(),
+                rbac.clone(),
+                redis_svc.clone(),
+                mail_svc.clone(),
+                role_constraint_svc.clone(),
+            )),
+            deny_access_request: Arc::new(DenyAccessRequest::new(
+                access_request_repo.clone(),
+                user_repo.clone(),
+                mail_svc.clone(),
+            )),
+            cancel_access_request: Arc::new(CancelAccessRequest::new(access_request_repo.clone())),
+            revoke_access_request: Arc::new(RevokeAccessRequest::new(
+                access_request_repo.clone(),
+                role_repo.clone(),
+                policy_repo.clone(),
+                user_role_repo.clone(),
+                rbac.clone(),
+                redis_svc.clone(),
+            )),
+            expire_access_requests: Arc::new(ExpireAccessRequests::new(
+                access_request_repo.clone(),
+                role_repo.clone(),
+                policy_repo.clone(),
+                user_role_repo.clone(),
+                rbac.clone(),
+                redis_svc.clone(),
+            )),
+        }
+    }
+}
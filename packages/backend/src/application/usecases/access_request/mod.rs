@@ -0,0 +1,11 @@
+pub mod approve_access_request;
+pub mod cancel_access_request;
+pub mod create_access_request;
+pub mod deny_access_request;
+pub mod expire_access_requests;
+pub mod get_access_request_by_id;
+pub mod get_access_requests;
+pub mod get_my_access_requests;
+pub mod granted_role;
+pub mod init;
+pub mod revoke_access_request;
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_request::decide_access_request_request::RevokeAccessRequestRequest,
+        services::redis_svc::RedisService,
+    },
+    domain::{
+        entities::access_request::{
+            AccessRequest, AccessRequestRevocation, ACCESS_REQUEST_REVOKED,
+        },
+        repositories::{
+            access_request_repo::AccessRequestRepository, redis_repo::RedisRepository,
+            policy_repo::PolicyRepository, role_repo::RoleRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac},
+    },
+};
+
+use super::granted_role::{in_scope, tx_end_grant, tx_remove_jit_role};
+
+#[derive(Clone)]
+pub struct RevokeAccessRequest<A, R, Q, M, C> {
+    access_request_repo: Arc<A>,
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    user_role_repo: Arc<M>,
+    rbac: Arc<Rbac>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<A, R, Q, M, C> RevokeAccessRequest<A, R, Q, M, C>
+where
+    A: AccessRequestRepository,
+    R: RoleRepository,
+    Q: PolicyRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        access_request_repo: Arc<A>,
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        user_role_repo: Arc<M>,
+        rbac: Arc<Rbac>,
+        redis_svc: Arc<RedisService<C>>,
+    ) -> Self {
+        Self {
+            access_request_repo,
+            role_repo,
+            policy_repo,
+            user_role_repo,
+            rbac,
+            redis_svc,
+        }
+    }
+
+    // ends an approved grant before it expires
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        revoked_by: &str,
+        domain: &str,
+        id: &str,
+        req: RevokeAccessRequestRequest,
+    ) -> Result<AccessRequest, AppError> {
+        req.validate()?;
+
+        // taken before the transaction like every policy write, ending a permission grant drops
+        // the role created for it
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let mut tx = db_pool.begin().await?;
+
+        let request = self.access_request_repo.tx_lock(&mut tx, id).await?;
+
+        if !in_scope(&request, domain) {
+            return Err(AppError::ResourceNotFound);
+        }
+        if !request.is_granted() {
+            return Err(AppError::ProcessError(
+                "Only approved requests can be revoked".to_owned(),
+            ));
+        }
+
+        let revocation = AccessRequestRevocation::new(
+            request.id.clone(),
+            Some(revoked_by.to_string()),
+            req.reason,
+        );
+        let request = tx_end_grant(
+            &mut tx,
+            self.access_request_repo.as_ref(),
+            self.user_role_repo.as_ref(),
+            request,
+            ACCESS_REQUEST_REVOKED,
+            revocation,
+        )
+        .await?;
+
+        let removed_policies = tx_remove_jit_role(
+            &mut tx,
+            self.role_repo.as_ref(),
+            self.policy_repo.as_ref(),
+            &enforcer,
+            &request,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        if !removed_policies.is_empty() {
+            self.rbac.apply_committed(
+                &mut enforcer,
+                CommittedRules {
+                    removed_policies,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+        drop(enforcer);
+
+        self.redis_svc.remove_current_user(&request.requester_id).await?;
+
+        info!("Access request {} revoked by {}", request.id, revoked_by);
+
+        Ok(request)
+    }
+}
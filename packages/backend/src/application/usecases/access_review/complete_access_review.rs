@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::{
+        entities::access_review::AccessReviewReport,
+        repositories::{
+            access_review_repo::AccessReviewRepository, redis_repo::RedisRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::review_items::{in_scope, tx_close_review};
+
+#[derive(Clone)]
+pub struct CompleteAccessReview<A, M, C> {
+    review_repo: Arc<A>,
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<A, M, C> CompleteAccessReview<A, M, C>
+where
+    A: AccessReviewRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        review_repo: Arc<A>,
+        user_role_repo: Arc<M>,
+        redis_svc: Arc<RedisService<C>>,
+    ) -> Self {
+        Self {
+            review_repo,
+            user_role_repo,
+            redis_svc,
+        }
+    }
+
+    // closes the campaign before its deadline, unreviewed assignments get the default action now
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        completed_by: &str,
+        domain: &str,
+        id: &str,
+    ) -> Result<AccessReviewReport, AppError> {
+        let mut tx = db_pool.begin().await?;
+
+        let review = self.review_repo.tx_lock(&mut tx, id).await?;
+        if !in_scope(&review, domain) {
+            return Err(AppError::ResourceNotFound);
+        }
+        if !review.is_open() {
+            return Err(AppError::ProcessError(
+                "The review is already completed".to_owned(),
+            ));
+        }
+
+        let (review, revoked) = tx_close_review(
+            &mut tx,
+            self.review_repo.as_ref(),
+            self.user_role_repo.as_ref(),
+            review,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        for user_id in &revoked {
+            self.redis_svc.remove_current_user(user_id).await?;
+        }
+
+        info!("Access review {} completed by {}", review.id, completed_by);
+
+        let items = self.review_repo.find_items(&review.id).await?;
+
+        Ok(AccessReviewReport::new(review, items))
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{
+        access_review_repo::AccessReviewRepository, redis_repo::RedisRepository,
+        user_role_repo::UserRoleRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::review_items::tx_close_review;
+
+const COMPLETE_BATCH_SIZE: i64 = 20;
+
+#[derive(Clone)]
+pub struct CompleteOverdueAccessReviews<A, M, C> {
+    review_repo: Arc<A>,
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<A, M, C> CompleteOverdueAccessReviews<A, M, C>
+where
+    A: AccessReviewRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        review_repo: Arc<A>,
+        user_role_repo: Arc<M>,
+        redis_svc: Arc<RedisService<C>>,
+    ) -> Self {
+        Self {
+            review_repo,
+            user_role_repo,
+            redis_svc,
+        }
+    }
+
+    // completes campaigns whose deadline passed, applying their default action
+    pub async fn execute(&self, db_pool: &sqlx::PgPool) -> Result<usize, AppError> {
+        let overdue = self.review_repo.find_overdue(COMPLETE_BATCH_SIZE).await?;
+        let mut completed = 0;
+
+        for candidate in overdue {
+            let mut tx = db_pool.begin().await?;
+
+            // another instance or an admin may have completed it meanwhile
+            let review = self.review_repo.tx_lock(&mut tx, &candidate.id).await?;
+            if !review.is_open() {
+                continue;
+            }
+
+            let (review, revoked) = tx_close_review(
+                &mut tx,
+                self.review_repo.as_ref(),
+                self.user_role_repo.as_ref(),
+                review,
+            )
+            .await?;
+
+            tx.commit().await?;
+
+            for user_id in &revoked {
+                self.redis_svc.remove_current_user(user_id).await?;
+            }
+
+            info!(
+                "Access review {} reached its deadline, {} users lost roles left unreviewed",
+                review.id,
+                revoked.len()
+            );
+            completed += 1;
+        }
+
+        Ok(completed)
+    }
+}
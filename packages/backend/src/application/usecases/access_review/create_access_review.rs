@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_review::create_access_review_request::CreateAccessReviewRequest,
+        services::mail_svc::MailService,
+    },
+    domain::{
+        entities::access_review::{AccessReview, AccessReviewDetail},
+        repositories::{
+            access_review_repo::AccessReviewRepository, role_repo::RoleRepository,
+            user_repo::UserRepository,
+        },
+    },
+    infra::{
+        common::constants::SUPER_ADMIN_ROLE,
+        config::AppConfig,
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        repositories::smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+    },
+};
+
+use super::review_items::is_action;
+
+#[derive(Clone)]
+pub struct CreateAccessReview<A, R, U> {
+    cfg: Arc<AppConfig>,
+    review_repo: Arc<A>,
+    role_repo: Arc<R>,
+    user_repo: Arc<U>,
+    mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+}
+
+impl<A, R, U> CreateAccessReview<A, R, U>
+where
+    A: AccessReviewRepository,
+    R: RoleRepository,
+    U: UserRepository,
+{
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        review_repo: Arc<A>,
+        role_repo: Arc<R>,
+        user_repo: Arc<U>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            cfg,
+            review_repo,
+            role_repo,
+            user_repo,
+            mail_svc,
+        }
+    }
+
+    // opens a campaign over the current holders of the roles, later assignments are not part of it
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        created_by: &str,
+        domain: &str,
+        req: CreateAccessReviewRequest,
+        manages_super_admins: bool,
+    ) -> Result<AccessReviewDetail, AppError> {
+        req.validate()?;
+
+        if req.deadline <= chrono::Utc::now() {
+            return Err(AppError::ProcessError(
+                "The deadline must be in the future".to_owned(),
+            ));
+        }
+
+        let default_action = req
+            .default_action
+            .unwrap_or_else(|| self.cfg.access_review_default_action.clone());
+        if !is_action(&default_action) {
+            return Err(AppError::ProcessError(
+                "The default action must be confirm or revoke".to_owned(),
+            ));
+        }
+
+        let mut role_ids: Vec<String> = Vec::new();
+        for role_id in &req.role_ids {
+            if role_ids.contains(role_id) {
+                continue;
+            }
+
+            let role = self.role_repo.find_by_id(role_id).await.map_err(|_| {
+                AppError::ProcessError(format!("Role {} does not exist", role_id))
+            })?;
+            if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} cannot be reviewed in {}",
+                    role.name, domain
+                )));
+            }
+            // revoking super admin needs the same right as /v1/super/admins
+            if role.name == SUPER_ADMIN_ROLE && !manages_super_admins {
+                return Err(AppError::Forbidden);
+            }
+
+            role_ids.push(role.id);
+        }
+
+        let mut reviewers: Vec<(String, String)> = Vec::new();
+        for reviewer_id in &req.reviewer_ids {
+            if reviewers.iter().any(|(id, _)| id == reviewer_id) {
+                continue;
+            }
+
+            let reviewer = self.user_repo.find_by_id(reviewer_id).await.map_err(|_| {
+                AppError::ProcessError(format!("Reviewer {} does not exist", reviewer_id))
+            })?;
+            reviewers.push((reviewer.id, reviewer.email));
+        }
+
+        let review_req = AccessReview::new(
+            req.name,
+            req.description,
+            domain.to_string(),
+            role_ids,
+            reviewers.iter().map(|(id, _)| id.clone()).collect(),
+            default_action,
+            req.deadline,
+            created_by.to_string(),
+        );
+
+        let mut tx = db_pool.begin().await?;
+        let review = self.review_repo.tx_create(&mut tx, &review_req).await?;
+        let assignments = self.review_repo.tx_create_items(&mut tx, &review).await?;
+        tx.commit().await?;
+
+        info!(
+            "Access review {} opened in {} by {} over {} assignments",
+            review.id, domain, created_by, assignments
+        );
+
+        let review_url = format!(
+            "{}/access-reviews/{}",
+            self.cfg.public_app_url.trim_end_matches('/'),
+            review.id
+        );
+
+        // the campaign stands even if a notification cannot be delivered
+        for (_, email) in &reviewers {
+            if let Err(err) = self
+                .mail_svc
+                .send_access_review_opened(
+                    email,
+                    &review.name,
+                    &review.domain,
+                    assignments,
+                    review.deadline,
+                    &review_url,
+                )
+                .await
+            {
+                warn!("Failed to notify reviewer {} of access review {}: {}", email, review.id, err);
+            }
+        }
+
+        let items = self.review_repo.find_items(&review.id).await?;
+
+        Ok(AccessReviewDetail { review, items })
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::access_review::decide_access_review_item_request::DecideAccessReviewItemRequest,
+        services::redis_svc::RedisService,
+    },
+    domain::{
+        entities::access_review::{AccessReviewItem, ACCESS_REVIEW_REVOKE},
+        repositories::{
+            access_review_repo::AccessReviewRepository, redis_repo::RedisRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{common::constants::SUPER_ADMIN_ROLE, errors::app_error::AppError},
+};
+
+use super::review_items::{is_action, tx_is_last_super_admin};
+
+#[derive(Clone)]
+pub struct DecideAccessReviewItem<A, M, C> {
+    review_repo: Arc<A>,
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<A, M, C> DecideAccessReviewItem<A, M, C>
+where
+    A: AccessReviewRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        review_repo: Arc<A>,
+        user_role_repo: Arc<M>,
+        redis_svc: Arc<RedisService<C>>,
+    ) -> Self {
+        Self {
+            review_repo,
+            user_role_repo,
+            redis_svc,
+        }
+    }
+
+    // a reviewer confirms or revokes one assignment, revoking takes the role away right away
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        reviewer_id: &str,
+        id: &str,
+        item_id: &str,
+        req: DecideAccessReviewItemRequest,
+        manages_super_admins: bool,
+    ) -> Result<AccessReviewItem, AppError> {
+        req.validate()?;
+
+        if !is_action(&req.decision) {
+            return Err(AppError::ProcessError(
+                "The decision must be confirm or revoke".to_owned(),
+            ));
+        }
+
+        let mut tx = db_pool.begin().await?;
+
+        // completing the campaign waits for the decision and the other way around
+        let review = self.review_repo.tx_lock(&mut tx, id).await?;
+        if !review.is_reviewer(reviewer_id) {
+            return Err(AppError::ResourceNotFound);
+        }
+        if !review.is_open() || review.deadline <= chrono::Utc::now() {
+            return Err(AppError::ProcessError(
+                "The review is closed for decisions".to_owned(),
+            ));
+        }
+
+        let mut item = self.review_repo.tx_lock_item(&mut tx, &review.id, item_id).await?;
+        if !item.is_pending() {
+            return Err(AppError::ProcessError(
+                "The assignment was already reviewed".to_owned(),
+            ));
+        }
+        if item.user_id == reviewer_id {
+            return Err(AppError::ProcessError(
+                "Reviewers cannot certify their own assignments".to_owned(),
+            ));
+        }
+
+        let revoke = req.decision == ACCESS_REVIEW_REVOKE;
+        if revoke {
+            if item.role_name == SUPER_ADMIN_ROLE && !manages_super_admins {
+                return Err(AppError::Forbidden);
+            }
+            if tx_is_last_super_admin(&mut tx, self.user_role_repo.as_ref(), &item).await? {
+                return Err(AppError::ProcessError(
+                    "Cannot remove the last super admin".to_owned(),
+                ));
+            }
+
+            // the assignment may already be gone, the decision is recorded all the same
+            self.user_role_repo
+                .tx_delete(&mut tx, &item.user_id, &item.role_id, &item.domain)
+                .await?;
+        }
+
+        item.decide(&req.decision, Some(reviewer_id.to_string()), req.comment);
+        let item = self.review_repo.tx_update_item(&mut tx, &item).await?;
+
+        tx.commit().await?;
+
+        if revoke {
+            self.redis_svc.remove_current_user(&item.user_id).await?;
+        }
+
+        info!(
+            "Access review {}: role {} of user {} in {} {} by {}",
+            review.id, item.role_id, item.user_id, item.domain, item.decision, reviewer_id
+        );
+
+        Ok(item)
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_review::AccessReviewReport,
+        repositories::access_review_repo::AccessReviewRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::review_items::in_scope;
+
+#[derive(Clone)]
+pub struct ExportAccessReview<A> {
+    review_repo: Arc<A>,
+}
+
+impl<A> ExportAccessReview<A>
+where
+    A: AccessReviewRepository,
+{
+    pub fn new(review_repo: Arc<A>) -> Self {
+        Self { review_repo }
+    }
+
+    pub async fn execute(&self, domain: &str, id: &str) -> Result<AccessReviewReport, AppError> {
+        let review = self.review_repo.find_by_id(id).await?;
+
+        if !in_scope(&review, domain) {
+            return Err(AppError::ResourceNotFound);
+        }
+        if review.is_open() {
+            return Err(AppError::ProcessError(
+                "Only completed reviews can be exported".to_owned(),
+            ));
+        }
+
+        let items = self.review_repo.find_items(&review.id).await?;
+
+        Ok(AccessReviewReport::new(review, items))
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_review::AccessReviewDetail,
+        repositories::access_review_repo::AccessReviewRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::review_items::in_scope;
+
+#[derive(Clone)]
+pub struct GetAccessReviewById<A> {
+    review_repo: Arc<A>,
+}
+
+impl<A> GetAccessReviewById<A>
+where
+    A: AccessReviewRepository,
+{
+    pub fn new(review_repo: Arc<A>) -> Self {
+        Self { review_repo }
+    }
+
+    // reviewers get the holders under review, managers of the campaign's domain as well
+    pub async fn execute(
+        &self,
+        domain: &str,
+        id: &str,
+        user_id: &str,
+        is_manager: bool,
+    ) -> Result<AccessReviewDetail, AppError> {
+        let review = self.review_repo.find_by_id(id).await?;
+
+        let visible = review.is_reviewer(user_id) || (is_manager && in_scope(&review, domain));
+        if !visible {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        let items = self.review_repo.find_items(&review.id).await?;
+
+        Ok(AccessReviewDetail { review, items })
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_review::{AccessReview, ACCESS_REVIEW_COMPLETED, ACCESS_REVIEW_OPEN},
+        repositories::access_review_repo::AccessReviewRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetAccessReviews<A> {
+    review_repo: Arc<A>,
+}
+
+impl<A> GetAccessReviews<A>
+where
+    A: AccessReviewRepository,
+{
+    pub fn new(review_repo: Arc<A>) -> Self {
+        Self { review_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessReview>, AppError> {
+        let known = [ACCESS_REVIEW_OPEN, ACCESS_REVIEW_COMPLETED];
+        if status.is_some_and(|status| !known.contains(&status)) {
+            return Err(AppError::ProcessError(format!(
+                "Unknown status, expected one of {}",
+                known.join(", ")
+            )));
+        }
+
+        self.review_repo.find_by_domain(domain, status).await
+    }
+}
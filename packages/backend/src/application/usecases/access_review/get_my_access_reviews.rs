@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::access_review::AccessReview,
+        repositories::access_review_repo::AccessReviewRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetMyAccessReviews<A> {
+    review_repo: Arc<A>,
+}
+
+impl<A> GetMyAccessReviews<A>
+where
+    A: AccessReviewRepository,
+{
+    pub fn new(review_repo: Arc<A>) -> Self {
+        Self { review_repo }
+    }
+
+    // open campaigns waiting for the user's decisions
+    pub async fn execute(&self, reviewer_id: &str) -> Result<Vec<AccessReview>, AppError> {
+        self.review_repo.find_open_by_reviewer(reviewer_id).await
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::{mail_svc::MailService, redis_svc::RedisService},
+    infra::{
+        config::AppConfig,
+        repositories::{
+            pg_access_review_repo::PgAccessReviewRepository, pg_role_repo::PgRoleRepository,
+            pg_user_repo::PgUserRepository, pg_user_role_repo::PgUserRoleRepository,
+            redis_repo_impl::RedisRepositoryImpl, smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        },
+    },
+};
+
+use super::{
+    complete_access_review::CompleteAccessReview,
+    complete_overdue_access_reviews::CompleteOverdueAccessReviews,
+    create_access_review::CreateAccessReview,
+    decide_access_review_item::DecideAccessReviewItem,
+    export_access_review::ExportAccessReview, get_access_review_by_id::GetAccessReviewById,
+    get_access_reviews::GetAccessReviews, get_my_access_reviews::GetMyAccessReviews,
+};
+
+#[derive(Clone)]
+pub struct AccessReviewUsecase {
+    pub create_access_review:
+        Arc<CreateAccessReview<PgAccessReviewRepository, PgRoleRepository, PgUserRepository>>,
+    pub get_access_reviews: Arc<GetAccessReviews<PgAccessReviewRepository>>,
+    pub get_my_access_reviews: Arc<GetMyAccessReviews<PgAccessReviewRepository>>,
+    pub get_access_review_by_id: Arc<GetAccessReviewById<PgAccessReviewRepository>>,
+    pub decide_access_review_item: Arc<
+        DecideAccessReviewItem<PgAccessReviewRepository, PgUserRoleRepository, RedisRepositoryImpl>,
+    >,
+    pub complete_access_review: Arc<
+        CompleteAccessReview<PgAccessReviewRepository, PgUserRoleRepository, RedisRepositoryImpl>,
+    >,
+    pub complete_overdue_access_reviews: Arc<
+        CompleteOverdueAccessReviews<
+            PgAccessReviewRepository,
+            PgUserRoleRepository,
+            RedisRepositoryImpl,
+        >,
+    >,
+    pub export_access_review: Arc<ExportAccessReview<PgAccessReviewRepository>>,
+}
+
+impl AccessReviewUsecase {
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        review_repo: Arc<PgAccessReviewRepository>,
+        role_repo: Arc<PgRoleRepository>,
+        user_repo: Arc<PgUserRepository>,
+        user_role_repo: Arc<PgUserRoleRepository>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            create_access_review: Arc::new(CreateAccessReview::new(
+                cfg,
+                review_repo.clone(),
+                role_repo,
+                user_repo,
+                mail_svc,
+            )),
+            get_access_reviews: Arc::new(GetAccessReviews::new(review_repo.clone())),
+            get_my_access_reviews: Arc::new(GetMyAccessReviews::new(review_repo.clone())),
+            get_access_review_by_id: Arc::new(GetAccessReviewById::new(review_repo.clone())),
+            decide_access_review_item: Arc::new(DecideAccessReviewItem::new(
+                review_repo.clone(),
+                user_role_repo.clone(),
+                redis_svc.clone(),
+            )),
+            complete_access_review: Arc::new(CompleteAccessReview::new(
+                review_repo.clone(),
+                user_role_repo.clone(),
+                redis_svc.clone(),
+            )),
+            complete_overdue_access_reviews: Arc::new(CompleteOverdueAccessReviews::new(
+                review_repo.clone(),
+                user_role_repo,
+                redis_svc,
+            )),
+            export_access_review: Arc::new(ExportAccessReview::new(review_repo)),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub mod complete_access_review;
+pub mod complete_overdue_access_reviews;
+pub mod create_access_review;
+pub mod decide_access_review_item;
+pub mod export_access_review;
+pub mod get_access_review_by_id;
+pub mod get_access_reviews;
+pub mod get_my_access_reviews;
+pub mod init;
+pub mod review_items;
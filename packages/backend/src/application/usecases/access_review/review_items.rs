@@ -0,0 +1,82 @@
+use tracing::warn;
+
+use crate::{
+    domain::{
+        entities::access_review::{
+            AccessReview, AccessReviewItem, ACCESS_REVIEW_CONFIRM, ACCESS_REVIEW_REVOKE,
+        },
+        repositories::{
+            access_review_repo::AccessReviewRepository, user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{common::constants::SUPER_ADMIN_ROLE, errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+pub fn is_action(action: &str) -> bool {
+    action == ACCESS_REVIEW_CONFIRM || action == ACCESS_REVIEW_REVOKE
+}
+
+pub fn in_scope(review: &AccessReview, domain: &str) -> bool {
+    domain == GLOBAL_DOMAIN || review.domain == domain
+}
+
+// holders stay locked until commit so concurrent revocations cannot both pass the check
+pub async fn tx_is_last_super_admin<M>(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_role_repo: &M,
+    item: &AccessReviewItem,
+) -> Result<bool, AppError>
+where
+    M: UserRoleRepository,
+{
+    if item.role_name != SUPER_ADMIN_ROLE {
+        return Ok(false);
+    }
+
+    Ok(user_role_repo.tx_lock_holders(tx, &item.role_id).await? <= 1)
+}
+
+// applies the default action to every assignment nobody reviewed and completes the campaign,
+// returns the users whose roles were revoked. The last super admin is kept whatever the default.
+pub async fn tx_close_review<A, M>(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    review_repo: &A,
+    user_role_repo: &M,
+    mut review: AccessReview,
+) -> Result<(AccessReview, Vec<String>), AppError>
+where
+    A: AccessReviewRepository,
+    M: UserRoleRepository,
+{
+    let mut revoked = Vec::new();
+
+    for mut item in review_repo.tx_lock_pending_items(tx, &review.id).await? {
+        if review.default_action == ACCESS_REVIEW_REVOKE {
+            if tx_is_last_super_admin(tx, user_role_repo, &item).await? {
+                warn!("Kept the last super admin {} unreviewed in access review {}", item.user_id, review.id);
+                item.decide(ACCESS_REVIEW_CONFIRM, None, Some("Kept as the last super admin".to_string()));
+                review_repo.tx_update_item(tx, &item).await?;
+                continue;
+            }
+
+            // the assignment may already be gone, the decision is recorded all the same
+            if user_role_repo
+                .tx_delete(tx, &item.user_id, &item.role_id, &item.domain)
+                .await?
+            {
+                revoked.push(item.user_id.clone());
+            }
+        }
+
+        item.decide(&review.default_action, None, None);
+        review_repo.tx_update_item(tx, &item).await?;
+    }
+
+    review.complete();
+    let review = review_repo.tx_update(tx, &review).await?;
+
+    revoked.sort();
+    revoked.dedup();
+
+    Ok((review, revoked))
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::repositories::user_session_repo::UserSessionRepository,
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct CheckRecentAuth<S> {
+    reauth_window_secs: i64,
+    user_session_repo: Arc<S>,
+}
+
+impl<S> CheckRecentAuth<S>
+where
+    S: UserSessionRepository,
+{
+    pub fn new(reauth_window_secs: i64, user_session_repo: Arc<S>) -> Self {
+        Self {
+            reauth_window_secs,
+            user_session_repo,
+        }
+    }
+
+    // guard for sensitive operations, an access token alone may be up to an hour old. The window
+    // belongs to the session holding the current refresh token, signing in elsewhere replaces it
+    pub async fn execute(&self, user_id: &str, session_token: &str) -> Result<(), AppError> {
+        let session = self
+            .user_session_repo
+            .find_by_user_id(user_id)
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::ReauthenticationRequired,
+                _ => err,
+            })?;
+
+        if session_token.is_empty()
+            || session.refresh_token != session_token
+            || !session.is_recently_authenticated(self.reauth_window_secs)
+        {
+            return Err(AppError::ReauthenticationRequired);
+        }
+
+        Ok(())
+    }
+}
@@ -3,14 +3,17 @@ use std::sync::Arc;
 use validator::Validate;
 
 use crate::{
-    application::dto::auth::email_request::EmailRegisterRequest,
+    application::{
+        dto::auth::email_request::EmailRegisterRequest,
+        services::registration_svc::{RegistrationService, SignupAttempt},
+    },
     domain::{
         entities::{user::User, user_oauth_provider::UserOauthProvider, user_role::UserRole},
         repositories::{role_repo::RoleRepository, user_repo::UserRepository},
     },
     infra::{
-        errors::app_error::AppError, oauth2::constants::EMAIL_PROVIDER,
-        utils::password::hash_password,
+        errors::app_error::AppError, oauth2::constants::EMAIL_PROVIDER, rbac::GLOBAL_DOMAIN,
+        repositories::pg_invite_repo::PgInviteRepository, utils::password::hash_password,
     },
 };
 
@@ -18,6 +21,7 @@ use crate::{
 pub struct EmailRegister<U, R> {
     user_repo: Arc<U>,
     role_repo: Arc<R>,
+    registration_svc: Arc<RegistrationService<PgInviteRepository>>,
 }
 
 impl<U, R> EmailRegister<U, R>
@@ -25,10 +29,15 @@ where
     U: UserRepository,
     R: RoleRepository,
 {
-    pub fn new(user_repo: Arc<U>, role_repo: Arc<R>) -> Self {
+    pub fn new(
+        user_repo: Arc<U>,
+        role_repo: Arc<R>,
+        registration_svc: Arc<RegistrationService<PgInviteRepository>>,
+    ) -> Self {
         Self {
             user_repo,
             role_repo,
+            registration_svc,
         }
     }
 
@@ -36,9 +45,21 @@ where
         &self,
         db_pool: &sqlx::PgPool,
         req: EmailRegisterRequest,
+        client_ip: &str,
     ) -> Result<User, AppError> {
         req.validate()?;
 
+        let invite = self
+            .registration_svc
+            .check_signup(&SignupAttempt {
+                email: &req.email,
+                hosted_domain: None,
+                invite_token: req.invite_token.as_deref(),
+                email_verified: false,
+                client_ip,
+            })
+            .await?;
+
         if self.user_repo.find_by_email(&req.email).await.is_ok() {
             return Err(AppError::UserEmailAlreadyExist);
         }
@@ -67,7 +88,10 @@ where
             EMAIL_PROVIDER.to_string(),
             new_user.id.clone(),
         );
-        let user_role = UserRole::new(new_user.id.clone(), default_role.id.clone());
+        let user_role = UserRole::new(new_user.id.clone(), default_role.id.clone(), GLOBAL_DOMAIN.to_string());
+        self.registration_svc
+            .tx_check_default_role(&mut tx, &new_user.id, &default_role.id)
+            .await?;
 
         let (user, _user_oauth_provider, _user_role) = self
             .user_repo
@@ -75,6 +99,10 @@ where
             .await
             .map_err(|err| AppError::ProcessError(err.to_string()))?;
 
+        self.registration_svc
+            .tx_accept_invite(&mut tx, invite, &user.id)
+            .await?;
+
         tx.commit().await?;
 
         Ok(user)
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::{redis_svc::RedisService, role_constraint_svc::RoleConstraintService},
+    domain::{
+        entities::user_role::UserRole,
+        repositories::{redis_repo::RedisRepository, role_repo::RoleRepository, user_repo::UserRepository},
+    },
+    infra::{
+        common::constants::SUPER_ADMIN_ROLE,
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+#[derive(Clone)]
+pub struct GrantSuperAdmin<U, R, C> {
+    user_repo: Arc<U>,
+    role_repo: Arc<R>,
+    redis_svc: Arc<RedisService<C>>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<U, R, C> GrantSuperAdmin<U, R, C>
+where
+    U: UserRepository,
+    R: RoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        user_repo: Arc<U>,
+        role_repo: Arc<R>,
+        redis_svc: Arc<RedisService<C>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            user_repo,
+            role_repo,
+            redis_svc,
+            role_constraint_svc,
+        }
+    }
+
+    pub async fn execute(&self, db_pool: &sqlx::PgPool, user_id: &str) -> Result<(), AppError> {
+        let user = self.user_repo.find_by_id(user_id).await?;
+        let super_role = self.role_repo.find_by_name(SUPER_ADMIN_ROLE, GLOBAL_DOMAIN).await?;
+
+        let mut tx = db_pool.begin().await?;
+        self.role_constraint_svc
+            .check_assignment(&mut tx, &user.id, &super_role.id, GLOBAL_DOMAIN)
+            .await?;
+
+        self.user_repo
+            .tx_add_role(
+                &mut tx,
+                &UserRole::new(user.id.clone(), super_role.id, GLOBAL_DOMAIN.to_string()),
+            )
+            .await?;
+        tx.commit().await?;
+
+        // cached roles would keep serving the old permissions until the cache expires
+        self.redis_svc.remove_current_user(&user.id).await?;
+
+        Ok(())
+    }
+}
@@ -1,13 +1,19 @@
 use std::sync::Arc;
 
 use crate::{
-    application::services::{oauth_svc::OauthService, redis_svc::RedisService},
+    application::services::{
+        oauth_svc::OauthService, redis_svc::RedisService, registration_svc::RegistrationService,
+        role_constraint_svc::RoleConstraintService,
+    },
     infra::{
         config::AppConfig,
         rbac::Rbac,
         repositories::{
-            pg_oauth_provider::PgOauthProviderRepository, pg_role_repo::PgRoleRepository,
-            pg_user_repo::PgUserRepository, pg_user_session::PgUserSessionRepository,
+            pg_app_setup_repo::PgAppSetupRepository, pg_invite_repo::PgInviteRepository,
+            pg_oauth_provider::PgOauthProviderRepository, pg_policy_repo::PgPolicyRepository,
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_repo::PgUserRepository, pg_user_role_repo::PgUserRoleRepository,
+            pg_user_session::PgUserSessionRepository,
             redis_repo_impl::RedisRepositoryImpl,
         },
         utils::jwt_maker::JwtMaker,
@@ -15,9 +21,10 @@ use crate::{
 };
 
 use super::{
-    email_login::EmailLogin, email_register::EmailRegister, get_google_auth_url::GetGoogleAuthUrl,
-    oauth2_login::Oauth2Login, oauth2_logout::Oauth2Logout, refresh_oauth_token::RefreshOauthToken,
-    seed_super_admin::SeedSuperAdmin,
+    check_recent_auth::CheckRecentAuth, email_login::EmailLogin, email_register::EmailRegister, get_google_auth_url::GetGoogleAuthUrl,
+    grant_super_admin::GrantSuperAdmin, issue_bootstrap_token::IssueBootstrapToken,
+    oauth2_login::Oauth2Login, oauth2_logout::Oauth2Logout, reauthenticate::Reauthenticate,
+    refresh_oauth_token::RefreshOauthToken, seed_super_admin::SeedSuperAdmin,
 };
 
 #[derive(Clone)]
@@ -48,7 +55,19 @@ pub struct AuthUsecase {
             PgOauthProviderRepository,
         >,
     >,
-    pub seed_super_admin: Arc<SeedSuperAdmin<PgUserRepository, PgRoleRepository>>,
+    pub seed_super_admin: Arc<
+        SeedSuperAdmin<
+            PgUserRepository,
+            PgRoleRepository,
+            PgPolicyRepository,
+            PgAppSetupRepository,
+            RedisRepositoryImpl,
+        >,
+    >,
+    pub issue_bootstrap_token: Arc<IssueBootstrapToken<PgAppSetupRepository, RedisRepositoryImpl>>,
+    pub grant_super_admin: Arc<
+        GrantSuperAdmin<PgUserRepository, PgRoleRepository, RedisRepositoryImpl>,
+    >,
     pub refresh_oauth_token: Arc<
         RefreshOauthToken<
             PgUserRepository,
@@ -57,9 +76,19 @@ pub struct AuthUsecase {
             PgOauthProviderRepository,
         >,
     >,
+    pub check_recent_auth: Arc<CheckRecentAuth<PgUserSessionRepository>>,
+    pub reauthenticate: Arc<
+        Reauthenticate<
+            PgUserRepository,
+            PgRoleRepository,
+            PgUserSessionRepository,
+            PgOauthProviderRepository,
+        >,
+    >,
 }
 
 impl AuthUsecase {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cfg: Arc<AppConfig>,
         oauth_svc: Arc<
@@ -76,6 +105,12 @@ impl AuthUsecase {
         user_session_repo: Arc<PgUserSessionRepository>,
         jwt_maker: Arc<JwtMaker>,
         redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        registration_svc: Arc<RegistrationService<PgInviteRepository>>,
+        app_setup_repo: Arc<PgAppSetupRepository>,
+        policy_repo: Arc<PgPolicyRepository>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
     ) -> Self {
         let get_google_auth_url = Arc::new(GetGoogleAuthUrl::new(
             cfg.google_client_id.clone(),
@@ -87,7 +122,11 @@ impl AuthUsecase {
             oauth_svc.clone(),
             redis_svc.clone(),
         ));
-        let email_register = Arc::new(EmailRegister::new(user_repo.clone(), role_repo.clone()));
+        let email_register = Arc::new(EmailRegister::new(
+            user_repo.clone(),
+            role_repo.clone(),
+            registration_svc.clone(),
+        ));
         let email_login = Arc::new(EmailLogin::new(
             user_repo.clone(),
             jwt_maker.clone(),
@@ -96,13 +135,37 @@ impl AuthUsecase {
         let seed_super_admin = Arc::new(SeedSuperAdmin::new(
             user_repo.clone(),
             role_repo.clone(),
+            policy_repo.clone(),
+            app_setup_repo.clone(),
+            redis_svc.clone(),
             rbac.clone(),
         ));
+        let issue_bootstrap_token = Arc::new(IssueBootstrapToken::new(
+            cfg.bootstrap_token_ttl_secs,
+            app_setup_repo.clone(),
+            redis_svc.clone(),
+        ));
+        let grant_super_admin = Arc::new(GrantSuperAdmin::new(
+            user_repo.clone(),
+            role_repo.clone(),
+            redis_svc.clone(),
+            role_constraint_svc.clone(),
+        ));
         let refresh_oauth_token = Arc::new(RefreshOauthToken::new(
             jwt_maker.clone(),
             user_session_repo.clone(),
             oauth_svc.clone(),
         ));
+        let check_recent_auth = Arc::new(CheckRecentAuth::new(
+            cfg.reauth_window_secs,
+            user_session_repo.clone(),
+        ));
+        let reauthenticate = Arc::new(Reauthenticate::new(
+            cfg.reauth_window_secs,
+            user_repo.clone(),
+            user_session_repo.clone(),
+            oauth_svc.clone(),
+        ));
 
         Self {
             get_google_auth_url,
@@ -111,7 +174,11 @@ impl AuthUsecase {
             email_register,
             email_login,
             seed_super_admin,
+            issue_bootstrap_token,
+            grant_super_admin,
             refresh_oauth_token,
+            check_recent_auth,
+            reauthenticate,
         }
     }
 }
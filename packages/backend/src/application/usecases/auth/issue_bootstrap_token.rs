@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{app_setup_repo::AppSetupRepository, redis_repo::RedisRepository},
+    infra::{
+        errors::app_error::AppError,
+        utils::token::{generate_token, hash_token},
+    },
+};
+
+#[derive(Clone)]
+pub struct IssueBootstrapToken<A, R> {
+    token_ttl_secs: u64,
+    app_setup_repo: Arc<A>,
+    redis_svc: Arc<RedisService<R>>,
+}
+
+impl<A, R> IssueBootstrapToken<A, R>
+where
+    A: AppSetupRepository,
+    R: RedisRepository,
+{
+    pub fn new(token_ttl_secs: u64, app_setup_repo: Arc<A>, redis_svc: Arc<RedisService<R>>) -> Self {
+        Self {
+            token_ttl_secs,
+            app_setup_repo,
+            redis_svc,
+        }
+    }
+
+    // returns the plain token to print at startup, None once the first super admin exists
+    pub async fn execute(&self) -> Result<Option<String>, AppError> {
+        if self.app_setup_repo.is_completed().await? {
+            return Ok(None);
+        }
+
+        // only the hash is stored, tokens of other instances and earlier starts stay valid until
+        // they expire
+        let token = generate_token();
+        self.redis_svc
+            .add_bootstrap_token_hash(&hash_token(&token), self.token_ttl_secs)
+            .await?;
+
+        Ok(Some(token))
+    }
+}
@@ -1,8 +1,12 @@
+pub mod check_recent_auth;
 pub mod email_login;
 pub mod email_register;
 pub mod get_google_auth_url;
+pub mod grant_super_admin;
 pub mod init;
+pub mod issue_bootstrap_token;
 pub mod oauth2_login;
 pub mod oauth2_logout;
+pub mod reauthenticate;
 pub mod refresh_oauth_token;
 pub mod seed_super_admin;
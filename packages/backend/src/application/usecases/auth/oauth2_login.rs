@@ -31,9 +31,13 @@ where
         db_pool: &sqlx::PgPool,
         provider: String,
         req: Oauth2Request,
+        client_ip: &str,
     ) -> Result<(String, String), AppError> {
         if provider == GOOGLE_PROVIDER {
-            let google_resp = self.oauth_svc.google_login(db_pool, &req.code).await?;
+            let google_resp = self
+                .oauth_svc
+                .google_login(db_pool, &req.code, client_ip)
+                .await?;
 
             return Ok((google_resp.id_token, google_resp.refresh_token));
         }
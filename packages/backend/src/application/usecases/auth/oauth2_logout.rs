@@ -8,7 +8,7 @@ use crate::{
     },
     infra::{
         errors::app_error::AppError,
-        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER},
+        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER, SAML_PROVIDER},
         repositories::redis_repo_impl::RedisRepositoryImpl,
     },
 };
@@ -55,7 +55,8 @@ where
                     .google_revoke_token(&user_session.access_token)
                     .await?;
             }
-            EMAIL_PROVIDER => {}
+            // no single logout, the IdP session is left alone
+            EMAIL_PROVIDER | SAML_PROVIDER => {}
             _ => return Err(AppError::InvalidOauthProvider),
         }
 
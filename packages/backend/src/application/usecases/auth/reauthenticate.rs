@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        dto::auth::reauth_request::{ReauthRequest, ReauthResponse},
+        services::oauth_svc::OauthService,
+    },
+    domain::{
+        entities::user::UserFull,
+        repositories::{
+            oauth_provider_repo::OauthProviderRepository, role_repo::RoleRepository,
+            user_repo::UserRepository, user_session_repo::UserSessionRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER, SAML_PROVIDER},
+        utils::password::verify_password,
+    },
+};
+
+#[derive(Clone)]
+pub struct Reauthenticate<U, R, S, O> {
+    reauth_window_secs: i64,
+    user_repo: Arc<U>,
+    user_session_repo: Arc<S>,
+    oauth_svc: Arc<OauthService<U, R, S, O>>,
+}
+
+impl<U, R, S, O> Reauthenticate<U, R, S, O>
+where
+    U: UserRepository,
+    R: RoleRepository,
+    S: UserSessionRepository,
+    O: OauthProviderRepository,
+{
+    pub fn new(
+        reauth_window_secs: i64,
+        user_repo: Arc<U>,
+        user_session_repo: Arc<S>,
+        oauth_svc: Arc<OauthService<U, R, S, O>>,
+    ) -> Self {
+        Self {
+            reauth_window_secs,
+            user_repo,
+            user_session_repo,
+            oauth_svc,
+        }
+    }
+
+    // proves the user is still at the keyboard and restarts the recent-auth window
+    pub async fn execute(
+        &self,
+        current_user: &UserFull,
+        session_token: &str,
+        req: ReauthRequest,
+    ) -> Result<ReauthResponse, AppError> {
+        match current_user.oauth_provider.provider.as_str() {
+            EMAIL_PROVIDER => {
+                let password = req.password.filter(|password| !password.is_empty()).ok_or_else(
+                    || AppError::ProcessError("Password is required".to_string()),
+                )?;
+
+                let user = self.user_repo.find_by_id(&current_user.user.id).await?;
+
+                tokio::task::spawn_blocking(move || {
+                    verify_password(
+                        &user.password_hash.unwrap_or_default(),
+                        password.as_bytes(),
+                    )
+                })
+                .await?
+                .map_err(|_err| AppError::UnauthorizedError(String::from("Invalid Credentials")))?;
+            }
+            GOOGLE_PROVIDER => {
+                let code = req.code.filter(|code| !code.is_empty()).ok_or_else(|| {
+                    AppError::ProcessError("Authorization code is required".to_string())
+                })?;
+
+                let resp = self.oauth_svc.exchange_google_code(&code).await?;
+                let user_info = self
+                    .oauth_svc
+                    .fetch_google_user(&resp.id_token, &resp.access_token)
+                    .await?;
+
+                // the consent must come from the same Google account that owns this session
+                if user_info.sub != current_user.oauth_provider.provider_user_id {
+                    return Err(AppError::UnauthorizedError(
+                        "Google account does not match the signed in user".to_string(),
+                    ));
+                }
+            }
+            // the IdP owns the credentials, signing in through it again restarts the window
+            SAML_PROVIDER => {
+                return Err(AppError::ProcessError(
+                    "Sign in again through your identity provider".to_string(),
+                ));
+            }
+            _ => return Err(AppError::InvalidOauthProvider),
+        }
+
+        let mut session = self
+            .user_session_repo
+            .find_by_user_id(&current_user.user.id)
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => {
+                    AppError::UnauthorizedError("Invalid Session, try to relogin".to_string())
+                }
+                _ => AppError::ProcessError(err.to_string()),
+            })?;
+
+        // only the session that signed in last can restart the window
+        if session_token.is_empty() || session.refresh_token != session_token {
+            return Err(AppError::UnauthorizedError(
+                "Invalid Session, try to relogin".to_string(),
+            ));
+        }
+
+        session.mark_authenticated();
+        self.user_session_repo.update_authenticated_at(&session).await?;
+
+        Ok(ReauthResponse {
+            authenticated_at: session.authenticated_at,
+            reauth_expires_at: session.authenticated_at
+                + chrono::Duration::seconds(self.reauth_window_secs),
+        })
+    }
+}
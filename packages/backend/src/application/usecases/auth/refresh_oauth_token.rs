@@ -8,7 +8,7 @@ use crate::{
     },
     infra::{
         errors::app_error::AppError,
-        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER},
+        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER, SAML_PROVIDER},
         utils::jwt_maker::JwtMaker,
     },
 };
@@ -45,7 +45,8 @@ where
     ) -> Result<(String, String), AppError> {
         match provider {
             GOOGLE_PROVIDER => self.google_refresh_token(refresh_token).await,
-            EMAIL_PROVIDER => self.email_refresh_token(refresh_token).await,
+            // SAML sessions run on our own JWTs just like email ones
+            EMAIL_PROVIDER | SAML_PROVIDER => self.email_refresh_token(refresh_token).await,
             _ => Err(AppError::InvalidOauthProvider),
         }
     }
@@ -65,10 +66,10 @@ where
                 _ => AppError::ProcessError(err.to_string()),
             })?;
 
-        if let Some(expire_at) = session.expires_at {
-            if expire_at < chrono::Utc::now() {
-                return Err(AppError::RefreshTokenExpired);
-            }
+        if let Some(expire_at) = session.expires_at
+            && expire_at < chrono::Utc::now()
+        {
+            return Err(AppError::RefreshTokenExpired);
         }
 
         let r = self.oauth_svc.google_refresh_token(refresh_token).await?;
@@ -100,10 +101,10 @@ where
                 _ => AppError::ProcessError(err.to_string()),
             })?;
 
-        if let Some(expire_at) = session.expires_at {
-            if expire_at < chrono::Utc::now() {
-                return Err(AppError::RefreshTokenExpired);
-            }
+        if let Some(expire_at) = session.expires_at
+            && expire_at < chrono::Utc::now()
+        {
+            return Err(AppError::RefreshTokenExpired);
         }
 
         let new_access_token = self.jwt_maker.make_token(claims.sub.clone(), 1)?;
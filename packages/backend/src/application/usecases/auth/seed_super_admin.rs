@@ -1,38 +1,59 @@
 use std::sync::Arc;
 
-use casbin::MgmtApi;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    application::dto::auth::email_request::EmailRegisterRequest,
+    application::{dto::auth::email_request::EmailRegisterRequest, services::redis_svc::RedisService},
     domain::{
         entities::{
             role::Role, user::User, user_oauth_provider::UserOauthProvider, user_role::UserRole,
         },
-        repositories::{role_repo::RoleRepository, user_repo::UserRepository},
+        repositories::{
+            app_setup_repo::AppSetupRepository, policy_repo::PolicyRepository,
+            redis_repo::RedisRepository, role_repo::RoleRepository, user_repo::UserRepository,
+        },
     },
     infra::{
         common::constants::SUPER_ADMIN_ROLE, errors::app_error::AppError,
-        oauth2::constants::EMAIL_PROVIDER, rbac::Rbac, utils::password::hash_password,
+        oauth2::constants::EMAIL_PROVIDER,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, GLOBAL_DOMAIN},
+        utils::{password::hash_password, token::hash_token},
     },
 };
 
 #[derive(Clone)]
-pub struct SeedSuperAdmin<U, R> {
+pub struct SeedSuperAdmin<U, R, Q, A, C> {
     user_repo: Arc<U>,
     role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    app_setup_repo: Arc<A>,
+    redis_svc: Arc<RedisService<C>>,
     rbac: Arc<Rbac>,
 }
 
-impl<U, R> SeedSuperAdmin<U, R>
+impl<U, R, Q, A, C> SeedSuperAdmin<U, R, Q, A, C>
 where
     U: UserRepository,
     R: RoleRepository,
+    Q: PolicyRepository,
+    A: AppSetupRepository,
+    C: RedisRepository,
 {
-    pub fn new(user_repo: Arc<U>, role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+    pub fn new(
+        user_repo: Arc<U>,
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        app_setup_repo: Arc<A>,
+        redis_svc: Arc<RedisService<C>>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
         Self {
             user_repo,
             role_repo,
+            policy_repo,
+            app_setup_repo,
+            redis_svc,
             rbac,
         }
     }
@@ -40,8 +61,21 @@ where
     pub async fn execute(
         &self,
         db_pool: &sqlx::PgPool,
+        bootstrap_token: &str,
         req: EmailRegisterRequest,
     ) -> Result<(), AppError> {
+        if self.app_setup_repo.is_completed().await? {
+            return Err(AppError::SetupCompleted);
+        }
+
+        // a missing or expired token reads the same as a wrong one
+        let token_hash = hash_token(bootstrap_token);
+        if bootstrap_token.is_empty() || !self.redis_svc.has_bootstrap_token_hash(&token_hash).await? {
+            return Err(AppError::Unauthorized);
+        }
+
+        req.validate()?;
+
         // Dissallow existing user to be Super Admin
         if self.user_repo.find_by_email(&req.email).await.is_ok() {
             return Err(AppError::ResourceExist(format!(
@@ -51,8 +85,9 @@ where
         }
 
         let mut tx = db_pool.begin().await?;
+        let mut super_policy = None;
 
-        let super_role = match self.role_repo.find_by_name(SUPER_ADMIN_ROLE).await {
+        let super_role = match self.role_repo.find_by_name(SUPER_ADMIN_ROLE, GLOBAL_DOMAIN).await {
             Ok(role) => role,
             Err(err) => match err {
                 AppError::SqlxError(sqlx::Error::RowNotFound) => {
@@ -60,16 +95,18 @@ where
                         Uuid::new_v4().to_string(),
                         SUPER_ADMIN_ROLE.to_string(),
                         false,
+                        GLOBAL_DOMAIN.to_string(),
                     );
 
-                    let mut enforcer = self.rbac.enforcer.write().await;
-
-                    let policy = vec![
-                        new_super_admin_role.id.clone(),
-                        "*".to_string(),
-                        "*".to_string(),
-                    ];
-                    enforcer.add_policy(policy).await?;
+                    // written with the role and the user, the enforcer learns about it after commit
+                    let policy = Rbac::permission_policy(
+                        &new_super_admin_role.id,
+                        GLOBAL_DOMAIN,
+                        "*:*",
+                        EFFECT_ALLOW,
+                    )?;
+                    self.policy_repo.tx_add_rules(&mut tx, "p", std::slice::from_ref(&policy)).await?;
+                    super_policy = Some(policy);
 
                     self.role_repo
                         .tx_create(&mut tx, new_super_admin_role)
@@ -90,16 +127,34 @@ where
             EMAIL_PROVIDER.to_string(),
             new_user.id.clone(),
         );
-        let user_role = UserRole::new(new_user.id.clone(), super_role.id.clone());
+        let user_role = UserRole::new(new_user.id.clone(), super_role.id.clone(), GLOBAL_DOMAIN.to_string());
 
-        let (_user, _user_oauth_provider, _user_role) = self
+        let (user, _user_oauth_provider, _user_role) = self
             .user_repo
             .tx_register_user(&mut tx, &new_user, &user_oauth_provider, &user_role)
             .await
             .map_err(|err| AppError::ProcessError(err.to_string()))?;
 
+        // fails when a concurrent request completed the setup first, rolling this one back
+        self.app_setup_repo.tx_complete(&mut tx, &user.id).await?;
+
         tx.commit().await?;
 
+        if let Some(policy) = super_policy {
+            let mut enforcer = self.rbac.enforcer.write().await;
+            self.rbac.apply_committed(
+                &mut enforcer,
+                CommittedRules {
+                    added_policies: vec![policy],
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+
+        // tokens of other instances are left to expire, the completed setup refuses them
+        self.redis_svc.remove_bootstrap_token_hash(&token_hash).await?;
+
         Ok(())
     }
 }
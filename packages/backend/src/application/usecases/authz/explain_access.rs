@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::dto::authz::explain_access_request::ExplainAccessRequest,
+    domain::{
+        entities::{access_context::AccessContext, access_decision::AccessDecision},
+        repositories::role_repo::RoleRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
+};
+
+use super::subject::resolve_subject_roles;
+
+#[derive(Clone)]
+pub struct ExplainAccess<R> {
+    role_repo: Arc<R>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R> ExplainAccess<R>
+where
+    R: RoleRepository,
+{
+    pub fn new(role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { role_repo, rbac }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        req: ExplainAccessRequest,
+    ) -> Result<AccessDecision, AppError> {
+        req.validate()?;
+
+        let roles = resolve_subject_roles(
+            self.role_repo.as_ref(),
+            domain,
+            req.user_id.as_deref(),
+            req.role_ids.as_deref(),
+        )
+        .await?;
+        let ctx = AccessContext::new(req.user_id.as_deref().unwrap_or_default(), None)
+            .with_resource(req.resource.clone().unwrap_or_default());
+
+        let decision = self
+            .rbac
+            .explain_access(&roles, domain, &req.object, &req.action, &ctx)
+            .await?;
+
+        Ok(decision)
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::dto::authz::explain_access_request::AccessMatrixRequest,
+    domain::{
+        entities::{access_context::AccessContext, access_decision::AccessMatrix},
+        repositories::{permission_repo::PermissionRepository, role_repo::RoleRepository},
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
+};
+
+use super::subject::resolve_subject_roles;
+
+#[derive(Clone)]
+pub struct GetAccessMatrix<R, P> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R, P> GetAccessMatrix<R, P>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+{
+    pub fn new(role_repo: Arc<R>, permission_repo: Arc<P>, rbac: Arc<Rbac>) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            rbac,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        req: AccessMatrixRequest,
+    ) -> Result<AccessMatrix, AppError> {
+        req.validate()?;
+
+        let roles = resolve_subject_roles(
+            self.role_repo.as_ref(),
+            domain,
+            req.user_id.as_deref(),
+            req.role_ids.as_deref(),
+        )
+        .await?;
+        let ctx = AccessContext::new(req.user_id.as_deref().unwrap_or_default(), None)
+            .with_resource(req.resource.clone().unwrap_or_default());
+
+        let pairs: Vec<(String, String)> = match (req.objects, req.actions) {
+            (Some(objects), Some(actions)) => objects
+                .iter()
+                .flat_map(|object| actions.iter().map(|action| (object.clone(), action.clone())))
+                .collect(),
+            (None, None) => self
+                .permission_repo
+                .find_all()
+                .await?
+                .into_iter()
+                .map(|permission| (permission.name, permission.action))
+                .collect(),
+            _ => {
+                return Err(AppError::ProcessError(
+                    "Provide both objects and actions, or neither to check the registry".to_owned(),
+                ));
+            }
+        };
+
+        let matrix = self.rbac.access_matrix(&roles, domain, &pairs, &ctx).await?;
+
+        Ok(matrix)
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::infra::{
+    rbac::Rbac,
+    repositories::{pg_permission_repo::PgPermissionRepository, pg_role_repo::PgRoleRepository},
+};
+
+use super::{explain_access::ExplainAccess, get_access_matrix::GetAccessMatrix};
+
+#[derive(Clone)]
+pub struct AuthzUsecase {
+    pub explain_access: Arc<ExplainAccess<PgRoleRepository>>,
+    pub get_access_matrix: Arc<GetAccessMatrix<PgRoleRepository, PgPermissionRepository>>,
+}
+
+impl AuthzUsecase {
+    pub fn new(
+        role_repo: Arc<PgRoleRepository>,
+        permission_repo: Arc<PgPermissionRepository>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            explain_access: Arc::new(ExplainAccess::new(role_repo.clone(), rbac.clone())),
+            get_access_matrix: Arc::new(GetAccessMatrix::new(
+                role_repo.clone(),
+                permission_repo.clone(),
+                rbac.clone(),
+            )),
+        }
+    }
+}
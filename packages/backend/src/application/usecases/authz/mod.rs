@@ -0,0 +1,4 @@
+pub mod explain_access;
+pub mod get_access_matrix;
+pub mod init;
+pub mod subject;
@@ -0,0 +1,56 @@
+use crate::{
+    domain::{entities::role::AssignedRole, repositories::role_repo::RoleRepository},
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+// roles of the subject being explained, a role set is treated as assigned where each role lives
+pub async fn resolve_subject_roles<R>(
+    role_repo: &R,
+    domain: &str,
+    user_id: Option<&str>,
+    role_ids: Option<&[String]>,
+) -> Result<Vec<AssignedRole>, AppError>
+where
+    R: RoleRepository,
+{
+    match (user_id, role_ids) {
+        (Some(user_id), None) => {
+            // assignments in other domains are none of this domain's business, and a user
+            // without one in the domain reads as unknown here
+            let roles: Vec<AssignedRole> = role_repo
+                .get_roles_by_user_id(user_id)
+                .await?
+                .into_iter()
+                .filter(|role| {
+                    role.assigned_domain == GLOBAL_DOMAIN || role.assigned_domain == domain
+                })
+                .collect();
+            if !roles.iter().any(|role| role.assigned_domain == domain) {
+                return Err(AppError::ResourceNotFound);
+            }
+
+            Ok(roles)
+        }
+        (None, Some(role_ids)) => {
+            let mut roles = Vec::with_capacity(role_ids.len());
+            for role_id in role_ids {
+                let role = role_repo.find_by_id(role_id).await?;
+                if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+                    return Err(AppError::ResourceNotFound);
+                }
+
+                roles.push(AssignedRole {
+                    assigned_domain: role.domain.clone(),
+                    role,
+                    starts_at: None,
+                    expires_at: None,
+                });
+            }
+
+            Ok(roles)
+        }
+        _ => Err(AppError::ProcessError(
+            "Provide either user_id or role_ids".to_owned(),
+        )),
+    }
+}
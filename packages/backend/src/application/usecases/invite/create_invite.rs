@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::{dto::invite::create_invite_request::CreateInviteRequest, services::mail_svc::MailService},
+    domain::{
+        entities::invite::Invite,
+        repositories::{invite_repo::InviteRepository, user_repo::UserRepository},
+    },
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        repositories::smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        utils::token::{generate_token, hash_token},
+    },
+};
+
+#[derive(Clone)]
+pub struct CreateInvite<I, U> {
+    cfg: Arc<AppConfig>,
+    invite_repo: Arc<I>,
+    user_repo: Arc<U>,
+    mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+}
+
+impl<I, U> CreateInvite<I, U>
+where
+    I: InviteRepository,
+    U: UserRepository,
+{
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        invite_repo: Arc<I>,
+        user_repo: Arc<U>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            cfg,
+            invite_repo,
+            user_repo,
+            mail_svc,
+        }
+    }
+
+    pub async fn execute(&self, invited_by: &str, req: CreateInviteRequest) -> Result<Invite, AppError> {
+        req.validate()?;
+
+        let email = req.email.trim().to_lowercase();
+        if self.user_repo.find_by_email(&email).await.is_ok() {
+            return Err(AppError::UserEmailAlreadyExist);
+        }
+
+        let token = generate_token();
+        let invite = Invite::new(
+            email,
+            hash_token(&token),
+            invited_by.to_string(),
+            req.valid_days.unwrap_or(7),
+        );
+        let invite = self.invite_repo.create(&invite).await?;
+
+        let signup_url = format!(
+            "{}/signup?invite={}",
+            self.cfg.public_app_url.trim_end_matches('/'),
+            token
+        );
+        self.mail_svc
+            .send_invite(&invite.email, &signup_url, invite.expires_at)
+            .await?;
+
+        Ok(invite)
+    }
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use crate::{domain::repositories::invite_repo::InviteRepository, infra::errors::app_error::AppError};
+
+#[derive(Clone)]
+pub struct DeleteInvite<I> {
+    invite_repo: Arc<I>,
+}
+
+impl<I> DeleteInvite<I>
+where
+    I: InviteRepository,
+{
+    pub fn new(invite_repo: Arc<I>) -> Self {
+        Self { invite_repo }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<(), AppError> {
+        self.invite_repo.delete(id).await
+    }
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{entities::invite::Invite, repositories::invite_repo::InviteRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetAllInvites<I> {
+    invite_repo: Arc<I>,
+}
+
+impl<I> GetAllInvites<I>
+where
+    I: InviteRepository,
+{
+    pub fn new(invite_repo: Arc<I>) -> Self {
+        Self { invite_repo }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<Invite>, AppError> {
+        self.invite_repo.find_all().await
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::mail_svc::MailService,
+    infra::{
+        config::AppConfig,
+        repositories::{
+            pg_invite_repo::PgInviteRepository, pg_user_repo::PgUserRepository,
+            smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        },
+    },
+};
+
+use super::{create_invite::CreateInvite, delete_invite::DeleteInvite, get_all_invites::GetAllInvites};
+
+#[derive(Clone)]
+pub struct InviteUsecase {
+    pub get_all_invites: Arc<GetAllInvites<PgInviteRepository>>,
+    pub create_invite: Arc<CreateInvite<PgInviteRepository, PgUserRepository>>,
+    pub delete_invite: Arc<DeleteInvite<PgInviteRepository>>,
+}
+
+impl InviteUsecase {
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        invite_repo: Arc<PgInviteRepository>,
+        user_repo: Arc<PgUserRepository>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            get_all_invites: Arc::new(GetAllInvites::new(invite_repo.clone())),
+            create_invite: Arc::new(CreateInvite::new(
+                cfg.clone(),
+                invite_repo.clone(),
+                user_repo.clone(),
+                mail_svc.clone(),
+            )),
+            delete_invite: Arc::new(DeleteInvite::new(invite_repo.clone())),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod create_invite;
+pub mod delete_invite;
+pub mod get_all_invites;
+pub mod init;
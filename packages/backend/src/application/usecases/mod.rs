@@ -1,4 +1,13 @@
+pub mod access_request;
+pub mod access_review;
 pub mod auth;
+pub mod authz;
+pub mod invite;
+pub mod permission;
+pub mod policy;
 pub mod role;
+pub mod role_constraint;
+pub mod saml;
 pub mod project;
 pub mod user;
+pub mod user_role;
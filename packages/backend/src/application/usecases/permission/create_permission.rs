@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::permission::create_update_permission_request::CreatePermissionRequest,
+        services::redis_svc::RedisService,
+    },
+    domain::{
+        entities::permission::Permission,
+        repositories::{permission_repo::PermissionRepository, redis_repo::RedisRepository},
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct CreatePermission<P, R> {
+    permission_repo: Arc<P>,
+    redis_svc: Arc<RedisService<R>>,
+}
+
+impl<P, R> CreatePermission<P, R>
+where
+    P: PermissionRepository,
+    R: RedisRepository,
+{
+    pub fn new(permission_repo: Arc<P>, redis_svc: Arc<RedisService<R>>) -> Self {
+        Self {
+            permission_repo,
+            redis_svc,
+        }
+    }
+
+    pub async fn execute(&self, req: CreatePermissionRequest) -> Result<Permission, AppError> {
+        req.validate()?;
+
+        let permission = Permission::from(&req);
+
+        // `:` separates resource and action, `*` is the policy wildcard
+        for part in [&permission.name, &permission.action] {
+            if part.is_empty() || part == "*" || part.contains(':') || part.contains(char::is_whitespace) {
+                return Err(AppError::ProcessError(format!(
+                    "Invalid permission {}, resource and action must be non-empty without ':', '*' or spaces",
+                    permission.key()
+                )));
+            }
+        }
+
+        if self
+            .permission_repo
+            .exists(&permission.name, &permission.action)
+            .await?
+        {
+            return Err(AppError::ResourceExist(format!(
+                "Permission {}",
+                permission.key()
+            )));
+        }
+
+        let permission = self.permission_repo.create(&permission).await?;
+        self.redis_svc.remove_permissions_list().await?;
+
+        info!("Permission {} registered", permission.key());
+
+        Ok(permission)
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{permission_repo::PermissionRepository, redis_repo::RedisRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct DeletePermission<P, R> {
+    permission_repo: Arc<P>,
+    redis_svc: Arc<RedisService<R>>,
+}
+
+impl<P, R> DeletePermission<P, R>
+where
+    P: PermissionRepository,
+    R: RedisRepository,
+{
+    pub fn new(permission_repo: Arc<P>, redis_svc: Arc<RedisService<R>>) -> Self {
+        Self {
+            permission_repo,
+            redis_svc,
+        }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<(), AppError> {
+        let permission = self.permission_repo.find_by_id(id).await?;
+
+        if self
+            .permission_repo
+            .is_in_use(&permission.name, &permission.action)
+            .await?
+        {
+            return Err(AppError::ProcessError(format!(
+                "Permission {} is still used by roles, remove it from them first",
+                permission.key()
+            )));
+        }
+
+        info!("Deleting permission {} ({})...", permission.key(), permission.id);
+        self.permission_repo.delete(&permission.id).await?;
+        self.redis_svc.remove_permissions_list().await?;
+
+        Ok(())
+    }
+}
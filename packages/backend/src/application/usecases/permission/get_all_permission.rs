@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::permission::PermissionGroup, repositories::permission_repo::PermissionRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetAllPermission<P> {
+    permission_repo: Arc<P>,
+}
+
+impl<P> GetAllPermission<P>
+where
+    P: PermissionRepository,
+{
+    pub fn new(permission_repo: Arc<P>) -> Self {
+        Self { permission_repo }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<PermissionGroup>, AppError> {
+        // the repository orders by group, so consecutive permissions share a group
+        let mut groups: Vec<PermissionGroup> = Vec::new();
+        for permission in self.permission_repo.find_all().await? {
+            match groups.last_mut() {
+                Some(group) if group.group_name == permission.group_name => {
+                    group.permissions.push(permission);
+                }
+                _ => groups.push(PermissionGroup {
+                    group_name: permission.group_name.clone(),
+                    permissions: vec![permission],
+                }),
+            }
+        }
+
+        Ok(groups)
+    }
+}
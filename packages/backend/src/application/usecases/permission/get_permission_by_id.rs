@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{entities::permission::Permission, repositories::permission_repo::PermissionRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetPermissionById<P> {
+    permission_repo: Arc<P>,
+}
+
+impl<P> GetPermissionById<P>
+where
+    P: PermissionRepository,
+{
+    pub fn new(permission_repo: Arc<P>) -> Self {
+        Self { permission_repo }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<Permission, AppError> {
+        let permission = self.permission_repo.find_by_id(id).await?;
+
+        Ok(permission)
+    }
+}
@@ -0,0 +1,47 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{permission_repo::PermissionRepository, redis_repo::RedisRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetPermissionList<P, R> {
+    permission_repo: Arc<P>,
+    redis_svc: Arc<RedisService<R>>,
+}
+
+impl<P, R> GetPermissionList<P, R>
+where
+    P: PermissionRepository,
+    R: RedisRepository,
+{
+    pub fn new(permission_repo: Arc<P>, redis_svc: Arc<RedisService<R>>) -> Self {
+        Self {
+            permission_repo,
+            redis_svc,
+        }
+    }
+
+    // resource -> actions, cached until the registry changes
+    pub async fn execute(&self) -> Result<HashMap<String, Vec<String>>, AppError> {
+        if let Ok(permissions) = self.redis_svc.get_permissions_list().await {
+            return Ok(permissions);
+        }
+
+        let mut permission_list: HashMap<String, Vec<String>> = HashMap::new();
+        for permission in self.permission_repo.find_all().await? {
+            permission_list
+                .entry(permission.name)
+                .or_default()
+                .push(permission.action);
+        }
+
+        self.redis_svc
+            .set_permissions_list(permission_list.clone())
+            .await?;
+
+        Ok(permission_list)
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    infra::repositories::{
+        pg_permission_repo::PgPermissionRepository, redis_repo_impl::RedisRepositoryImpl,
+    },
+};
+
+use super::{
+    create_permission::CreatePermission, delete_permission::DeletePermission,
+    get_all_permission::GetAllPermission, get_permission_by_id::GetPermissionById,
+    get_permission_list::GetPermissionList, update_permission::UpdatePermission,
+};
+
+#[derive(Clone)]
+pub struct PermissionUsecase {
+    pub get_all_permission: Arc<GetAllPermission<PgPermissionRepository>>,
+    pub get_permission_list: Arc<GetPermissionList<PgPermissionRepository, RedisRepositoryImpl>>,
+    pub get_permission_by_id: Arc<GetPermissionById<PgPermissionRepository>>,
+    pub create_permission: Arc<CreatePermission<PgPermissionRepository, RedisRepositoryImpl>>,
+    pub update_permission: Arc<UpdatePermission<PgPermissionRepository, RedisRepositoryImpl>>,
+    pub delete_permission: Arc<DeletePermission<PgPermissionRepository, RedisRepositoryImpl>>,
+}
+
+impl PermissionUsecase {
+    pub fn new(
+        permission_repo: Arc<PgPermissionRepository>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            get_all_permission: Arc::new(GetAllPermission::new(permission_repo.clone())),
+            get_permission_list: Arc::new(GetPermissionList::new(
+                permission_repo.clone(),
+                redis_svc.clone(),
+            )),
+            get_permission_by_id: Arc::new(GetPermissionById::new(permission_repo.clone())),
+            create_permission: Arc::new(CreatePermission::new(
+                permission_repo.clone(),
+                redis_svc.clone(),
+            )),
+            update_permission: Arc::new(UpdatePermission::new(
+                permission_repo.clone(),
+                redis_svc.clone(),
+            )),
+            delete_permission: Arc::new(DeletePermission::new(
+                permission_repo.clone(),
+                redis_svc.clone(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod create_permission;
+pub mod delete_permission;
+pub mod get_all_permission;
+pub mod get_permission_by_id;
+pub mod get_permission_list;
+pub mod init;
+pub mod update_permission;
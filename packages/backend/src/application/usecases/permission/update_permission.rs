@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::permission::create_update_permission_request::{
+            UpdatePermissionRequest, DEFAULT_PERMISSION_GROUP,
+        },
+        services::redis_svc::RedisService,
+    },
+    domain::{
+        entities::permission::Permission,
+        repositories::{permission_repo::PermissionRepository, redis_repo::RedisRepository},
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct UpdatePermission<P, R> {
+    permission_repo: Arc<P>,
+    redis_svc: Arc<RedisService<R>>,
+}
+
+impl<P, R> UpdatePermission<P, R>
+where
+    P: PermissionRepository,
+    R: RedisRepository,
+{
+    pub fn new(permission_repo: Arc<P>, redis_svc: Arc<RedisService<R>>) -> Self {
+        Self {
+            permission_repo,
+            redis_svc,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        id: &str,
+        req: UpdatePermissionRequest,
+    ) -> Result<Permission, AppError> {
+        req.validate()?;
+
+        let mut permission = self.permission_repo.find_by_id(id).await?;
+        permission.update(
+            req.description,
+            req.group_name
+                .unwrap_or_else(|| DEFAULT_PERMISSION_GROUP.to_string()),
+        );
+
+        let permission = self.permission_repo.update(&permission).await?;
+        self.redis_svc.remove_permissions_list().await?;
+
+        Ok(permission)
+    }
+}
@@ -0,0 +1,234 @@
+use std::{collections::HashSet, sync::Arc};
+
+use casbin::MgmtApi;
+use tracing::warn;
+
+use crate::{
+    application::usecases::access_request::granted_role::JIT_ROLE_PREFIX,
+    domain::{
+        entities::{
+            policy_consistency::{PolicyConsistencyReport, RoleDrift},
+            role::Role,
+        },
+        repositories::{
+            policy_repo::PolicyRepository, role_policy_change_repo::RolePolicyChangeRepository,
+            role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        common::constants::SUPER_ADMIN_ROLE,
+        errors::app_error::AppError,
+        rbac::{Rbac, EFFECT_ALLOW, EFFECT_DENY, GLOBAL_DOMAIN, SHARED_OBJECT_PREFIX},
+    },
+};
+
+#[derive(Clone)]
+pub struct CheckPolicyConsistency<R, Q, H> {
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R, Q, H> CheckPolicyConsistency<R, Q, H>
+where
+    R: RoleRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+        }
+    }
+
+    // compares the stored rules with the roles and their latest recorded version, a repair drops
+    // orphaned rules, restores each drifted role and reloads the enforcers
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        repair: bool,
+    ) -> Result<PolicyConsistencyReport, AppError> {
+        // no role or policy write can run in between the comparison and the repair
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let roles = self.role_repo.find_all_across_domains().await?;
+        let rules = self
+            .policy_repo
+            .find_all_rules()
+            .await?
+            .into_iter()
+            .map(|(ptype, rule)| [vec![ptype], rule].concat())
+            .collect::<Vec<Vec<String>>>();
+
+        let role_ids = roles.iter().map(|role| role.id.as_str()).collect::<HashSet<&str>>();
+        let mut report = PolicyConsistencyReport {
+            orphaned_rules: rules
+                .iter()
+                .filter(|rule| match rule[0].as_str() {
+                    "p" => !rule.get(1).is_some_and(|id| role_ids.contains(id.as_str())),
+                    _ => !rule[1..]
+                        .iter()
+                        .take(2)
+                        .all(|id| role_ids.contains(id.as_str())),
+                })
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        // grants on shared objects are made at runtime without a recorded version, they are not
+        // drift and a repair must keep them
+        let is_shared = |rule: &Vec<String>| {
+            rule[0] == "p"
+                && rule
+                    .get(3)
+                    .is_some_and(|object| object.starts_with(SHARED_OBJECT_PREFIX))
+        };
+
+        for role in &roles {
+            let Some((recorded_version, expected)) = self.expected_rules(role).await? else {
+                continue;
+            };
+            let expected = expected
+                .into_iter()
+                .filter(|rule| !is_shared(rule))
+                .collect::<Vec<Vec<String>>>();
+            let actual = rules
+                .iter()
+                .filter(|rule| rule.get(1) == Some(&role.id))
+                .filter(|rule| recorded_version.is_some() || rule[0] == "p")
+                .filter(|rule| !is_shared(rule))
+                .cloned()
+                .collect::<Vec<Vec<String>>>();
+
+            let drift = RoleDrift {
+                role_id: role.id.clone(),
+                role_name: role.name.clone(),
+                domain: role.domain.clone(),
+                recorded_version,
+                missing_rules: expected
+                    .iter()
+                    .filter(|rule| !actual.contains(rule))
+                    .cloned()
+                    .collect(),
+                unexpected_rules: actual
+                    .iter()
+                    .filter(|rule| !expected.contains(rule))
+                    .cloned()
+                    .collect(),
+            };
+            if !drift.missing_rules.is_empty() || !drift.unexpected_rules.is_empty() {
+                report.drifted_roles.push(drift);
+            }
+        }
+
+        let mut loaded = enforcer
+            .get_policy()
+            .into_iter()
+            .map(|rule| [vec!["p".to_string()], rule].concat())
+            .chain(
+                enforcer
+                    .get_grouping_policy()
+                    .into_iter()
+                    .map(|rule| [vec!["g".to_string()], rule].concat()),
+            )
+            .collect::<Vec<Vec<String>>>();
+        let mut stored = rules;
+        loaded.sort();
+        stored.sort();
+        report.stale_enforcer = loaded != stored;
+
+        if !repair || report.is_consistent() {
+            return Ok(report);
+        }
+
+        let removed = report
+            .orphaned_rules
+            .iter()
+            .chain(report.drifted_roles.iter().flat_map(|drift| &drift.unexpected_rules))
+            .collect::<Vec<&Vec<String>>>();
+        let added = report
+            .drifted_roles
+            .iter()
+            .flat_map(|drift| &drift.missing_rules)
+            .collect::<Vec<&Vec<String>>>();
+
+        let mut tx = db_pool.begin().await?;
+        for rule in removed {
+            self.policy_repo
+                .tx_remove_rules(&mut tx, &rule[0], &[rule[1..].to_vec()])
+                .await?;
+        }
+        for rule in added {
+            self.policy_repo
+                .tx_add_rules(&mut tx, &rule[0], &[rule[1..].to_vec()])
+                .await?;
+        }
+        tx.commit().await?;
+
+        self.rbac.reload_policy(&mut enforcer).await?;
+        report.repaired = true;
+
+        warn!(
+            "Repaired policy drift: {} orphaned rules, {} drifted roles",
+            report.orphaned_rules.len(),
+            report.drifted_roles.len()
+        );
+
+        Ok(report)
+    }
+
+    // the super admin always holds the wildcard policy, other roles what their latest version
+    // recorded; roles without history and those of access requests are not checked
+    async fn expected_rules(
+        &self,
+        role: &Role,
+    ) -> Result<Option<(Option<i32>, Vec<Vec<String>>)>, AppError> {
+        if role.name == SUPER_ADMIN_ROLE && role.domain == GLOBAL_DOMAIN {
+            let policy = Rbac::permission_policy(&role.id, GLOBAL_DOMAIN, "*:*", EFFECT_ALLOW)?;
+            return Ok(Some((None, vec![[vec!["p".to_string()], policy].concat()])));
+        }
+        if role.name.starts_with(JIT_ROLE_PREFIX) {
+            return Ok(None);
+        }
+
+        let (changes, _) = self.policy_change_repo.paginate_by_role(&role.id, 1, 1).await?;
+        let Some(change) = changes.into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(snapshot) = change.after else {
+            return Ok(None);
+        };
+
+        let mut expected = Vec::new();
+        let effects = snapshot
+            .permissions
+            .iter()
+            .map(|permission| (permission, EFFECT_ALLOW))
+            .chain(snapshot.denied_permissions.iter().map(|permission| (permission, EFFECT_DENY)));
+        for (permission, effect) in effects {
+            let condition = snapshot.conditions.get(permission).map(String::as_str);
+            let policy =
+                Rbac::conditional_policy(&role.id, &role.domain, permission, effect, condition)?;
+            expected.push([vec!["p".to_string()], policy].concat());
+        }
+        for parent_id in &snapshot.parents {
+            expected.push(vec![
+                "g".to_string(),
+                role.id.clone(),
+                parent_id.clone(),
+                role.domain.clone(),
+            ]);
+        }
+
+        Ok(Some((Some(change.version), expected)))
+    }
+}
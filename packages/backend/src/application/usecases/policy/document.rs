@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use casbin::{Enforcer, MgmtApi};
+
+use crate::{
+    application::usecases::access_request::granted_role::JIT_ROLE_PREFIX,
+    domain::entities::{
+        policy_document::{PolicyDocument, PolicyRole, RoleKey},
+        role::Role,
+    },
+    infra::{common::constants::SUPER_ADMIN_ROLE, rbac::Rbac},
+};
+
+// the super admin and roles created for access requests are owned by the application
+pub fn is_reserved_name(name: &str) -> bool {
+    name == SUPER_ADMIN_ROLE || name.starts_with(JIT_ROLE_PREFIX)
+}
+
+// document of every exportable role as the enforcer currently holds it
+pub fn current_document(roles: &[Role], enforcer: &Enforcer) -> PolicyDocument {
+    let keys = roles
+        .iter()
+        .map(|role| {
+            (
+                role.id.as_str(),
+                RoleKey {
+                    name: role.name.clone(),
+                    domain: role.domain.clone(),
+                },
+            )
+        })
+        .collect::<HashMap<&str, RoleKey>>();
+    let grouping_policies = enforcer.get_grouping_policy();
+
+    let mut document_roles = roles
+        .iter()
+        .filter(|role| !is_reserved_name(&role.name))
+        .map(|role| {
+            let policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+            let conditions = Rbac::policy_conditions(&policies);
+            let (mut permissions, mut denied_permissions) = Rbac::split_permissions(policies);
+            permissions.sort();
+            permissions.dedup();
+            denied_permissions.sort();
+            denied_permissions.dedup();
+
+            // links to roles that no longer exist are not carried over
+            let mut parents = grouping_policies
+                .iter()
+                .filter(|rule| rule.len() >= 2 && rule[0] == role.id)
+                .filter_map(|rule| keys.get(rule[1].as_str()).cloned())
+                .collect::<Vec<RoleKey>>();
+            parents.sort();
+            parents.dedup();
+
+            PolicyRole {
+                name: role.name.clone(),
+                domain: role.domain.clone(),
+                is_default: role.is_default,
+                permissions,
+                denied_permissions,
+                parents,
+                conditions,
+                renamed_from: None,
+            }
+        })
+        .collect::<Vec<PolicyRole>>();
+    document_roles.sort_by(|a, b| (&a.domain, &a.name).cmp(&(&b.domain, &b.name)));
+
+    PolicyDocument::new(document_roles)
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{entities::policy_document::PolicyDocument, repositories::role_repo::RoleRepository},
+    infra::{errors::app_error::AppError, rbac::Rbac},
+};
+
+use super::document::current_document;
+
+#[derive(Clone)]
+pub struct ExportPolicies<R> {
+    role_repo: Arc<R>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R> ExportPolicies<R>
+where
+    R: RoleRepository,
+{
+    pub fn new(role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { role_repo, rbac }
+    }
+
+    pub async fn execute(&self) -> Result<PolicyDocument, AppError> {
+        // roles are read under the lock so they match the policies exported with them
+        let enforcer = self.rbac.enforcer.read().await;
+        let roles = self.role_repo.find_all_across_domains().await?;
+
+        Ok(current_document(&roles, &enforcer))
+    }
+}
@@ -0,0 +1,643 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use casbin::{Enforcer, MgmtApi};
+use tracing::info;
+
+use crate::{
+    application::services::role_constraint_svc::RoleConstraintService,
+    domain::{
+        entities::{
+            policy_document::{
+                PolicyDiff, PolicyDocument, PolicyRole, RoleKey, POLICY_DOCUMENT_VERSION,
+            },
+            role::Role,
+            role_policy_change::{
+                RolePolicyChange, RolePolicySnapshot, POLICY_CHANGE_IMPORTED,
+                POLICY_CHANGE_RECONCILED,
+            },
+        },
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{Rbac, EFFECT_ALLOW, EFFECT_DENY, GLOBAL_DOMAIN, SHARED_OBJECT_PREFIX},
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+use super::document::{current_document, is_reserved_name};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportMode {
+    // the document replaces every role the application does not own itself
+    Replace,
+    // the document is layered over the existing roles, see `reconcile_target`
+    Reconcile { never_delete: bool },
+}
+
+// everything an import writes, worked out before anything is touched
+struct ImportPlan {
+    diff: PolicyDiff,
+    created: Vec<Role>,
+    updated: Vec<Role>,
+    removed_ids: Vec<String>,
+    managed_ids: Vec<String>,
+    policies: Vec<Vec<String>>,
+    groupings: Vec<Vec<String>>,
+    changes: Vec<RolePolicyChange>,
+}
+
+#[derive(Clone)]
+pub struct ImportPolicies<R, P, Q, H> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<R, P, Q, H> ImportPolicies<R, P, Q, H>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+            role_constraint_svc,
+        }
+    }
+
+    // with dry_run only the diff is returned, otherwise roles and rules are replaced in one
+    // transaction while the enforcer is locked, then every instance reloads; actor_id is None
+    // when the application reconciles on its own
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: Option<&str>,
+        document: PolicyDocument,
+        mode: ImportMode,
+        dry_run: bool,
+    ) -> Result<PolicyDiff, AppError> {
+        self.validate(&document).await?;
+
+        if dry_run {
+            let enforcer = self.rbac.enforcer.read().await;
+            let roles = self.role_repo.find_all_across_domains().await?;
+            let plan = Self::plan(&roles, &enforcer, &document, actor_id, mode)?;
+            self.check_constraints(&enforcer, &plan).await?;
+
+            return Ok(plan.diff);
+        }
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let roles = self.role_repo.find_all_across_domains().await?;
+        let plan = Self::plan(&roles, &enforcer, &document, actor_id, mode)?;
+
+        if plan.diff.is_empty() {
+            return Ok(plan.diff);
+        }
+
+        self.check_constraints(&enforcer, &plan).await?;
+
+        let mut tx = db_pool.begin().await?;
+
+        for id in &plan.removed_ids {
+            self.role_repo.tx_delete(&mut tx, id).await?;
+        }
+        for role in &plan.updated {
+            self.role_repo
+                .tx_release_deleted_name(&mut tx, &role.name, &role.domain)
+                .await?;
+            self.role_repo.tx_update(&mut tx, role).await?;
+        }
+        for role in &plan.created {
+            self.role_repo
+                .tx_release_deleted_name(&mut tx, &role.name, &role.domain)
+                .await?;
+            self.role_repo.tx_create(&mut tx, role.clone()).await?;
+        }
+
+        self.policy_repo
+            .tx_replace_role_rules(
+                &mut tx,
+                &plan.managed_ids,
+                &plan.removed_ids,
+                &plan.policies,
+                &plan.groupings,
+            )
+            .await?;
+
+        // every role the import touched gets a version in its history
+        for change in &plan.changes {
+            self.policy_change_repo.tx_create(&mut tx, change).await?;
+        }
+
+        tx.commit().await?;
+
+        self.rbac.reload_policy(&mut enforcer).await?;
+
+        info!(
+            "{} policies: {} roles added, {} removed, {} renamed",
+            match mode {
+                ImportMode::Replace => "Imported",
+                ImportMode::Reconcile { .. } => "Reconciled",
+            },
+            plan.diff.roles_added.len(),
+            plan.diff.roles_removed.len(),
+            plan.diff.roles_renamed.len()
+        );
+
+        Ok(plan.diff)
+    }
+
+    // the inheritance the import leaves behind must not combine roles of a segregation-of-duties
+    // constraint, same rules as `tx_replace_role_rules` applies
+    async fn check_constraints(&self, enforcer: &Enforcer, plan: &ImportPlan) -> Result<(), AppError> {
+        let current = enforcer.get_grouping_policy();
+        let proposed = current
+            .iter()
+            .filter(|rule| {
+                rule.len() >= 2
+                    && !plan.managed_ids.contains(&rule[0])
+                    && !plan.removed_ids.contains(&rule[1])
+            })
+            .chain(&plan.groupings)
+            .cloned()
+            .collect::<Vec<Vec<String>>>();
+
+        self.role_constraint_svc.check_groupings(&current, &proposed).await
+    }
+
+    async fn validate(&self, document: &PolicyDocument) -> Result<(), AppError> {
+        if document.version != POLICY_DOCUMENT_VERSION {
+            return Err(AppError::ProcessError(format!(
+                "Unsupported policy document version {}",
+                document.version
+            )));
+        }
+
+        let mut keys = HashSet::new();
+        let mut defaults = 0;
+        let mut requested = Vec::new();
+
+        for role in &document.roles {
+            if role.name.trim().is_empty() || role.domain.trim().is_empty() {
+                return Err(AppError::ProcessError(
+                    "Role name and domain are required".to_owned(),
+                ));
+            }
+
+            if is_reserved_name(&role.name)
+                || role.renamed_from.as_deref().is_some_and(is_reserved_name)
+            {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} is managed by the application and cannot be imported",
+                    role.name
+                )));
+            }
+
+            if !keys.insert(role.key()) {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} appears more than once in {}",
+                    role.name, role.domain
+                )));
+            }
+
+            if role.is_default {
+                if role.domain != GLOBAL_DOMAIN {
+                    return Err(AppError::ProcessError(
+                        "Only global roles can be the default role".to_owned(),
+                    ));
+                }
+                defaults += 1;
+            }
+
+            for permission in role.permissions.iter().chain(&role.denied_permissions) {
+                let condition = role.conditions.get(permission).map(String::as_str);
+                Rbac::conditional_policy(&role.name, &role.domain, permission, EFFECT_ALLOW, condition)?;
+                requested.push(permission.clone());
+            }
+
+            if let Some(permission) = role.conditions.keys().find(|permission| {
+                !role.permissions.contains(permission) && !role.denied_permissions.contains(permission)
+            }) {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} has a condition for {} without allowing or denying it",
+                    role.name, permission
+                )));
+            }
+        }
+
+        if defaults > 1 {
+            return Err(AppError::ProcessError(
+                "Only one role can be the default role".to_owned(),
+            ));
+        }
+
+        // only permissions from the registry can be granted or denied, shared objects are
+        // handed out per resource and never registered
+        requested.retain(|permission| !permission.starts_with(SHARED_OBJECT_PREFIX));
+        requested.sort();
+        requested.dedup();
+        let unknown = self.permission_repo.find_unknown(&requested).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permissions: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn plan(
+        roles: &[Role],
+        enforcer: &Enforcer,
+        document: &PolicyDocument,
+        actor_id: Option<&str>,
+        mode: ImportMode,
+    ) -> Result<ImportPlan, AppError> {
+        let current = current_document(roles, enforcer);
+        let reconciled;
+        let (document, action) = match mode {
+            ImportMode::Replace => (document, POLICY_CHANGE_IMPORTED),
+            ImportMode::Reconcile { never_delete } => {
+                reconciled = reconcile_target(&current, document.clone(), never_delete);
+                (&reconciled, POLICY_CHANGE_RECONCILED)
+            }
+        };
+        let diff = current.diff(document);
+        let renamed_from = current
+            .renames(document)
+            .into_iter()
+            .map(|(from, to)| (to, from))
+            .collect::<HashMap<RoleKey, RoleKey>>();
+
+        let key_of = |role: &Role| RoleKey {
+            name: role.name.clone(),
+            domain: role.domain.clone(),
+        };
+        let live_roles = roles
+            .iter()
+            .map(|role| (key_of(role), role))
+            .collect::<HashMap<RoleKey, &Role>>();
+        let removed_ids = diff
+            .roles_removed
+            .iter()
+            .filter_map(|key| live_roles.get(key).map(|role| role.id.clone()))
+            .collect::<Vec<String>>();
+
+        // existing roles keep their id through a rename so assignments stay attached
+        let mut ids: HashMap<RoleKey, String> = roles
+            .iter()
+            .filter(|role| is_reserved_name(&role.name))
+            .map(|role| (key_of(role), role.id.clone()))
+            .collect();
+        let mut created = Vec::new();
+        let mut updated = Vec::new();
+
+        for target in &document.roles {
+            let key = target.key();
+            let existing = live_roles
+                .get(&key)
+                .or_else(|| renamed_from.get(&key).and_then(|from| live_roles.get(from)));
+
+            match existing {
+                Some(role) => {
+                    if role.name != target.name || role.is_default != target.is_default {
+                        let mut role = (*role).clone();
+                        role.update(&target.name, target.is_default);
+                        updated.push(role);
+                    }
+                    ids.insert(key, role.id.clone());
+                }
+                None => {
+                    let role = Role::new(
+                        uuid::Uuid::new_v4().to_string(),
+                        target.name.clone(),
+                        target.is_default,
+                        target.domain.clone(),
+                    );
+                    ids.insert(key, role.id.clone());
+                    created.push(role);
+                }
+            }
+        }
+
+        let mut policies = Vec::new();
+        let mut groupings = Vec::new();
+        let mut changes = Vec::new();
+
+        for target in &document.roles {
+            let id = &ids[&target.key()];
+            let mut parent_ids = Vec::new();
+
+            let condition = |permission: &String| target.conditions.get(permission).map(String::as_str);
+            for permission in &target.permissions {
+                policies.push(Rbac::conditional_policy(
+                    id,
+                    &target.domain,
+                    permission,
+                    EFFECT_ALLOW,
+                    condition(permission),
+                )?);
+            }
+            for permission in &target.denied_permissions {
+                policies.push(Rbac::conditional_policy(
+                    id,
+                    &target.domain,
+                    permission,
+                    EFFECT_DENY,
+                    condition(permission),
+                )?);
+            }
+
+            for parent in &target.parents {
+                let Some(parent_id) = ids.get(parent) else {
+                    return Err(AppError::ProcessError(format!(
+                        "Parent role {} in {} of role {} is not in the document",
+                        parent.name, parent.domain, target.name
+                    )));
+                };
+
+                // same rule as adding a parent through the role endpoints
+                if parent.domain != GLOBAL_DOMAIN && parent.domain != target.domain {
+                    return Err(AppError::ProcessError(format!(
+                        "Role {} can only inherit from global roles or roles in {}",
+                        target.name, target.domain
+                    )));
+                }
+
+                groupings.push(vec![id.clone(), parent_id.clone(), target.domain.clone()]);
+                parent_ids.push(parent_id.clone());
+            }
+
+            let is_new = created.iter().any(|role| role.id == *id);
+            changes.push(RolePolicyChange::new(
+                id.clone(),
+                target.name.clone(),
+                target.domain.clone(),
+                action,
+                actor_id.map(str::to_string),
+                (!is_new).then(|| Rbac::role_snapshot(enforcer, id)),
+                Some(RolePolicySnapshot::new(
+                    target.permissions.clone(),
+                    target.denied_permissions.clone(),
+                    parent_ids,
+                    target.conditions.clone(),
+                )),
+            ));
+        }
+        for key in &diff.roles_removed {
+            if let Some(role) = live_roles.get(key) {
+                changes.push(RolePolicyChange::new(
+                    role.id.clone(),
+                    role.name.clone(),
+                    role.domain.clone(),
+                    action,
+                    actor_id.map(str::to_string),
+                    Some(Rbac::role_snapshot(enforcer, &role.id)),
+                    None,
+                ));
+            }
+        }
+        changes.retain(|change| !change.is_noop());
+
+        // links of roles outside the document stay and are part of the hierarchy too
+        let managed_ids = current
+            .roles
+            .iter()
+            .filter_map(|role| live_roles.get(&role.key()).map(|role| role.id.clone()))
+            .collect::<Vec<String>>();
+        let kept_groupings = enforcer
+            .get_grouping_policy()
+            .into_iter()
+            .filter(|rule| {
+                rule.len() >= 2 && !managed_ids.contains(&rule[0]) && !removed_ids.contains(&rule[1])
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        if let Some(role_id) = find_cycle(groupings.iter().chain(&kept_groupings)) {
+            let name = ids
+                .iter()
+                .find(|(_, id)| **id == role_id)
+                .map(|(key, _)| key.name.clone())
+                .unwrap_or(role_id);
+            return Err(AppError::ProcessError(format!(
+                "Role {} would inherit from itself",
+                name
+            )));
+        }
+
+        Ok(ImportPlan {
+            diff,
+            created,
+            updated,
+            removed_ids,
+            managed_ids,
+            policies,
+            groupings,
+            changes,
+        })
+    }
+}
+
+// reconciling layers the document over the existing roles: `shared:<id>` grants handed out at
+// runtime are never dropped, and with never_delete nothing the document leaves out is removed
+// either, permissions a role already has keep their condition and the default role stays put
+fn reconcile_target(
+    current: &PolicyDocument,
+    mut document: PolicyDocument,
+    never_delete: bool,
+) -> PolicyDocument {
+    let renames = current.renames(&document);
+    let renamed_from = renames
+        .iter()
+        .map(|(from, to)| (to.clone(), from.clone()))
+        .collect::<HashMap<RoleKey, RoleKey>>();
+    let current_roles = current
+        .roles
+        .iter()
+        .map(|role| (role.key(), role))
+        .collect::<HashMap<RoleKey, &PolicyRole>>();
+    let has_default = current.roles.iter().any(|role| role.is_default);
+
+    for target in &mut document.roles {
+        let key = target.key();
+        let existing = match renamed_from.get(&key) {
+            Some(from) => {
+                target.renamed_from = Some(from.name.clone());
+                current_roles.get(from)
+            }
+            None => current_roles.get(&key),
+        };
+
+        let Some(existing) = existing else {
+            if never_delete && has_default {
+                target.is_default = false;
+            }
+            continue;
+        };
+
+        let kept = |permission: &&String| never_delete || permission.starts_with(SHARED_OBJECT_PREFIX);
+        for permission in existing.permissions.iter().filter(kept) {
+            if !target.permissions.contains(permission) {
+                target.permissions.push(permission.clone());
+            }
+        }
+        for permission in existing.denied_permissions.iter().filter(kept) {
+            if !target.denied_permissions.contains(permission) {
+                target.denied_permissions.push(permission.clone());
+            }
+        }
+        for permission in existing.permissions.iter().chain(&existing.denied_permissions).filter(kept) {
+            match existing.conditions.get(permission) {
+                Some(condition) => target.conditions.insert(permission.clone(), condition.clone()),
+                None => target.conditions.remove(permission),
+            };
+        }
+
+        if never_delete {
+            target.is_default = existing.is_default;
+            for parent in &existing.parents {
+                let parent = renames.get(parent).unwrap_or(parent);
+                if !target.parents.contains(parent) {
+                    target.parents.push(parent.clone());
+                }
+            }
+        }
+    }
+
+    if never_delete {
+        let listed = document
+            .roles
+            .iter()
+            .map(PolicyRole::key)
+            .chain(renames.keys().cloned())
+            .collect::<HashSet<RoleKey>>();
+        let kept_roles = current
+            .roles
+            .iter()
+            .filter(|role| !listed.contains(&role.key()))
+            .map(|role| {
+                let mut role = role.clone();
+                // links to a renamed role follow it to its new name
+                for parent in &mut role.parents {
+                    if let Some(to) = renames.get(parent) {
+                        *parent = to.clone();
+                    }
+                }
+                role
+            })
+            .collect::<Vec<PolicyRole>>();
+        document.roles.extend(kept_roles);
+    }
+
+    document
+}
+
+// a role reachable from itself through `g` rules, in any domain
+fn find_cycle<'a>(rules: impl Iterator<Item = &'a Vec<String>>) -> Option<String> {
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for rule in rules.filter(|rule| rule.len() >= 2) {
+        parents.entry(rule[0].as_str()).or_default().push(rule[1].as_str());
+    }
+
+    let mut done: HashSet<&str> = HashSet::new();
+    for start in parents.keys() {
+        let mut path: Vec<(&str, usize)> = vec![(start, 0)];
+        let mut on_path: HashSet<&str> = HashSet::from([*start]);
+
+        while let Some((role, next)) = path.last_mut() {
+            let role = *role;
+            let Some(parent) = parents.get(role).and_then(|list| list.get(*next)).copied() else {
+                on_path.remove(role);
+                done.insert(role);
+                path.pop();
+                continue;
+            };
+            *next += 1;
+
+            if on_path.contains(parent) {
+                return Some(parent.to_string());
+            }
+            if !done.contains(parent) {
+                on_path.insert(parent);
+                path.push((parent, 0));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(role_id: &str, parent_id: &str) -> Vec<String> {
+        vec![role_id.to_string(), parent_id.to_string(), "*".to_string()]
+    }
+
+    #[test]
+    fn finds_no_cycle_in_a_tree() {
+        let rules = [rule("admin", "editor"), rule("admin", "auditor"), rule("editor", "viewer")];
+
+        assert_eq!(find_cycle(rules.iter()), None);
+    }
+
+    #[test]
+    fn shared_ancestors_are_not_a_cycle() {
+        let rules = [rule("a", "b"), rule("a", "c"), rule("b", "d"), rule("c", "d")];
+
+        assert_eq!(find_cycle(rules.iter()), None);
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let rules = [rule("a", "b"), rule("b", "c"), rule("c", "a")];
+        let role = find_cycle(rules.iter()).unwrap();
+        assert!(["a", "b", "c"].contains(&role.as_str()));
+
+        let rules = [rule("a", "a")];
+        assert_eq!(find_cycle(rules.iter()).as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn rules_of_any_domain_can_close_a_cycle() {
+        let rules = [
+            vec!["a".to_string(), "b".to_string(), "org-1".to_string()],
+            vec!["b".to_string(), "a".to_string(), "org-2".to_string()],
+        ];
+
+        assert!(find_cycle(rules.iter()).is_some());
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::role_constraint_svc::RoleConstraintService,
+    infra::{
+        config::AppConfig,
+        rbac::Rbac,
+        repositories::{
+            pg_permission_repo::PgPermissionRepository, pg_policy_repo::PgPolicyRepository,
+            pg_role_constraint_repo::PgRoleConstraintRepository,
+            pg_role_policy_change_repo::PgRolePolicyChangeRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+use super::{
+    check_policy_consistency::CheckPolicyConsistency, export_policies::ExportPolicies,
+    import_policies::ImportPolicies, reconcile_roles::ReconcileRoles,
+};
+
+#[derive(Clone)]
+pub struct PolicyUsecase {
+    pub export_policies: Arc<ExportPolicies<PgRoleRepository>>,
+    pub import_policies: Arc<
+        ImportPolicies<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub reconcile_roles: Arc<
+        ReconcileRoles<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub check_policy_consistency: Arc<
+        CheckPolicyConsistency<PgRoleRepository, PgPolicyRepository, PgRolePolicyChangeRepository>,
+    >,
+}
+
+impl PolicyUsecase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        role_repo: Arc<PgRoleRepository>,
+        permission_repo: Arc<PgPermissionRepository>,
+        policy_repo: Arc<PgPolicyRepository>,
+        policy_change_repo: Arc<PgRolePolicyChangeRepository>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        let import_policies = Arc::new(ImportPolicies::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+            role_constraint_svc,
+        ));
+
+        Self {
+            export_policies: Arc::new(ExportPolicies::new(role_repo.clone(), rbac.clone())),
+            reconcile_roles: Arc::new(ReconcileRoles::new(cfg, import_policies.clone())),
+            import_policies,
+            check_policy_consistency: Arc::new(CheckPolicyConsistency::new(
+                role_repo.clone(),
+                policy_repo.clone(),
+                policy_change_repo.clone(),
+                rbac.clone(),
+            )),
+        }
+    }
+}
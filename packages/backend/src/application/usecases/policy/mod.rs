@@ -0,0 +1,6 @@
+pub mod check_policy_consistency;
+pub mod document;
+pub mod export_policies;
+pub mod import_policies;
+pub mod init;
+pub mod reconcile_roles;
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::policy_document::{PolicyDiff, PolicyDocument},
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{config::AppConfig, errors::app_error::AppError},
+};
+
+use super::import_policies::{ImportMode, ImportPolicies};
+
+#[derive(Clone)]
+pub struct ReconcileRoles<R, P, Q, H> {
+    cfg: Arc<AppConfig>,
+    import_policies: Arc<ImportPolicies<R, P, Q, H>>,
+}
+
+impl<R, P, Q, H> ReconcileRoles<R, P, Q, H>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(cfg: Arc<AppConfig>, import_policies: Arc<ImportPolicies<R, P, Q, H>>) -> Self {
+        Self {
+            cfg,
+            import_policies,
+        }
+    }
+
+    // brings the roles in line with the bootstrap file, None when there is no file; never_delete
+    // falls back to the configured flag
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: Option<&str>,
+        never_delete: Option<bool>,
+        dry_run: bool,
+    ) -> Result<Option<PolicyDiff>, AppError> {
+        let path = &self.cfg.role_bootstrap_file;
+        if path.is_empty() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(path).await?;
+
+        let document = if path.ends_with(".csv") {
+            PolicyDocument::from_csv(&content)?
+        } else {
+            serde_json::from_str(&content)?
+        };
+
+        let never_delete = never_delete.unwrap_or(self.cfg.role_bootstrap_never_delete);
+        let diff = self
+            .import_policies
+            .execute(
+                db_pool,
+                actor_id,
+                document,
+                ImportMode::Reconcile { never_delete },
+                dry_run,
+            )
+            .await?;
+
+        Ok(Some(diff))
+    }
+}
@@ -2,29 +2,52 @@ use std::sync::Arc;
 
 use crate::{
     application::dto::project::create_update_project_request::CreateOrUpdateProject,
-    domain::{entities::project::Project, repositories::project_repo::ProjectRepository},
-    infra::{errors::app_error::AppError},
+    domain::{
+        entities::{access_context::ResourceCheck, project::Project, user::UserFull},
+        repositories::project_repo::ProjectRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
 };
 
+use super::project_access::authorize_project;
+
 #[derive(Clone)]
 pub struct CreateProject<R> {
-    project_repo: Arc<R>
+    project_repo: Arc<R>,
+    rbac: Arc<Rbac>,
 }
 
 impl<R> CreateProject<R>
 where
     R: ProjectRepository,
 {
-    pub fn new(project_repo: Arc<R>) -> Self {
-        Self { project_repo }
+    pub fn new(project_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { project_repo, rbac }
     }
 
-    pub async fn execute(&self, user_id: &str, req: CreateOrUpdateProject) -> Result<Project, AppError> {
+    pub async fn execute(
+        &self,
+        check: &ResourceCheck,
+        user: &UserFull,
+        client_ip: &str,
+        req: CreateOrUpdateProject,
+    ) -> Result<Project, AppError> {
+        let user_id = &user.user.id;
+
+        // projects are handed over by updating them once they exist
+        if req.user_id.is_some() {
+            return Err(AppError::ProcessError(
+                "The owner of a new project is the user creating it".to_owned(),
+            ));
+        }
 
         let mut project_req = Project::from(&req);
         project_req.user_id = Some(user_id.to_string());
 
-        let project = self.project_repo.create(project_req).await?;
+        let grant =
+            authorize_project(&self.rbac, check, user, client_ip, &project_req.id, Some(user_id))
+                .await?;
+        let project = self.project_repo.create(&grant, project_req).await?;
 
         Ok(project)
     }
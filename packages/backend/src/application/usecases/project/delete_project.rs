@@ -1,26 +1,40 @@
 use std::sync::Arc;
 
 use crate::{
-    domain::repositories::project_repo::ProjectRepository,
-    infra::{errors::app_error::AppError},
+    domain::{
+        entities::{access_context::ResourceCheck, user::UserFull},
+        repositories::project_repo::ProjectRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
 };
 
+use super::project_access::authorize_existing_project;
+
 #[derive(Clone)]
 pub struct DeleteProject<R> {
-    project_repo: Arc<R>
+    project_repo: Arc<R>,
+    rbac: Arc<Rbac>,
 }
 
 impl<R> DeleteProject<R>
 where
     R: ProjectRepository,
 {
-    pub fn new(project_repo: Arc<R>) -> Self {
-        Self { project_repo }
+    pub fn new(project_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { project_repo, rbac }
     }
 
-    pub async fn execute(&self, id: &str, user_id: &str) -> Result<(), AppError> {
+    pub async fn execute(
+        &self,
+        id: &str,
+        check: &ResourceCheck,
+        user: &UserFull,
+        client_ip: &str,
+    ) -> Result<(), AppError> {
+        let project = self.project_repo.find_by_id(id).await?;
+        let grant = authorize_existing_project(&self.rbac, check, user, client_ip, &project).await?;
 
-        self.project_repo.delete(id, user_id).await?;
+        self.project_repo.delete(&grant).await?;
 
         Ok(())
     }
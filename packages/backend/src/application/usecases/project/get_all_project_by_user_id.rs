@@ -1,24 +1,39 @@
 use std::sync::Arc;
 
 use crate::{
-    domain::{entities::project::Project, repositories::project_repo::ProjectRepository},
-    infra::errors::app_error::AppError,
+    domain::{
+        entities::{access_context::ResourceCheck, project::Project, user::UserFull},
+        repositories::project_repo::ProjectRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
 };
 
+use super::project_access::authorize_project;
+
 #[derive(Clone)]
 pub struct GetAllProjectByUserId<R> {
     project_repo: Arc<R>,
+    rbac: Arc<Rbac>,
 }
 
 impl<R> GetAllProjectByUserId<R>
 where
     R: ProjectRepository,
 {
-    pub fn new(project_repo: Arc<R>) -> Self {
-        Self { project_repo }
+    pub fn new(project_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { project_repo, rbac }
     }
 
-    pub async fn execute(&self, user_id: &str) -> Result<Vec<Project>, AppError> {
+    pub async fn execute(
+        &self,
+        check: &ResourceCheck,
+        user: &UserFull,
+        client_ip: &str,
+    ) -> Result<Vec<Project>, AppError> {
+        let user_id = &user.user.id;
+        // the caller's own projects, checked as a project they own
+        authorize_project(&self.rbac, check, user, client_ip, "", Some(user_id)).await?;
+
         let projects = self.project_repo.find_all_by_user_id(user_id).await?;
 
         Ok(projects)
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::{
+            access_context::ResourceCheck, field_access::Redacted, project::ProjectWithOwnerEmail,
+            user::UserFull,
+        },
+        repositories::project_repo::ProjectRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
+};
+
+use super::project_access::{authorize_existing_project, redact};
+
+#[derive(Clone)]
+pub struct GetProjectById<R> {
+    project_repo: Arc<R>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R> GetProjectById<R>
+where
+    R: ProjectRepository,
+{
+    pub fn new(project_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { project_repo, rbac }
+    }
+
+    pub async fn execute(
+        &self,
+        project_id: &str,
+        check: &ResourceCheck,
+        user: &UserFull,
+        client_ip: &str,
+    ) -> Result<Redacted<ProjectWithOwnerEmail>, AppError> {
+        let project = self.project_repo.find_by_id(project_id).await?;
+
+        authorize_existing_project(&self.rbac, check, user, client_ip, &project).await?;
+
+        redact(&self.rbac, user, client_ip, project).await
+    }
+}
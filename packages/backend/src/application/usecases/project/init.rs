@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
-use crate::infra::repositories::pg_project_repo::PgProjectRepository;
+use crate::infra::{rbac::Rbac, repositories::pg_project_repo::PgProjectRepository};
 
 use super::{
     get_all_project_by_user_id::GetAllProjectByUserId,
     create_project::CreateProject,
-    get_project_by_id_and_user_id::GetProjectByIdAndUserId,
+    get_project_by_id::GetProjectById,
     update_project::UpdateProject,
     delete_project::DeleteProject,
 };
@@ -14,23 +14,23 @@ use super::{
 pub struct ProjectUsecase {
     pub get_all_by_user_id: Arc<GetAllProjectByUserId<PgProjectRepository>>,
     pub create_project: Arc<CreateProject<PgProjectRepository>>,
-    pub get_by_id_and_user_id: Arc<GetProjectByIdAndUserId<PgProjectRepository>>,
+    pub get_by_id: Arc<GetProjectById<PgProjectRepository>>,
     pub update_project: Arc<UpdateProject<PgProjectRepository>>,
     pub delete_project: Arc<DeleteProject<PgProjectRepository>>,
 }
 
 impl ProjectUsecase {
-    pub fn new(project_repo: Arc<PgProjectRepository>) -> Self {
-        let get_all_by_user_id = Arc::new(GetAllProjectByUserId::new(project_repo.clone()));
-        let create_project = Arc::new(CreateProject::new(project_repo.clone()));
-        let get_by_id_and_user_id = Arc::new(GetProjectByIdAndUserId::new(project_repo.clone()));
-        let update_project = Arc::new(UpdateProject::new(project_repo.clone()));
-        let delete_project = Arc::new(DeleteProject::new(project_repo.clone()));
+    pub fn new(project_repo: Arc<PgProjectRepository>, rbac: Arc<Rbac>) -> Self {
+        let get_all_by_user_id = Arc::new(GetAllProjectByUserId::new(project_repo.clone(), rbac.clone()));
+        let create_project = Arc::new(CreateProject::new(project_repo.clone(), rbac.clone()));
+        let get_by_id = Arc::new(GetProjectById::new(project_repo.clone(), rbac.clone()));
+        let update_project = Arc::new(UpdateProject::new(project_repo.clone(), rbac.clone()));
+        let delete_project = Arc::new(DeleteProject::new(project_repo.clone(), rbac.clone()));
 
         Self {
             get_all_by_user_id,
             create_project,
-            get_by_id_and_user_id,
+            get_by_id,
             update_project,
             delete_project,
         }
@@ -1,6 +1,7 @@
 pub mod init;
 pub mod get_all_project_by_user_id;
-pub mod get_project_by_id_and_user_id;
+pub mod get_project_by_id;
 pub mod create_project;
 pub mod update_project;
 pub mod delete_project;
+mod project_access;
@@ -0,0 +1,85 @@
+use crate::{
+    domain::entities::{
+        access_context::{AccessContext, ResourceAttributes, ResourceCheck},
+        field_access::{ProtectedFields, Redacted},
+        project::ProjectWithOwnerEmail,
+        user::UserFull,
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{Rbac, ResourceGrant, GLOBAL_DOMAIN},
+    },
+};
+
+// projects policies may depend on the owner, e.g. owners delete their own and admins any,
+// the action is the one the route declared
+pub(super) async fn authorize_project(
+    rbac: &Rbac,
+    check: &ResourceCheck,
+    user: &UserFull,
+    client_ip: &str,
+    project_id: &str,
+    owner_id: Option<&str>,
+) -> Result<ResourceGrant, AppError> {
+    if check.object != "projects" || check.domain != GLOBAL_DOMAIN {
+        return Err(AppError::Forbidden);
+    }
+
+    let ctx = AccessContext::new(&user.user.id, Some(client_ip)).with_resource(ResourceAttributes {
+        owner_id: owner_id.unwrap_or_default().to_string(),
+        ..Default::default()
+    });
+
+    rbac.authorize_resource(check, &user.roles, project_id, &ctx)
+        .await?
+        .ok_or(AppError::Forbidden)
+}
+
+// a project the caller may not act on answers like a missing one, so the status does not tell
+// whether it exists
+pub(super) async fn authorize_existing_project(
+    rbac: &Rbac,
+    check: &ResourceCheck,
+    user: &UserFull,
+    client_ip: &str,
+    project: &ProjectWithOwnerEmail,
+) -> Result<ResourceGrant, AppError> {
+    authorize_project(rbac, check, user, client_ip, &project.id, project.user_id.as_deref())
+        .await
+        .map_err(|err| match err {
+            AppError::Forbidden => AppError::ResourceNotFound,
+            err => err,
+        })
+}
+
+// the value without the fields the caller may not read
+pub(super) async fn redact<T: ProtectedFields>(
+    rbac: &Rbac,
+    user: &UserFull,
+    client_ip: &str,
+    value: T,
+) -> Result<Redacted<T>, AppError> {
+    let ctx = AccessContext::new(&user.user.id, Some(client_ip));
+
+    Ok(rbac.redact(&user.roles, GLOBAL_DOMAIN, value, &ctx).await?)
+}
+
+// rejects requests setting fields the caller may not write, owner_id is the project's owner
+pub(super) async fn authorize_fields<T: ProtectedFields>(
+    rbac: &Rbac,
+    user: &UserFull,
+    client_ip: &str,
+    request: &T,
+    owner_id: Option<&str>,
+) -> Result<(), AppError> {
+    let ctx = AccessContext::new(&user.user.id, Some(client_ip));
+    let forbidden = rbac
+        .forbidden_writes(&user.roles, GLOBAL_DOMAIN, request, owner_id, &ctx)
+        .await?;
+
+    if !forbidden.is_empty() {
+        return Err(AppError::ProtectedFieldWrite(forbidden.join(", ")));
+    }
+
+    Ok(())
+}
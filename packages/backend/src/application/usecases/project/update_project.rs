@@ -2,31 +2,52 @@ use std::sync::Arc;
 
 use crate::{
     application::dto::project::create_update_project_request::CreateOrUpdateProject,
-    domain::{entities::project::{Project, ProjectWithOwnerEmail}, repositories::project_repo::ProjectRepository},
-    infra::errors::app_error::AppError,
+    domain::{
+        entities::{
+            access_context::ResourceCheck,
+            field_access::Redacted,
+            project::{Project, ProjectWithOwnerEmail},
+            user::UserFull,
+        },
+        repositories::project_repo::ProjectRepository,
+    },
+    infra::{errors::app_error::AppError, rbac::Rbac},
 };
 
+use super::project_access::{authorize_existing_project, authorize_fields, redact};
+
 #[derive(Clone)]
 pub struct UpdateProject<R> {
-    project_repo: Arc<R>
+    project_repo: Arc<R>,
+    rbac: Arc<Rbac>,
 }
 
 impl<R> UpdateProject<R>
 where
     R: ProjectRepository,
 {
-    pub fn new(project_repo: Arc<R>) -> Self {
-        Self { project_repo }
+    pub fn new(project_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
+        Self { project_repo, rbac }
     }
 
-    pub async fn execute(&self, id: &str, user_id: &str, req: CreateOrUpdateProject) -> Result<ProjectWithOwnerEmail, AppError> {
-
+    pub async fn execute(
+        &self,
+        id: &str,
+        check: &ResourceCheck,
+        user: &UserFull,
+        client_ip: &str,
+        req: CreateOrUpdateProject,
+    ) -> Result<Redacted<ProjectWithOwnerEmail>, AppError> {
+        let existing = self.project_repo.find_by_id(id).await?;
+        let grant = authorize_existing_project(&self.rbac, check, user, client_ip, &existing).await?;
+        authorize_fields(&self.rbac, user, client_ip, &req, existing.user_id.as_deref()).await?;
+
+        // the owner stays the same unless the project is handed over
         let mut project_req = Project::from(&req);
+        project_req.user_id = req.user_id.or(existing.user_id);
 
-        project_req.user_id = Some(user_id.to_string());
-
-        let project = self.project_repo.update(id, user_id, project_req).await?;
+        let project = self.project_repo.update(&grant, project_req).await?;
 
-        Ok(project)
+        redact(&self.rbac, user, client_ip, project).await
     }
 }
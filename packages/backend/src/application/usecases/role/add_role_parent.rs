@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use casbin::MgmtApi;
+use tracing::info;
+
+use crate::{
+    application::{
+        services::role_constraint_svc::RoleConstraintService,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::role_policy_change::POLICY_CHANGE_PARENT_ADDED,
+        repositories::{
+            policy_repo::PolicyRepository, role_policy_change_repo::RolePolicyChangeRepository,
+            role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, GLOBAL_DOMAIN},
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+use super::policy_history::record_policy_change;
+
+#[derive(Clone)]
+pub struct AddRoleParent<R, Q, H> {
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<R, Q, H> AddRoleParent<R, Q, H>
+where
+    R: RoleRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            role_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+            role_constraint_svc,
+        }
+    }
+
+    // the role inherits every policy of the parent
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+        parent_id: &str,
+    ) -> Result<(), AppError> {
+        let role = self.role_repo.find_by_id(id).await?;
+
+        // global roles can only be managed globally
+        if role.domain != domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::Forbidden
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        let parent = self.role_repo.find_by_id(parent_id).await?;
+        if parent.domain != GLOBAL_DOMAIN && parent.domain != role.domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::ProcessError(
+                    "Global roles can only inherit other global roles".to_owned(),
+                )
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&role.name) || is_reserved_name(&parent.name) {
+            return Err(AppError::ProcessError(
+                "Reserved roles cannot take part in inheritance".to_owned(),
+            ));
+        }
+
+        if parent.id == role.id {
+            return Err(AppError::ProcessError(
+                "A role cannot inherit itself".to_owned(),
+            ));
+        }
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let before = Rbac::role_snapshot(&enforcer, &role.id);
+
+        // the parent must not already inherit the role, directly or through other roles
+        let grouping_policies = enforcer.get_grouping_policy();
+        if Rbac::ancestor_roles(&grouping_policies, &parent.id, &role.domain).contains(&role.id) {
+            return Err(AppError::ProcessError(format!(
+                "Role {} already inherits {}, adding it as a parent would create a cycle",
+                parent.name, role.name
+            )));
+        }
+
+        let link = vec![role.id.clone(), parent.id.clone(), role.domain.clone()];
+        if grouping_policies.contains(&link) {
+            return Ok(());
+        }
+
+        let mut proposed = grouping_policies.clone();
+        proposed.push(link.clone());
+        self.role_constraint_svc
+            .check_groupings(&grouping_policies, &proposed)
+            .await?;
+
+        let mut parents = before.parents.clone();
+        parents.push(parent.id.clone());
+        let after = Rbac::policy_snapshot(
+            enforcer.get_filtered_policy(0, vec![role.id.clone()]),
+            parents,
+        );
+
+        let mut tx = db_pool.begin().await?;
+        self.policy_repo.tx_add_rules(&mut tx, "g", std::slice::from_ref(&link)).await?;
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_PARENT_ADDED,
+            Some(before),
+            Some(after),
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_groupings: vec![link],
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        info!("Role {} now inherits {}", role.id, parent.id);
+
+        Ok(())
+    }
+}
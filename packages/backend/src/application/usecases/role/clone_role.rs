@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use casbin::MgmtApi;
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::role::clone_role_request::CloneRoleRequest,
+        services::role_constraint_svc::RoleConstraintService,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::{role::Role, role_policy_change::POLICY_CHANGE_CREATED},
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, GLOBAL_DOMAIN, SHARED_OBJECT_PREFIX},
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+use super::policy_history::record_policy_change;
+
+#[derive(Clone)]
+pub struct CloneRole<R, P, Q, H> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<R, P, Q, H> CloneRole<R, P, Q, H>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+            role_constraint_svc,
+        }
+    }
+
+    // a new role of the domain with the permissions, denies, conditions and parents of the source,
+    // its members and default flag are not copied
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+        req: CloneRoleRequest,
+    ) -> Result<Role, AppError> {
+        req.validate()?;
+
+        let source = self.role_repo.find_by_id(id).await?;
+
+        // roles of other organizations are invisible here, global ones can be copied into any
+        if source.domain != GLOBAL_DOMAIN && source.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&source.name) {
+            return Err(AppError::ProcessError(
+                "Reserved roles cannot be cloned".to_owned(),
+            ));
+        }
+
+        let role_req = Role::new(
+            uuid::Uuid::new_v4().to_string(),
+            req.name,
+            false,
+            domain.to_string(),
+        );
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+
+        let policies = enforcer
+            .get_filtered_policy(0, vec![source.id.clone()])
+            .into_iter()
+            .map(|mut policy| {
+                policy[0] = role_req.id.clone();
+                policy[1] = role_req.domain.clone();
+                policy
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        // the registry may have lost permissions the source still holds, shared objects are
+        // handed out per resource and never registered
+        let mut requested = policies
+            .iter()
+            .filter(|policy| !policy[2].starts_with(SHARED_OBJECT_PREFIX))
+            .map(|policy| format!("{}:{}", policy[2], policy[3]))
+            .collect::<Vec<String>>();
+        requested.sort();
+        requested.dedup();
+        let unknown = self.permission_repo.find_unknown(&requested).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permissions: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let grouping_policies = enforcer.get_grouping_policy();
+        let parents = Rbac::parent_roles(&grouping_policies, &source.id, &source.domain)
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        let groupings = parents
+            .iter()
+            .map(|parent_id| vec![role_req.id.clone(), parent_id.clone(), role_req.domain.clone()])
+            .collect::<Vec<Vec<String>>>();
+
+        // the copy inherits the same roles, possibly ones a constraint keeps apart in this domain
+        let mut proposed = grouping_policies.clone();
+        proposed.extend(groupings.iter().cloned());
+        self.role_constraint_svc
+            .check_groupings(&grouping_policies, &proposed)
+            .await?;
+
+        let mut tx = db_pool.begin().await?;
+
+        let role = self.role_repo.tx_create(&mut tx, role_req).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "p", &policies).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "g", &groupings).await?;
+
+        let after = Rbac::policy_snapshot(policies.clone(), parents);
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_CREATED,
+            None,
+            Some(after),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies: policies,
+                added_groupings: groupings,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        info!("Role {} cloned from {}", role.id, source.id);
+
+        Ok(role)
+    }
+}
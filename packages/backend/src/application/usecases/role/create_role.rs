@@ -1,53 +1,157 @@
 use std::sync::Arc;
 
-use casbin::MgmtApi;
-
 use crate::{
-    application::dto::role::create_update_role_request::CreateOrUpdateRole,
-    domain::{entities::role::Role, repositories::role_repo::RoleRepository},
-    infra::{errors::app_error::AppError, rbac::Rbac},
+    application::{
+        dto::role::create_update_role_request::CreateOrUpdateRole,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::{role::Role, role_policy_change::POLICY_CHANGE_CREATED},
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, EFFECT_DENY, GLOBAL_DOMAIN},
+    },
 };
 
+use super::policy_history::record_policy_change;
+
 #[derive(Clone)]
-pub struct CreateRole<R> {
+pub struct CreateRole<R, P, Q, H> {
     role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
     rbac: Arc<Rbac>,
 }
 
-impl<R> CreateRole<R>
+impl<R, P, Q, H> CreateRole<R, P, Q, H>
 where
     R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
 {
-    pub fn new(role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
-        Self { role_repo, rbac }
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+        }
     }
 
-    pub async fn execute(&self, req: CreateOrUpdateRole) -> Result<Role, AppError> {
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        req: CreateOrUpdateRole,
+    ) -> Result<Role, AppError> {
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&req.name) {
+            return Err(AppError::ProcessError("Reserved role names cannot be used".to_owned()));
+        }
+
+        if req.is_default && domain != GLOBAL_DOMAIN {
+            return Err(AppError::ProcessError(
+                "Only global roles can be the default role".to_owned(),
+            ));
+        }
+
         if req.is_default && self.role_repo.find_default().await.is_ok() {
             return Err(AppError::ResourceExist(
                 "Default role already exist".to_owned(),
             ));
         }
 
-        let role_req = Role::from(&req);
+        let unmatched = req.unmatched_conditions();
+        if !unmatched.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Conditions for permissions the role does not have: {}",
+                unmatched.join(", ")
+            )));
+        }
 
-        let mut enforcer = self.rbac.enforcer.write().await;
-        if let Some(permissions) = req.permissions {
-            for permission in permissions {
-                let permission_action = permission.split(':').collect::<Vec<&str>>();
-                let policy = vec![
-                    role_req.id.clone(),
-                    permission_action[0].to_string(),
-                    permission_action[1].to_string(),
-                ];
+        let mut role_req = Role::from(&req);
+        role_req.domain = domain.to_string();
 
-                println!("Adding policy: {:?}", policy);
+        let mut policies = Vec::new();
+        for permission in req.permissions.iter().flatten() {
+            policies.push(Rbac::conditional_policy(
+                &role_req.id,
+                &role_req.domain,
+                permission,
+                EFFECT_ALLOW,
+                req.condition(permission),
+            )?);
+        }
+        for permission in req.denied_permissions.iter().flatten() {
+            policies.push(Rbac::conditional_policy(
+                &role_req.id,
+                &role_req.domain,
+                permission,
+                EFFECT_DENY,
+                req.condition(permission),
+            )?);
+        }
 
-                let _ = enforcer.add_policy(policy).await;
-            }
+        // only permissions from the registry can be granted or denied
+        let requested = req
+            .permissions
+            .iter()
+            .flatten()
+            .chain(req.denied_permissions.iter().flatten())
+            .cloned()
+            .collect::<Vec<String>>();
+        let unknown = self.permission_repo.find_unknown(&requested).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permissions: {}",
+                unknown.join(", ")
+            )));
         }
 
-        let role = self.role_repo.create(role_req).await?;
+        // the role and its policies commit together, the enforcer only learns about them after
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let mut tx = db_pool.begin().await?;
+
+        let role = self.role_repo.tx_create(&mut tx, role_req).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "p", &policies).await?;
+
+        let after = Rbac::policy_snapshot(policies.clone(), Vec::new());
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_CREATED,
+            None,
+            Some(after),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies: policies,
+                ..Default::default()
+            },
+        )
+        .await?;
 
         Ok(role)
     }
@@ -4,42 +4,112 @@ use casbin::MgmtApi;
 use tracing::info;
 
 use crate::{
-    domain::repositories::role_repo::RoleRepository,
-    infra::{common::constants::SUPER_ADMIN_ROLE, errors::app_error::AppError, rbac::Rbac},
+    application::usecases::policy::document::is_reserved_name,
+    domain::{
+        entities::role_policy_change::POLICY_CHANGE_DELETED,
+        repositories::{
+            policy_repo::PolicyRepository, role_policy_change_repo::RolePolicyChangeRepository,
+            role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, GLOBAL_DOMAIN},
+    },
 };
 
+use super::policy_history::record_policy_change;
+
 #[derive(Clone)]
-pub struct DeleteRoleById<R> {
+pub struct DeleteRoleById<R, Q, H> {
     role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
     rbac: Arc<Rbac>,
 }
 
-impl<R> DeleteRoleById<R>
+impl<R, Q, H> DeleteRoleById<R, Q, H>
 where
     R: RoleRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
 {
-    pub fn new(role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
-        Self { role_repo, rbac }
+    pub fn new(
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+        }
     }
 
-    pub async fn execute(&self, id: &str) -> Result<(), AppError> {
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+    ) -> Result<(), AppError> {
         let role = self.role_repo.find_by_id(id).await?;
 
-        if role.name == SUPER_ADMIN_ROLE {
+        // global roles can only be managed globally
+        if role.domain != domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::Forbidden
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&role.name) {
             return Err(AppError::ProcessError(
-                "Cannot delete super admin role".to_string(),
+                "Cannot delete a reserved role".to_string(),
             ));
         }
 
-        info!("Deleting Role with id {}...", id);
-        self.role_repo.delete(id).await?;
         let mut enforcer = self.rbac.enforcer.write().await;
-        let current_policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+        let before = Rbac::role_snapshot(&enforcer, &role.id);
+        let policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+        let links = enforcer
+            .get_grouping_policy()
+            .into_iter()
+            .filter(|rule| rule.len() >= 2 && (rule[0] == role.id || rule[1] == role.id))
+            .collect::<Vec<Vec<String>>>();
 
-        info!("Removing policies for Role with id {}...", id);
-        for policy in current_policies {
-            enforcer.remove_policy(policy).await?;
-        }
+        info!("Deleting Role with id {} with its policies and inheritance links...", id);
+        let mut tx = db_pool.begin().await?;
+        self.role_repo.tx_delete(&mut tx, id).await?;
+        let role_ids = std::slice::from_ref(&role.id);
+        self.policy_repo
+            .tx_replace_role_rules(&mut tx, role_ids, role_ids, &[], &[])
+            .await?;
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_DELETED,
+            Some(before),
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                removed_policies: policies,
+                removed_groupings: links,
+                ..Default::default()
+            },
+        )
+        .await?;
 
         Ok(())
     }
@@ -18,8 +18,8 @@ where
         Self { role_repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Role>, AppError> {
-        let roles = self.role_repo.find_all().await?;
+    pub async fn execute(&self, domain: &str) -> Result<Vec<Role>, AppError> {
+        let roles = self.role_repo.find_all(domain).await?;
 
         Ok(roles)
     }
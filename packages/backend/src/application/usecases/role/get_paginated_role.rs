@@ -23,10 +23,11 @@ where
 
     pub async fn execute(
         &self,
+        domain: &str,
         page: i64,
         limit: i64,
     ) -> Result<PaginatedResponse<Role>, AppError> {
-        let (roles, total_items) = self.role_repo.paginate(page, limit).await?;
+        let (roles, total_items) = self.role_repo.paginate(domain, page, limit).await?;
 
         let total_pages = (total_items as f64 / limit as f64).ceil() as i64;
 
@@ -3,9 +3,12 @@ use std::sync::Arc;
 use casbin::MgmtApi;
 
 use crate::{
-    application::dto::role::get_role_request::RoleWithPermission,
+    application::dto::role::get_role_request::{InheritedPermission, RoleWithPermission},
     domain::repositories::role_repo::RoleRepository,
-    infra::{errors::app_error::AppError, rbac::Rbac},
+    infra::{
+        errors::app_error::AppError,
+        rbac::{Rbac, CONDITION_ALWAYS, EFFECT_DENY, GLOBAL_DOMAIN},
+    },
 };
 
 #[derive(Clone)]
@@ -22,23 +25,74 @@ where
         Self { role_repo, rbac }
     }
 
-    pub async fn execute(&self, id: &str) -> Result<RoleWithPermission, AppError> {
+    pub async fn execute(&self, domain: &str, id: &str) -> Result<RoleWithPermission, AppError> {
         let role = self.role_repo.find_by_id(id).await?;
 
-        let policies = self
-            .rbac
-            .enforcer
-            .read()
-            .await
-            .get_filtered_policy(0, vec![role.id.clone()]);
+        // roles of other organizations are invisible here
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
 
-        let permissions = policies
-            .into_iter()
-            .filter(|policy| policy.len() == 3)
-            .map(|policy| format!("{}:{}", policy[1], policy[2]))
+        let enforcer = self.rbac.enforcer.read().await;
+        let grouping_policies = enforcer.get_grouping_policy();
+        let policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+        let parent_ids = Rbac::parent_roles(&grouping_policies, &role.id, &role.domain)
+            .map(str::to_string)
             .collect::<Vec<String>>();
+        let ancestor_policies = Rbac::ancestor_roles(&grouping_policies, &role.id, &role.domain)
+            .into_iter()
+            .map(|ancestor_id| {
+                let policies = enforcer.get_filtered_policy(0, vec![ancestor_id.clone()]);
+                (ancestor_id, policies)
+            })
+            .collect::<Vec<(String, Vec<Vec<String>>)>>();
+        drop(enforcer);
+
+        let conditions = Rbac::policy_conditions(&policies);
+        let (permissions, denied_permissions) = Rbac::split_permissions(policies);
+
+        let mut parents = Vec::with_capacity(parent_ids.len());
+        for parent_id in &parent_ids {
+            parents.push(self.role_repo.find_by_id(parent_id).await?);
+        }
+
+        let mut inherited_permissions = Vec::new();
+        for (ancestor_id, policies) in ancestor_policies {
+            let ancestor = self.role_repo.find_by_id(&ancestor_id).await?;
+            for policy in policies.into_iter().filter(|policy| policy.len() >= 4) {
+                inherited_permissions.push(InheritedPermission {
+                    permission: format!("{}:{}", policy[2], policy[3]),
+                    effect: policy.get(4).cloned().unwrap_or_default(),
+                    condition: policy.get(5).filter(|condition| *condition != CONDITION_ALWAYS).cloned(),
+                    role_id: ancestor.id.clone(),
+                    role_name: ancestor.name.clone(),
+                });
+            }
+        }
+
+        let mut effective_permissions = permissions.clone();
+        let mut effective_denied_permissions = denied_permissions.clone();
+        for inherited in &inherited_permissions {
+            let effective = if inherited.effect == EFFECT_DENY {
+                &mut effective_denied_permissions
+            } else {
+                &mut effective_permissions
+            };
+            if !effective.contains(&inherited.permission) {
+                effective.push(inherited.permission.clone());
+            }
+        }
 
-        let role_with_permissions = RoleWithPermission { role, permissions };
+        let role_with_permissions = RoleWithPermission {
+            role,
+            permissions,
+            denied_permissions,
+            conditions,
+            parents,
+            inherited_permissions,
+            effective_permissions,
+            effective_denied_permissions,
+        };
 
         Ok(role_with_permissions)
     }
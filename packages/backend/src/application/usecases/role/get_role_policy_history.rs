@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::role_policy_change::RolePolicyChange,
+        repositories::{
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        utils::pagination::{PaginatedResponse, PaginationMeta},
+    },
+};
+
+#[derive(Clone)]
+pub struct GetRolePolicyHistory<R, H> {
+    role_repo: Arc<R>,
+    policy_change_repo: Arc<H>,
+}
+
+impl<R, H> GetRolePolicyHistory<R, H>
+where
+    R: RoleRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(role_repo: Arc<R>, policy_change_repo: Arc<H>) -> Self {
+        Self {
+            role_repo,
+            policy_change_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        id: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<PaginatedResponse<RolePolicyChange>, AppError> {
+        let role = self.role_repo.find_by_id(id).await?;
+
+        // roles of other organizations are invisible here
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        let (changes, total_items) = self
+            .policy_change_repo
+            .paginate_by_role(&role.id, page, limit)
+            .await?;
+
+        let total_pages = (total_items as f64 / limit as f64).ceil() as i64;
+
+        let pagination = PaginationMeta {
+            total_items,
+            total_pages,
+            current_page: page as i32,
+            items_per_page: limit as i32,
+        };
+
+        Ok(PaginatedResponse {
+            items: changes,
+            pagination,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{
+    application::dto::role::role_template_response::{
+        RoleTemplateInstance, RoleTemplateWithInstances,
+    },
+    domain::{
+        entities::role_template::ROLE_TEMPLATES,
+        repositories::{role_repo::RoleRepository, role_template_repo::RoleTemplateRepository},
+    },
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+#[derive(Clone)]
+pub struct GetRoleTemplates<R, T> {
+    role_repo: Arc<R>,
+    template_repo: Arc<T>,
+}
+
+impl<R, T> GetRoleTemplates<R, T>
+where
+    R: RoleRepository,
+    T: RoleTemplateRepository,
+{
+    pub fn new(role_repo: Arc<R>, template_repo: Arc<T>) -> Self {
+        Self {
+            role_repo,
+            template_repo,
+        }
+    }
+
+    pub async fn execute(&self, domain: &str) -> Result<Vec<RoleTemplateWithInstances>, AppError> {
+        // every role is visible when managed globally
+        let roles = if domain == GLOBAL_DOMAIN {
+            self.role_repo.find_all_across_domains().await?
+        } else {
+            self.role_repo.find_all(domain).await?
+        };
+        let links = self.template_repo.find_all().await?;
+
+        let templates = ROLE_TEMPLATES
+            .iter()
+            .map(|template| {
+                let instances = links
+                    .iter()
+                    .filter(|link| link.template_key == template.key)
+                    .filter_map(|link| {
+                        let role = roles.iter().find(|role| role.id == link.role_id)?;
+                        Some(RoleTemplateInstance {
+                            role_id: role.id.clone(),
+                            role_name: role.name.clone(),
+                            domain: role.domain.clone(),
+                            template_version: link.template_version,
+                            outdated: link.is_outdated(template),
+                        })
+                    })
+                    .collect();
+
+                RoleTemplateWithInstances {
+                    template: template.clone(),
+                    instances,
+                }
+            })
+            .collect();
+
+        Ok(templates)
+    }
+}
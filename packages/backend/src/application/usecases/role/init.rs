@@ -1,10 +1,25 @@
 use std::sync::Arc;
 
-use crate::infra::{rbac::Rbac, repositories::pg_role_repo::PgRoleRepository};
+use crate::{
+    application::services::role_constraint_svc::RoleConstraintService,
+    infra::{
+        rbac::Rbac,
+        repositories::{
+            pg_permission_repo::PgPermissionRepository, pg_policy_repo::PgPolicyRepository,
+            pg_role_constraint_repo::PgRoleConstraintRepository,
+            pg_role_policy_change_repo::PgRolePolicyChangeRepository, pg_role_repo::PgRoleRepository,
+            pg_role_template_repo::PgRoleTemplateRepository, pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
 
 use super::{
-    create_role::CreateRole, delete_role_by_id::DeleteRoleById, get_all_role::GetAllRole,
+    add_role_parent::AddRoleParent, clone_role::CloneRole, create_role::CreateRole,
+    delete_role_by_id::DeleteRoleById, get_all_role::GetAllRole,
     get_paginated_role::GetPaginatedRole, get_role_by_id::GetRoleById,
+    get_role_policy_history::GetRolePolicyHistory, get_role_templates::GetRoleTemplates,
+    instantiate_role_template::InstantiateRoleTemplate, remove_role_parent::RemoveRoleParent,
+    rollback_role_policy::RollbackRolePolicy, sync_role_template::SyncRoleTemplate,
     update_role_by_id::UpdateRoleById,
 };
 
@@ -13,19 +28,155 @@ pub struct RoleUsecase {
     pub get_paginated_role: Arc<GetPaginatedRole<PgRoleRepository>>,
     pub get_all_role: Arc<GetAllRole<PgRoleRepository>>,
     pub get_role_by_id: Arc<GetRoleById<PgRoleRepository>>,
-    pub create_role: Arc<CreateRole<PgRoleRepository>>,
-    pub update_role_by_id: Arc<UpdateRoleById<PgRoleRepository>>,
-    pub delete_role_by_id: Arc<DeleteRoleById<PgRoleRepository>>,
+    pub create_role: Arc<
+        CreateRole<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub update_role_by_id: Arc<
+        UpdateRoleById<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub delete_role_by_id:
+        Arc<DeleteRoleById<PgRoleRepository, PgPolicyRepository, PgRolePolicyChangeRepository>>,
+    pub add_role_parent:
+        Arc<AddRoleParent<PgRoleRepository, PgPolicyRepository, PgRolePolicyChangeRepository>>,
+    pub remove_role_parent:
+        Arc<RemoveRoleParent<PgRoleRepository, PgPolicyRepository, PgRolePolicyChangeRepository>>,
+    pub get_role_policy_history:
+        Arc<GetRolePolicyHistory<PgRoleRepository, PgRolePolicyChangeRepository>>,
+    pub rollback_role_policy: Arc<
+        RollbackRolePolicy<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub clone_role: Arc<
+        CloneRole<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+        >,
+    >,
+    pub get_role_templates: Arc<GetRoleTemplates<PgRoleRepository, PgRoleTemplateRepository>>,
+    pub instantiate_role_template: Arc<
+        InstantiateRoleTemplate<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+            PgRoleTemplateRepository,
+        >,
+    >,
+    pub sync_role_template: Arc<
+        SyncRoleTemplate<
+            PgRoleRepository,
+            PgPermissionRepository,
+            PgPolicyRepository,
+            PgRolePolicyChangeRepository,
+            PgRoleTemplateRepository,
+        >,
+    >,
 }
 
 impl RoleUsecase {
-    pub fn new(role_repo: Arc<PgRoleRepository>, rbac: Arc<Rbac>) -> Self {
+    pub fn new(
+        role_repo: Arc<PgRoleRepository>,
+        permission_repo: Arc<PgPermissionRepository>,
+        policy_repo: Arc<PgPolicyRepository>,
+        policy_change_repo: Arc<PgRolePolicyChangeRepository>,
+        template_repo: Arc<PgRoleTemplateRepository>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
         let get_paginated_role = Arc::new(GetPaginatedRole::new(role_repo.clone()));
         let get_all_role = Arc::new(GetAllRole::new(role_repo.clone()));
         let get_role_by_id = Arc::new(GetRoleById::new(role_repo.clone(), rbac.clone()));
-        let create_role = Arc::new(CreateRole::new(role_repo.clone(), rbac.clone()));
-        let update_role = Arc::new(UpdateRoleById::new(role_repo.clone(), rbac.clone()));
-        let delete_role_by_id = Arc::new(DeleteRoleById::new(role_repo.clone(), rbac.clone()));
+        let create_role = Arc::new(CreateRole::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+        ));
+        let update_role = Arc::new(UpdateRoleById::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+        ));
+        let delete_role_by_id = Arc::new(DeleteRoleById::new(
+            role_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+        ));
+        let add_role_parent = Arc::new(AddRoleParent::new(
+            role_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+            role_constraint_svc.clone(),
+        ));
+        let remove_role_parent = Arc::new(RemoveRoleParent::new(
+            role_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+        ));
+        let get_role_policy_history = Arc::new(GetRolePolicyHistory::new(
+            role_repo.clone(),
+            policy_change_repo.clone(),
+        ));
+        let rollback_role_policy = Arc::new(RollbackRolePolicy::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+            role_constraint_svc.clone(),
+        ));
+        let clone_role = Arc::new(CloneRole::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            rbac.clone(),
+            role_constraint_svc.clone(),
+        ));
+        let get_role_templates = Arc::new(GetRoleTemplates::new(
+            role_repo.clone(),
+            template_repo.clone(),
+        ));
+        let instantiate_role_template = Arc::new(InstantiateRoleTemplate::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            template_repo.clone(),
+            rbac.clone(),
+        ));
+        let sync_role_template = Arc::new(SyncRoleTemplate::new(
+            role_repo.clone(),
+            permission_repo.clone(),
+            policy_repo.clone(),
+            policy_change_repo.clone(),
+            template_repo.clone(),
+            rbac.clone(),
+        ));
 
         Self {
             get_paginated_role,
@@ -34,6 +185,14 @@ impl RoleUsecase {
             create_role,
             update_role_by_id: update_role,
             delete_role_by_id,
+            add_role_parent,
+            remove_role_parent,
+            get_role_policy_history,
+            rollback_role_policy,
+            clone_role,
+            get_role_templates,
+            instantiate_role_template,
+            sync_role_template,
         }
     }
 }
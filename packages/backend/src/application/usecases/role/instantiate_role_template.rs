@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::dto::role::role_template_request::InstantiateRoleTemplateRequest,
+    domain::{
+        entities::{
+            role::Role,
+            role_policy_change::POLICY_CHANGE_CREATED,
+            role_template::{RoleTemplate, RoleTemplateLink},
+        },
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+            role_template_repo::RoleTemplateRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW},
+    },
+};
+
+use super::policy_history::record_policy_change;
+
+#[derive(Clone)]
+pub struct InstantiateRoleTemplate<R, P, Q, H, T> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    template_repo: Arc<T>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R, P, Q, H, T> InstantiateRoleTemplate<R, P, Q, H, T>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+    T: RoleTemplateRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        template_repo: Arc<T>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            template_repo,
+            rbac,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        key: &str,
+        req: InstantiateRoleTemplateRequest,
+    ) -> Result<Role, AppError> {
+        req.validate()?;
+
+        let template = RoleTemplate::find(key).ok_or(AppError::ResourceNotFound)?;
+
+        // the registry can lose permissions a template still lists
+        let unknown = self.permission_repo.find_unknown(&template.permissions()).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Template {} uses unknown permissions: {}",
+                template.key,
+                unknown.join(", ")
+            )));
+        }
+
+        let role_req = Role::new(
+            uuid::Uuid::new_v4().to_string(),
+            req.name.unwrap_or_else(|| template.name.to_string()),
+            false,
+            domain.to_string(),
+        );
+
+        let policies = template
+            .permissions
+            .iter()
+            .map(|permission| {
+                Rbac::permission_policy(&role_req.id, &role_req.domain, permission, EFFECT_ALLOW)
+            })
+            .collect::<Result<Vec<Vec<String>>, AppError>>()?;
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let mut tx = db_pool.begin().await?;
+
+        let role = self.role_repo.tx_create(&mut tx, role_req).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "p", &policies).await?;
+        self.template_repo
+            .tx_save(&mut tx, &RoleTemplateLink::new(role.id.clone(), template))
+            .await?;
+
+        let after = Rbac::policy_snapshot(policies.clone(), Vec::new());
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_CREATED,
+            None,
+            Some(after),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies: policies,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        info!("Role {} created from template {} v{}", role.id, template.key, template.version);
+
+        Ok(role)
+    }
+}
@@ -1,7 +1,16 @@
+pub mod add_role_parent;
+pub mod clone_role;
 pub mod create_role;
 pub mod delete_role_by_id;
 pub mod get_all_role;
 pub mod get_paginated_role;
 pub mod get_role_by_id;
+pub mod get_role_policy_history;
+pub mod get_role_templates;
 pub mod init;
+pub mod instantiate_role_template;
+pub mod policy_history;
+pub mod remove_role_parent;
+pub mod rollback_role_policy;
+pub mod sync_role_template;
 pub mod update_role_by_id;
@@ -0,0 +1,41 @@
+use crate::{
+    domain::{
+        entities::{
+            role::Role,
+            role_policy_change::{RolePolicyChange, RolePolicySnapshot},
+        },
+        repositories::role_policy_change_repo::RolePolicyChangeRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+// adds the next version to the role's history unless the policies did not change, in the
+// transaction that writes the rules; callers still hold the enforcer write lock so versions
+// follow the order of the changes
+pub async fn record_policy_change<H>(
+    policy_change_repo: &H,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    role: &Role,
+    actor_id: &str,
+    action: &str,
+    before: Option<RolePolicySnapshot>,
+    after: Option<RolePolicySnapshot>,
+) -> Result<Option<RolePolicyChange>, AppError>
+where
+    H: RolePolicyChangeRepository,
+{
+    let change = RolePolicyChange::new(
+        role.id.clone(),
+        role.name.clone(),
+        role.domain.clone(),
+        action,
+        Some(actor_id.to_string()),
+        before,
+        after,
+    );
+    if change.is_noop() {
+        return Ok(None);
+    }
+
+    Ok(Some(policy_change_repo.tx_create(tx, &change).await?))
+}
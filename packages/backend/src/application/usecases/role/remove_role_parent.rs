@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use casbin::MgmtApi;
+use tracing::info;
+
+use crate::{
+    application::usecases::policy::document::is_reserved_name,
+    domain::{
+        entities::role_policy_change::POLICY_CHANGE_PARENT_REMOVED,
+        repositories::{
+            policy_repo::PolicyRepository, role_policy_change_repo::RolePolicyChangeRepository,
+            role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, GLOBAL_DOMAIN},
+    },
+};
+
+use super::policy_history::record_policy_change;
+
+#[derive(Clone)]
+pub struct RemoveRoleParent<R, Q, H> {
+    role_repo: Arc<R>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R, Q, H> RemoveRoleParent<R, Q, H>
+where
+    R: RoleRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+        parent_id: &str,
+    ) -> Result<(), AppError> {
+        let role = self.role_repo.find_by_id(id).await?;
+
+        // global roles can only be managed globally
+        if role.domain != domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::Forbidden
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        let parent_reserved = match self.role_repo.find_by_id(parent_id).await {
+            Ok(parent) => is_reserved_name(&parent.name),
+            Err(_) => false,
+        };
+        if is_reserved_name(&role.name) || parent_reserved {
+            return Err(AppError::ProcessError(
+                "Reserved roles cannot take part in inheritance".to_owned(),
+            ));
+        }
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let link = vec![role.id.clone(), parent_id.to_string(), role.domain.clone()];
+        if !enforcer.has_grouping_policy(link.clone()) {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        let before = Rbac::role_snapshot(&enforcer, &role.id);
+        let after = Rbac::policy_snapshot(
+            enforcer.get_filtered_policy(0, vec![role.id.clone()]),
+            before
+                .parents
+                .iter()
+                .filter(|id| *id != parent_id)
+                .cloned()
+                .collect(),
+        );
+
+        let mut tx = db_pool.begin().await?;
+        self.policy_repo.tx_remove_rules(&mut tx, "g", std::slice::from_ref(&link)).await?;
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_PARENT_REMOVED,
+            Some(before),
+            Some(after),
+        )
+        .await?;
+        tx.commit().await?;
+
+        // links removed from `*` stay copied into organization domains until rebuilt,
+        // applying a removed grouping rebuilds them
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                removed_groupings: vec![link],
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        info!("Role {} no longer inherits {}", role.id, parent_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use casbin::MgmtApi;
+use tracing::info;
+
+use crate::{
+    application::{
+        services::role_constraint_svc::RoleConstraintService,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::role_policy_change::{RolePolicyChange, POLICY_CHANGE_ROLLED_BACK},
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, EFFECT_DENY, GLOBAL_DOMAIN},
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+#[derive(Clone)]
+pub struct RollbackRolePolicy<R, P, Q, H> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    rbac: Arc<Rbac>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<R, P, Q, H> RollbackRolePolicy<R, P, Q, H>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+            role_constraint_svc,
+        }
+    }
+
+    // restores the permissions, denials and parents the role had right after the given version,
+    // the rollback itself is recorded as a new version
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+        version: i32,
+    ) -> Result<RolePolicyChange, AppError> {
+        let role = self.role_repo.find_by_id(id).await?;
+
+        // global roles can only be managed globally
+        if role.domain != domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::Forbidden
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&role.name) {
+            return Err(AppError::ProcessError(
+                "Reserved roles cannot be rolled back".to_owned(),
+            ));
+        }
+
+        let change = self.policy_change_repo.find_by_version(&role.id, version).await?;
+        let Some(target) = change.after else {
+            return Err(AppError::ProcessError(format!(
+                "Version {} has no permissions to restore",
+                version
+            )));
+        };
+
+        // the registry may have lost permissions since that version
+        let requested = target
+            .permissions
+            .iter()
+            .chain(&target.denied_permissions)
+            .cloned()
+            .collect::<Vec<String>>();
+        let unknown = self.permission_repo.find_unknown(&requested).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permissions: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let mut desired_policies = Vec::new();
+        for permission in &target.permissions {
+            let condition = target.conditions.get(permission).map(String::as_str);
+            desired_policies.push(Rbac::conditional_policy(
+                &role.id,
+                &role.domain,
+                permission,
+                EFFECT_ALLOW,
+                condition,
+            )?);
+        }
+        for permission in &target.denied_permissions {
+            let condition = target.conditions.get(permission).map(String::as_str);
+            desired_policies.push(Rbac::conditional_policy(
+                &role.id,
+                &role.domain,
+                permission,
+                EFFECT_DENY,
+                condition,
+            )?);
+        }
+
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let before = Rbac::role_snapshot(&enforcer, &role.id);
+
+        // parents are checked the same way as when they are added through the role endpoints
+        let grouping_policies = enforcer.get_grouping_policy();
+        for parent_id in target.parents.iter().filter(|parent_id| !before.parents.contains(parent_id)) {
+            let parent = self.role_repo.find_by_id(parent_id).await.map_err(|_| {
+                AppError::ProcessError(format!("Parent role {} no longer exists", parent_id))
+            })?;
+            if is_reserved_name(&parent.name)
+                || (parent.domain != GLOBAL_DOMAIN && parent.domain != role.domain)
+            {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} can no longer inherit {}",
+                    role.name, parent.name
+                )));
+            }
+            if Rbac::ancestor_roles(&grouping_policies, &parent.id, &role.domain).contains(&role.id) {
+                return Err(AppError::ProcessError(format!(
+                    "Role {} already inherits {}, restoring it as a parent would create a cycle",
+                    parent.name, role.name
+                )));
+            }
+        }
+
+        let current_policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+        let added_policies = desired_policies
+            .iter()
+            .filter(|policy| !current_policies.contains(policy))
+            .cloned()
+            .collect::<Vec<Vec<String>>>();
+        let removed_policies = current_policies
+            .into_iter()
+            .filter(|policy| !desired_policies.contains(policy))
+            .collect::<Vec<Vec<String>>>();
+        let link = |parent_id: &String| vec![role.id.clone(), parent_id.clone(), role.domain.clone()];
+        let added_groupings = target
+            .parents
+            .iter()
+            .filter(|parent_id| !before.parents.contains(parent_id))
+            .map(link)
+            .collect::<Vec<Vec<String>>>();
+        let removed_groupings = enforcer
+            .get_filtered_grouping_policy(0, vec![role.id.clone()])
+            .into_iter()
+            .filter(|rule| rule.len() >= 2 && !target.parents.contains(&rule[1]))
+            .collect::<Vec<Vec<String>>>();
+
+        let proposed = grouping_policies
+            .iter()
+            .filter(|rule| !removed_groupings.contains(rule))
+            .chain(&added_groupings)
+            .cloned()
+            .collect::<Vec<Vec<String>>>();
+        self.role_constraint_svc
+            .check_groupings(&grouping_policies, &proposed)
+            .await?;
+
+        let mut tx = db_pool.begin().await?;
+        self.policy_repo.tx_remove_rules(&mut tx, "p", &removed_policies).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "p", &added_policies).await?;
+        self.policy_repo.tx_remove_rules(&mut tx, "g", &removed_groupings).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "g", &added_groupings).await?;
+
+        let after = Rbac::policy_snapshot(desired_policies, target.parents.clone());
+        let mut rollback = RolePolicyChange::new(
+            role.id.clone(),
+            role.name.clone(),
+            role.domain.clone(),
+            POLICY_CHANGE_ROLLED_BACK,
+            Some(actor_id.to_string()),
+            Some(before),
+            Some(after),
+        );
+        rollback.rolled_back_to = Some(version);
+        let rollback = self.policy_change_repo.tx_create(&mut tx, &rollback).await?;
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies,
+                removed_policies,
+                added_groupings,
+                removed_groupings,
+            },
+        )
+        .await?;
+
+        info!("Rolled back policies of Role {} to version {}", role.id, version);
+
+        Ok(rollback)
+    }
+}
@@ -0,0 +1,187 @@
+use std::{collections::HashSet, sync::Arc};
+
+use casbin::MgmtApi;
+use tracing::info;
+
+use crate::{
+    domain::{
+        entities::{
+            role::Role,
+            role_policy_change::POLICY_CHANGE_TEMPLATE_SYNCED,
+            role_template::{RoleTemplate, RoleTemplateLink},
+        },
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+            role_template_repo::RoleTemplateRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, GLOBAL_DOMAIN},
+    },
+};
+
+use super::policy_history::record_policy_change;
+
+#[derive(Clone)]
+pub struct SyncRoleTemplate<R, P, Q, H, T> {
+    role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
+    template_repo: Arc<T>,
+    rbac: Arc<Rbac>,
+}
+
+impl<R, P, Q, H, T> SyncRoleTemplate<R, P, Q, H, T>
+where
+    R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
+    T: RoleTemplateRepository,
+{
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        template_repo: Arc<T>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            template_repo,
+            rbac,
+        }
+    }
+
+    // brings the domain's roles created from an earlier version of the template up to date, all of
+    // them when managed globally. Only permissions the template added or dropped since the role's
+    // last sync change, permissions granted or denied on the role by hand stay as they are.
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        key: &str,
+    ) -> Result<Vec<Role>, AppError> {
+        let template = RoleTemplate::find(key).ok_or(AppError::ResourceNotFound)?;
+
+        let unknown = self.permission_repo.find_unknown(&template.permissions()).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Template {} uses unknown permissions: {}",
+                template.key,
+                unknown.join(", ")
+            )));
+        }
+
+        let mut outdated = Vec::new();
+        for link in self.template_repo.find_by_template(template.key).await? {
+            if !link.is_outdated(template) {
+                continue;
+            }
+            let role = self.role_repo.find_by_id(&link.role_id).await?;
+            if domain == GLOBAL_DOMAIN || role.domain == domain {
+                outdated.push((role, link));
+            }
+        }
+        if outdated.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let template_permissions = template.permissions();
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let mut added_policies = Vec::new();
+        let mut removed_policies = Vec::new();
+
+        let mut tx = db_pool.begin().await?;
+        for (role, link) in &outdated {
+            let before = Rbac::role_snapshot(&enforcer, &role.id);
+            let current_policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
+
+            let dropped = link
+                .permissions
+                .iter()
+                .filter(|permission| !template_permissions.contains(permission))
+                .collect::<HashSet<&String>>();
+            let role_removed = current_policies
+                .iter()
+                .filter(|policy| {
+                    policy.len() >= 5
+                        && policy[4] == EFFECT_ALLOW
+                        && dropped.contains(&format!("{}:{}", policy[2], policy[3]))
+                })
+                .cloned()
+                .collect::<Vec<Vec<String>>>();
+
+            // a permission the role already has, with or without a condition, is left alone
+            let (allowed, denied) = Rbac::split_permissions(current_policies.clone());
+            let mut role_added = Vec::new();
+            for permission in template_permissions
+                .iter()
+                .filter(|permission| !link.permissions.contains(permission))
+                .filter(|permission| !allowed.contains(permission) && !denied.contains(permission))
+            {
+                role_added.push(Rbac::permission_policy(
+                    &role.id,
+                    &role.domain,
+                    permission,
+                    EFFECT_ALLOW,
+                )?);
+            }
+
+            let after_policies = current_policies
+                .into_iter()
+                .filter(|policy| !role_removed.contains(policy))
+                .chain(role_added.iter().cloned())
+                .collect::<Vec<Vec<String>>>();
+
+            self.policy_repo.tx_remove_rules(&mut tx, "p", &role_removed).await?;
+            self.policy_repo.tx_add_rules(&mut tx, "p", &role_added).await?;
+            self.template_repo
+                .tx_save(&mut tx, &RoleTemplateLink::new(role.id.clone(), template))
+                .await?;
+
+            let after = Rbac::policy_snapshot(after_policies, before.parents.clone());
+            record_policy_change(
+                self.policy_change_repo.as_ref(),
+                &mut tx,
+                role,
+                actor_id,
+                POLICY_CHANGE_TEMPLATE_SYNCED,
+                Some(before),
+                Some(after),
+            )
+            .await?;
+
+            added_policies.extend(role_added);
+            removed_policies.extend(role_removed);
+        }
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies,
+                removed_policies,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        info!(
+            "Synced {} roles with template {} v{}",
+            outdated.len(),
+            template.key,
+            template.version
+        );
+
+        Ok(outdated.into_iter().map(|(role, _)| role).collect())
+    }
+}
@@ -3,84 +3,181 @@ use std::sync::Arc;
 use casbin::MgmtApi;
 
 use crate::{
-    application::dto::role::create_update_role_request::CreateOrUpdateRole,
-    domain::repositories::role_repo::RoleRepository,
-    infra::{errors::app_error::AppError, rbac::Rbac},
+    application::{
+        dto::role::create_update_role_request::CreateOrUpdateRole,
+        usecases::policy::document::is_reserved_name,
+    },
+    domain::{
+        entities::role_policy_change::POLICY_CHANGE_UPDATED,
+        repositories::{
+            permission_repo::PermissionRepository, policy_repo::PolicyRepository,
+            role_policy_change_repo::RolePolicyChangeRepository, role_repo::RoleRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::{CommittedRules, Rbac, EFFECT_ALLOW, EFFECT_DENY, GLOBAL_DOMAIN},
+    },
 };
 
+use super::policy_history::record_policy_change;
+
 #[derive(Clone)]
-pub struct UpdateRoleById<R> {
+pub struct UpdateRoleById<R, P, Q, H> {
     role_repo: Arc<R>,
+    permission_repo: Arc<P>,
+    policy_repo: Arc<Q>,
+    policy_change_repo: Arc<H>,
     rbac: Arc<Rbac>,
 }
 
-impl<R> UpdateRoleById<R>
+impl<R, P, Q, H> UpdateRoleById<R, P, Q, H>
 where
     R: RoleRepository,
+    P: PermissionRepository,
+    Q: PolicyRepository,
+    H: RolePolicyChangeRepository,
 {
-    pub fn new(role_repo: Arc<R>, rbac: Arc<Rbac>) -> Self {
-        Self { role_repo, rbac }
+    pub fn new(
+        role_repo: Arc<R>,
+        permission_repo: Arc<P>,
+        policy_repo: Arc<Q>,
+        policy_change_repo: Arc<H>,
+        rbac: Arc<Rbac>,
+    ) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+            policy_repo,
+            policy_change_repo,
+            rbac,
+        }
     }
 
-    pub async fn execute(&self, id: &str, req: CreateOrUpdateRole) -> Result<(), AppError> {
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        actor_id: &str,
+        domain: &str,
+        id: &str,
+        req: CreateOrUpdateRole,
+    ) -> Result<(), AppError> {
+        if req.is_default && domain != GLOBAL_DOMAIN {
+            return Err(AppError::ProcessError(
+                "Only global roles can be the default role".to_owned(),
+            ));
+        }
+
         if req.is_default && self.role_repo.find_default().await.is_ok() {
             return Err(AppError::ResourceExist(
                 "Default role already exist".to_owned(),
             ));
         }
 
+        let unmatched = req.unmatched_conditions();
+        if !unmatched.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Conditions for permissions the role does not have: {}",
+                unmatched.join(", ")
+            )));
+        }
+
         let mut role = self.role_repo.find_by_id(id).await?;
 
+        // global roles can only be managed globally
+        if role.domain != domain {
+            return Err(if role.domain == GLOBAL_DOMAIN {
+                AppError::Forbidden
+            } else {
+                AppError::ResourceNotFound
+            });
+        }
+
+        // the super admin role and just-in-time roles are managed by the system alone
+        if is_reserved_name(&role.name) || is_reserved_name(&req.name) {
+            return Err(AppError::ProcessError("Reserved roles cannot be updated".to_owned()));
+        }
+
         role.update(&req.name, req.is_default);
 
+        // policies the role should end up with, anything else it currently has is removed
+        let mut desired_policies = Vec::new();
+        for permission in req.permissions.iter().flatten() {
+            desired_policies.push(Rbac::conditional_policy(
+                &role.id,
+                &role.domain,
+                permission,
+                EFFECT_ALLOW,
+                req.condition(permission),
+            )?);
+        }
+        for permission in req.denied_permissions.iter().flatten() {
+            desired_policies.push(Rbac::conditional_policy(
+                &role.id,
+                &role.domain,
+                permission,
+                EFFECT_DENY,
+                req.condition(permission),
+            )?);
+        }
+
+        // only permissions from the registry can be granted or denied
+        let requested = req
+            .permissions
+            .iter()
+            .flatten()
+            .chain(req.denied_permissions.iter().flatten())
+            .cloned()
+            .collect::<Vec<String>>();
+        let unknown = self.permission_repo.find_unknown(&requested).await?;
+        if !unknown.is_empty() {
+            return Err(AppError::ProcessError(format!(
+                "Unknown permissions: {}",
+                unknown.join(", ")
+            )));
+        }
+
         let mut enforcer = self.rbac.enforcer.write().await;
+        let before = Rbac::role_snapshot(&enforcer, &role.id);
         let current_policies = enforcer.get_filtered_policy(0, vec![role.id.clone()]);
 
-        if let Some(permissions) = req.permissions {
-            // Transform current policies into "permission_name:action" format
-            let current_permissions: Vec<String> = current_policies
-                .into_iter()
-                .filter(|policy| policy.len() == 3)
-                .map(|policy| format!("{}:{}", policy[1], policy[2]))
-                .collect();
-
-            // Determine permissions to add (in new but not in current)
-            let permissions_to_add: Vec<&String> = permissions
-                .iter()
-                .filter(|perm| !current_permissions.contains(perm))
-                .collect();
-
-            // Determine permissions to remove (in current but not in new)
-            let permissions_to_remove: Vec<&String> = current_permissions
-                .iter()
-                .filter(|perm| !permissions.contains(perm))
-                .collect();
-
-            // Add missing permissions
-            for permission in permissions_to_add {
-                let parts: Vec<&str> = permission.split(':').collect();
-                if parts.len() == 2 {
-                    let policy = vec![role.id.clone(), parts[0].to_string(), parts[1].to_string()];
-                    println!("Adding policy: {:?}", policy);
-                    enforcer.add_policy(policy).await?;
-                }
-            }
-
-            // Remove extra permissions
-            for permission in permissions_to_remove {
-                let parts: Vec<&str> = permission.split(':').collect();
-                if parts.len() == 2 {
-                    let policy = vec![role.id.clone(), parts[0].to_string(), parts[1].to_string()];
-                    println!("Removing policy: {:?}", policy);
-                    enforcer.remove_policy(policy).await?;
-                }
-            }
-        } else {
-            // remove all policies that this role has
-            for policy in current_policies {
-                enforcer.remove_policy(policy).await?;
-            }
-        }
+        let added_policies = desired_policies
+            .iter()
+            .filter(|policy| !current_policies.contains(policy))
+            .cloned()
+            .collect::<Vec<Vec<String>>>();
+        let removed_policies = current_policies
+            .into_iter()
+            .filter(|policy| !desired_policies.contains(policy))
+            .collect::<Vec<Vec<String>>>();
+
+        let mut tx = db_pool.begin().await?;
+        self.role_repo.tx_update(&mut tx, &role).await?;
+        self.policy_repo.tx_remove_rules(&mut tx, "p", &removed_policies).await?;
+        self.policy_repo.tx_add_rules(&mut tx, "p", &added_policies).await?;
+
+        let after = Rbac::policy_snapshot(desired_policies, before.parents.clone());
+        record_policy_change(
+            self.policy_change_repo.as_ref(),
+            &mut tx,
+            &role,
+            actor_id,
+            POLICY_CHANGE_UPDATED,
+            Some(before),
+            Some(after),
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.rbac.apply_committed(
+            &mut enforcer,
+            CommittedRules {
+                added_policies,
+                removed_policies,
+                ..Default::default()
+            },
+        )
+        .await?;
 
         Ok(())
     }
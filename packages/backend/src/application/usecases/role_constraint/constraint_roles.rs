@@ -0,0 +1,55 @@
+use crate::{
+    domain::{entities::role_constraint::RoleConstraint, repositories::role_repo::RoleRepository},
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+// the distinct roles of a constraint, each one visible where the constraint applies; global
+// constraints can only name global roles
+pub async fn resolve_role_ids<R>(
+    role_repo: &R,
+    domain: &str,
+    role_ids: &[String],
+) -> Result<Vec<String>, AppError>
+where
+    R: RoleRepository,
+{
+    let mut resolved = Vec::new();
+    for role_id in role_ids {
+        if resolved.contains(role_id) {
+            continue;
+        }
+
+        let role = role_repo.find_by_id(role_id).await.map_err(|_| {
+            AppError::ProcessError(format!("Role {} does not exist", role_id))
+        })?;
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ProcessError(format!(
+                "Role {} cannot be constrained in {}",
+                role.name, domain
+            )));
+        }
+
+        resolved.push(role.id);
+    }
+
+    if resolved.len() < 2 {
+        return Err(AppError::ProcessError(
+            "A constraint needs at least two different roles".to_owned(),
+        ));
+    }
+
+    Ok(resolved)
+}
+
+// global constraints can only be managed globally
+pub fn ensure_managed_in(constraint: &RoleConstraint, domain: &str) -> Result<(), AppError> {
+    if constraint.domain != domain {
+        return Err(if constraint.domain == GLOBAL_DOMAIN {
+            AppError::Forbidden
+        } else {
+            AppError::ResourceNotFound
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    application::dto::role_constraint::create_update_role_constraint_request::CreateOrUpdateRoleConstraint,
+    domain::{
+        entities::role_constraint::RoleConstraint,
+        repositories::{role_constraint_repo::RoleConstraintRepository, role_repo::RoleRepository},
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::constraint_roles::resolve_role_ids;
+
+#[derive(Clone)]
+pub struct CreateRoleConstraint<C, R> {
+    constraint_repo: Arc<C>,
+    role_repo: Arc<R>,
+}
+
+impl<C, R> CreateRoleConstraint<C, R>
+where
+    C: RoleConstraintRepository,
+    R: RoleRepository,
+{
+    pub fn new(constraint_repo: Arc<C>, role_repo: Arc<R>) -> Self {
+        Self {
+            constraint_repo,
+            role_repo,
+        }
+    }
+
+    // users already holding several of the roles are not touched, they show up in the
+    // violations report
+    pub async fn execute(
+        &self,
+        domain: &str,
+        req: CreateOrUpdateRoleConstraint,
+    ) -> Result<RoleConstraint, AppError> {
+        req.validate()?;
+
+        let role_ids = resolve_role_ids(self.role_repo.as_ref(), domain, &req.role_ids).await?;
+        let constraint = self
+            .constraint_repo
+            .create(&RoleConstraint::new(
+                req.name,
+                req.description,
+                domain.to_string(),
+                role_ids,
+            ))
+            .await?;
+
+        info!("Role constraint {} created in {}", constraint.id, domain);
+
+        Ok(constraint)
+    }
+}
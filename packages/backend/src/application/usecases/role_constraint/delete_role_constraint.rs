@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    domain::repositories::role_constraint_repo::RoleConstraintRepository,
+    infra::errors::app_error::AppError,
+};
+
+use super::constraint_roles::ensure_managed_in;
+
+#[derive(Clone)]
+pub struct DeleteRoleConstraint<C> {
+    constraint_repo: Arc<C>,
+}
+
+impl<C> DeleteRoleConstraint<C>
+where
+    C: RoleConstraintRepository,
+{
+    pub fn new(constraint_repo: Arc<C>) -> Self {
+        Self { constraint_repo }
+    }
+
+    pub async fn execute(&self, domain: &str, id: &str) -> Result<(), AppError> {
+        let constraint = self.constraint_repo.find_by_id(id).await?;
+        ensure_managed_in(&constraint, domain)?;
+
+        self.constraint_repo.delete(&constraint.id).await?;
+
+        info!("Role constraint {} deleted from {}", constraint.id, domain);
+
+        Ok(())
+    }
+}
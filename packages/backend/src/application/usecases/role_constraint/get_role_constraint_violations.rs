@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::role_constraint_svc::RoleConstraintService,
+    domain::entities::role_constraint::RoleConstraintViolation,
+    infra::{
+        errors::app_error::AppError,
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+#[derive(Clone)]
+pub struct GetRoleConstraintViolations {
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl GetRoleConstraintViolations {
+    pub fn new(
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self { role_constraint_svc }
+    }
+
+    pub async fn execute(&self, domain: &str) -> Result<Vec<RoleConstraintViolation>, AppError> {
+        self.role_constraint_svc.find_violations(domain).await
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::role_constraint::RoleConstraint,
+        repositories::role_constraint_repo::RoleConstraintRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetRoleConstraints<C> {
+    constraint_repo: Arc<C>,
+}
+
+impl<C> GetRoleConstraints<C>
+where
+    C: RoleConstraintRepository,
+{
+    pub fn new(constraint_repo: Arc<C>) -> Self {
+        Self { constraint_repo }
+    }
+
+    pub async fn execute(&self, domain: &str) -> Result<Vec<RoleConstraint>, AppError> {
+        self.constraint_repo.find_all(domain).await
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::role_constraint_svc::RoleConstraintService,
+    infra::repositories::{
+        pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+        pg_user_role_repo::PgUserRoleRepository,
+    },
+};
+
+use super::{
+    create_role_constraint::CreateRoleConstraint, delete_role_constraint::DeleteRoleConstraint,
+    get_role_constraint_violations::GetRoleConstraintViolations,
+    get_role_constraints::GetRoleConstraints, update_role_constraint::UpdateRoleConstraint,
+};
+
+#[derive(Clone)]
+pub struct RoleConstraintUsecase {
+    pub get_role_constraints: Arc<GetRoleConstraints<PgRoleConstraintRepository>>,
+    pub create_role_constraint:
+        Arc<CreateRoleConstraint<PgRoleConstraintRepository, PgRoleRepository>>,
+    pub update_role_constraint:
+        Arc<UpdateRoleConstraint<PgRoleConstraintRepository, PgRoleRepository>>,
+    pub delete_role_constraint: Arc<DeleteRoleConstraint<PgRoleConstraintRepository>>,
+    pub get_role_constraint_violations: Arc<GetRoleConstraintViolations>,
+}
+
+impl RoleConstraintUsecase {
+    pub fn new(
+        constraint_repo: Arc<PgRoleConstraintRepository>,
+        role_repo: Arc<PgRoleRepository>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            get_role_constraints: Arc::new(GetRoleConstraints::new(constraint_repo.clone())),
+            create_role_constraint: Arc::new(CreateRoleConstraint::new(
+                constraint_repo.clone(),
+                role_repo.clone(),
+            )),
+            update_role_constraint: Arc::new(UpdateRoleConstraint::new(
+                constraint_repo.clone(),
+                role_repo.clone(),
+            )),
+            delete_role_constraint: Arc::new(DeleteRoleConstraint::new(constraint_repo.clone())),
+            get_role_constraint_violations: Arc::new(GetRoleConstraintViolations::new(
+                role_constraint_svc,
+            )),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod constraint_roles;
+pub mod create_role_constraint;
+pub mod delete_role_constraint;
+pub mod get_role_constraint_violations;
+pub mod get_role_constraints;
+pub mod init;
+pub mod update_role_constraint;
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::dto::role_constraint::create_update_role_constraint_request::CreateOrUpdateRoleConstraint,
+    domain::{
+        entities::role_constraint::RoleConstraint,
+        repositories::{role_constraint_repo::RoleConstraintRepository, role_repo::RoleRepository},
+    },
+    infra::errors::app_error::AppError,
+};
+
+use super::constraint_roles::{ensure_managed_in, resolve_role_ids};
+
+#[derive(Clone)]
+pub struct UpdateRoleConstraint<C, R> {
+    constraint_repo: Arc<C>,
+    role_repo: Arc<R>,
+}
+
+impl<C, R> UpdateRoleConstraint<C, R>
+where
+    C: RoleConstraintRepository,
+    R: RoleRepository,
+{
+    pub fn new(constraint_repo: Arc<C>, role_repo: Arc<R>) -> Self {
+        Self {
+            constraint_repo,
+            role_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        id: &str,
+        req: CreateOrUpdateRoleConstraint,
+    ) -> Result<RoleConstraint, AppError> {
+        req.validate()?;
+
+        let mut constraint = self.constraint_repo.find_by_id(id).await?;
+        ensure_managed_in(&constraint, domain)?;
+
+        let role_ids = resolve_role_ids(self.role_repo.as_ref(), domain, &req.role_ids).await?;
+        constraint.update(req.name, req.description, role_ids);
+
+        self.constraint_repo.update(&constraint).await
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::dto::saml::create_update_saml_provider_request::CreateOrUpdateSamlProvider,
+    domain::{
+        entities::saml_identity_provider::SamlIdentityProvider,
+        repositories::saml_provider_repo::SamlProviderRepository,
+    },
+    infra::{errors::app_error::AppError, saml::response::parse_certificate},
+};
+
+#[derive(Clone)]
+pub struct CreateSamlProvider<P> {
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> CreateSamlProvider<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(saml_provider_repo: Arc<P>) -> Self {
+        Self { saml_provider_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        req: CreateOrUpdateSamlProvider,
+    ) -> Result<SamlIdentityProvider, AppError> {
+        req.validate()?;
+
+        // reject unusable certificates now instead of on the first login
+        parse_certificate(&req.x509_cert).map_err(|err| AppError::ProcessError(err.to_string()))?;
+
+        let provider = self
+            .saml_provider_repo
+            .create(SamlIdentityProvider::from(&req))
+            .await?;
+
+        Ok(provider)
+    }
+}
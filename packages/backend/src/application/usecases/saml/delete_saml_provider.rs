@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    domain::repositories::saml_provider_repo::SamlProviderRepository,
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct DeleteSamlProvider<P> {
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> DeleteSamlProvider<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(saml_provider_repo: Arc<P>) -> Self {
+        Self { saml_provider_repo }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<(), AppError> {
+        let provider = self.saml_provider_repo.find_by_id(id).await?;
+
+        info!("Deleting SAML IdP {} ({})...", provider.slug, provider.id);
+        self.saml_provider_repo.delete(&provider.id).await?;
+
+        Ok(())
+    }
+}
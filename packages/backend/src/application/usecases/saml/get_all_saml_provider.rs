@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::saml_identity_provider::SamlIdentityProvider,
+        repositories::saml_provider_repo::SamlProviderRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetAllSamlProvider<P> {
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> GetAllSamlProvider<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(saml_provider_repo: Arc<P>) -> Self {
+        Self { saml_provider_repo }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<SamlIdentityProvider>, AppError> {
+        let providers = self.saml_provider_repo.find_all().await?;
+
+        Ok(providers)
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::saml_provider_repo::SamlProviderRepository,
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        repositories::redis_repo_impl::RedisRepositoryImpl,
+        saml::request::{acs_url, build_authn_request, build_redirect_url, sp_entity_id},
+    },
+};
+
+#[derive(Clone)]
+pub struct GetSamlLoginUrl<P> {
+    cfg: Arc<AppConfig>,
+    saml_provider_repo: Arc<P>,
+    redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+}
+
+impl<P> GetSamlLoginUrl<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        saml_provider_repo: Arc<P>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    ) -> Self {
+        Self {
+            cfg,
+            saml_provider_repo,
+            redis_svc,
+        }
+    }
+
+    // SP-initiated login, the IdP answers on the ACS with InResponseTo set to this request id
+    pub async fn execute(&self, slug: &str, redirect_to: Option<String>) -> Result<String, AppError> {
+        let provider = self.saml_provider_repo.find_active_by_slug(slug).await?;
+
+        // xsd:ID must not start with a digit
+        let request_id = format!("_{}", uuid::Uuid::new_v4().simple());
+        let authn_request = build_authn_request(
+            &request_id,
+            &sp_entity_id(&self.cfg),
+            &provider.sso_url,
+            &acs_url(&self.cfg, &provider.slug),
+        );
+
+        self.redis_svc
+            .set_saml_request(&request_id, &provider.id)
+            .await?;
+
+        build_redirect_url(&provider.sso_url, &authn_request, redirect_to.as_deref())
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::repositories::saml_provider_repo::SamlProviderRepository,
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        saml::request::{acs_url, build_sp_metadata, sp_entity_id},
+    },
+};
+
+#[derive(Clone)]
+pub struct GetSamlMetadata<P> {
+    cfg: Arc<AppConfig>,
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> GetSamlMetadata<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(cfg: Arc<AppConfig>, saml_provider_repo: Arc<P>) -> Self {
+        Self {
+            cfg,
+            saml_provider_repo,
+        }
+    }
+
+    pub async fn execute(&self, slug: &str) -> Result<String, AppError> {
+        let provider = self.saml_provider_repo.find_active_by_slug(slug).await?;
+
+        Ok(build_sp_metadata(
+            &sp_entity_id(&self.cfg),
+            &acs_url(&self.cfg, &provider.slug),
+        ))
+    }
+}
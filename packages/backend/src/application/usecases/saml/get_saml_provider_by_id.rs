@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::saml_identity_provider::SamlIdentityProvider,
+        repositories::saml_provider_repo::SamlProviderRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone)]
+pub struct GetSamlProviderById<P> {
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> GetSamlProviderById<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(saml_provider_repo: Arc<P>) -> Self {
+        Self { saml_provider_repo }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<SamlIdentityProvider, AppError> {
+        let provider = self.saml_provider_repo.find_by_id(id).await?;
+
+        Ok(provider)
+    }
+}
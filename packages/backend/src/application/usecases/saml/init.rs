@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::{
+        oauth_svc::OauthService, redis_svc::RedisService, registration_svc::RegistrationService,
+    },
+    infra::{
+        config::AppConfig,
+        repositories::{
+            pg_invite_repo::PgInviteRepository, pg_oauth_provider::PgOauthProviderRepository,
+            pg_role_repo::PgRoleRepository,
+            pg_saml_provider_repo::PgSamlProviderRepository, pg_user_repo::PgUserRepository,
+            pg_user_session::PgUserSessionRepository, redis_repo_impl::RedisRepositoryImpl,
+        },
+        utils::jwt_maker::JwtMaker,
+    },
+};
+
+use super::{
+    create_saml_provider::CreateSamlProvider, delete_saml_provider::DeleteSamlProvider,
+    get_all_saml_provider::GetAllSamlProvider, get_saml_login_url::GetSamlLoginUrl,
+    get_saml_metadata::GetSamlMetadata, get_saml_provider_by_id::GetSamlProviderById,
+    saml_login::SamlLogin, update_saml_provider::UpdateSamlProvider,
+};
+
+#[derive(Clone)]
+pub struct SamlUsecase {
+    pub get_saml_metadata: Arc<GetSamlMetadata<PgSamlProviderRepository>>,
+    pub get_saml_login_url: Arc<GetSamlLoginUrl<PgSamlProviderRepository>>,
+    pub saml_login: Arc<
+        SamlLogin<
+            PgUserRepository,
+            PgRoleRepository,
+            PgUserSessionRepository,
+            PgOauthProviderRepository,
+            PgSamlProviderRepository,
+        >,
+    >,
+    pub get_all_saml_provider: Arc<GetAllSamlProvider<PgSamlProviderRepository>>,
+    pub get_saml_provider_by_id: Arc<GetSamlProviderById<PgSamlProviderRepository>>,
+    pub create_saml_provider: Arc<CreateSamlProvider<PgSamlProviderRepository>>,
+    pub update_saml_provider: Arc<UpdateSamlProvider<PgSamlProviderRepository>>,
+    pub delete_saml_provider: Arc<DeleteSamlProvider<PgSamlProviderRepository>>,
+}
+
+impl SamlUsecase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        oauth_svc: Arc<
+            OauthService<
+                PgUserRepository,
+                PgRoleRepository,
+                PgUserSessionRepository,
+                PgOauthProviderRepository,
+            >,
+        >,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        user_repo: Arc<PgUserRepository>,
+        role_repo: Arc<PgRoleRepository>,
+        oauth_provider_repo: Arc<PgOauthProviderRepository>,
+        saml_provider_repo: Arc<PgSamlProviderRepository>,
+        jwt_maker: Arc<JwtMaker>,
+        registration_svc: Arc<RegistrationService<PgInviteRepository>>,
+    ) -> Self {
+        let get_saml_metadata = Arc::new(GetSamlMetadata::new(
+            cfg.clone(),
+            saml_provider_repo.clone(),
+        ));
+        let get_saml_login_url = Arc::new(GetSamlLoginUrl::new(
+            cfg.clone(),
+            saml_provider_repo.clone(),
+            redis_svc.clone(),
+        ));
+        let saml_login = Arc::new(SamlLogin::new(
+            cfg.clone(),
+            user_repo.clone(),
+            role_repo.clone(),
+            oauth_provider_repo.clone(),
+            saml_provider_repo.clone(),
+            jwt_maker.clone(),
+            oauth_svc.clone(),
+            redis_svc.clone(),
+            registration_svc.clone(),
+        ));
+        let get_all_saml_provider = Arc::new(GetAllSamlProvider::new(saml_provider_repo.clone()));
+        let get_saml_provider_by_id =
+            Arc::new(GetSamlProviderById::new(saml_provider_repo.clone()));
+        let create_saml_provider = Arc::new(CreateSamlProvider::new(saml_provider_repo.clone()));
+        let update_saml_provider = Arc::new(UpdateSamlProvider::new(saml_provider_repo.clone()));
+        let delete_saml_provider = Arc::new(DeleteSamlProvider::new(saml_provider_repo.clone()));
+
+        Self {
+            get_saml_metadata,
+            get_saml_login_url,
+            saml_login,
+            get_all_saml_provider,
+            get_saml_provider_by_id,
+            create_saml_provider,
+            update_saml_provider,
+            delete_saml_provider,
+        }
+    }
+}
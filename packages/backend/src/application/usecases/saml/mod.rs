@@ -0,0 +1,9 @@
+pub mod create_saml_provider;
+pub mod delete_saml_provider;
+pub mod get_all_saml_provider;
+pub mod get_saml_login_url;
+pub mod get_saml_metadata;
+pub mod get_saml_provider_by_id;
+pub mod init;
+pub mod saml_login;
+pub mod update_saml_provider;
@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use base64::Engine;
+use validator::ValidateEmail;
+
+use crate::{
+    application::{
+        dto::saml::saml_acs_request::SamlAcsRequest,
+        services::{
+            oauth_svc::OauthService,
+            redis_svc::RedisService,
+            registration_svc::{RegistrationService, SignupAttempt},
+        },
+    },
+    domain::{
+        entities::{
+            saml_identity_provider::SamlIdentityProvider, user::User,
+            user_oauth_provider::UserOauthProvider, user_role::UserRole,
+        },
+        repositories::{
+            oauth_provider_repo::OauthProviderRepository, role_repo::RoleRepository,
+            saml_provider_repo::SamlProviderRepository, user_repo::UserRepository,
+            user_session_repo::UserSessionRepository,
+        },
+    },
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        oauth2::constants::SAML_PROVIDER,
+        rbac::GLOBAL_DOMAIN,
+        repositories::{pg_invite_repo::PgInviteRepository, redis_repo_impl::RedisRepositoryImpl},
+        saml::{
+            request::{acs_url, sp_entity_id},
+            response::{verify_response, SamlAssertion, SamlValidation},
+        },
+        utils::jwt_maker::JwtMaker,
+    },
+};
+
+#[derive(Clone)]
+pub struct SamlLogin<U, R, S, O, P> {
+    cfg: Arc<AppConfig>,
+    user_repo: Arc<U>,
+    role_repo: Arc<R>,
+    oauth_provider_repo: Arc<O>,
+    saml_provider_repo: Arc<P>,
+    jwt_maker: Arc<JwtMaker>,
+    oauth_svc: Arc<OauthService<U, R, S, O>>,
+    redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    registration_svc: Arc<RegistrationService<PgInviteRepository>>,
+}
+
+impl<U, R, S, O, P> SamlLogin<U, R, S, O, P>
+where
+    U: UserRepository,
+    R: RoleRepository,
+    S: UserSessionRepository,
+    O: OauthProviderRepository,
+    P: SamlProviderRepository,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        user_repo: Arc<U>,
+        role_repo: Arc<R>,
+        oauth_provider_repo: Arc<O>,
+        saml_provider_repo: Arc<P>,
+        jwt_maker: Arc<JwtMaker>,
+        oauth_svc: Arc<OauthService<U, R, S, O>>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        registration_svc: Arc<RegistrationService<PgInviteRepository>>,
+    ) -> Self {
+        Self {
+            cfg,
+            user_repo,
+            role_repo,
+            oauth_provider_repo,
+            saml_provider_repo,
+            jwt_maker,
+            oauth_svc,
+            redis_svc,
+            registration_svc,
+        }
+    }
+
+    /// Consumes a SAMLResponse posted to the ACS, provisioning the user on first login.
+    /// Returns the access token, refresh token and the app path to send the browser to.
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        slug: &str,
+        req: SamlAcsRequest,
+        client_ip: &str,
+    ) -> Result<(String, String, String), AppError> {
+        let provider = self.saml_provider_repo.find_active_by_slug(slug).await?;
+
+        let compact_response = req
+            .saml_response
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let xml = base64::engine::general_purpose::STANDARD
+            .decode(compact_response)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| AppError::SamlValidationError("malformed SAMLResponse".to_string()))?;
+
+        let clock_skew = chrono::Duration::seconds(self.cfg.saml_clock_skew_secs);
+        let assertion = verify_response(
+            &xml,
+            &SamlValidation {
+                idp_entity_id: &provider.entity_id,
+                idp_certificate: &provider.x509_cert,
+                sp_entity_id: &sp_entity_id(&self.cfg),
+                acs_url: &acs_url(&self.cfg, &provider.slug),
+                clock_skew,
+            },
+        )?;
+
+        match &assertion.in_response_to {
+            Some(request_id) => {
+                let requested_idp = self
+                    .redis_svc
+                    .take_saml_request(request_id)
+                    .await
+                    .unwrap_or_default();
+
+                if requested_idp != provider.id {
+                    return Err(AppError::SamlValidationError(
+                        "response does not answer a pending login request".to_string(),
+                    ));
+                }
+            }
+            None if !provider.allow_idp_initiated => {
+                return Err(AppError::SamlValidationError(
+                    "IdP-initiated login is disabled for this IdP".to_string(),
+                ));
+            }
+            None => {}
+        }
+
+        // an assertion is only good once, keep its id until it would have expired anyway
+        let replay_window = (assertion.not_on_or_after - chrono::Utc::now() + clock_skew)
+            .num_seconds()
+            .max(1) as u64;
+        if !self
+            .redis_svc
+            .mark_saml_assertion_used(&assertion.id, replay_window)
+            .await?
+        {
+            return Err(AppError::SamlValidationError(
+                "assertion has already been used".to_string(),
+            ));
+        }
+
+        let user = self
+            .find_or_provision_user(db_pool, &provider, &assertion, client_ip)
+            .await?;
+
+        let access_token = self.jwt_maker.make_token(user.id.clone(), 1)?;
+        let refresh_token = self.jwt_maker.make_refresh_token(user.id.clone(), 24 * 7)?;
+
+        let _session = self
+            .oauth_svc
+            .get_or_create_session(
+                &user.id,
+                &access_token,
+                &refresh_token,
+                Some(60 * 60 * 24 * 7),
+            )
+            .await?;
+
+        let redirect_path = req
+            .relay_state
+            .filter(|path| path.starts_with('/') && !path.starts_with("//") && !path.contains('\\'))
+            .unwrap_or_else(|| "/".to_string());
+
+        Ok((access_token, refresh_token, redirect_path))
+    }
+
+    async fn find_or_provision_user(
+        &self,
+        db_pool: &sqlx::PgPool,
+        provider: &SamlIdentityProvider,
+        assertion: &SamlAssertion,
+        client_ip: &str,
+    ) -> Result<User, AppError> {
+        let provider_user_id = provider.provider_user_id(&assertion.name_id);
+
+        match self
+            .oauth_provider_repo
+            .get_by_provider_and_id(SAML_PROVIDER, &provider_user_id)
+            .await
+        {
+            Ok(linked) => return self.user_repo.find_by_id(&linked.user_id).await,
+            Err(AppError::SqlxError(sqlx::Error::RowNotFound)) => {}
+            Err(err) => return Err(err),
+        }
+
+        let email = provider
+            .email_attribute
+            .as_deref()
+            .and_then(|attribute| assertion.attribute(attribute))
+            .unwrap_or(&assertion.name_id)
+            .to_lowercase();
+
+        if !email.validate_email() {
+            return Err(AppError::SamlValidationError(
+                "assertion does not carry a valid email address".to_string(),
+            ));
+        }
+
+        if let Ok(existing) = self.user_repo.find_by_email(&email).await {
+            return Err(AppError::AccountAlreadyExistsWithEmail(existing.email));
+        }
+
+        // the IdP vouches for the address, like Google does
+        let invite = self
+            .registration_svc
+            .check_signup(&SignupAttempt {
+                email: &email,
+                hosted_domain: None,
+                invite_token: None,
+                email_verified: true,
+                client_ip,
+            })
+            .await?;
+
+        // just-in-time provisioning, same shape as a Google registration
+        let mut tx = db_pool.begin().await?;
+
+        let default_role = self
+            .role_repo
+            .find_default()
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::ProcessError(
+                    "Default Role is not Found please contact Administrator!".to_string(),
+                ),
+                _ => AppError::ProcessError(err.to_string()),
+            })?;
+
+        let mut new_user = User::new(email, None);
+        if let Some(fullname) = provider
+            .name_attribute
+            .as_deref()
+            .and_then(|attribute| assertion.attribute(attribute))
+        {
+            new_user.update(Some(fullname.to_string()), None);
+        }
+
+        let user_oauth_provider = UserOauthProvider::new(
+            new_user.id.clone(),
+            SAML_PROVIDER.to_string(),
+            provider_user_id,
+        );
+        let user_role = UserRole::new(new_user.id.clone(), default_role.id.clone(), GLOBAL_DOMAIN.to_string());
+        self.registration_svc
+            .tx_check_default_role(&mut tx, &new_user.id, &default_role.id)
+            .await?;
+
+        let (user, _user_oauth_provider, _user_role) = self
+            .user_repo
+            .tx_register_user(&mut tx, &new_user, &user_oauth_provider, &user_role)
+            .await?;
+
+        self.registration_svc
+            .tx_accept_invite(&mut tx, invite, &user.id)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
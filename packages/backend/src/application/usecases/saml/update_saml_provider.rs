@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::dto::saml::create_update_saml_provider_request::CreateOrUpdateSamlProvider,
+    domain::{
+        entities::saml_identity_provider::SamlIdentityProvider,
+        repositories::saml_provider_repo::SamlProviderRepository,
+    },
+    infra::{errors::app_error::AppError, saml::response::parse_certificate},
+};
+
+#[derive(Clone)]
+pub struct UpdateSamlProvider<P> {
+    saml_provider_repo: Arc<P>,
+}
+
+impl<P> UpdateSamlProvider<P>
+where
+    P: SamlProviderRepository,
+{
+    pub fn new(saml_provider_repo: Arc<P>) -> Self {
+        Self { saml_provider_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        id: &str,
+        req: CreateOrUpdateSamlProvider,
+    ) -> Result<SamlIdentityProvider, AppError> {
+        req.validate()?;
+
+        parse_certificate(&req.x509_cert).map_err(|err| AppError::ProcessError(err.to_string()))?;
+
+        let mut provider = self.saml_provider_repo.find_by_id(id).await?;
+        provider.update(SamlIdentityProvider::from(&req));
+
+        let provider = self.saml_provider_repo.update(&provider).await?;
+
+        Ok(provider)
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{email_change_repo::EmailChangeRepository, user_repo::UserRepository},
+    infra::{
+        errors::app_error::AppError,
+        repositories::{
+            pg_email_change_repo::PgEmailChangeRepository, pg_user_repo::PgUserRepository,
+            redis_repo_impl::RedisRepositoryImpl,
+        },
+        utils::token::hash_token,
+    },
+};
+
+pub struct ConfirmEmailChangeUseCase {
+    pub user_repo: Arc<PgUserRepository>,
+    pub email_change_repo: Arc<PgEmailChangeRepository>,
+    pub redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    pub db_pool: sqlx::PgPool,
+}
+
+impl ConfirmEmailChangeUseCase {
+    pub fn new(
+        user_repo: Arc<PgUserRepository>,
+        email_change_repo: Arc<PgEmailChangeRepository>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        db_pool: sqlx::PgPool,
+    ) -> Self {
+        Self { user_repo, email_change_repo, redis_svc, db_pool }
+    }
+
+    pub async fn execute(&self, token: &str) -> Result<(), AppError> {
+        let mut request = self
+            .email_change_repo
+            .find_by_confirm_token_hash(&hash_token(token))
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::ResourceNotFound,
+                _ => err,
+            })?;
+
+        if !request.is_confirmable() {
+            return Err(AppError::ProcessError("This link has expired or was already used".to_string()));
+        }
+
+        // the address may have been taken since the request was made
+        if self.user_repo.find_by_email(&request.new_email).await.is_ok() {
+            return Err(AppError::UserEmailAlreadyExist);
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut user = self.user_repo.find_by_id(&request.user_id).await?;
+        user.change_email(request.new_email.clone());
+        self.user_repo.tx_update(&mut tx, &user).await?;
+
+        request.confirm();
+        self.email_change_repo.tx_update_status(&mut tx, &request).await?;
+        self.email_change_repo
+            .tx_cancel_revertable(&mut tx, &request.user_id, &request.id)
+            .await?;
+
+        tx.commit().await?;
+
+        // cached current user still carries the old address
+        self.redis_svc.remove_current_user(&user.id).await?;
+
+        Ok(())
+    }
+}
@@ -2,17 +2,21 @@ use std::sync::Arc;
 
 use crate::{
     application::dto::auth::user_settings_dto::UserSettingsDto,
-    domain::repositories::user_repo::UserRepository,
-    infra::{errors::app_error::AppError, repositories::pg_user_repo::PgUserRepository},
+    domain::repositories::{email_change_repo::EmailChangeRepository, user_repo::UserRepository},
+    infra::{
+        errors::app_error::AppError,
+        repositories::{pg_email_change_repo::PgEmailChangeRepository, pg_user_repo::PgUserRepository},
+    },
 };
 
 pub struct GetUserSettingsUseCase {
     pub user_repo: Arc<PgUserRepository>,
+    pub email_change_repo: Arc<PgEmailChangeRepository>,
 }
 
 impl GetUserSettingsUseCase {
-    pub fn new(user_repo: Arc<PgUserRepository>) -> Self {
-        Self { user_repo }
+    pub fn new(user_repo: Arc<PgUserRepository>, email_change_repo: Arc<PgEmailChangeRepository>) -> Self {
+        Self { user_repo, email_change_repo }
     }
 
     pub async fn execute(
@@ -23,10 +27,14 @@ impl GetUserSettingsUseCase {
 
         let provider = self.user_repo.find_provider_by_user_id(user_id).await?;
 
+        let pending_change = self.email_change_repo.find_pending_by_user_id(user_id).await.ok();
+
         Ok(UserSettingsDto {
           fullname: user.fullname,
           email: user.email,
           provider: provider.provider,
+          pending_email: pending_change.as_ref().map(|change| change.new_email.clone()),
+          pending_email_expires_at: pending_change.map(|change| change.expires_at),
         })
     }
 }
@@ -2,19 +2,56 @@ use std::sync::Arc;
 
 use super::update_user_settings::UpdateUserSettingsUseCase;
 use super::get_user_settings::GetUserSettingsUseCase;
-use crate::infra::repositories::pg_user_repo::PgUserRepository;
+use super::confirm_email_change::ConfirmEmailChangeUseCase;
+use super::revert_email_change::RevertEmailChangeUseCase;
+use crate::application::services::{mail_svc::MailService, redis_svc::RedisService};
+use crate::infra::config::AppConfig;
+use crate::infra::repositories::{
+    pg_email_change_repo::PgEmailChangeRepository, pg_user_repo::PgUserRepository,
+    pg_user_session::PgUserSessionRepository, redis_repo_impl::RedisRepositoryImpl,
+    smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+};
 use sqlx::PgPool;
 
 pub struct UserUseCases {
     pub update_user_settings: UpdateUserSettingsUseCase,
     pub get_user_settings: GetUserSettingsUseCase,
+    pub confirm_email_change: ConfirmEmailChangeUseCase,
+    pub revert_email_change: RevertEmailChangeUseCase,
 }
 
 impl UserUseCases {
-    pub fn new(user_repo: Arc<PgUserRepository>, db_pool: PgPool) -> Self {
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        user_repo: Arc<PgUserRepository>,
+        email_change_repo: Arc<PgEmailChangeRepository>,
+        user_session_repo: Arc<PgUserSessionRepository>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        db_pool: PgPool,
+    ) -> Self {
         Self {
-            update_user_settings: UpdateUserSettingsUseCase::new(user_repo.clone(), db_pool.clone()),
-            get_user_settings: GetUserSettingsUseCase::new(user_repo.clone()),
+            update_user_settings: UpdateUserSettingsUseCase::new(
+                cfg.clone(),
+                user_repo.clone(),
+                email_change_repo.clone(),
+                mail_svc.clone(),
+                db_pool.clone(),
+            ),
+            get_user_settings: GetUserSettingsUseCase::new(user_repo.clone(), email_change_repo.clone()),
+            confirm_email_change: ConfirmEmailChangeUseCase::new(
+                user_repo.clone(),
+                email_change_repo.clone(),
+                redis_svc.clone(),
+                db_pool.clone(),
+            ),
+            revert_email_change: RevertEmailChangeUseCase::new(
+                user_repo.clone(),
+                email_change_repo.clone(),
+                user_session_repo.clone(),
+                redis_svc.clone(),
+                db_pool.clone(),
+            ),
         }
     }
 }
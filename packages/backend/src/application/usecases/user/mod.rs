@@ -1,3 +1,5 @@
+pub mod confirm_email_change;
 pub mod get_user_settings;
+pub mod revert_email_change;
 pub mod update_user_settings;
-pub mod init;
\ No newline at end of file
+pub mod init;
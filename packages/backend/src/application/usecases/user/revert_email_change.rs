@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use validator::Validate;
+
+use crate::{
+    application::{
+        dto::auth::user_settings_dto::RevertEmailChangeRequest,
+        services::redis_svc::RedisService,
+    },
+    domain::{
+        entities::email_change_request::EMAIL_CHANGE_CONFIRMED,
+        repositories::{
+            email_change_repo::EmailChangeRepository, user_repo::UserRepository,
+            user_session_repo::UserSessionRepository,
+        },
+    },
+    infra::{
+        errors::app_error::AppError,
+        repositories::{
+            pg_email_change_repo::PgEmailChangeRepository, pg_user_repo::PgUserRepository,
+            pg_user_session::PgUserSessionRepository, redis_repo_impl::RedisRepositoryImpl,
+        },
+        utils::{password::hash_password, token::hash_token},
+    },
+};
+
+pub struct RevertEmailChangeUseCase {
+    pub user_repo: Arc<PgUserRepository>,
+    pub email_change_repo: Arc<PgEmailChangeRepository>,
+    pub user_session_repo: Arc<PgUserSessionRepository>,
+    pub redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+    pub db_pool: sqlx::PgPool,
+}
+
+impl RevertEmailChangeUseCase {
+    pub fn new(
+        user_repo: Arc<PgUserRepository>,
+        email_change_repo: Arc<PgEmailChangeRepository>,
+        user_session_repo: Arc<PgUserSessionRepository>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        db_pool: sqlx::PgPool,
+    ) -> Self {
+        Self { user_repo, email_change_repo, user_session_repo, redis_svc, db_pool }
+    }
+
+    // used from the notice sent to the old address, cancels or undoes the change, replaces the
+    // password and signs everyone out, so whoever made the change is locked out
+    pub async fn execute(&self, req: RevertEmailChangeRequest) -> Result<(), AppError> {
+        req.validate()?;
+
+        // unknown links are turned away before paying for the password hash
+        let token_hash = hash_token(&req.token);
+        self.email_change_repo
+            .find_by_revert_token_hash(&token_hash)
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::ResourceNotFound,
+                _ => err,
+            })?;
+
+        let new_password = req.new_password;
+        let password_hash =
+            tokio::task::spawn_blocking(move || hash_password(new_password.as_bytes())).await??;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        // locked so a confirmation or another revert cannot slip in between the checks and writes
+        let mut request = self
+            .email_change_repo
+            .tx_lock_by_revert_token_hash(&mut tx, &token_hash)
+            .await
+            .map_err(|err| match err {
+                AppError::SqlxError(sqlx::Error::RowNotFound) => AppError::ResourceNotFound,
+                _ => err,
+            })?;
+
+        if !request.is_revertable() {
+            return Err(AppError::ProcessError("This link has expired or was already used".to_string()));
+        }
+
+        // the account must still carry the address this link was sent about, a stale link must
+        // not undo a later change or reset the password
+        let mut user = self.user_repo.tx_lock(&mut tx, &request.user_id).await?;
+        let current_email = if request.status == EMAIL_CHANGE_CONFIRMED {
+            &request.new_email
+        } else {
+            &request.old_email
+        };
+        if &user.email != current_email {
+            return Err(AppError::ProcessError("This link has expired or was already used".to_string()));
+        }
+
+        if request.status == EMAIL_CHANGE_CONFIRMED {
+            user.change_email(request.old_email.clone());
+        }
+        user.change_password(Some(password_hash));
+
+        self.user_repo.tx_update(&mut tx, &user).await?;
+
+        request.revert();
+        self.email_change_repo.tx_update_status(&mut tx, &request).await?;
+
+        tx.commit().await?;
+
+        if let Ok(session) = self.user_session_repo.find_by_user_id(&request.user_id).await {
+            self.user_session_repo.delete_by_id(&session.id).await?;
+        }
+        self.redis_svc.remove_current_user(&request.user_id).await?;
+
+        Ok(())
+    }
+}
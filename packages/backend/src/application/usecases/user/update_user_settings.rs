@@ -1,19 +1,44 @@
 use std::sync::Arc;
 
+use validator::ValidateEmail;
+
 use crate::{
-    application::dto::auth::user_settings_dto::{UserSettingsDto, UserSettingsUpdateDto},
-    domain::repositories::user_repo::UserRepository,
-    infra::{errors::app_error::AppError, repositories::pg_user_repo::PgUserRepository, utils::password::{verify_password, hash_password}},
+    application::{
+        dto::auth::user_settings_dto::{UserSettingsDto, UserSettingsUpdateDto},
+        services::mail_svc::MailService,
+    },
+    domain::{
+        entities::email_change_request::EmailChangeRequest,
+        repositories::{email_change_repo::EmailChangeRepository, user_repo::UserRepository},
+    },
+    infra::{
+        config::AppConfig,
+        errors::app_error::AppError,
+        repositories::{
+            pg_email_change_repo::PgEmailChangeRepository, pg_user_repo::PgUserRepository,
+            smtp_mail_repo_impl::SmtpMailRepositoryImpl,
+        },
+        utils::{password::{verify_password, hash_password}, token::{generate_token, hash_token}},
+    },
 };
 
 pub struct UpdateUserSettingsUseCase {
+    pub cfg: Arc<AppConfig>,
     pub user_repo: Arc<PgUserRepository>,
+    pub email_change_repo: Arc<PgEmailChangeRepository>,
+    pub mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
     pub db_pool: sqlx::PgPool,
 }
 
 impl UpdateUserSettingsUseCase {
-    pub fn new(user_repo: Arc<PgUserRepository>, db_pool: sqlx::PgPool) -> Self {
-        Self { user_repo, db_pool }
+    pub fn new(
+        cfg: Arc<AppConfig>,
+        user_repo: Arc<PgUserRepository>,
+        email_change_repo: Arc<PgEmailChangeRepository>,
+        mail_svc: Arc<MailService<SmtpMailRepositoryImpl>>,
+        db_pool: sqlx::PgPool,
+    ) -> Self {
+        Self { cfg, user_repo, email_change_repo, mail_svc, db_pool }
     }
 
     pub async fn execute(
@@ -28,22 +53,39 @@ impl UpdateUserSettingsUseCase {
 
         let provider = self.user_repo.find_provider_by_user_id(user_id).await?;
 
-        // Update user fields
-        if let Some(email) = update_dto.email {
-          if provider.provider == "email" {
-            user.change_email(email);
-          }else{
-            ()
+        // Email only changes once the new address is confirmed, see ConfirmEmailChangeUseCase
+        let mut email_change = None;
+        if let Some(email) = update_dto.email && provider.provider == "email" {
+          let email = email.trim().to_lowercase();
+
+          if email != user.email {
+            if !email.validate_email() {
+              return Err(AppError::ProcessError("Invalid email address".to_string()));
+            }
+
+            if self.user_repo.find_by_email(&email).await.is_ok() {
+              return Err(AppError::UserEmailAlreadyExist);
+            }
+
+            let confirm_token = generate_token();
+            let revert_token = generate_token();
+            let request = EmailChangeRequest::new(
+              user.id.clone(),
+              user.email.clone(),
+              email,
+              hash_token(&confirm_token),
+              hash_token(&revert_token),
+            );
+            let request = self.email_change_repo.tx_replace_pending(&mut tx, &request).await?;
+
+            email_change = Some((request, confirm_token, revert_token));
           }
         }
 
-        if let (Some(current_password), Some(new_password)) = (update_dto.current_password, update_dto.new_password) {
-          if verify_password(user.password_hash.as_ref().unwrap(), current_password.as_bytes()).is_ok() {
-            let password_hash = hash_password(new_password.as_bytes()).unwrap();
-            user.change_password(Some(password_hash));
-          } else {
-            ()
-          }
+        if let (Some(current_password), Some(new_password)) = (update_dto.current_password, update_dto.new_password)
+          && verify_password(user.password_hash.as_ref().unwrap(), current_password.as_bytes()).is_ok() {
+          let password_hash = hash_password(new_password.as_bytes()).unwrap();
+          user.change_password(Some(password_hash));
         }
 
         user.update(update_dto.name, None); // avatar_url will be handled separately when needed
@@ -53,10 +95,26 @@ impl UpdateUserSettingsUseCase {
 
         tx.commit().await?;
 
+        if let Some((request, confirm_token, revert_token)) = &email_change {
+          let api_url = self.cfg.public_api_url.trim_end_matches('/');
+          let confirm_url = format!("{}/oauth/email/change/confirm?token={}", api_url, confirm_token);
+          let revert_url = format!("{}/oauth/email/change/revert?token={}", api_url, revert_token);
+
+          self.mail_svc.send_email_change_confirmation(&request.new_email, &confirm_url).await?;
+          self.mail_svc.send_email_change_notice(&request.old_email, &request.new_email, &revert_url).await?;
+        }
+
+        let pending_change = match email_change {
+          Some((request, _, _)) => Some(request),
+          None => self.email_change_repo.find_pending_by_user_id(user_id).await.ok(),
+        };
+
         Ok(UserSettingsDto {
             fullname: updated_user.fullname,
             email: updated_user.email,
             provider: provider.provider,
+            pending_email: pending_change.as_ref().map(|change| change.new_email.clone()),
+            pending_email_expires_at: pending_change.map(|change| change.expires_at),
         })
     }
 }
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::{
+        dto::user_role::assign_user_role_request::AssignUserRoleRequest,
+        services::{redis_svc::RedisService, role_constraint_svc::RoleConstraintService},
+        usecases::access_request::granted_role::JIT_ROLE_PREFIX,
+    },
+    domain::{
+        entities::user_role::UserRole,
+        repositories::{
+            redis_repo::RedisRepository, role_repo::RoleRepository, user_repo::UserRepository,
+            user_role_repo::UserRoleRepository,
+        },
+    },
+    infra::{
+        common::constants::SUPER_ADMIN_ROLE,
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        repositories::{
+            pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+            pg_user_role_repo::PgUserRoleRepository,
+        },
+    },
+};
+
+#[derive(Clone)]
+pub struct AssignUserRole<U, R, M, C> {
+    user_repo: Arc<U>,
+    role_repo: Arc<R>,
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+    role_constraint_svc: Arc<
+        RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+    >,
+}
+
+impl<U, R, M, C> AssignUserRole<U, R, M, C>
+where
+    U: UserRepository,
+    R: RoleRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(
+        user_repo: Arc<U>,
+        role_repo: Arc<R>,
+        user_role_repo: Arc<M>,
+        redis_svc: Arc<RedisService<C>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            user_repo,
+            role_repo,
+            user_role_repo,
+            redis_svc,
+            role_constraint_svc,
+        }
+    }
+
+    // the role is held in the given domain, a global role can be held in a single organization
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        domain: &str,
+        user_id: &str,
+        role_id: &str,
+        req: AssignUserRoleRequest,
+        manages_super_admins: bool,
+    ) -> Result<UserRole, AppError> {
+        if let Some(expires_at) = req.expires_at {
+            if expires_at <= chrono::Utc::now() {
+                return Err(AppError::ProcessError(
+                    "expires_at must be in the future".to_owned(),
+                ));
+            }
+            if req.starts_at.is_some_and(|starts_at| starts_at >= expires_at) {
+                return Err(AppError::ProcessError(
+                    "starts_at must be before expires_at".to_owned(),
+                ));
+            }
+        }
+
+        let user = self.user_repo.find_by_id(user_id).await?;
+        let role = self.role_repo.find_by_id(role_id).await?;
+
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        // just-in-time roles are only granted through approved access requests
+        if role.name.starts_with(JIT_ROLE_PREFIX) {
+            return Err(AppError::ProcessError(
+                "Just-in-time roles cannot be assigned directly".to_owned(),
+            ));
+        }
+
+        if role.name == SUPER_ADMIN_ROLE {
+            if !manages_super_admins {
+                return Err(AppError::Forbidden);
+            }
+            if domain != GLOBAL_DOMAIN {
+                return Err(AppError::ProcessError(
+                    "The super admin role can only be assigned globally".to_owned(),
+                ));
+            }
+            // an expiring grant could silently remove the last super admin
+            if req.starts_at.is_some() || req.expires_at.is_some() {
+                return Err(AppError::ProcessError(
+                    "The super admin role cannot be granted temporarily".to_owned(),
+                ));
+            }
+        }
+
+        let mut tx = db_pool.begin().await?;
+        self.role_constraint_svc
+            .check_assignment(&mut tx, &user.id, &role.id, domain)
+            .await?;
+
+        let user_role = self
+            .user_role_repo
+            .tx_create(
+                &mut tx,
+                &UserRole::new(user.id.clone(), role.id.clone(), domain.to_string())
+                    .with_validity(req.starts_at, req.expires_at),
+            )
+            .await?;
+        tx.commit().await?;
+
+        // cached roles would keep serving the old permissions until the cache expires
+        self.redis_svc.remove_current_user(&user.id).await?;
+
+        info!("Role {} assigned to user {} in {}", role.id, user.id, domain);
+
+        Ok(user_role)
+    }
+}
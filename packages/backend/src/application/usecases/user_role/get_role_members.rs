@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::user_role::RoleMember,
+        repositories::{role_repo::RoleRepository, user_role_repo::UserRoleRepository},
+    },
+    infra::{
+        errors::app_error::AppError,
+        rbac::GLOBAL_DOMAIN,
+        utils::pagination::{PaginatedResponse, PaginationMeta},
+    },
+};
+
+#[derive(Clone)]
+pub struct GetRoleMembers<R, M> {
+    role_repo: Arc<R>,
+    user_role_repo: Arc<M>,
+}
+
+impl<R, M> GetRoleMembers<R, M>
+where
+    R: RoleRepository,
+    M: UserRoleRepository,
+{
+    pub fn new(role_repo: Arc<R>, user_role_repo: Arc<M>) -> Self {
+        Self {
+            role_repo,
+            user_role_repo,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        domain: &str,
+        role_id: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<PaginatedResponse<RoleMember>, AppError> {
+        let role = self.role_repo.find_by_id(role_id).await?;
+
+        // roles of other organizations are invisible here
+        if role.domain != GLOBAL_DOMAIN && role.domain != domain {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        let (members, total_items) = self
+            .user_role_repo
+            .paginate_members(&role.id, domain, page, limit)
+            .await?;
+
+        let total_pages = (total_items as f64 / limit as f64).ceil() as i64;
+
+        let pagination = PaginationMeta {
+            total_items,
+            total_pages,
+            current_page: page as i32,
+            items_per_page: limit as i32,
+        };
+
+        Ok(PaginatedResponse {
+            items: members,
+            pagination,
+        })
+    }
+}
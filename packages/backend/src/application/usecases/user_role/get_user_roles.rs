@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::role::AssignedRole,
+        repositories::{role_repo::RoleRepository, user_repo::UserRepository},
+    },
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+#[derive(Clone)]
+pub struct GetUserRoles<U, R> {
+    user_repo: Arc<U>,
+    role_repo: Arc<R>,
+}
+
+impl<U, R> GetUserRoles<U, R>
+where
+    U: UserRepository,
+    R: RoleRepository,
+{
+    pub fn new(user_repo: Arc<U>, role_repo: Arc<R>) -> Self {
+        Self {
+            user_repo,
+            role_repo,
+        }
+    }
+
+    // assignments applying in the domain, or every assignment when managing globally
+    pub async fn execute(&self, domain: &str, user_id: &str) -> Result<Vec<AssignedRole>, AppError> {
+        let user = self.user_repo.find_by_id(user_id).await?;
+
+        let roles = self
+            .role_repo
+            .get_roles_by_user_id(&user.id)
+            .await?
+            .into_iter()
+            .filter(|role| {
+                domain == GLOBAL_DOMAIN
+                    || role.assigned_domain == GLOBAL_DOMAIN
+                    || role.assigned_domain == domain
+            })
+            .collect();
+
+        Ok(roles)
+    }
+}
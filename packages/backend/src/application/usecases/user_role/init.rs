@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::{
+    application::services::{redis_svc::RedisService, role_constraint_svc::RoleConstraintService},
+    infra::repositories::{
+        pg_role_constraint_repo::PgRoleConstraintRepository, pg_role_repo::PgRoleRepository,
+        pg_user_repo::PgUserRepository,
+        pg_user_role_repo::PgUserRoleRepository, redis_repo_impl::RedisRepositoryImpl,
+    },
+};
+
+use super::{
+    assign_user_role::AssignUserRole, get_role_members::GetRoleMembers,
+    get_user_roles::GetUserRoles, sweep_expired_grants::SweepExpiredGrants,
+    unassign_user_role::UnassignUserRole,
+};
+
+#[derive(Clone)]
+pub struct UserRoleUsecase {
+    pub get_user_roles: Arc<GetUserRoles<PgUserRepository, PgRoleRepository>>,
+    pub get_role_members: Arc<GetRoleMembers<PgRoleRepository, PgUserRoleRepository>>,
+    pub assign_user_role: Arc<
+        AssignUserRole<PgUserRepository, PgRoleRepository, PgUserRoleRepository, RedisRepositoryImpl>,
+    >,
+    pub unassign_user_role:
+        Arc<UnassignUserRole<PgRoleRepository, PgUserRoleRepository, RedisRepositoryImpl>>,
+    pub sweep_expired_grants: Arc<SweepExpiredGrants<PgUserRoleRepository, RedisRepositoryImpl>>,
+}
+
+impl UserRoleUsecase {
+    pub fn new(
+        user_repo: Arc<PgUserRepository>,
+        role_repo: Arc<PgRoleRepository>,
+        user_role_repo: Arc<PgUserRoleRepository>,
+        redis_svc: Arc<RedisService<RedisRepositoryImpl>>,
+        role_constraint_svc: Arc<
+            RoleConstraintService<PgRoleConstraintRepository, PgRoleRepository, PgUserRoleRepository>,
+        >,
+    ) -> Self {
+        Self {
+            get_user_roles: Arc::new(GetUserRoles::new(user_repo.clone(), role_repo.clone())),
+            get_role_members: Arc::new(GetRoleMembers::new(
+                role_repo.clone(),
+                user_role_repo.clone(),
+            )),
+            assign_user_role: Arc::new(AssignUserRole::new(
+                user_repo.clone(),
+                role_repo.clone(),
+                user_role_repo.clone(),
+                redis_svc.clone(),
+                role_constraint_svc.clone(),
+            )),
+            unassign_user_role: Arc::new(UnassignUserRole::new(
+                role_repo.clone(),
+                user_role_repo.clone(),
+                redis_svc.clone(),
+            )),
+            sweep_expired_grants: Arc::new(SweepExpiredGrants::new(
+                user_role_repo.clone(),
+                redis_svc.clone(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod assign_user_role;
+pub mod get_role_members;
+pub mod get_user_roles;
+pub mod init;
+pub mod sweep_expired_grants;
+pub mod unassign_user_role;
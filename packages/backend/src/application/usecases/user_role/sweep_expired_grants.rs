@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::{
+        entities::user_role::UserRoleExpiration,
+        repositories::{redis_repo::RedisRepository, user_role_repo::UserRoleRepository},
+    },
+    infra::errors::app_error::AppError,
+};
+
+const SWEEP_BATCH_SIZE: i64 = 500;
+
+#[derive(Clone)]
+pub struct SweepExpiredGrants<M, C> {
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<M, C> SweepExpiredGrants<M, C>
+where
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(user_role_repo: Arc<M>, redis_svc: Arc<RedisService<C>>) -> Self {
+        Self {
+            user_role_repo,
+            redis_svc,
+        }
+    }
+
+    // enforcement already ignores expired grants, this cleans them up and records them
+    pub async fn execute(&self, db_pool: &sqlx::PgPool) -> Result<usize, AppError> {
+        let mut swept = 0;
+
+        loop {
+            let mut tx = db_pool.begin().await?;
+
+            let expired = self
+                .user_role_repo
+                .tx_delete_expired(&mut tx, SWEEP_BATCH_SIZE)
+                .await?;
+            for user_role in &expired {
+                if let Some(expires_at) = user_role.expires_at {
+                    self.user_role_repo
+                        .tx_record_expiration(&mut tx, &UserRoleExpiration::new(user_role, expires_at))
+                        .await?;
+                }
+            }
+
+            tx.commit().await?;
+
+            let mut user_ids = expired
+                .iter()
+                .map(|user_role| user_role.user_id.as_str())
+                .collect::<Vec<&str>>();
+            user_ids.sort();
+            user_ids.dedup();
+            for user_id in user_ids {
+                self.redis_svc.remove_current_user(user_id).await?;
+            }
+
+            for user_role in &expired {
+                info!(
+                    "Role {} of user {} in {} expired",
+                    user_role.role_id, user_role.user_id, user_role.domain
+                );
+            }
+
+            swept += expired.len();
+            if (expired.len() as i64) < SWEEP_BATCH_SIZE {
+                return Ok(swept);
+            }
+        }
+    }
+}
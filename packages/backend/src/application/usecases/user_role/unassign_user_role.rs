@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{
+    application::services::redis_svc::RedisService,
+    domain::repositories::{
+        redis_repo::RedisRepository, role_repo::RoleRepository,
+        user_role_repo::UserRoleRepository,
+    },
+    infra::{common::constants::SUPER_ADMIN_ROLE, errors::app_error::AppError},
+};
+
+#[derive(Clone)]
+pub struct UnassignUserRole<R, M, C> {
+    role_repo: Arc<R>,
+    user_role_repo: Arc<M>,
+    redis_svc: Arc<RedisService<C>>,
+}
+
+impl<R, M, C> UnassignUserRole<R, M, C>
+where
+    R: RoleRepository,
+    M: UserRoleRepository,
+    C: RedisRepository,
+{
+    pub fn new(role_repo: Arc<R>, user_role_repo: Arc<M>, redis_svc: Arc<RedisService<C>>) -> Self {
+        Self {
+            role_repo,
+            user_role_repo,
+            redis_svc,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        db_pool: &sqlx::PgPool,
+        domain: &str,
+        user_id: &str,
+        role_id: &str,
+        manages_super_admins: bool,
+    ) -> Result<(), AppError> {
+        let role = self.role_repo.find_by_id(role_id).await?;
+        let is_super_admin_role = role.name == SUPER_ADMIN_ROLE;
+
+        if is_super_admin_role && !manages_super_admins {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut tx = db_pool.begin().await?;
+
+        // holders stay locked until commit so concurrent removals cannot both pass the check
+        let holders = if is_super_admin_role {
+            self.user_role_repo.tx_lock_holders(&mut tx, &role.id).await?
+        } else {
+            0
+        };
+
+        let removed = self
+            .user_role_repo
+            .tx_delete(&mut tx, user_id, &role.id, domain)
+            .await?;
+        if !removed {
+            return Err(AppError::ResourceNotFound);
+        }
+
+        if is_super_admin_role && holders <= 1 {
+            return Err(AppError::ProcessError(
+                "Cannot remove the last super admin".to_owned(),
+            ));
+        }
+
+        tx.commit().await?;
+
+        self.redis_svc.remove_current_user(user_id).await?;
+
+        info!("Role {} unassigned from user {} in {}", role.id, user_id, domain);
+
+        Ok(())
+    }
+}
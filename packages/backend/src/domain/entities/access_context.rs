@@ -0,0 +1,82 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+// attributes of the resource being accessed, empty when it has none
+#[derive(Clone, Debug, Default, Hash, Serialize, Deserialize)]
+pub struct ResourceAttributes {
+    #[serde(default)]
+    pub owner_id: String,
+    #[serde(default)]
+    pub organization: String,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// Everything a policy condition can refer to as `r.ctx.<field>`.
+#[derive(Clone, Debug, Default, Hash, Serialize)]
+pub struct AccessContext {
+    pub user_id: String,
+    pub ip: String,
+    // `HH:MM` in UTC, filled in when the request is evaluated
+    pub time: String,
+    pub owner_id: String,
+    pub organization: String,
+    pub status: String,
+}
+
+impl AccessContext {
+    pub fn new(user_id: &str, ip: Option<&str>) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            ip: ip.unwrap_or_default().to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_resource(mut self, resource: ResourceAttributes) -> Self {
+        self.owner_id = resource.owner_id;
+        self.organization = resource.organization;
+        self.status = resource.status;
+        self
+    }
+
+    pub fn at(&self, now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            time: now.format("%H:%M").to_string(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Permission a resource route left to its handler, checked once the resource is loaded. The
+/// route fails closed unless the check is carried out with it.
+#[derive(Clone, Debug)]
+pub struct ResourceCheck {
+    pub object: &'static str,
+    pub action: &'static str,
+    pub domain: String,
+    checked: Arc<AtomicBool>,
+}
+
+impl ResourceCheck {
+    pub fn new(object: &'static str, action: &'static str, domain: String) -> Self {
+        Self {
+            object,
+            action,
+            domain,
+            checked: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn mark_checked(&self) {
+        self.checked.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked.load(Ordering::Relaxed)
+    }
+}
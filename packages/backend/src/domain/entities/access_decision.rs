@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessOutcome {
+    Allowed,
+    // an explicit deny policy matched
+    Denied,
+    // no policy matched, access is refused by default
+    NoMatch,
+}
+
+// a role held by the subject and whether it applies in the checked domain
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsideredRole {
+    pub role_id: String,
+    pub role_name: String,
+    pub assigned_domain: String,
+    pub applies: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedPolicy {
+    pub policy: Vec<String>,
+    pub effect: String,
+    // role held by the subject first, role owning the policy last
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessDecision {
+    pub domain: String,
+    pub object: String,
+    pub action: String,
+    pub outcome: AccessOutcome,
+    pub allowed: bool,
+    pub considered_roles: Vec<ConsideredRole>,
+    pub matched_policies: Vec<MatchedPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessMatrixCell {
+    pub object: String,
+    pub action: String,
+    pub outcome: AccessOutcome,
+    pub allowed: bool,
+}
+
+/// Effective permissions of a subject over a set of resources and actions.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessMatrix {
+    pub domain: String,
+    pub considered_roles: Vec<ConsideredRole>,
+    pub cells: Vec<AccessMatrixCell>,
+}
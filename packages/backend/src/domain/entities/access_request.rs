@@ -0,0 +1,143 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const ACCESS_REQUEST_PENDING: &str = "pending";
+pub const ACCESS_REQUEST_APPROVED: &str = "approved";
+pub const ACCESS_REQUEST_DENIED: &str = "denied";
+pub const ACCESS_REQUEST_CANCELLED: &str = "cancelled";
+pub const ACCESS_REQUEST_REVOKED: &str = "revoked";
+pub const ACCESS_REQUEST_EXPIRED: &str = "expired";
+
+// a request for either a role or a single `resource:action` permission in a domain
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessRequest {
+    pub id: String,
+    pub requester_id: String,
+    pub domain: String,
+    pub role_id: Option<String>,
+    pub permission: Option<String>,
+    pub justification: String,
+    pub duration_secs: i64,
+    pub status: String,
+    // the role actually granted, a dedicated one when a permission was requested
+    pub granted_role_id: Option<String>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AccessRequest {
+    pub fn new(
+        requester_id: String,
+        domain: String,
+        role_id: Option<String>,
+        permission: Option<String>,
+        justification: String,
+        duration_secs: i64,
+    ) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4().to_string(),
+            requester_id,
+            domain,
+            role_id,
+            permission,
+            justification,
+            duration_secs,
+            status: ACCESS_REQUEST_PENDING.to_string(),
+            granted_role_id: None,
+            starts_at: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == ACCESS_REQUEST_PENDING
+    }
+
+    pub fn is_granted(&self) -> bool {
+        self.status == ACCESS_REQUEST_APPROVED
+    }
+
+    // what was asked for, for notifications and logs
+    pub fn target(&self) -> String {
+        match (&self.role_id, &self.permission) {
+            (_, Some(permission)) => format!("permission {}", permission),
+            (Some(role_id), None) => format!("role {}", role_id),
+            (None, None) => "nothing".to_string(),
+        }
+    }
+
+    pub fn approve(&mut self, granted_role_id: String, duration_secs: i64) {
+        let now = chrono::Utc::now();
+
+        self.status = ACCESS_REQUEST_APPROVED.to_string();
+        self.granted_role_id = Some(granted_role_id);
+        self.starts_at = Some(now);
+        self.expires_at = Some(now + chrono::Duration::seconds(duration_secs));
+        self.updated_at = now;
+    }
+
+    pub fn close(&mut self, status: &str) {
+        self.status = status.to_string();
+        self.updated_at = chrono::Utc::now();
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessRequestDecision {
+    pub id: String,
+    pub request_id: String,
+    pub approver_id: String,
+    pub decision: String,
+    pub comment: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AccessRequestDecision {
+    pub fn new(request_id: String, approver_id: String, decision: &str, comment: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            request_id,
+            approver_id,
+            decision: decision.to_string(),
+            comment,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+// the end of an approved grant, revoked_by is empty when it simply expired
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessRequestRevocation {
+    pub id: String,
+    pub request_id: String,
+    pub revoked_by: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AccessRequestRevocation {
+    pub fn new(request_id: String, revoked_by: Option<String>, reason: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            request_id,
+            revoked_by,
+            reason,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+// a request with everything that happened to it
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessRequestDetail {
+    #[serde(flatten)]
+    pub request: AccessRequest,
+    pub decisions: Vec<AccessRequestDecision>,
+    pub revocations: Vec<AccessRequestRevocation>,
+}
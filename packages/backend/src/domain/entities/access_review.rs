@@ -0,0 +1,209 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::policy_document::escape_csv_field;
+
+pub const ACCESS_REVIEW_OPEN: &str = "open";
+pub const ACCESS_REVIEW_COMPLETED: &str = "completed";
+
+// what a reviewer, or the default action at the deadline, does with an assignment
+pub const ACCESS_REVIEW_CONFIRM: &str = "confirm";
+pub const ACCESS_REVIEW_REVOKE: &str = "revoke";
+
+pub const ACCESS_REVIEW_ITEM_PENDING: &str = "pending";
+pub const ACCESS_REVIEW_ITEM_CONFIRMED: &str = "confirmed";
+pub const ACCESS_REVIEW_ITEM_REVOKED: &str = "revoked";
+
+const CSV_HEADER: [&str; 11] = [
+    "user_id",
+    "email",
+    "role",
+    "domain",
+    "assigned_at",
+    "expires_at",
+    "decision",
+    "by_default",
+    "decided_by",
+    "decided_at",
+    "comment",
+];
+
+// a certification campaign over the holders of some roles in a domain, every role holder of
+// the domain when it is `*`
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessReview {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub domain: String,
+    pub role_ids: Vec<String>,
+    pub reviewer_ids: Vec<String>,
+    pub default_action: String,
+    pub status: String,
+    pub deadline: chrono::DateTime<chrono::Utc>,
+    pub created_by: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl AccessReview {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        description: Option<String>,
+        domain: String,
+        role_ids: Vec<String>,
+        reviewer_ids: Vec<String>,
+        default_action: String,
+        deadline: chrono::DateTime<chrono::Utc>,
+        created_by: String,
+    ) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            description,
+            domain,
+            role_ids,
+            reviewer_ids,
+            default_action,
+            status: ACCESS_REVIEW_OPEN.to_string(),
+            deadline,
+            created_by: Some(created_by),
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == ACCESS_REVIEW_OPEN
+    }
+
+    pub fn is_reviewer(&self, user_id: &str) -> bool {
+        self.reviewer_ids.iter().any(|reviewer_id| reviewer_id == user_id)
+    }
+
+    pub fn complete(&mut self) {
+        let now = chrono::Utc::now();
+
+        self.status = ACCESS_REVIEW_COMPLETED.to_string();
+        self.updated_at = now;
+        self.completed_at = Some(now);
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessReviewItem {
+    pub id: String,
+    pub review_id: String,
+    pub user_id: String,
+    pub email: String,
+    pub role_id: String,
+    pub role_name: String,
+    pub domain: String,
+    pub assigned_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub decision: String,
+    pub by_default: bool,
+    // empty when decided by the default action
+    pub decided_by: Option<String>,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub comment: Option<String>,
+}
+
+impl AccessReviewItem {
+    pub fn is_pending(&self) -> bool {
+        self.decision == ACCESS_REVIEW_ITEM_PENDING
+    }
+
+    pub fn decide(&mut self, action: &str, decided_by: Option<String>, comment: Option<String>) {
+        self.decision = if action == ACCESS_REVIEW_REVOKE {
+            ACCESS_REVIEW_ITEM_REVOKED
+        } else {
+            ACCESS_REVIEW_ITEM_CONFIRMED
+        }
+        .to_string();
+        self.by_default = decided_by.is_none();
+        self.decided_by = decided_by;
+        self.decided_at = Some(chrono::Utc::now());
+        self.comment = comment;
+    }
+}
+
+// a campaign with the assignments under review
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessReviewDetail {
+    #[serde(flatten)]
+    pub review: AccessReview,
+    pub items: Vec<AccessReviewItem>,
+}
+
+// the outcome of a completed campaign, for auditors
+#[derive(Clone, Debug, Serialize)]
+pub struct AccessReviewReport {
+    #[serde(flatten)]
+    pub review: AccessReview,
+    pub total: usize,
+    pub confirmed: usize,
+    pub revoked: usize,
+    pub by_default: usize,
+    pub items: Vec<AccessReviewItem>,
+}
+
+impl AccessReviewReport {
+    pub fn new(review: AccessReview, items: Vec<AccessReviewItem>) -> Self {
+        let count = |decision: &str| items.iter().filter(|item| item.decision == decision).count();
+
+        Self {
+            total: items.len(),
+            confirmed: count(ACCESS_REVIEW_ITEM_CONFIRMED),
+            revoked: count(ACCESS_REVIEW_ITEM_REVOKED),
+            by_default: items.iter().filter(|item| item.by_default).count(),
+            review,
+            items,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let timestamp = |at: Option<chrono::DateTime<chrono::Utc>>| {
+            at.map(|at| at.to_rfc3339()).unwrap_or_default()
+        };
+
+        let mut csv = format!(
+            "#review={}\n#domain={}\n#completed_at={}\n",
+            escape_csv_field(&self.review.name),
+            escape_csv_field(&self.review.domain),
+            timestamp(self.review.completed_at)
+        );
+        csv.push_str(&CSV_HEADER.join(","));
+        csv.push('\n');
+
+        for item in &self.items {
+            let row = [
+                item.user_id.clone(),
+                item.email.clone(),
+                item.role_name.clone(),
+                item.domain.clone(),
+                item.assigned_at.to_rfc3339(),
+                timestamp(item.expires_at),
+                item.decision.clone(),
+                item.by_default.to_string(),
+                item.decided_by.clone().unwrap_or_default(),
+                timestamp(item.decided_at),
+                item.comment.clone().unwrap_or_default(),
+            ];
+            csv.push_str(
+                &row.iter()
+                    .map(|field| escape_csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
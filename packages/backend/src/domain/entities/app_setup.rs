@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AppSetup {
+    pub id: i16,
+    pub super_admin_id: Option<String>,
+    pub completed_at: chrono::DateTime<chrono::Utc>,
+}
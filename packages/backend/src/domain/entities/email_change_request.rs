@@ -0,0 +1,71 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const EMAIL_CHANGE_PENDING: &str = "pending";
+pub const EMAIL_CHANGE_CONFIRMED: &str = "confirmed";
+pub const EMAIL_CHANGE_CANCELLED: &str = "cancelled";
+pub const EMAIL_CHANGE_REVERTED: &str = "reverted";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EmailChangeRequest {
+    pub id: String,
+    pub user_id: String,
+    pub old_email: String,
+    pub new_email: String,
+    #[serde(skip_serializing)]
+    pub confirm_token_hash: String,
+    #[serde(skip_serializing)]
+    pub revert_token_hash: String,
+    pub status: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub revert_expires_at: chrono::DateTime<chrono::Utc>,
+    pub confirmed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub reverted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl EmailChangeRequest {
+    pub fn new(
+        user_id: String,
+        old_email: String,
+        new_email: String,
+        confirm_token_hash: String,
+        revert_token_hash: String,
+    ) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4().to_string(),
+            user_id,
+            old_email,
+            new_email,
+            confirm_token_hash,
+            revert_token_hash,
+            status: EMAIL_CHANGE_PENDING.to_string(),
+            expires_at: now + chrono::Duration::hours(24),
+            revert_expires_at: now + chrono::Duration::days(7),
+            confirmed_at: None,
+            reverted_at: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_confirmable(&self) -> bool {
+        self.status == EMAIL_CHANGE_PENDING && self.expires_at > chrono::Utc::now()
+    }
+
+    pub fn is_revertable(&self) -> bool {
+        (self.status == EMAIL_CHANGE_PENDING || self.status == EMAIL_CHANGE_CONFIRMED)
+            && self.revert_expires_at > chrono::Utc::now()
+    }
+
+    pub fn confirm(&mut self) {
+        self.status = EMAIL_CHANGE_CONFIRMED.to_string();
+        self.confirmed_at = Some(chrono::Utc::now());
+    }
+
+    pub fn revert(&mut self) {
+        self.status = EMAIL_CHANGE_REVERTED.to_string();
+        self.reverted_at = Some(chrono::Utc::now());
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{ser::Error as _, Serialize, Serializer};
+
+// a field only callers holding `permission` (`resource:action`) may read in a response or set
+// in a request
+#[derive(Clone, Copy, Debug)]
+pub struct ProtectedField {
+    pub name: &'static str,
+    pub permission: &'static str,
+    // the owner of the resource reads it without the permission, e.g. their own email
+    pub owner_readable: bool,
+}
+
+/// Declares which fields of a response or update request need a permission of their own.
+pub trait ProtectedFields {
+    const PROTECTED_FIELDS: &'static [ProtectedField];
+
+    // owner of the resource, checked against owner conditions of the field permissions
+    fn owner_id(&self) -> Option<&str> {
+        None
+    }
+
+    // fields an update request sets, only these are checked on writes
+    fn provided_fields(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+/// A response value serialized without the fields the caller may not read.
+#[derive(Clone, Debug)]
+pub struct Redacted<T> {
+    pub value: T,
+    pub hidden: Vec<&'static str>,
+}
+
+impl<T> Redacted<T> {
+    pub fn new(value: T, hidden: Vec<&'static str>) -> Self {
+        Self { value, hidden }
+    }
+}
+
+impl<T: Serialize> Serialize for Redacted<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.hidden.is_empty() {
+            return self.value.serialize(serializer);
+        }
+
+        let mut value = serde_json::to_value(&self.value).map_err(S::Error::custom)?;
+        if let Some(fields) = value.as_object_mut() {
+            for name in &self.hidden {
+                fields.remove(*name);
+            }
+        }
+
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Account {
+        id: &'static str,
+        email: &'static str,
+        phone: Option<&'static str>,
+    }
+
+    fn account() -> Account {
+        Account {
+            id: "alice",
+            email: "alice@example.com",
+            phone: None,
+        }
+    }
+
+    #[test]
+    fn serializes_the_value_as_is_without_hidden_fields() {
+        let redacted = Redacted::new(account(), Vec::new());
+
+        assert_eq!(
+            serde_json::to_value(&redacted).unwrap(),
+            json!({ "id": "alice", "email": "alice@example.com", "phone": null })
+        );
+    }
+
+    #[test]
+    fn leaves_out_hidden_fields() {
+        let redacted = Redacted::new(account(), vec!["email", "phone"]);
+
+        assert_eq!(serde_json::to_value(&redacted).unwrap(), json!({ "id": "alice" }));
+    }
+
+    #[test]
+    fn ignores_hidden_fields_the_value_does_not_have() {
+        let redacted = Redacted::new(account(), vec!["password"]);
+
+        assert_eq!(
+            serde_json::to_value(&redacted).unwrap(),
+            json!({ "id": "alice", "email": "alice@example.com", "phone": null })
+        );
+    }
+
+    #[test]
+    fn hides_fields_inside_lists() {
+        let redacted = vec![Redacted::new(account(), vec!["email"])];
+
+        assert_eq!(
+            serde_json::to_value(&redacted).unwrap(),
+            json!([{ "id": "alice", "phone": null }])
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Invite {
+    pub id: String,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub invited_by: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub accepted_user_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Invite {
+    pub fn new(email: String, token_hash: String, invited_by: String, valid_days: i64) -> Self {
+        let now = chrono::Utc::now();
+
+        Self {
+            id: Uuid::new_v4().to_string(),
+            email,
+            token_hash,
+            invited_by: Some(invited_by),
+            expires_at: now + chrono::Duration::days(valid_days),
+            accepted_at: None,
+            accepted_user_id: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.accepted_at.is_none() && self.expires_at > chrono::Utc::now()
+    }
+
+    pub fn accept(&mut self, user_id: String) {
+        self.accepted_at = Some(chrono::Utc::now());
+        self.accepted_user_id = Some(user_id);
+    }
+}
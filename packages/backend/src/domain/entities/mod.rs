@@ -1,5 +1,19 @@
+pub mod access_request;
+pub mod access_review;
+pub mod access_context;
+pub mod access_decision;
+pub mod app_setup;
+pub mod email_change_request;
+pub mod field_access;
+pub mod invite;
 pub mod permission;
+pub mod policy_consistency;
+pub mod policy_document;
 pub mod role;
+pub mod role_constraint;
+pub mod role_policy_change;
+pub mod role_template;
+pub mod saml_identity_provider;
 pub mod user;
 pub mod user_oauth_provider;
 pub mod user_role;
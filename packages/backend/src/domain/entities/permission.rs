@@ -5,20 +5,47 @@ pub struct Permission {
     pub id: String,
     pub name: String,
     pub action: String,
+    pub description: Option<String>,
+    pub group_name: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl Permission {
-    pub fn new(id: String, name: String, action: String) -> Self {
+    pub fn new(
+        name: String,
+        action: String,
+        description: Option<String>,
+        group_name: String,
+    ) -> Self {
         Self {
-            id,
+            id: uuid::Uuid::new_v4().to_string(),
             name,
             action,
+            description,
+            group_name,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
         }
     }
+
+    pub fn update(&mut self, description: Option<String>, group_name: String) {
+        self.description = description;
+        self.group_name = group_name;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    // `resource:action` as used in role requests
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.name, self.action)
+    }
+}
+
+/// Permissions sharing a group, for display in role editors.
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionGroup {
+    pub group_name: String,
+    pub permissions: Vec<Permission>,
 }
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+// rules are listed like casbin's CSV lines, the ptype first
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RoleDrift {
+    pub role_id: String,
+    pub role_name: String,
+    pub domain: String,
+    // history version the expected rules come from, none for roles owned by the application
+    pub recorded_version: Option<i32>,
+    pub missing_rules: Vec<Vec<String>>,
+    pub unexpected_rules: Vec<Vec<String>>,
+}
+
+/// Drift between the roles table, the recorded role history and the stored casbin rules.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PolicyConsistencyReport {
+    // rules naming a role that does not exist or was deleted
+    pub orphaned_rules: Vec<Vec<String>>,
+    pub drifted_roles: Vec<RoleDrift>,
+    // the enforcer of this instance holds other rules than the database
+    pub stale_enforcer: bool,
+    pub repaired: bool,
+}
+
+impl PolicyConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.orphaned_rules.is_empty() && self.drifted_roles.is_empty() && !self.stale_enforcer
+    }
+}
@@ -0,0 +1,662 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::infra::errors::app_error::AppError;
+
+pub const POLICY_DOCUMENT_VERSION: u32 = 1;
+
+// `condition` rows carry the permission as value and the expression as value_domain
+const CSV_HEADER: [&str; 5] = ["kind", "role", "domain", "value", "value_domain"];
+
+// roles are identified by name within their domain so documents move between environments
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RoleKey {
+    pub name: String,
+    pub domain: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolicyRole {
+    pub name: String,
+    pub domain: String,
+    #[serde(default)]
+    pub is_default: bool,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub denied_permissions: Vec<String>,
+    #[serde(default)]
+    pub parents: Vec<RoleKey>,
+    // `resource:action` to its condition, permissions missing here apply unconditionally
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conditions: BTreeMap<String, String>,
+    // previous name in the same domain, so an import renames the role instead of replacing it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+}
+
+impl PolicyRole {
+    pub fn key(&self) -> RoleKey {
+        RoleKey {
+            name: self.name.clone(),
+            domain: self.domain.clone(),
+        }
+    }
+
+    fn same_content(&self, other: &PolicyRole) -> bool {
+        self.is_default == other.is_default
+            && sorted(&self.permissions) == sorted(&other.permissions)
+            && sorted(&self.denied_permissions) == sorted(&other.denied_permissions)
+            && self.parents.iter().collect::<BTreeSet<&RoleKey>>()
+                == other.parents.iter().collect::<BTreeSet<&RoleKey>>()
+            && self.conditions == other.conditions
+    }
+}
+
+/// Every role with its policies and parents, as exported from one environment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolicyDocument {
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub roles: Vec<PolicyRole>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RoleRename {
+    pub domain: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PolicyLine {
+    pub role: String,
+    pub domain: String,
+    pub permission: String,
+    pub effect: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ParentLink {
+    pub role: String,
+    pub domain: String,
+    pub parent: String,
+    pub parent_domain: String,
+}
+
+/// What importing a document would change, roles are matched by name or rename.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PolicyDiff {
+    pub roles_added: Vec<RoleKey>,
+    pub roles_removed: Vec<RoleKey>,
+    pub roles_renamed: Vec<RoleRename>,
+    pub default_changed: Vec<RoleKey>,
+    pub policies_added: Vec<PolicyLine>,
+    pub policies_removed: Vec<PolicyLine>,
+    pub parents_added: Vec<ParentLink>,
+    pub parents_removed: Vec<ParentLink>,
+}
+
+impl PolicyDiff {
+    pub fn is_empty(&self) -> bool {
+        self.roles_added.is_empty()
+            && self.roles_removed.is_empty()
+            && self.roles_renamed.is_empty()
+            && self.default_changed.is_empty()
+            && self.policies_added.is_empty()
+            && self.policies_removed.is_empty()
+            && self.parents_added.is_empty()
+            && self.parents_removed.is_empty()
+    }
+}
+
+impl PolicyDocument {
+    pub fn new(roles: Vec<PolicyRole>) -> Self {
+        Self {
+            version: POLICY_DOCUMENT_VERSION,
+            exported_at: Some(chrono::Utc::now()),
+            roles,
+        }
+    }
+
+    // current role key -> target role key, from `renamed_from` first, then roles whose
+    // name is the only difference
+    pub fn renames(&self, target: &PolicyDocument) -> HashMap<RoleKey, RoleKey> {
+        let current = self
+            .roles
+            .iter()
+            .map(|role| (role.key(), role))
+            .collect::<BTreeMap<RoleKey, &PolicyRole>>();
+        let target_roles = target
+            .roles
+            .iter()
+            .map(|role| (role.key(), role))
+            .collect::<BTreeMap<RoleKey, &PolicyRole>>();
+
+        let mut renames = HashMap::new();
+        let mut renamed_to = HashSet::new();
+
+        for role in &target.roles {
+            let Some(from) = role.renamed_from.as_deref() else {
+                continue;
+            };
+            let from = RoleKey {
+                name: from.to_string(),
+                domain: role.domain.clone(),
+            };
+            if current.contains_key(&from)
+                && !target_roles.contains_key(&from)
+                && !current.contains_key(&role.key())
+                && !renames.contains_key(&from)
+            {
+                renamed_to.insert(role.key());
+                renames.insert(from, role.key());
+            }
+        }
+
+        for (key, role) in &target_roles {
+            if current.contains_key(key) || renamed_to.contains(key) {
+                continue;
+            }
+            let candidate = current.iter().find(|(current_key, current_role)| {
+                current_key.domain == key.domain
+                    && !target_roles.contains_key(*current_key)
+                    && !renames.contains_key(*current_key)
+                    && current_role.same_content(role)
+            });
+            if let Some((current_key, _)) = candidate {
+                renamed_to.insert(key.clone());
+                renames.insert(current_key.clone(), key.clone());
+            }
+        }
+
+        renames
+    }
+
+    pub fn diff(&self, target: &PolicyDocument) -> PolicyDiff {
+        let renames = self.renames(target);
+        let renamed = |key: &RoleKey| renames.get(key).cloned().unwrap_or_else(|| key.clone());
+
+        let current_keys = self.roles.iter().map(PolicyRole::key).collect::<BTreeSet<RoleKey>>();
+        let target_keys = target.roles.iter().map(PolicyRole::key).collect::<BTreeSet<RoleKey>>();
+        let renamed_to = renames.values().cloned().collect::<HashSet<RoleKey>>();
+
+        let mut diff = PolicyDiff {
+            roles_added: target_keys
+                .iter()
+                .filter(|key| !current_keys.contains(*key) && !renamed_to.contains(*key))
+                .cloned()
+                .collect(),
+            roles_removed: current_keys
+                .iter()
+                .filter(|key| !target_keys.contains(*key) && !renames.contains_key(*key))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut roles_renamed = renames
+            .iter()
+            .map(|(from, to)| RoleRename {
+                domain: from.domain.clone(),
+                from: from.name.clone(),
+                to: to.name.clone(),
+            })
+            .collect::<Vec<RoleRename>>();
+        roles_renamed.sort_by(|a, b| (&a.domain, &a.from).cmp(&(&b.domain, &b.from)));
+        diff.roles_renamed = roles_renamed;
+
+        let current_defaults = self
+            .roles
+            .iter()
+            .map(|role| (renamed(&role.key()), role.is_default))
+            .collect::<HashMap<RoleKey, bool>>();
+        diff.default_changed = target
+            .roles
+            .iter()
+            .filter(|role| {
+                current_defaults
+                    .get(&role.key())
+                    .is_some_and(|is_default| *is_default != role.is_default)
+            })
+            .map(PolicyRole::key)
+            .collect();
+
+        let current_lines = policy_lines(&self.roles, &renamed);
+        let target_lines = policy_lines(&target.roles, &|key: &RoleKey| key.clone());
+        diff.policies_added = target_lines.difference(&current_lines).cloned().collect();
+        diff.policies_removed = current_lines.difference(&target_lines).cloned().collect();
+
+        let current_links = parent_links(&self.roles, &renamed);
+        let target_links = parent_links(&target.roles, &|key: &RoleKey| key.clone());
+        diff.parents_added = target_links.difference(&current_links).cloned().collect();
+        diff.parents_removed = current_links.difference(&target_links).cloned().collect();
+
+        diff
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![CSV_HEADER.iter().map(|field| field.to_string()).collect::<Vec<String>>()];
+
+        for role in &self.roles {
+            let row = |kind: &str, value: &str, value_domain: &str| {
+                vec![
+                    kind.to_string(),
+                    role.name.clone(),
+                    role.domain.clone(),
+                    value.to_string(),
+                    value_domain.to_string(),
+                ]
+            };
+
+            rows.push(row("role", if role.is_default { "default" } else { "" }, ""));
+            if let Some(from) = role.renamed_from.as_deref() {
+                rows.push(row("renamed_from", from, ""));
+            }
+            for permission in &role.permissions {
+                rows.push(row("allow", permission, ""));
+            }
+            for permission in &role.denied_permissions {
+                rows.push(row("deny", permission, ""));
+            }
+            for (permission, condition) in &role.conditions {
+                rows.push(row("condition", permission, condition));
+            }
+            for parent in &role.parents {
+                rows.push(row("parent", &parent.name, &parent.domain));
+            }
+        }
+
+        let mut csv = format!("#version={}\n", self.version);
+        for row in rows {
+            csv.push_str(
+                &row.iter()
+                    .map(|field| escape_csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    pub fn from_csv(input: &str) -> Result<Self, AppError> {
+        let mut version = None;
+        let mut roles: BTreeMap<RoleKey, PolicyRole> = BTreeMap::new();
+        let mut declared = HashSet::new();
+
+        for (index, record) in parse_csv_records(input)?.into_iter().enumerate() {
+            let line = index + 1;
+
+            if record.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+            if let Some(meta) = record[0].trim().strip_prefix('#') {
+                if let Some(value) = meta.trim().strip_prefix("version=") {
+                    version = Some(value.trim().parse::<u32>().map_err(|_| {
+                        AppError::ProcessError(format!("Invalid version on line {}", line))
+                    })?);
+                }
+                continue;
+            }
+            if record.len() != CSV_HEADER.len() {
+                return Err(AppError::ProcessError(format!(
+                    "Expected {} columns on line {}",
+                    CSV_HEADER.len(),
+                    line
+                )));
+            }
+            if record[0] == CSV_HEADER[0] {
+                continue;
+            }
+
+            let (kind, value, value_domain) = (record[0].trim(), record[3].trim(), record[4].trim());
+            let key = RoleKey {
+                name: record[1].trim().to_string(),
+                domain: record[2].trim().to_string(),
+            };
+            let role = roles.entry(key.clone()).or_insert_with(|| PolicyRole {
+                name: key.name.clone(),
+                domain: key.domain.clone(),
+                is_default: false,
+                permissions: Vec::new(),
+                denied_permissions: Vec::new(),
+                parents: Vec::new(),
+                conditions: BTreeMap::new(),
+                renamed_from: None,
+            });
+
+            match kind {
+                "role" => {
+                    role.is_default = value == "default";
+                    declared.insert(key);
+                }
+                "renamed_from" => role.renamed_from = Some(value.to_string()),
+                "allow" => role.permissions.push(value.to_string()),
+                "deny" => role.denied_permissions.push(value.to_string()),
+                "condition" => {
+                    role.conditions.insert(value.to_string(), value_domain.to_string());
+                }
+                "parent" => role.parents.push(RoleKey {
+                    name: value.to_string(),
+                    domain: value_domain.to_string(),
+                }),
+                _ => {
+                    return Err(AppError::ProcessError(format!(
+                        "Unknown row kind {} on line {}",
+                        kind, line
+                    )))
+                }
+            }
+        }
+
+        if let Some(key) = roles.keys().find(|key| !declared.contains(*key)) {
+            return Err(AppError::ProcessError(format!(
+                "Role {} in {} is used without a role row",
+                key.name, key.domain
+            )));
+        }
+
+        Ok(Self {
+            version: version
+                .ok_or_else(|| AppError::ProcessError("Missing #version line".to_owned()))?,
+            exported_at: None,
+            roles: roles.into_values().collect(),
+        })
+    }
+}
+
+fn policy_lines(roles: &[PolicyRole], key_of: &dyn Fn(&RoleKey) -> RoleKey) -> BTreeSet<PolicyLine> {
+    let mut lines = BTreeSet::new();
+
+    for role in roles {
+        let key = key_of(&role.key());
+        let effects = role
+            .permissions
+            .iter()
+            .map(|permission| (permission, "allow"))
+            .chain(role.denied_permissions.iter().map(|permission| (permission, "deny")));
+        for (permission, effect) in effects {
+            lines.insert(PolicyLine {
+                role: key.name.clone(),
+                domain: key.domain.clone(),
+                permission: permission.clone(),
+                effect: effect.to_string(),
+                condition: role.conditions.get(permission).cloned(),
+            });
+        }
+    }
+
+    lines
+}
+
+fn parent_links(roles: &[PolicyRole], key_of: &dyn Fn(&RoleKey) -> RoleKey) -> BTreeSet<ParentLink> {
+    let mut links = BTreeSet::new();
+
+    for role in roles {
+        let key = key_of(&role.key());
+        for parent in &role.parents {
+            let parent = key_of(parent);
+            links.insert(ParentLink {
+                role: key.name.clone(),
+                domain: key.domain.clone(),
+                parent: parent.name,
+                parent_domain: parent.domain,
+            });
+        }
+    }
+
+    links
+}
+
+fn sorted(items: &[String]) -> BTreeSet<&String> {
+    items.iter().collect()
+}
+
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// RFC 4180 records, quoted fields may contain separators, quotes and line breaks
+fn parse_csv_records(input: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::ProcessError("Unterminated quoted field".to_owned()));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(name: &str, permissions: &[&str]) -> PolicyRole {
+        PolicyRole {
+            name: name.to_string(),
+            domain: "*".to_string(),
+            is_default: false,
+            permissions: permissions.iter().map(|permission| permission.to_string()).collect(),
+            denied_permissions: Vec::new(),
+            parents: Vec::new(),
+            conditions: BTreeMap::new(),
+            renamed_from: None,
+        }
+    }
+
+    fn key(name: &str) -> RoleKey {
+        RoleKey {
+            name: name.to_string(),
+            domain: "*".to_string(),
+        }
+    }
+
+    #[test]
+    fn identical_documents_have_no_diff() {
+        let current = PolicyDocument::new(vec![role("editor", &["projects:write"])]);
+
+        assert!(current.diff(&current.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_added_and_removed_roles_and_policies() {
+        let current = PolicyDocument::new(vec![
+            role("editor", &["projects:read", "projects:write"]),
+            role("viewer", &["projects:read"]),
+        ]);
+        let target = PolicyDocument::new(vec![
+            role("editor", &["projects:read", "projects:delete"]),
+            role("auditor", &["audit:read"]),
+        ]);
+
+        let diff = current.diff(&target);
+
+        assert_eq!(diff.roles_added, vec![key("auditor")]);
+        assert_eq!(diff.roles_removed, vec![key("viewer")]);
+        assert!(diff.roles_renamed.is_empty());
+        assert_eq!(
+            diff.policies_added
+                .iter()
+                .map(|line| format!("{}={}", line.role, line.permission))
+                .collect::<Vec<String>>(),
+            vec!["auditor=audit:read", "editor=projects:delete"]
+        );
+        assert_eq!(
+            diff.policies_removed
+                .iter()
+                .map(|line| format!("{}={}", line.role, line.permission))
+                .collect::<Vec<String>>(),
+            vec!["editor=projects:write", "viewer=projects:read"]
+        );
+    }
+
+    #[test]
+    fn diff_lists_default_condition_and_parent_changes() {
+        let mut editor = role("editor", &["projects:write"]);
+        let current = PolicyDocument::new(vec![editor.clone(), role("viewer", &[])]);
+
+        editor.is_default = true;
+        editor
+            .conditions
+            .insert("projects:write".to_string(), "r.ctx.owner_id == r.ctx.user_id".to_string());
+        editor.parents.push(key("viewer"));
+        let target = PolicyDocument::new(vec![editor, role("viewer", &[])]);
+
+        let diff = current.diff(&target);
+
+        assert_eq!(diff.default_changed, vec![key("editor")]);
+        assert_eq!(diff.policies_added.len(), 1);
+        assert_eq!(
+            diff.policies_added[0].condition.as_deref(),
+            Some("r.ctx.owner_id == r.ctx.user_id")
+        );
+        assert_eq!(diff.policies_removed.len(), 1);
+        assert_eq!(diff.policies_removed[0].condition, None);
+        assert_eq!(
+            diff.parents_added,
+            vec![ParentLink {
+                role: "editor".to_string(),
+                domain: "*".to_string(),
+                parent: "viewer".to_string(),
+                parent_domain: "*".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn renamed_from_is_a_rename() {
+        let current = PolicyDocument::new(vec![role("editor", &["projects:write"])]);
+        let mut writer = role("writer", &["projects:write", "projects:delete"]);
+        writer.renamed_from = Some("editor".to_string());
+        let target = PolicyDocument::new(vec![writer]);
+
+        assert_eq!(current.renames(&target), HashMap::from([(key("editor"), key("writer"))]));
+
+        let diff = current.diff(&target);
+        assert!(diff.roles_added.is_empty());
+        assert!(diff.roles_removed.is_empty());
+        assert_eq!(diff.roles_renamed.len(), 1);
+        assert_eq!(diff.roles_renamed[0].from, "editor");
+        assert_eq!(diff.roles_renamed[0].to, "writer");
+        // only the permission the role gained, the rest moves with it
+        assert_eq!(diff.policies_added.len(), 1);
+        assert_eq!(diff.policies_added[0].permission, "projects:delete");
+        assert!(diff.policies_removed.is_empty());
+    }
+
+    #[test]
+    fn role_differing_only_by_name_is_a_rename() {
+        let current = PolicyDocument::new(vec![role("editor", &["projects:write"])]);
+        let target = PolicyDocument::new(vec![role("writer", &["projects:write"])]);
+
+        assert_eq!(current.renames(&target), HashMap::from([(key("editor"), key("writer"))]));
+    }
+
+    #[test]
+    fn role_with_other_content_is_not_a_rename() {
+        let current = PolicyDocument::new(vec![role("editor", &["projects:write"])]);
+        let target = PolicyDocument::new(vec![role("writer", &["projects:read"])]);
+
+        assert!(current.renames(&target).is_empty());
+
+        let diff = current.diff(&target);
+        assert_eq!(diff.roles_added, vec![key("writer")]);
+        assert_eq!(diff.roles_removed, vec![key("editor")]);
+    }
+
+    #[test]
+    fn rename_needs_the_old_role_gone_and_the_new_name_free() {
+        let current = PolicyDocument::new(vec![
+            role("editor", &["projects:write"]),
+            role("writer", &["projects:read"]),
+        ]);
+
+        // the old role is still in the target
+        let mut kept = role("copy", &["projects:write"]);
+        kept.renamed_from = Some("editor".to_string());
+        let target = PolicyDocument::new(vec![role("editor", &["projects:write"]), kept]);
+        assert!(current.renames(&target).is_empty());
+
+        // the new name is already taken
+        let mut taken = role("writer", &["projects:write"]);
+        taken.renamed_from = Some("editor".to_string());
+        let target = PolicyDocument::new(vec![taken]);
+        assert!(current.renames(&target).is_empty());
+    }
+
+    #[test]
+    fn renamed_parents_are_followed() {
+        let mut editor = role("editor", &["projects:write"]);
+        editor.parents.push(key("viewer"));
+        let current = PolicyDocument::new(vec![editor.clone(), role("viewer", &["projects:read"])]);
+
+        let mut reader = role("reader", &["projects:read"]);
+        reader.renamed_from = Some("viewer".to_string());
+        editor.parents = vec![key("reader")];
+        let target = PolicyDocument::new(vec![editor, reader]);
+
+        let diff = current.diff(&target);
+        assert!(diff.parents_added.is_empty());
+        assert!(diff.parents_removed.is_empty());
+    }
+
+    #[test]
+    fn csv_round_trips() {
+        let mut editor = role("editor, lead", &["projects:write"]);
+        editor.denied_permissions.push("projects:delete".to_string());
+        editor
+            .conditions
+            .insert("projects:write".to_string(), r#"r.ctx.status == "open""#.to_string());
+        editor.parents.push(key("viewer"));
+        let document = PolicyDocument::new(vec![editor, role("viewer", &["projects:read"])]);
+
+        let parsed = PolicyDocument::from_csv(&document.to_csv()).unwrap();
+
+        assert_eq!(parsed.version, POLICY_DOCUMENT_VERSION);
+        assert!(document.diff(&parsed).is_empty());
+        assert_eq!(parsed.roles[0].name, "editor, lead");
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::field_access::{ProtectedField, ProtectedFields};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Project {
     pub id: String,
@@ -24,6 +26,19 @@ pub struct ProjectWithOwnerEmail {
     pub user_email: Option<String>,
 }
 
+// owners see their own email, anyone else needs to be a user manager
+impl ProtectedFields for ProjectWithOwnerEmail {
+    const PROTECTED_FIELDS: &'static [ProtectedField] = &[ProtectedField {
+        name: "user_email",
+        permission: "user-management:read",
+        owner_readable: true,
+    }];
+
+    fn owner_id(&self) -> Option<&str> {
+        self.user_id.as_deref()
+    }
+}
+
 impl Project {
     pub fn new(user_id: Option<String>, name: String, description: Option<String>) -> Self {
         Self {
@@ -5,17 +5,19 @@ pub struct Role {
     pub id: String,
     pub name: String,
     pub is_default: bool,
+    pub domain: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl Role {
-    pub fn new(id: String, name: String, is_default: bool) -> Self {
+    pub fn new(id: String, name: String, is_default: bool, domain: String) -> Self {
         Self {
             id,
             name,
             is_default,
+            domain,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -29,6 +31,26 @@ impl Role {
     }
 }
 
+// a role together with the domain the user holds it in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignedRole {
+    #[serde(flatten)]
+    pub role: Role,
+    pub assigned_domain: String,
+    #[serde(default)]
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl AssignedRole {
+    // time-bound grants are checked on every use, cached roles may outlive them
+    pub fn is_active_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.starts_at.is_none_or(|starts_at| starts_at <= now)
+            && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoleCount {
     pub total_items: Option<i64>,
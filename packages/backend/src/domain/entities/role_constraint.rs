@@ -0,0 +1,63 @@
+use serde::Serialize;
+
+/// Roles no user may hold more than one of in the same domain, directly or inherited.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleConstraint {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    // `*` applies in every domain
+    pub domain: String,
+    pub role_ids: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RoleConstraint {
+    pub fn new(name: String, description: Option<String>, domain: String, role_ids: Vec<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            description,
+            domain,
+            role_ids,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn update(&mut self, name: String, description: Option<String>, role_ids: Vec<String>) {
+        self.name = name;
+        self.description = description;
+        self.role_ids = role_ids;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    pub fn applies_in(&self, domain: &str) -> bool {
+        self.domain == "*" || self.domain == domain
+    }
+}
+
+// a user, or a role through what it inherits, holding several roles of one constraint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RoleConstraintViolation {
+    pub constraint_id: String,
+    pub constraint_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role_id: Option<String>,
+    pub domain: String,
+    pub role_ids: Vec<String>,
+    pub role_names: Vec<String>,
+}
+
+impl RoleConstraintViolation {
+    // the same conflict, whatever domain it was found in
+    pub fn is_same(&self, other: &RoleConstraintViolation) -> bool {
+        self.constraint_id == other.constraint_id
+            && self.user_id == other.user_id
+            && self.role_id == other.role_id
+            && self.role_ids == other.role_ids
+    }
+}
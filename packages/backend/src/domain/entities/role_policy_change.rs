@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const POLICY_CHANGE_CREATED: &str = "created";
+pub const POLICY_CHANGE_UPDATED: &str = "updated";
+pub const POLICY_CHANGE_DELETED: &str = "deleted";
+pub const POLICY_CHANGE_PARENT_ADDED: &str = "parent_added";
+pub const POLICY_CHANGE_PARENT_REMOVED: &str = "parent_removed";
+pub const POLICY_CHANGE_IMPORTED: &str = "imported";
+pub const POLICY_CHANGE_ROLLED_BACK: &str = "rolled_back";
+pub const POLICY_CHANGE_RECONCILED: &str = "reconciled";
+pub const POLICY_CHANGE_TEMPLATE_SYNCED: &str = "template_synced";
+
+// what a role grants directly: its allowed and denied permissions, the conditions some of them
+// carry and the roles it inherits
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RolePolicySnapshot {
+    pub permissions: Vec<String>,
+    pub denied_permissions: Vec<String>,
+    pub parents: Vec<String>,
+    pub conditions: BTreeMap<String, String>,
+}
+
+impl RolePolicySnapshot {
+    pub fn new(
+        mut permissions: Vec<String>,
+        mut denied_permissions: Vec<String>,
+        mut parents: Vec<String>,
+        conditions: BTreeMap<String, String>,
+    ) -> Self {
+        for items in [&mut permissions, &mut denied_permissions, &mut parents] {
+            items.sort();
+            items.dedup();
+        }
+
+        Self {
+            permissions,
+            denied_permissions,
+            parents,
+            conditions,
+        }
+    }
+}
+
+/// One versioned change to a role's policies, `before` is empty for new roles
+/// and `after` for deleted ones.
+#[derive(Clone, Debug, Serialize)]
+pub struct RolePolicyChange {
+    pub id: String,
+    pub role_id: String,
+    pub role_name: String,
+    pub domain: String,
+    // assigned on insert, counting up per role
+    pub version: i32,
+    pub action: String,
+    pub actor_id: Option<String>,
+    pub before: Option<RolePolicySnapshot>,
+    pub after: Option<RolePolicySnapshot>,
+    pub rolled_back_to: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RolePolicyChange {
+    pub fn new(
+        role_id: String,
+        role_name: String,
+        domain: String,
+        action: &str,
+        actor_id: Option<String>,
+        before: Option<RolePolicySnapshot>,
+        after: Option<RolePolicySnapshot>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            role_id,
+            role_name,
+            domain,
+            version: 0,
+            action: action.to_string(),
+            actor_id,
+            before,
+            after,
+            rolled_back_to: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.before == self.after
+    }
+}
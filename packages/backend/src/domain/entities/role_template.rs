@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+/// A built-in role that can be instantiated in any domain. Bump `version` whenever the
+/// permissions change so roles created from an earlier version show up as outdated.
+#[derive(Clone, Debug, Serialize)]
+pub struct RoleTemplate {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub version: i32,
+    pub permissions: &'static [&'static str],
+}
+
+pub const ROLE_TEMPLATES: &[RoleTemplate] = &[
+    RoleTemplate {
+        key: "viewer",
+        name: "Viewer",
+        description: "Read-only access to projects",
+        version: 1,
+        permissions: &["projects:read"],
+    },
+    RoleTemplate {
+        key: "editor",
+        name: "Editor",
+        description: "Create and update projects",
+        version: 1,
+        permissions: &["projects:read", "projects:write"],
+    },
+    RoleTemplate {
+        key: "project-admin",
+        name: "Project admin",
+        description: "Full control over projects, including deleting them and changing owners",
+        version: 1,
+        permissions: &[
+            "projects:read",
+            "projects:write",
+            "projects:delete",
+            "projects:transfer",
+        ],
+    },
+    RoleTemplate {
+        key: "auditor",
+        name: "Auditor",
+        description: "Read-only access to projects, users, roles and access settings",
+        version: 1,
+        permissions: &[
+            "projects:read",
+            "user-management:read",
+            "role-management:read",
+            "permission-management:read",
+            "saml-management:read",
+        ],
+    },
+];
+
+impl RoleTemplate {
+    pub fn find(key: &str) -> Option<&'static RoleTemplate> {
+        ROLE_TEMPLATES.iter().find(|template| template.key == key)
+    }
+
+    pub fn permissions(&self) -> Vec<String> {
+        self.permissions.iter().map(|permission| permission.to_string()).collect()
+    }
+}
+
+// a role created from a template and the template permissions it was last synced with
+#[derive(Clone, Debug, Serialize)]
+pub struct RoleTemplateLink {
+    pub role_id: String,
+    pub template_key: String,
+    pub template_version: i32,
+    pub permissions: Vec<String>,
+    pub synced_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RoleTemplateLink {
+    pub fn new(role_id: String, template: &RoleTemplate) -> Self {
+        Self {
+            role_id,
+            template_key: template.key.to_string(),
+            template_version: template.version,
+            permissions: template.permissions(),
+            synced_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn is_outdated(&self, template: &RoleTemplate) -> bool {
+        self.template_version < template.version
+    }
+}
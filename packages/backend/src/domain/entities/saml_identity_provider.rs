@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamlIdentityProvider {
+    pub id: String,
+    pub slug: String,
+    pub name: String,
+    pub entity_id: String,
+    pub sso_url: String,
+    pub x509_cert: String,
+    pub email_attribute: Option<String>,
+    pub name_attribute: Option<String>,
+    pub allow_idp_initiated: bool,
+    pub is_active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl SamlIdentityProvider {
+    pub fn new(slug: String, name: String, entity_id: String, sso_url: String, x509_cert: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            slug,
+            name,
+            entity_id,
+            sso_url,
+            x509_cert,
+            email_attribute: None,
+            name_attribute: None,
+            allow_idp_initiated: false,
+            is_active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    pub fn update(&mut self, changes: SamlIdentityProvider) {
+        self.slug = changes.slug;
+        self.name = changes.name;
+        self.entity_id = changes.entity_id;
+        self.sso_url = changes.sso_url;
+        self.x509_cert = changes.x509_cert;
+        self.email_attribute = changes.email_attribute;
+        self.name_attribute = changes.name_attribute;
+        self.allow_idp_initiated = changes.allow_idp_initiated;
+        self.is_active = changes.is_active;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    // provider_user_id stored in user_oauth_providers, NameIDs are only unique per IdP
+    pub fn provider_user_id(&self, name_id: &str) -> String {
+        format!("{}:{}", self.id, name_id)
+    }
+}
@@ -1,7 +1,35 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{role::Role, user_oauth_provider::UserOauthProvider};
+use super::{
+    field_access::{ProtectedField, ProtectedFields},
+    role::AssignedRole,
+    user_oauth_provider::UserOauthProvider,
+};
+
+// account state is only shown to the user themselves and to user managers
+const USER_ACCOUNT_FIELDS: &[ProtectedField] = &[
+    ProtectedField {
+        name: "is_active",
+        permission: "user-management:read",
+        owner_readable: true,
+    },
+    ProtectedField {
+        name: "created_at",
+        permission: "user-management:read",
+        owner_readable: true,
+    },
+    ProtectedField {
+        name: "updated_at",
+        permission: "user-management:read",
+        owner_readable: true,
+    },
+    ProtectedField {
+        name: "deleted_at",
+        permission: "user-management:read",
+        owner_readable: true,
+    },
+];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct User {
@@ -61,17 +89,25 @@ impl User {
     }
 }
 
+impl ProtectedFields for User {
+    const PROTECTED_FIELDS: &'static [ProtectedField] = USER_ACCOUNT_FIELDS;
+
+    fn owner_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UserFull {
     #[serde(flatten)]
     pub user: User,
 
     pub oauth_provider: UserOauthProvider,
-    pub roles: Vec<Role>,
+    pub roles: Vec<AssignedRole>,
 }
 
 impl UserFull {
-    pub fn new(user: User, oauth_provider: UserOauthProvider, roles: Vec<Role>) -> Self {
+    pub fn new(user: User, oauth_provider: UserOauthProvider, roles: Vec<AssignedRole>) -> Self {
         Self {
             user,
             oauth_provider,
@@ -79,3 +115,12 @@ impl UserFull {
         }
     }
 }
+
+// the user is flattened, so its fields are protected at the top level
+impl ProtectedFields for UserFull {
+    const PROTECTED_FIELDS: &'static [ProtectedField] = USER_ACCOUNT_FIELDS;
+
+    fn owner_id(&self) -> Option<&str> {
+        Some(&self.user.id)
+    }
+}
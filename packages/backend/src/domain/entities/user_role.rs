@@ -4,17 +4,72 @@ use serde::Serialize;
 pub struct UserRole {
     pub user_id: String,
     pub role_id: String,
+    pub domain: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    // the grant only counts inside this window, open ends are unbounded
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl UserRole {
-    pub fn new(user_id: String, role_id: String) -> Self {
+    pub fn new(user_id: String, role_id: String, domain: String) -> Self {
         Self {
             user_id,
             role_id,
+            domain,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            starts_at: None,
+            expires_at: None,
+        }
+    }
+
+    pub fn with_validity(
+        mut self,
+        starts_at: Option<chrono::DateTime<chrono::Utc>>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Self {
+        self.starts_at = starts_at;
+        self.expires_at = expires_at;
+        self
+    }
+}
+
+// a user holding a role, as listed on the role
+#[derive(Clone, Debug, Serialize)]
+pub struct RoleMember {
+    pub user_id: String,
+    pub email: String,
+    pub fullname: Option<String>,
+    pub domain: String,
+    pub assigned_at: chrono::DateTime<chrono::Utc>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// a time-bound grant removed once it expired
+#[derive(Clone, Debug, Serialize)]
+pub struct UserRoleExpiration {
+    pub id: String,
+    pub user_id: String,
+    pub role_id: String,
+    pub domain: String,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub expired_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl UserRoleExpiration {
+    pub fn new(user_role: &UserRole, expires_at: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_role.user_id.clone(),
+            role_id: user_role.role_id.clone(),
+            domain: user_role.domain.clone(),
+            starts_at: user_role.starts_at,
+            expires_at,
+            expired_at: chrono::Utc::now(),
         }
     }
 }
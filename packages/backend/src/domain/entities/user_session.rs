@@ -9,6 +9,9 @@ pub struct UserSession {
     pub refresh_token: String,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    // last interactive authentication of the session holding refresh_token, token refreshes do
+    // not move it
+    pub authenticated_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl UserSession {
@@ -25,6 +28,7 @@ impl UserSession {
             refresh_token,
             expires_at,
             created_at: chrono::Utc::now(),
+            authenticated_at: chrono::Utc::now(),
         }
     }
 
@@ -38,4 +42,12 @@ impl UserSession {
         self.refresh_token = refresh_token;
         self.expires_at = expires_at;
     }
+
+    pub fn mark_authenticated(&mut self) {
+        self.authenticated_at = chrono::Utc::now();
+    }
+
+    pub fn is_recently_authenticated(&self, window_secs: i64) -> bool {
+        self.authenticated_at + chrono::Duration::seconds(window_secs) >= chrono::Utc::now()
+    }
 }
@@ -0,0 +1,49 @@
+use crate::{
+    domain::entities::access_request::{
+        AccessRequest, AccessRequestDecision, AccessRequestRevocation,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait AccessRequestRepository {
+    async fn find_by_id(&self, id: &str) -> Result<AccessRequest, AppError>;
+    async fn find_by_requester(&self, requester_id: &str) -> Result<Vec<AccessRequest>, AppError>;
+    // requests of one domain unless it is `*`, optionally with a given status
+    async fn find_by_domain(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessRequest>, AppError>;
+    // pending or approved requests of the user for the same role or permission
+    async fn has_open_request(&self, entity: &AccessRequest) -> Result<bool, AppError>;
+    // approved requests whose grant ran out
+    async fn find_expired(&self, limit: i64) -> Result<Vec<AccessRequest>, AppError>;
+    async fn find_decisions(&self, request_id: &str) -> Result<Vec<AccessRequestDecision>, AppError>;
+    async fn find_revocations(
+        &self,
+        request_id: &str,
+    ) -> Result<Vec<AccessRequestRevocation>, AppError>;
+    async fn create(&self, entity: &AccessRequest) -> Result<AccessRequest, AppError>;
+    // locks the request until the transaction ends so it is decided only once
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<AccessRequest, AppError>;
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequest,
+    ) -> Result<AccessRequest, AppError>;
+    async fn tx_create_decision(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequestDecision,
+    ) -> Result<(), AppError>;
+    async fn tx_create_revocation(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequestRevocation,
+    ) -> Result<(), AppError>;
+}
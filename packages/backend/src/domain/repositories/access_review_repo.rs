@@ -0,0 +1,59 @@
+use crate::{
+    domain::entities::access_review::{AccessReview, AccessReviewItem},
+    infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait AccessReviewRepository {
+    async fn find_by_id(&self, id: &str) -> Result<AccessReview, AppError>;
+    // campaigns of one domain unless it is `*`, optionally with a given status
+    async fn find_by_domain(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessReview>, AppError>;
+    // open campaigns the user reviews
+    async fn find_open_by_reviewer(&self, reviewer_id: &str) -> Result<Vec<AccessReview>, AppError>;
+    // open campaigns past their deadline
+    async fn find_overdue(&self, limit: i64) -> Result<Vec<AccessReview>, AppError>;
+    async fn find_items(&self, review_id: &str) -> Result<Vec<AccessReviewItem>, AppError>;
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<AccessReview, AppError>;
+    // one item per unexpired assignment of the campaign's roles held by a live user, returns how
+    // many were added
+    async fn tx_create_items(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<u64, AppError>;
+    // locks the campaign until the transaction ends so it is completed only once
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<AccessReview, AppError>;
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<AccessReview, AppError>;
+    async fn tx_lock_item(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        review_id: &str,
+        id: &str,
+    ) -> Result<AccessReviewItem, AppError>;
+    async fn tx_lock_pending_items(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        review_id: &str,
+    ) -> Result<Vec<AccessReviewItem>, AppError>;
+    async fn tx_update_item(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReviewItem,
+    ) -> Result<AccessReviewItem, AppError>;
+}
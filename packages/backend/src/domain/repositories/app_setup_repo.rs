@@ -0,0 +1,11 @@
+use crate::{domain::entities::app_setup::AppSetup, infra::errors::app_error::AppError};
+
+#[async_trait::async_trait]
+pub trait AppSetupRepository {
+    async fn is_completed(&self) -> Result<bool, AppError>;
+    async fn tx_complete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        super_admin_id: &str,
+    ) -> Result<AppSetup, AppError>;
+}
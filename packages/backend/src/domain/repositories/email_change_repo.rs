@@ -0,0 +1,32 @@
+use crate::{
+    domain::entities::email_change_request::EmailChangeRequest,
+    infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait EmailChangeRepository {
+    async fn find_pending_by_user_id(&self, user_id: &str) -> Result<EmailChangeRequest, AppError>;
+    async fn find_by_confirm_token_hash(&self, token_hash: &str) -> Result<EmailChangeRequest, AppError>;
+    async fn find_by_revert_token_hash(&self, token_hash: &str) -> Result<EmailChangeRequest, AppError>;
+    async fn tx_lock_by_revert_token_hash(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        token_hash: &str,
+    ) -> Result<EmailChangeRequest, AppError>;
+    async fn tx_replace_pending(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &EmailChangeRequest,
+    ) -> Result<EmailChangeRequest, AppError>;
+    async fn tx_update_status(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &EmailChangeRequest,
+    ) -> Result<(), AppError>;
+    async fn tx_cancel_revertable(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        except_id: &str,
+    ) -> Result<(), AppError>;
+}
@@ -0,0 +1,15 @@
+use crate::{domain::entities::invite::Invite, infra::errors::app_error::AppError};
+
+#[async_trait::async_trait]
+pub trait InviteRepository {
+    async fn find_all(&self) -> Result<Vec<Invite>, AppError>;
+    async fn find_pending_by_email(&self, email: &str) -> Result<Invite, AppError>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Invite, AppError>;
+    async fn create(&self, entity: &Invite) -> Result<Invite, AppError>;
+    async fn tx_accept(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &Invite,
+    ) -> Result<(), AppError>;
+    async fn delete(&self, id: &str) -> Result<(), AppError>;
+}
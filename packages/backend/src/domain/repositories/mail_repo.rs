@@ -0,0 +1,6 @@
+use crate::infra::errors::app_error::AppError;
+
+#[async_trait::async_trait]
+pub trait MailRepository {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError>;
+}
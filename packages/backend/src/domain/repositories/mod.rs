@@ -1,7 +1,19 @@
+pub mod access_request_repo;
+pub mod access_review_repo;
+pub mod app_setup_repo;
+pub mod email_change_repo;
+pub mod invite_repo;
+pub mod mail_repo;
 pub mod oauth_provider_repo;
 pub mod permission_repo;
+pub mod policy_repo;
 pub mod redis_repo;
+pub mod role_constraint_repo;
+pub mod role_policy_change_repo;
 pub mod role_repo;
+pub mod role_template_repo;
+pub mod saml_provider_repo;
 pub mod user_repo;
+pub mod user_role_repo;
 pub mod user_session_repo;
 pub mod project_repo;
@@ -0,0 +1,15 @@
+use crate::{domain::entities::permission::Permission, infra::errors::app_error::AppError};
+
+#[async_trait::async_trait]
+pub trait PermissionRepository {
+    async fn find_all(&self) -> Result<Vec<Permission>, AppError>;
+    async fn find_by_id(&self, id: &str) -> Result<Permission, AppError>;
+    async fn exists(&self, name: &str, action: &str) -> Result<bool, AppError>;
+    // `resource:action` keys matching no registered permission, `*` matches any part
+    async fn find_unknown(&self, keys: &[String]) -> Result<Vec<String>, AppError>;
+    // whether any role policy still grants or denies the permission
+    async fn is_in_use(&self, name: &str, action: &str) -> Result<bool, AppError>;
+    async fn create(&self, entity: &Permission) -> Result<Permission, AppError>;
+    async fn update(&self, entity: &Permission) -> Result<Permission, AppError>;
+    async fn delete(&self, id: &str) -> Result<(), AppError>;
+}
@@ -0,0 +1,33 @@
+use crate::infra::errors::app_error::AppError;
+
+// casbin rules written directly, for changes that have to commit together with other tables
+#[async_trait::async_trait]
+pub trait PolicyRepository {
+    // drops every policy and inheritance link of the roles and writes the given ones instead,
+    // links from other roles into removed roles go as well
+    async fn tx_replace_role_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_ids: &[String],
+        removed_role_ids: &[String],
+        policies: &[Vec<String>],
+        groupings: &[Vec<String>],
+    ) -> Result<(), AppError>;
+
+    // `p` and `g` rules as stored, without trailing empty fields like the adapter loads them
+    async fn find_all_rules(&self) -> Result<Vec<(String, Vec<String>)>, AppError>;
+
+    async fn tx_add_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ptype: &str,
+        rules: &[Vec<String>],
+    ) -> Result<(), AppError>;
+
+    async fn tx_remove_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ptype: &str,
+        rules: &[Vec<String>],
+    ) -> Result<(), AppError>;
+}
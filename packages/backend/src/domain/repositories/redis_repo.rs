@@ -10,6 +10,13 @@ pub trait RedisRepository {
         value: &str,
         expiry: u64,
     ) -> Result<(), AppError>;
+    async fn set_value_if_absent_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        expiry: u64,
+    ) -> Result<bool, AppError>;
+    async fn increment_with_expiry(&self, key: &str, expiry: i64) -> Result<i64, AppError>;
     async fn delete_value(&self, key: &str) -> Result<(), AppError>;
     async fn set_expiry(&self, key: &str, expiry: i64) -> Result<(), AppError>;
 }
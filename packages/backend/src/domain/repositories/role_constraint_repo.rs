@@ -0,0 +1,13 @@
+use crate::{
+    domain::entities::role_constraint::RoleConstraint, infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait RoleConstraintRepository {
+    // constraints of the domain and the global ones, every constraint for `*`
+    async fn find_all(&self, domain: &str) -> Result<Vec<RoleConstraint>, AppError>;
+    async fn find_by_id(&self, id: &str) -> Result<RoleConstraint, AppError>;
+    async fn create(&self, entity: &RoleConstraint) -> Result<RoleConstraint, AppError>;
+    async fn update(&self, entity: &RoleConstraint) -> Result<RoleConstraint, AppError>;
+    async fn delete(&self, id: &str) -> Result<(), AppError>;
+}
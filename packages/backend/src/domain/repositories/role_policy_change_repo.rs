@@ -0,0 +1,22 @@
+use crate::{
+    domain::entities::role_policy_change::RolePolicyChange, infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait RolePolicyChangeRepository {
+    // newest first
+    async fn paginate_by_role(
+        &self,
+        role_id: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<RolePolicyChange>, i64), AppError>;
+    async fn find_by_version(&self, role_id: &str, version: i32) -> Result<RolePolicyChange, AppError>;
+    // the version is the role's next one, assigned by the insert
+    async fn create(&self, entity: &RolePolicyChange) -> Result<RolePolicyChange, AppError>;
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &RolePolicyChange,
+    ) -> Result<RolePolicyChange, AppError>;
+}
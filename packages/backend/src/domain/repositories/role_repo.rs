@@ -1,12 +1,22 @@
-use crate::{domain::entities::role::Role, infra::errors::app_error::AppError};
+use crate::{
+    domain::entities::role::{AssignedRole, Role},
+    infra::errors::app_error::AppError,
+};
 
 #[async_trait::async_trait]
 pub trait RoleRepository {
-    async fn paginate(&self, page: i64, limit: i64) -> Result<(Vec<Role>, i64), AppError>;
-    async fn find_all(&self) -> Result<Vec<Role>, AppError>;
+    // roles visible in a domain are the global ones plus the domain's own
+    async fn paginate(
+        &self,
+        domain: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<Role>, i64), AppError>;
+    async fn find_all(&self, domain: &str) -> Result<Vec<Role>, AppError>;
+    async fn find_all_across_domains(&self) -> Result<Vec<Role>, AppError>;
     async fn find_by_id(&self, id: &str) -> Result<Role, AppError>;
     async fn find_default(&self) -> Result<Role, AppError>;
-    async fn find_by_name(&self, role_name: &str) -> Result<Role, AppError>;
+    async fn find_by_name(&self, role_name: &str, domain: &str) -> Result<Role, AppError>;
     async fn create(&self, entity: Role) -> Result<Role, AppError>;
     async fn tx_create(
         &self,
@@ -15,6 +25,23 @@ pub trait RoleRepository {
     ) -> Result<Role, AppError>;
     async fn update(&self, id: &str, entity: Role) -> Result<(), AppError>;
     async fn delete(&self, id: &str) -> Result<(), AppError>;
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &Role,
+    ) -> Result<(), AppError>;
+    async fn tx_delete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<(), AppError>;
+    // deleted roles keep their name, it is moved aside so a live role can take it again
+    async fn tx_release_deleted_name(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_name: &str,
+        domain: &str,
+    ) -> Result<(), AppError>;
 
-    async fn get_roles_by_user_id(&self, user_id: &str) -> Result<Vec<Role>, AppError>;
+    async fn get_roles_by_user_id(&self, user_id: &str) -> Result<Vec<AssignedRole>, AppError>;
 }
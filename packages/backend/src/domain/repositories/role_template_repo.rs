@@ -0,0 +1,16 @@
+use crate::{
+    domain::entities::role_template::RoleTemplateLink, infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait RoleTemplateRepository {
+    // links of roles that are not deleted
+    async fn find_by_template(&self, template_key: &str) -> Result<Vec<RoleTemplateLink>, AppError>;
+    async fn find_all(&self) -> Result<Vec<RoleTemplateLink>, AppError>;
+    // creates the link or replaces the one the role already has
+    async fn tx_save(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &RoleTemplateLink,
+    ) -> Result<(), AppError>;
+}
@@ -0,0 +1,14 @@
+use crate::{
+    domain::entities::saml_identity_provider::SamlIdentityProvider,
+    infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait SamlProviderRepository {
+    async fn find_all(&self) -> Result<Vec<SamlIdentityProvider>, AppError>;
+    async fn find_by_id(&self, id: &str) -> Result<SamlIdentityProvider, AppError>;
+    async fn find_active_by_slug(&self, slug: &str) -> Result<SamlIdentityProvider, AppError>;
+    async fn create(&self, entity: SamlIdentityProvider) -> Result<SamlIdentityProvider, AppError>;
+    async fn update(&self, entity: &SamlIdentityProvider) -> Result<SamlIdentityProvider, AppError>;
+    async fn delete(&self, id: &str) -> Result<(), AppError>;
+}
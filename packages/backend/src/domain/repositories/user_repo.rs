@@ -8,6 +8,11 @@ pub trait UserRepository {
     async fn find_by_email(&self, email: &str) -> Result<User, AppError>;
     async fn find_by_id(&self, id: &str) -> Result<User, AppError>;
     async fn find_provider_by_user_id(&self, user_id: &str) -> Result<UserOauthProvider, AppError>;
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<User, AppError>;
     async fn tx_create(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -25,4 +30,9 @@ pub trait UserRepository {
         user_oauth_provider: &UserOauthProvider,
         user_role: &UserRole,
     ) -> Result<(User, UserOauthProvider, UserRole), AppError>;
+    async fn tx_add_role(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_role: &UserRole,
+    ) -> Result<UserRole, AppError>;
 }
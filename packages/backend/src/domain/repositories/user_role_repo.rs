@@ -0,0 +1,60 @@
+use crate::{
+    domain::entities::user_role::{RoleMember, UserRole, UserRoleExpiration},
+    infra::errors::app_error::AppError,
+};
+
+#[async_trait::async_trait]
+pub trait UserRoleRepository {
+    // members of a role, limited to one domain unless it is `*`
+    async fn paginate_members(
+        &self,
+        role_id: &str,
+        domain: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<RoleMember>, i64), AppError>;
+    // active holders of any of the roles, in the domain or globally
+    async fn find_members_by_role_ids(
+        &self,
+        role_ids: &[String],
+        domain: &str,
+    ) -> Result<Vec<RoleMember>, AppError>;
+    // every grant of a live user and role that has not expired, including ones not started yet
+    async fn find_unexpired(&self) -> Result<Vec<UserRole>, AppError>;
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &UserRole,
+    ) -> Result<UserRole, AppError>;
+    // serializes grants to the user until the transaction ends and returns the grants they hold
+    // that have not expired
+    async fn tx_lock_by_user_id(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+    ) -> Result<Vec<UserRole>, AppError>;
+    // locks the role's assignments until the transaction ends and counts active holders
+    async fn tx_lock_holders(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_id: &str,
+    ) -> Result<i64, AppError>;
+    async fn tx_delete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        role_id: &str,
+        domain: &str,
+    ) -> Result<bool, AppError>;
+    // removes up to `limit` grants past their expiry, skipping rows locked by other sweepers
+    async fn tx_delete_expired(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        limit: i64,
+    ) -> Result<Vec<UserRole>, AppError>;
+    async fn tx_record_expiration(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &UserRoleExpiration,
+    ) -> Result<(), AppError>;
+}
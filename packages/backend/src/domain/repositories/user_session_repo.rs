@@ -6,5 +6,6 @@ pub trait UserSessionRepository {
     async fn find_by_refresh_token(&self, refresh_token: &str) -> Result<UserSession, AppError>;
     async fn create(&self, entity: UserSession) -> Result<UserSession, AppError>;
     async fn update_token(&self, session: &UserSession) -> Result<(), AppError>;
+    async fn update_authenticated_at(&self, session: &UserSession) -> Result<(), AppError>;
     async fn delete_by_id(&self, session_id: &str) -> Result<(), AppError>;
 }
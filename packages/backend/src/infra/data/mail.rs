@@ -0,0 +1,14 @@
+use lettre::{AsyncSmtpTransport, Tokio1Executor};
+
+// without SMTP_URL mails are only logged, which is enough for local development
+pub fn get_mail_transport(smtp_url: &str) -> Option<AsyncSmtpTransport<Tokio1Executor>> {
+    if smtp_url.is_empty() {
+        return None;
+    }
+
+    let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(smtp_url)
+        .unwrap()
+        .build();
+
+    Some(transport)
+}
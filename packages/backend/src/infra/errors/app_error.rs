@@ -90,6 +90,27 @@ pub enum AppError {
 
     #[error("Access denied. You do not have permission to perform this action.")]
     Forbidden,
+
+    #[error("You do not have permission to set {0}")]
+    ProtectedFieldWrite(String),
+
+    #[error("Segregation of duties violated: {0}")]
+    RoleConstraintViolation(String),
+
+    #[error("SAML response rejected: {0}")]
+    SamlValidationError(String),
+
+    #[error("Recent authentication required")]
+    ReauthenticationRequired,
+
+    #[error("Registration not allowed: {0}")]
+    RegistrationNotAllowed(String),
+
+    #[error("Too many requests")]
+    TooManyRequests,
+
+    #[error("Initial setup has already been completed")]
+    SetupCompleted,
 }
 
 impl IntoResponse for AppError {
@@ -187,6 +208,41 @@ impl IntoResponse for AppError {
                 "forbidden".to_string(),
                 "Access denied. You do not have permission to perform this action.".to_string(),
             ),
+            AppError::ProtectedFieldWrite(value) => (
+                StatusCode::FORBIDDEN,
+                "protected_field".to_string(),
+                format!("You do not have permission to set {}", value),
+            ),
+            AppError::RoleConstraintViolation(value) => (
+                StatusCode::CONFLICT,
+                "role_constraint_violation".to_string(),
+                format!("Segregation of duties violated: {}", value),
+            ),
+            AppError::SamlValidationError(value) => (
+                StatusCode::UNAUTHORIZED,
+                "saml_validation_failed".to_string(),
+                format!("SAML response rejected: {}", value),
+            ),
+            AppError::ReauthenticationRequired => (
+                StatusCode::FORBIDDEN,
+                "reauthentication_required".to_string(),
+                "Please confirm your identity again to perform this action.".to_string(),
+            ),
+            AppError::RegistrationNotAllowed(value) => (
+                StatusCode::FORBIDDEN,
+                "registration_not_allowed".to_string(),
+                format!("Registration not allowed: {}", value),
+            ),
+            AppError::TooManyRequests => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "too_many_requests".to_string(),
+                "Too many attempts. Please try again later.".to_string(),
+            ),
+            AppError::SetupCompleted => (
+                StatusCode::GONE,
+                "setup_completed".to_string(),
+                "Initial setup has already been completed.".to_string(),
+            ),
             _ => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "internal_server_error".to_string(),
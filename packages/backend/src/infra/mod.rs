@@ -4,7 +4,10 @@ pub mod data;
 pub mod errors;
 pub mod graceful;
 pub mod oauth2;
+pub mod policy_snapshot;
 pub mod rbac;
+pub mod rbac_sync;
 pub mod repositories;
+pub mod saml;
 pub mod server;
 pub mod utils;
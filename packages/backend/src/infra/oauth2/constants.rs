@@ -1,2 +1,3 @@
 pub const GOOGLE_PROVIDER: &str = "google";
 pub const EMAIL_PROVIDER: &str = "email";
+pub const SAML_PROVIDER: &str = "saml";
@@ -0,0 +1,410 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        PoisonError, RwLock,
+    },
+};
+
+use casbin::{CoreApi, DefaultModel, Enforcer, MemoryAdapter, MgmtApi};
+
+use crate::domain::entities::access_context::AccessContext;
+
+use super::rbac::{Rbac, CONDITION_ALWAYS};
+
+// entries beyond this are evaluated every time until the next snapshot
+const DECISION_CACHE_CAPACITY: usize = 50_000;
+
+// (role id, domain, object, action)
+type DecisionKey = (String, String, String, String);
+
+/// What evaluating a single held role gave.
+#[derive(Clone, Debug)]
+pub struct RoleDecision {
+    pub allowed: bool,
+    pub matched: Vec<Vec<String>>,
+}
+
+/// Read-only copy of the policy that access checks run against without taking the enforcer
+/// lock. A new snapshot replaces it as a whole after every policy change and takes over the
+/// cached decisions the change cannot have affected.
+pub struct PolicySnapshot {
+    pub enforcer: Enforcer,
+    pub generation: u64,
+    // objects to the actions of their conditional policies, decisions on these depend on the
+    // request context
+    conditional: HashMap<String, HashSet<String>>,
+    decisions: RwLock<HashMap<DecisionKey, RoleDecision>>,
+}
+
+impl PolicySnapshot {
+    pub async fn build(
+        model: &DefaultModel,
+        policies: Vec<Vec<String>>,
+        groupings: Vec<Vec<String>>,
+        previous: Option<&PolicySnapshot>,
+        metrics: &DecisionCacheMetrics,
+    ) -> Result<Self, casbin::Error> {
+        let mut conditional: HashMap<String, HashSet<String>> = HashMap::new();
+        for policy in policies
+            .iter()
+            .filter(|policy| policy.len() >= 6 && policy[5] != CONDITION_ALWAYS)
+        {
+            conditional
+                .entry(policy[2].clone())
+                .or_default()
+                .insert(policy[3].clone());
+        }
+
+        let mut enforcer = Enforcer::new(model.clone(), MemoryAdapter::default()).await?;
+        enforcer.get_mut_model().add_policies("p", "p", policies);
+        enforcer.get_mut_model().add_policies("g", "g", groupings);
+        Rbac::configure_enforcer(&mut enforcer)?;
+
+        let mut snapshot = Self {
+            enforcer,
+            generation: 0,
+            conditional,
+            decisions: RwLock::new(HashMap::new()),
+        };
+
+        if let Some(previous) = previous {
+            snapshot.generation = previous.generation + 1;
+
+            let affected = previous.affected_roles(&snapshot);
+            let previous_decisions = previous
+                .decisions
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+            let kept = previous_decisions
+                .iter()
+                .filter(|((role_id, _, object, action), _)| {
+                    !affected.contains(role_id) && snapshot.is_cacheable(object, action)
+                })
+                .map(|(key, decision)| (key.clone(), decision.clone()))
+                .collect::<HashMap<DecisionKey, RoleDecision>>();
+
+            metrics
+                .invalidated_total
+                .fetch_add((previous_decisions.len() - kept.len()) as u64, Ordering::Relaxed);
+            metrics.entries.store(kept.len() as u64, Ordering::Relaxed);
+            *snapshot.decisions.get_mut().unwrap_or_else(PoisonError::into_inner) = kept;
+        }
+        metrics.snapshots_total.fetch_add(1, Ordering::Relaxed);
+
+        Ok(snapshot)
+    }
+
+    // one held role evaluated against the snapshot, cached unless a condition could apply
+    pub fn decide(
+        &self,
+        role_id: &str,
+        domain: &str,
+        object: &str,
+        action: &str,
+        ctx: &AccessContext,
+        metrics: &DecisionCacheMetrics,
+    ) -> Result<RoleDecision, casbin::Error> {
+        if !self.is_cacheable(object, action) {
+            metrics.bypassed_total.fetch_add(1, Ordering::Relaxed);
+            return self.enforce(role_id, domain, object, action, ctx);
+        }
+
+        let key = (
+            role_id.to_string(),
+            domain.to_string(),
+            object.to_string(),
+            action.to_string(),
+        );
+        if let Some(decision) = self
+            .decisions
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            metrics.hits_total.fetch_add(1, Ordering::Relaxed);
+            return Ok(decision.clone());
+        }
+
+        metrics.misses_total.fetch_add(1, Ordering::Relaxed);
+        let decision = self.enforce(role_id, domain, object, action, ctx)?;
+
+        let mut decisions = self.decisions.write().unwrap_or_else(PoisonError::into_inner);
+        if decisions.len() < DECISION_CACHE_CAPACITY {
+            decisions.insert(key, decision.clone());
+            metrics.entries.store(decisions.len() as u64, Ordering::Relaxed);
+        }
+
+        Ok(decision)
+    }
+
+    fn enforce(
+        &self,
+        role_id: &str,
+        domain: &str,
+        object: &str,
+        action: &str,
+        ctx: &AccessContext,
+    ) -> Result<RoleDecision, casbin::Error> {
+        let (allowed, matched) = self.enforcer.enforce_ex((role_id, domain, object, action, ctx))?;
+
+        Ok(RoleDecision { allowed, matched })
+    }
+
+    fn is_cacheable(&self, object: &str, action: &str) -> bool {
+        [object, "*"].iter().all(|object| {
+            self.conditional
+                .get(*object)
+                .is_none_or(|actions| !actions.contains(action) && !actions.contains("*"))
+        })
+    }
+
+    // roles whose own policies or links differ between the snapshots, and every role inheriting
+    // from one of them in either
+    fn affected_roles(&self, next: &PolicySnapshot) -> HashSet<String> {
+        let changed = |before: Vec<Vec<String>>, after: Vec<Vec<String>>| {
+            let before = before.into_iter().collect::<HashSet<Vec<String>>>();
+            let after = after.into_iter().collect::<HashSet<Vec<String>>>();
+            before
+                .symmetric_difference(&after)
+                .filter_map(|rule| rule.first().cloned())
+                .collect::<Vec<String>>()
+        };
+
+        let mut affected = changed(self.enforcer.get_policy(), next.enforcer.get_policy())
+            .into_iter()
+            .chain(changed(
+                self.enforcer.get_grouping_policy(),
+                next.enforcer.get_grouping_policy(),
+            ))
+            .collect::<HashSet<String>>();
+
+        let mut children: HashMap<String, Vec<String>> = HashMap::new();
+        for rule in self
+            .enforcer
+            .get_grouping_policy()
+            .into_iter()
+            .chain(next.enforcer.get_grouping_policy())
+            .filter(|rule| rule.len() >= 2)
+        {
+            children.entry(rule[1].clone()).or_default().push(rule[0].clone());
+        }
+
+        let mut queue = affected.iter().cloned().collect::<VecDeque<String>>();
+        while let Some(role_id) = queue.pop_front() {
+            for child in children.get(&role_id).into_iter().flatten() {
+                if affected.insert(child.clone()) {
+                    queue.push_back(child.clone());
+                }
+            }
+        }
+
+        affected
+    }
+}
+
+/// Counters of the decision cache kept with the policy snapshots.
+#[derive(Debug, Default)]
+pub struct DecisionCacheMetrics {
+    pub hits_total: AtomicU64,
+    pub misses_total: AtomicU64,
+    pub bypassed_total: AtomicU64,
+    pub invalidated_total: AtomicU64,
+    pub snapshots_total: AtomicU64,
+    pub entries: AtomicU64,
+}
+
+impl DecisionCacheMetrics {
+    // Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let metrics = [
+            (
+                "casbin_decision_cache_hits_total",
+                "counter",
+                "Role decisions answered from the cache",
+                self.hits_total.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_decision_cache_misses_total",
+                "counter",
+                "Role decisions evaluated and added to the cache",
+                self.misses_total.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_decision_cache_bypassed_total",
+                "counter",
+                "Role decisions evaluated every time because a conditional policy could apply",
+                self.bypassed_total.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_decision_cache_invalidated_total",
+                "counter",
+                "Cached decisions dropped by policy changes",
+                self.invalidated_total.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_policy_snapshots_total",
+                "counter",
+                "Policy snapshots published",
+                self.snapshots_total.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_decision_cache_entries",
+                "gauge",
+                "Decisions cached for the current snapshot",
+                self.entries.load(Ordering::Relaxed),
+            ),
+        ];
+
+        metrics
+            .iter()
+            .map(|(name, kind, help, value)| {
+                format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    fn rules(rules: &[&[&str]]) -> Vec<Vec<String>> {
+        rules.iter().map(|fields| rule(fields)).collect()
+    }
+
+    fn policies() -> Vec<Vec<String>> {
+        rules(&[
+            &["viewer", "*", "projects", "read", "allow", "true"],
+            &["editor", "*", "projects", "write", "allow", "true"],
+            &["auditor", "*", "audit", "read", "allow", "true"],
+        ])
+    }
+
+    // admin inherits editor, which inherits viewer
+    fn groupings() -> Vec<Vec<String>> {
+        rules(&[&["editor", "viewer", "*"], &["admin", "editor", "*"]])
+    }
+
+    async fn snapshot(
+        policies: Vec<Vec<String>>,
+        groupings: Vec<Vec<String>>,
+        previous: Option<&PolicySnapshot>,
+        metrics: &DecisionCacheMetrics,
+    ) -> PolicySnapshot {
+        let model = DefaultModel::from_str(include_str!("../../etc/rbac_model.conf"))
+            .await
+            .unwrap();
+
+        PolicySnapshot::build(&model, policies, groupings, previous, metrics)
+            .await
+            .unwrap()
+    }
+
+    fn cached_roles(snapshot: &PolicySnapshot) -> HashSet<String> {
+        snapshot
+            .decisions
+            .read()
+            .unwrap()
+            .keys()
+            .map(|(role_id, ..)| role_id.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn policy_change_affects_the_role_and_everyone_inheriting_it() {
+        let metrics = DecisionCacheMetrics::default();
+        let before = snapshot(policies(), groupings(), None, &metrics).await;
+
+        let mut changed = policies();
+        changed.push(rule(&["viewer", "*", "reports", "read", "allow", "true"]));
+        let after = snapshot(changed, groupings(), None, &metrics).await;
+
+        assert_eq!(
+            before.affected_roles(&after),
+            HashSet::from(["viewer", "editor", "admin"].map(str::to_string))
+        );
+    }
+
+    #[tokio::test]
+    async fn link_change_affects_the_child_and_not_the_parent() {
+        let metrics = DecisionCacheMetrics::default();
+        let before = snapshot(policies(), groupings(), None, &metrics).await;
+
+        let mut changed = groupings();
+        changed.push(rule(&["editor", "auditor", "*"]));
+        let after = snapshot(policies(), changed, None, &metrics).await;
+
+        assert_eq!(
+            before.affected_roles(&after),
+            HashSet::from(["editor", "admin"].map(str::to_string))
+        );
+    }
+
+    #[tokio::test]
+    async fn removed_links_still_reach_former_children() {
+        let metrics = DecisionCacheMetrics::default();
+        let before = snapshot(policies(), groupings(), None, &metrics).await;
+
+        let mut changed = policies();
+        changed.retain(|policy| policy[0] != "editor");
+        let unlinked = rules(&[&["editor", "viewer", "*"]]);
+        let after = snapshot(changed, unlinked, None, &metrics).await;
+
+        // admin inherited editor before the change, so its decisions are stale too
+        assert!(before.affected_roles(&after).contains("admin"));
+    }
+
+    #[tokio::test]
+    async fn new_snapshot_keeps_only_unaffected_decisions() {
+        let metrics = DecisionCacheMetrics::default();
+        let ctx = AccessContext::default();
+        let before = snapshot(policies(), groupings(), None, &metrics).await;
+        for role_id in ["viewer", "editor", "admin", "auditor"] {
+            before.decide(role_id, "*", "projects", "read", &ctx, &metrics).unwrap();
+        }
+
+        let mut changed = policies();
+        changed.retain(|policy| policy[0] != "editor");
+        let after = snapshot(changed, groupings(), Some(&before), &metrics).await;
+
+        assert_eq!(after.generation, before.generation + 1);
+        assert_eq!(
+            cached_roles(&after),
+            HashSet::from(["viewer", "auditor"].map(str::to_string))
+        );
+        assert_eq!(metrics.invalidated_total.load(Ordering::Relaxed), 2);
+        assert_eq!(metrics.entries.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn decisions_are_cached_until_a_condition_could_apply() {
+        let metrics = DecisionCacheMetrics::default();
+        let ctx = AccessContext::new("alice", None);
+        let mut conditional = policies();
+        conditional.push(rule(&[
+            "editor",
+            "*",
+            "projects",
+            "delete",
+            "allow",
+            "r.ctx.owner_id == r.ctx.user_id",
+        ]));
+        let snapshot = snapshot(conditional, groupings(), None, &metrics).await;
+
+        for _ in 0..2 {
+            let decision = snapshot.decide("admin", "*", "projects", "write", &ctx, &metrics);
+            assert!(decision.unwrap().allowed);
+        }
+        assert_eq!(metrics.misses_total.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.hits_total.load(Ordering::Relaxed), 1);
+
+        snapshot.decide("admin", "*", "projects", "delete", &ctx, &metrics).unwrap();
+        assert_eq!(metrics.bypassed_total.load(Ordering::Relaxed), 1);
+        assert_eq!(cached_roles(&snapshot).len(), 1);
+    }
+}
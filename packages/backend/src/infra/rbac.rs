@@ -1,113 +1,1028 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::IpAddr,
+    sync::Arc,
+};
 
-use casbin::{CoreApi, Enforcer, MgmtApi, RbacApi};
+use arc_swap::ArcSwap;
+use casbin::{
+    function_map::{dynamic_to_str, key_match, OperatorFunction},
+    CoreApi, DefaultModel, Enforcer, EventData, MgmtApi,
+};
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::warn;
 
-use crate::domain::entities::role::Role;
+use crate::{
+    domain::entities::{
+        access_context::{AccessContext, ResourceAttributes, ResourceCheck},
+        access_decision::{
+            AccessDecision, AccessMatrix, AccessMatrixCell, AccessOutcome, ConsideredRole,
+            MatchedPolicy,
+        },
+        field_access::{ProtectedField, ProtectedFields, Redacted},
+        role::AssignedRole,
+        role_policy_change::RolePolicySnapshot,
+    },
+    infra::{
+        errors::app_error::AppError,
+        policy_snapshot::{DecisionCacheMetrics, PolicySnapshot},
+        rbac_sync::PolicySyncMetrics,
+    },
+};
 
+pub const EFFECT_ALLOW: &str = "allow";
+pub const EFFECT_DENY: &str = "deny";
+
+// roles, assignments and policies in this domain apply in every organization
+pub const GLOBAL_DOMAIN: &str = "*";
+
+// condition of policies that apply whatever the request context
+pub const CONDITION_ALWAYS: &str = "true";
+// condition of policies limited to resources the caller owns
+pub const CONDITION_OWNER: &str = "r.ctx.owner_id == r.ctx.user_id";
+
+// objects of grants on a single resource, `shared:<id>`, which are not in the permission registry
+pub const SHARED_OBJECT_PREFIX: &str = "shared:";
+
+// policy fields are stored in VARCHAR(128) columns
+const CONDITION_MAX_LEN: usize = 128;
+const CONDITION_CONTEXT_FIELDS: [&str; 6] =
+    ["user_id", "ip", "time", "owner_id", "organization", "status"];
+const CONDITION_FUNCTIONS: [&str; 2] = ["timeBetween", "ipInRange"];
+const CONDITION_OPERATORS: [&str; 11] =
+    ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "(", ")"];
+
+/// Rule changes committed to casbin_rule in a database transaction, applied to the enforcer after
+/// the commit so a rolled back transaction never leaves policies behind.
+#[derive(Clone, Debug, Default)]
+pub struct CommittedRules {
+    pub added_policies: Vec<Vec<String>>,
+    pub removed_policies: Vec<Vec<String>>,
+    pub added_groupings: Vec<Vec<String>>,
+    pub removed_groupings: Vec<Vec<String>>,
+}
+
+/// Proof that a route's `ResourceCheck` passed for one resource. Only `Rbac::authorize_resource`
+/// hands one out and repositories require it to write the resource, so a write cannot happen
+/// before the check.
+#[derive(Debug)]
+pub struct ResourceGrant {
+    object: &'static str,
+    resource_id: String,
+}
+
+impl ResourceGrant {
+    pub fn object(&self) -> &'static str {
+        self.object
+    }
+
+    pub fn resource_id(&self) -> &str {
+        &self.resource_id
+    }
+}
+
+// a matched policy line and the held role it was reached from
+type RoleMatch = (String, Vec<String>);
+
+// `enforcer` is where policies are changed and read for management, access checks only ever
+// use `snapshot`, which every change replaces before the write lock is released
 #[derive(Clone)]
 pub struct Rbac {
     pub enforcer: Arc<RwLock<Enforcer>>,
+    snapshot: Arc<ArcSwap<PolicySnapshot>>,
+    model: DefaultModel,
+    pub sync_metrics: Arc<PolicySyncMetrics>,
+    pub decision_metrics: Arc<DecisionCacheMetrics>,
 }
 
 impl Rbac {
-    pub fn new(enforcer: Arc<RwLock<Enforcer>>) -> Self {
-        Self { enforcer }
+    pub async fn new(enforcer: Enforcer, model: DefaultModel) -> Result<Self, casbin::Error> {
+        let decision_metrics = Arc::new(DecisionCacheMetrics::default());
+        let snapshot = PolicySnapshot::build(
+            &model,
+            enforcer.get_policy(),
+            enforcer.get_grouping_policy(),
+            None,
+            &decision_metrics,
+        )
+        .await?;
+
+        Ok(Self {
+            enforcer: Arc::new(RwLock::new(enforcer)),
+            snapshot: Arc::new(ArcSwap::from_pointee(snapshot)),
+            model,
+            sync_metrics: Arc::new(PolicySyncMetrics::default()),
+            decision_metrics,
+        })
     }
 
+    // role inheritance declared in the global domain applies in every organization
+    pub fn configure_enforcer(enforcer: &mut Enforcer) -> Result<(), casbin::Error> {
+        enforcer.get_role_manager().write().matching_fn(None, Some(key_match));
+        enforcer.build_role_links()?;
+        Self::register_condition_functions(enforcer);
+
+        Ok(())
+    }
+
+    // replaces the snapshot checks run against with the enforcer's current policy, callers hold
+    // the write lock so snapshots are published in the order the changes were made
+    pub async fn publish_snapshot(&self, enforcer: &Enforcer) -> Result<(), casbin::Error> {
+        let previous = self.snapshot.load_full();
+        let snapshot = PolicySnapshot::build(
+            &self.model,
+            enforcer.get_policy(),
+            enforcer.get_grouping_policy(),
+            Some(&previous),
+            &self.decision_metrics,
+        )
+        .await?;
+        self.snapshot.store(Arc::new(snapshot));
+
+        Ok(())
+    }
+
+    // every role held globally or in the domain is evaluated,
+    // an explicit deny on any of them overrides allows from the others
     pub async fn check_access(
         &self,
-        roles: &[Role],
+        roles: &[AssignedRole],
+        domain: &str,
         object: &str,
         action: &str,
+        ctx: &AccessContext,
     ) -> Result<bool, casbin::Error> {
-        let roles = roles.to_owned();
-        if roles.is_empty() {
-            return Ok(false);
+        let snapshot = self.snapshot.load();
+        let (outcome, _) = self.evaluate(&snapshot, roles, domain, object, action, ctx)?;
+
+        Ok(outcome == AccessOutcome::Allowed)
+    }
+
+    // the route's action on one resource, `ctx` carries the resource's attributes
+    pub async fn authorize_resource(
+        &self,
+        check: &ResourceCheck,
+        roles: &[AssignedRole],
+        resource_id: &str,
+        ctx: &AccessContext,
+    ) -> Result<Option<ResourceGrant>, casbin::Error> {
+        if !self
+            .check_access(roles, &check.domain, check.object, check.action, ctx)
+            .await?
+        {
+            return Ok(None);
         }
+        check.mark_checked();
 
-        let enforcer = self.enforcer.read().await;
+        Ok(Some(ResourceGrant {
+            object: check.object,
+            resource_id: resource_id.to_string(),
+        }))
+    }
 
-        // for now get the first one
-        let subject = match roles.first() {
-            Some(role) => role.id.clone(),
-            None => String::default(),
-        };
+    // `value` without the fields the caller may not read
+    pub async fn redact<T: ProtectedFields>(
+        &self,
+        roles: &[AssignedRole],
+        domain: &str,
+        value: T,
+        ctx: &AccessContext,
+    ) -> Result<Redacted<T>, casbin::Error> {
+        let owner_id = value.owner_id().unwrap_or_default();
+        let is_owner = !owner_id.is_empty() && owner_id == ctx.user_id;
+        let fields = T::PROTECTED_FIELDS
+            .iter()
+            .filter(|field| !(is_owner && field.owner_readable));
+        let hidden = self
+            .denied_fields(roles, domain, fields, owner_id, ctx)
+            .await?;
 
-        let has_access = enforcer.enforce((subject, object, action))?;
+        Ok(Redacted::new(value, hidden))
+    }
 
-        Ok(has_access)
+    // fields an update request sets without the caller holding their permission, owner_id is
+    // the owner of the resource being updated
+    pub async fn forbidden_writes<T: ProtectedFields>(
+        &self,
+        roles: &[AssignedRole],
+        domain: &str,
+        request: &T,
+        owner_id: Option<&str>,
+        ctx: &AccessContext,
+    ) -> Result<Vec<&'static str>, casbin::Error> {
+        let provided = request.provided_fields();
+        let fields = T::PROTECTED_FIELDS
+            .iter()
+            .filter(|field| provided.contains(&field.name));
+
+        self.denied_fields(roles, domain, fields, owner_id.unwrap_or_default(), ctx)
+            .await
     }
 
-    pub async fn setup_roles_and_permissions(&self) {
-        info!("Setting up Roles and Permissions...");
+    // every permission is evaluated once against the same snapshot
+    async fn denied_fields<'a>(
+        &self,
+        roles: &[AssignedRole],
+        domain: &str,
+        fields: impl Iterator<Item = &'a ProtectedField>,
+        owner_id: &str,
+        ctx: &AccessContext,
+    ) -> Result<Vec<&'static str>, casbin::Error> {
+        let ctx = ctx.clone().with_resource(ResourceAttributes {
+            owner_id: owner_id.to_string(),
+            ..Default::default()
+        });
+        let snapshot = self.snapshot.load();
+        let mut allowed: HashMap<&str, bool> = HashMap::new();
+        let mut denied = Vec::new();
 
-        let mut enforcer = self.enforcer.write().await;
+        for field in fields {
+            let is_allowed = match allowed.get(field.permission) {
+                Some(is_allowed) => *is_allowed,
+                None => {
+                    // declared permissions are `resource:action`, anything else is never granted
+                    let is_allowed = match field.permission.rsplit_once(':') {
+                        Some((object, action)) => {
+                            let (outcome, _) =
+                                self.evaluate(&snapshot, roles, domain, object, action, &ctx)?;
+                            outcome == AccessOutcome::Allowed
+                        }
+                        None => false,
+                    };
+                    allowed.insert(field.permission, is_allowed);
+                    is_allowed
+                }
+            };
 
-                // Expected policies
-        let expected_policies = vec![
-            vec!["user".to_owned(), "projects".to_owned(), "read".to_owned()],
-            vec!["user".to_owned(), "projects".to_owned(), "write".to_owned()],
-            vec!["user".to_owned(), "projects".to_owned(), "delete".to_owned()],
-            vec!["user".to_owned(), "user-settings".to_owned(), "read".to_owned()],
-            vec!["user".to_owned(), "user-settings".to_owned(), "write".to_owned()],
+            if !is_allowed {
+                denied.push(field.name);
+            }
+        }
 
-            // Admin permissions (inherits from user via role hierarchy)
-            vec!["admin".to_owned(), "user-management".to_owned(), "read".to_owned()],
-            vec!["admin".to_owned(), "user-management".to_owned(), "write".to_owned()],
-            vec!["admin".to_owned(), "all-resources".to_owned(), "read".to_owned()],
-            vec!["admin".to_owned(), "all-resources".to_owned(), "write".to_owned()],
-        ];
+        Ok(denied)
+    }
+
+    // same evaluation as check_access, keeping the matched policies and how each was reached
+    pub async fn explain_access(
+        &self,
+        roles: &[AssignedRole],
+        domain: &str,
+        object: &str,
+        action: &str,
+        ctx: &AccessContext,
+    ) -> Result<AccessDecision, casbin::Error> {
+        let snapshot = self.snapshot.load();
+        let (outcome, matched) = self.evaluate(&snapshot, roles, domain, object, action, ctx)?;
+        let grouping_policies = snapshot.enforcer.get_grouping_policy();
+
+        let matched_policies = matched
+            .into_iter()
+            .map(|(role_id, policy)| MatchedPolicy {
+                effect: policy.get(4).cloned().unwrap_or_else(|| EFFECT_ALLOW.to_string()),
+                path: Self::inheritance_path(&grouping_policies, &role_id, &policy[0], domain),
+                policy,
+            })
+            .collect();
+
+        Ok(AccessDecision {
+            domain: domain.to_string(),
+            object: object.to_string(),
+            action: action.to_string(),
+            outcome,
+            allowed: outcome == AccessOutcome::Allowed,
+            considered_roles: Self::considered_roles(roles, domain),
+            matched_policies,
+        })
+    }
 
-        // Expected role hierarchies
-        let expected_roles = vec![("admin", "user")];
+    // decisions for every (object, action) pair, evaluated against the same snapshot
+    pub async fn access_matrix(
+        &self,
+        roles: &[AssignedRole],
+        domain: &str,
+        pairs: &[(String, String)],
+        ctx: &AccessContext,
+    ) -> Result<AccessMatrix, casbin::Error> {
+        let snapshot = self.snapshot.load();
+
+        let mut cells = Vec::with_capacity(pairs.len());
+        for (object, action) in pairs {
+            let (outcome, _) = self.evaluate(&snapshot, roles, domain, object, action, ctx)?;
+            cells.push(AccessMatrixCell {
+                object: object.clone(),
+                action: action.clone(),
+                outcome,
+                allowed: outcome == AccessOutcome::Allowed,
+            });
+        }
+
+        Ok(AccessMatrix {
+            domain: domain.to_string(),
+            considered_roles: Self::considered_roles(roles, domain),
+            cells,
+        })
+    }
+
+    // returns the outcome and the policies matched per applying role,
+    // casbin stops collecting a role's matches at its first deny
+    fn evaluate(
+        &self,
+        snapshot: &PolicySnapshot,
+        roles: &[AssignedRole],
+        domain: &str,
+        object: &str,
+        action: &str,
+        ctx: &AccessContext,
+    ) -> Result<(AccessOutcome, Vec<RoleMatch>), casbin::Error> {
+        let mut outcome = AccessOutcome::NoMatch;
+        let mut matched = Vec::new();
+        let ctx = ctx.at(chrono::Utc::now());
 
-        // Get current policies from the enforcer
-        let current_policies = enforcer.get_policy();
+        for role in roles.iter().filter(|role| Self::applies_in(role, domain)) {
+            let decision = snapshot.decide(
+                &role.role.id,
+                domain,
+                object,
+                action,
+                &ctx,
+                &self.decision_metrics,
+            )?;
 
-        // Detect and remove extra policies, but keep those with 'shared:<id>' pattern
-        for policy in &current_policies {
-            let is_shared_policy = policy[1].starts_with("shared:");
-            if !expected_policies.contains(policy) && !is_shared_policy {
-                info!("Removing extra policy {:?}", policy);
-                enforcer.remove_policy(policy.clone()).await.unwrap();
+            for policy in decision.matched {
+                if policy.get(4).map(String::as_str) == Some(EFFECT_DENY) {
+                    outcome = AccessOutcome::Denied;
+                }
+                matched.push((role.role.id.clone(), policy));
             }
+
+            if decision.allowed && outcome == AccessOutcome::NoMatch {
+                outcome = AccessOutcome::Allowed;
+            }
+        }
+
+        Ok((outcome, matched))
+    }
+
+    // held in the domain and inside the grant's validity window
+    fn applies_in(role: &AssignedRole, domain: &str) -> bool {
+        (role.assigned_domain == GLOBAL_DOMAIN || role.assigned_domain == domain)
+            && role.is_active_at(chrono::Utc::now())
+    }
+
+    // only roles held globally or in the domain, assignments elsewhere are not disclosed
+    fn considered_roles(roles: &[AssignedRole], domain: &str) -> Vec<ConsideredRole> {
+        roles
+            .iter()
+            .filter(|role| role.assigned_domain == GLOBAL_DOMAIN || role.assigned_domain == domain)
+            .map(|role| ConsideredRole {
+                role_id: role.role.id.clone(),
+                role_name: role.role.name.clone(),
+                assigned_domain: role.assigned_domain.clone(),
+                applies: Self::applies_in(role, domain),
+            })
+            .collect()
+    }
+
+    // shortest chain of `g` rules leading from a held role to the role owning a policy
+    fn inheritance_path(
+        grouping_policies: &[Vec<String>],
+        from: &str,
+        to: &str,
+        domain: &str,
+    ) -> Vec<String> {
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![to.to_string()];
+                let mut step = to;
+                while let Some(parent) = previous.get(step) {
+                    path.push(parent.to_string());
+                    step = parent;
+                }
+                path.reverse();
+
+                return path;
+            }
+
+            for parent in Self::parent_roles(grouping_policies, current, domain) {
+                if parent != from && !previous.contains_key(parent) {
+                    previous.insert(parent, current);
+                    queue.push_back(parent);
+                }
+            }
+        }
+
+        vec![from.to_string(), to.to_string()]
+    }
+
+    // parents of a role through `g` rules applying in the domain
+    pub fn parent_roles<'a>(
+        grouping_policies: &'a [Vec<String>],
+        role_id: &'a str,
+        domain: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        grouping_policies
+            .iter()
+            .filter(move |rule| {
+                rule.len() >= 3
+                    && rule[0] == role_id
+                    && (rule[2] == domain || rule[2] == GLOBAL_DOMAIN)
+            })
+            .map(|rule| rule[1].as_str())
+    }
+
+    // every role inherited directly or transitively, nearest first
+    pub fn ancestor_roles(
+        grouping_policies: &[Vec<String>],
+        role_id: &str,
+        domain: &str,
+    ) -> Vec<String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let mut queue = VecDeque::from([role_id.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            for parent in Self::parent_roles(grouping_policies, &current, domain) {
+                if parent != role_id && !ancestors.iter().any(|ancestor| ancestor == parent) {
+                    ancestors.push(parent.to_string());
+                    queue.push_back(parent.to_string());
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    // builds an unconditional policy line from a `resource:action` permission
+    pub fn permission_policy(
+        role_id: &str,
+        domain: &str,
+        permission: &str,
+        effect: &str,
+    ) -> Result<Vec<String>, AppError> {
+        Self::conditional_policy(role_id, domain, permission, effect, None)
+    }
+
+    pub fn conditional_policy(
+        role_id: &str,
+        domain: &str,
+        permission: &str,
+        effect: &str,
+        condition: Option<&str>,
+    ) -> Result<Vec<String>, AppError> {
+        let condition = match condition.map(str::trim) {
+            Some(condition) => {
+                Self::validate_condition(condition)?;
+                condition
+            }
+            None => CONDITION_ALWAYS,
+        };
+
+        // the action is the last segment, objects like `shared:<id>` contain a colon themselves
+        match permission.rsplit_once(':') {
+            Some((object, action)) if !object.is_empty() && !action.is_empty() => Ok(vec![
+                role_id.to_string(),
+                domain.to_string(),
+                object.to_string(),
+                action.to_string(),
+                effect.to_string(),
+                condition.to_string(),
+            ]),
+            _ => Err(AppError::ProcessError(format!(
+                "Invalid permission {}, expected resource:action",
+                permission
+            ))),
         }
+    }
 
-        // Seed policies
-        for policy in &expected_policies {
-            if !enforcer.has_policy(policy.clone()) {
-                enforcer.add_policy(policy.clone()).await.unwrap();
+    // splits a role's policy lines into allowed and denied `resource:action` permissions
+    pub fn split_permissions(policies: Vec<Vec<String>>) -> (Vec<String>, Vec<String>) {
+        let mut allowed = Vec::new();
+        let mut denied = Vec::new();
+
+        for policy in policies.into_iter().filter(|policy| policy.len() >= 4) {
+            let permission = format!("{}:{}", policy[2], policy[3]);
+            match policy.get(4).map(String::as_str) {
+                Some(EFFECT_DENY) => denied.push(permission),
+                _ => allowed.push(permission),
             }
         }
 
-        // Get current roles from the enforcer
-        for (parent_role, child_role) in &expected_roles {
-            let current_roles = enforcer.get_roles_for_user(child_role, None);
-            if !current_roles.contains(&parent_role.to_string()) {
-                info!(
-                    "Role hierarchy for {} -> {} has been deleted!",
-                    child_role, parent_role
-                );
-                // Re-add the role if it was deleted
-                enforcer
-                    .add_role_for_user(child_role, parent_role, None)
-                    .await
-                    .unwrap();
+        (allowed, denied)
+    }
+
+    // conditions of a role's policy lines by `resource:action`, unconditional ones are left out
+    pub fn policy_conditions(policies: &[Vec<String>]) -> BTreeMap<String, String> {
+        policies
+            .iter()
+            .filter(|policy| policy.len() >= 6 && policy[5] != CONDITION_ALWAYS)
+            .map(|policy| (format!("{}:{}", policy[2], policy[3]), policy[5].clone()))
+            .collect()
+    }
+
+    // conditions are evaluated as script, so only comparisons of `r.ctx` fields, literals and the
+    // registered functions are accepted
+    pub fn validate_condition(condition: &str) -> Result<(), AppError> {
+        let invalid = |reason: &str| {
+            Err(AppError::ProcessError(format!(
+                "Invalid condition {}: {}",
+                condition, reason
+            )))
+        };
+
+        if condition.is_empty() || condition.len() > CONDITION_MAX_LEN {
+            return invalid("expected 1 to 128 characters");
+        }
+
+        let mut depth = 0_i32;
+        let mut rest = condition.trim_start();
+        while !rest.is_empty() {
+            if let Some(literal) = rest.strip_prefix('"') {
+                // rhai reads escapes such as \" inside strings, so none are let through
+                let Some(end) = literal.find(['"', '\\']) else {
+                    return invalid("unterminated string");
+                };
+                if literal[end..].starts_with('\\') {
+                    return invalid("escapes are not allowed in strings");
+                }
+                rest = &literal[end + 1..];
+            } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+            } else if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len());
+                let (word, after) = rest.split_at(end);
+                let known = match word.strip_prefix("r.ctx.") {
+                    Some(field) => CONDITION_CONTEXT_FIELDS.contains(&field),
+                    None => {
+                        matches!(word, "true" | "false")
+                            || (CONDITION_FUNCTIONS.contains(&word)
+                                && after.trim_start().starts_with('('))
+                    }
+                };
+                if !known {
+                    return invalid(&format!("unknown name {}", word));
+                }
+                rest = after;
+            } else if let Some(operator) = CONDITION_OPERATORS
+                .iter()
+                .chain(&[","])
+                .find(|operator| rest.starts_with(**operator))
+            {
+                depth += match *operator {
+                    "(" => 1,
+                    ")" => -1,
+                    _ => 0,
+                };
+                if depth < 0 {
+                    return invalid("unbalanced parentheses");
+                }
+                rest = &rest[operator.len()..];
+            } else {
+                return invalid("unexpected character");
             }
+            rest = rest.trim_start();
         }
 
-        // Seed Role Hierarchies
-        for (child_role, parent_role) in &expected_roles {
-            if !enforcer.has_role_for_user(child_role, parent_role, None) {
-                enforcer
-                    .add_role_for_user(child_role, parent_role, None)
-                    .await
-                    .unwrap();
+        if depth != 0 {
+            return invalid("unbalanced parentheses");
+        }
+
+        Ok(())
+    }
+
+    // functions conditions can call besides the comparison operators
+    pub fn register_condition_functions(enforcer: &mut Enforcer) {
+        enforcer.add_function(
+            "timeBetween",
+            OperatorFunction::Arg3(|time, from, to| {
+                time_between(&dynamic_to_str(&time), &dynamic_to_str(&from), &dynamic_to_str(&to))
+                    .into()
+            }),
+        );
+        enforcer.add_function(
+            "ipInRange",
+            OperatorFunction::Arg2(|ip, range| {
+                ip_in_range(&dynamic_to_str(&ip), &dynamic_to_str(&range)).into()
+            }),
+        );
+    }
+
+    // the role's own policies and direct parents as recorded in its change history
+    pub fn role_snapshot(enforcer: &Enforcer, role_id: &str) -> RolePolicySnapshot {
+        let parents = enforcer
+            .get_filtered_grouping_policy(0, vec![role_id.to_string()])
+            .into_iter()
+            .filter_map(|rule| rule.get(1).cloned())
+            .collect();
+
+        Self::policy_snapshot(enforcer.get_filtered_policy(0, vec![role_id.to_string()]), parents)
+    }
+
+    // snapshot of policy lines and parent ids that are not in the enforcer yet
+    pub fn policy_snapshot(policies: Vec<Vec<String>>, parents: Vec<String>) -> RolePolicySnapshot {
+        let conditions = Self::policy_conditions(&policies);
+        let (permissions, denied_permissions) = Self::split_permissions(policies);
+
+        RolePolicySnapshot::new(permissions, denied_permissions, parents, conditions)
+    }
+
+    // brings the enforcer in line with rules already committed to casbin_rule without the adapter
+    // writing them again, peers still hear about each change through the watcher
+    pub async fn apply_committed(
+        &self,
+        enforcer: &mut Enforcer,
+        rules: CommittedRules,
+    ) -> Result<(), casbin::Error> {
+        let has_groupings = !rules.added_groupings.is_empty() || !rules.removed_groupings.is_empty();
+
+        enforcer.enable_auto_save(false);
+        let applied = async {
+            for policy in rules.removed_policies {
+                enforcer.remove_policy(policy).await?;
+            }
+            for policy in rules.added_policies {
+                enforcer.add_policy(policy).await?;
+            }
+            for rule in rules.removed_groupings {
+                enforcer.remove_grouping_policy(rule).await?;
+            }
+            for rule in rules.added_groupings {
+                enforcer.add_grouping_policy(rule).await?;
             }
+            if has_groupings {
+                enforcer.build_role_links()?;
+            }
+
+            Ok::<(), casbin::Error>(())
+        }
+        .await;
+        enforcer.enable_auto_save(true);
+
+        // the database already holds the change, so a full reload still ends up consistent
+        if let Err(err) = applied {
+            warn!("Reloading policies after failing to apply a committed change: {}", err);
+            return self.reload_policy(enforcer).await;
         }
 
-        info!("Roles and Permissions Setup Completed!");
+        self.publish_snapshot(enforcer).await
+    }
+
+    // picks up rules written to casbin_rule directly and has the other instances reload as well,
+    // callers hold the write lock so no check sees a partly loaded model
+    pub async fn reload_policy(&self, enforcer: &mut Enforcer) -> Result<(), casbin::Error> {
+        enforcer.load_policy().await?;
+        if let Some(watcher) = enforcer.get_mut_watcher() {
+            watcher.update(EventData::ClearPolicy);
+        }
+
+        self.publish_snapshot(enforcer).await
+    }
+}
+
+// `HH:MM` times, a window ending before it starts runs over midnight
+fn time_between(time: &str, from: &str, to: &str) -> bool {
+    let minutes = |value: &str| {
+        let (hours, minutes) = value.trim().split_once(':')?;
+        let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    };
+
+    match (minutes(time), minutes(from), minutes(to)) {
+        (Some(time), Some(from), Some(to)) if from <= to => from <= time && time < to,
+        (Some(time), Some(from), Some(to)) => time >= from || time < to,
+        _ => false,
+    }
+}
+
+// a single address or a CIDR range, anything unparsable never matches
+pub fn ip_in_range(ip: &str, range: &str) -> bool {
+    let (network, prefix) = match range.trim().split_once('/') {
+        Some((network, prefix)) => (network, prefix.parse::<u32>().ok()),
+        None => (range.trim(), None),
+    };
+
+    match (ip.trim().parse::<IpAddr>(), network.parse::<IpAddr>()) {
+        (Ok(IpAddr::V4(ip)), Ok(IpAddr::V4(network))) => {
+            let prefix = prefix.unwrap_or(32);
+            prefix <= 32
+                && u32::from(ip).checked_shr(32 - prefix).unwrap_or(0)
+                    == u32::from(network).checked_shr(32 - prefix).unwrap_or(0)
+        }
+        (Ok(IpAddr::V6(ip)), Ok(IpAddr::V6(network))) => {
+            let prefix = prefix.unwrap_or(128);
+            prefix <= 128
+                && u128::from(ip).checked_shr(128 - prefix).unwrap_or(0)
+                    == u128::from(network).checked_shr(128 - prefix).unwrap_or(0)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casbin::{CoreApi, MemoryAdapter};
+    use serde::Serialize;
+
+    use super::*;
+    use crate::domain::entities::role::Role;
+
+    #[derive(Serialize)]
+    struct Account {
+        id: String,
+        email: String,
+        note: Option<String>,
+    }
+
+    impl ProtectedFields for Account {
+        const PROTECTED_FIELDS: &'static [ProtectedField] = &[
+            ProtectedField {
+                name: "email",
+                permission: "user-management:read",
+                owner_readable: true,
+            },
+            ProtectedField {
+                name: "note",
+                permission: "notes:write",
+                owner_readable: false,
+            },
+        ];
+
+        fn owner_id(&self) -> Option<&str> {
+            Some(&self.id)
+        }
+
+        fn provided_fields(&self) -> Vec<&'static str> {
+            self.note.iter().map(|_| "note").collect()
+        }
+    }
+
+    fn account(id: &str, note: Option<&str>) -> Account {
+        Account {
+            id: id.to_string(),
+            email: format!("{}@example.com", id),
+            note: note.map(str::to_string),
+        }
+    }
+
+    fn held(role_id: &str) -> AssignedRole {
+        AssignedRole {
+            role: Role::new(
+                role_id.to_string(),
+                role_id.to_string(),
+                false,
+                GLOBAL_DOMAIN.to_string(),
+            ),
+            assigned_domain: GLOBAL_DOMAIN.to_string(),
+            starts_at: None,
+            expires_at: None,
+        }
+    }
+
+    // support reads emails, members edit the notes of their own account
+    async fn rbac() -> Rbac {
+        let model = DefaultModel::from_str(include_str!("../../etc/rbac_model.conf"))
+            .await
+            .unwrap();
+        let mut enforcer = Enforcer::new(model.clone(), MemoryAdapter::default())
+            .await
+            .unwrap();
+        enforcer.get_mut_model().add_policies(
+            "p",
+            "p",
+            vec![
+                vec!["support", "*", "user-management", "read", EFFECT_ALLOW, CONDITION_ALWAYS],
+                vec!["member", "*", "notes", "write", EFFECT_ALLOW, CONDITION_OWNER],
+            ]
+            .into_iter()
+            .map(|policy| policy.into_iter().map(str::to_string).collect())
+            .collect(),
+        );
+        Rbac::configure_enforcer(&mut enforcer).unwrap();
+
+        Rbac::new(enforcer, model).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn redact_hides_fields_the_caller_may_not_read() {
+        let rbac = rbac().await;
+        let ctx = AccessContext::new("bob", None);
+
+        let redacted = rbac
+            .redact(&[held("member")], GLOBAL_DOMAIN, account("alice", Some("vip")), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(redacted.hidden, vec!["email", "note"]);
+    }
+
+    #[tokio::test]
+    async fn redact_shows_owners_their_own_fields() {
+        let rbac = rbac().await;
+        let ctx = AccessContext::new("alice", None);
+
+        let redacted = rbac
+            .redact(&[held("member")], GLOBAL_DOMAIN, account("alice", Some("vip")), &ctx)
+            .await
+            .unwrap();
+
+        assert!(redacted.hidden.is_empty());
+    }
+
+    #[tokio::test]
+    async fn redact_shows_fields_the_caller_holds_the_permission_for() {
+        let rbac = rbac().await;
+        let ctx = AccessContext::new("bob", None);
+
+        let redacted = rbac
+            .redact(&[held("support")], GLOBAL_DOMAIN, account("alice", Some("vip")), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(redacted.hidden, vec!["note"]);
+    }
+
+    #[tokio::test]
+    async fn forbidden_writes_lists_provided_fields_without_permission() {
+        let rbac = rbac().await;
+        let ctx = AccessContext::new("bob", None);
+        let roles = [held("member"), held("support")];
+        let request = account("alice", Some("vip"));
+
+        let forbidden = rbac
+            .forbidden_writes(&roles, GLOBAL_DOMAIN, &request, Some("alice"), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(forbidden, vec!["note"]);
+
+        // fields the request leaves out are not checked
+        let forbidden = rbac
+            .forbidden_writes(&roles, GLOBAL_DOMAIN, &account("alice", None), Some("alice"), &ctx)
+            .await
+            .unwrap();
+        assert!(forbidden.is_empty());
+    }
+
+    #[tokio::test]
+    async fn forbidden_writes_applies_owner_conditions() {
+        let rbac = rbac().await;
+        let ctx = AccessContext::new("alice", None);
+
+        let roles = [held("member")];
+        let request = account("alice", Some("vip"));
+
+        let forbidden = rbac
+            .forbidden_writes(&roles, GLOBAL_DOMAIN, &request, Some("alice"), &ctx)
+            .await
+            .unwrap();
+        assert!(forbidden.is_empty());
+
+        let forbidden = rbac
+            .forbidden_writes(&roles, GLOBAL_DOMAIN, &request, Some("bob"), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(forbidden, vec!["note"]);
+    }
+
+    fn grouping(role_id: &str, parent_id: &str, domain: &str) -> Vec<String> {
+        vec![role_id.to_string(), parent_id.to_string(), domain.to_string()]
+    }
+
+    #[test]
+    fn ancestor_roles_are_listed_nearest_first() {
+        let groupings = [
+            grouping("editor", "viewer", GLOBAL_DOMAIN),
+            grouping("admin", "editor", GLOBAL_DOMAIN),
+            grouping("admin", "auditor", GLOBAL_DOMAIN),
+        ];
+
+        assert_eq!(
+            Rbac::ancestor_roles(&groupings, "admin", "org-1"),
+            vec!["editor", "auditor", "viewer"]
+        );
+        assert!(Rbac::ancestor_roles(&groupings, "viewer", "org-1").is_empty());
+    }
+
+    #[test]
+    fn ancestor_roles_only_follow_rules_of_the_domain() {
+        let groupings = [
+            grouping("editor", "viewer", "org-1"),
+            grouping("editor", "auditor", "org-2"),
+        ];
+
+        assert_eq!(Rbac::ancestor_roles(&groupings, "editor", "org-1"), vec!["viewer"]);
+        assert_eq!(Rbac::ancestor_roles(&groupings, "editor", "org-2"), vec!["auditor"]);
+    }
+
+    #[test]
+    fn ancestor_roles_terminate_on_cycles() {
+        let groupings = [
+            grouping("a", "b", GLOBAL_DOMAIN),
+            grouping("b", "c", GLOBAL_DOMAIN),
+            grouping("c", "a", GLOBAL_DOMAIN),
+        ];
+
+        assert_eq!(Rbac::ancestor_roles(&groupings, "a", GLOBAL_DOMAIN), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn new_parent_closing_a_loop_is_detected() {
+        let groupings = [
+            grouping("admin", "editor", GLOBAL_DOMAIN),
+            grouping("editor", "viewer", GLOBAL_DOMAIN),
+        ];
+
+        // the check add_role_parent runs: the parent must not already inherit the role
+        let closes_loop = |role: &str, parent: &str| {
+            Rbac::ancestor_roles(&groupings, parent, GLOBAL_DOMAIN).contains(&role.to_string())
+        };
+
+        assert!(closes_loop("viewer", "admin"));
+        assert!(closes_loop("viewer", "editor"));
+        assert!(!closes_loop("admin", "viewer"));
+    }
+
+    #[test]
+    fn accepts_known_conditions() {
+        for condition in [
+            CONDITION_ALWAYS,
+            CONDITION_OWNER,
+            r#"r.ctx.organization == "acme" && r.ctx.status != "archived""#,
+            r#"timeBetween(r.ctx.time, "09:00", "17:00") || ipInRange(r.ctx.ip, "10.0.0.0/8")"#,
+            "!(r.ctx.owner_id == r.ctx.user_id)",
+        ] {
+            assert!(Rbac::validate_condition(condition).is_ok(), "{}", condition);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_malformed_conditions() {
+        for condition in [
+            "",
+            "r.ctx.password == \"x\"",
+            "system(\"ls\")",
+            "timeBetween == true",
+            "(r.ctx.user_id == \"a\"",
+            "r.ctx.user_id == \"a\")",
+            "r.ctx.user_id == \"a",
+            "r.ctx.user_id == `a`",
+            "r.ctx.user_id = \"a\"",
+            &"1".repeat(CONDITION_MAX_LEN + 1),
+        ] {
+            assert!(Rbac::validate_condition(condition).is_err(), "{}", condition);
+        }
+    }
+
+    #[test]
+    fn rejects_escapes_in_strings() {
+        // rhai would end the string at the second quote and run what follows
+        assert!(Rbac::validate_condition(r#"r.ctx.user_id == "a\" || true || "b""#).is_err());
+        assert!(Rbac::validate_condition(r#"r.ctx.user_id == "a\\""#).is_err());
+        assert!(Rbac::validate_condition(r#"r.ctx.user_id == "\u0041""#).is_err());
+    }
+
+    #[test]
+    fn time_between_handles_windows() {
+        assert!(time_between("09:00", "09:00", "17:00"));
+        assert!(time_between("16:59", "09:00", "17:00"));
+        assert!(!time_between("17:00", "09:00", "17:00"));
+        assert!(!time_between("08:59", "09:00", "17:00"));
+    }
+
+    #[test]
+    fn time_between_runs_over_midnight() {
+        assert!(time_between("23:30", "22:00", "06:00"));
+        assert!(time_between("05:59", "22:00", "06:00"));
+        assert!(!time_between("12:00", "22:00", "06:00"));
+    }
+
+    #[test]
+    fn time_between_never_matches_invalid_times() {
+        assert!(!time_between("24:00", "00:00", "23:59"));
+        assert!(!time_between("12:60", "00:00", "23:59"));
+        assert!(!time_between("noon", "00:00", "23:59"));
+        assert!(!time_between("12:00", "", "23:59"));
+    }
+
+    #[test]
+    fn ip_in_range_matches_addresses_and_ranges() {
+        assert!(ip_in_range("10.1.2.3", "10.0.0.0/8"));
+        assert!(!ip_in_range("11.1.2.3", "10.0.0.0/8"));
+        assert!(ip_in_range("192.168.1.7", "192.168.1.7"));
+        assert!(!ip_in_range("192.168.1.8", "192.168.1.7"));
+        assert!(ip_in_range("1.2.3.4", "0.0.0.0/0"));
+        assert!(ip_in_range("2001:db8::1", "2001:db8::/32"));
+        assert!(!ip_in_range("2001:db9::1", "2001:db8::/32"));
+    }
+
+    #[test]
+    fn ip_in_range_never_matches_invalid_input() {
+        assert!(!ip_in_range("10.1.2.3", "10.0.0.0/33"));
+        assert!(!ip_in_range("10.1.2.3", "2001:db8::/32"));
+        assert!(!ip_in_range("not-an-ip", "10.0.0.0/8"));
+        assert!(!ip_in_range("10.1.2.3", "10.0.0.0/x"));
+        assert!(!ip_in_range("", ""));
     }
 }
@@ -0,0 +1,372 @@
+use std::{
+    sync::{
+        atomic::{AtomicI64, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bb8_redis::{bb8::Pool, redis::AsyncCommands, RedisConnectionManager};
+use casbin::{CoreApi, EventData, Watcher};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{error, info, warn};
+
+use super::{config::AppConfig, rbac::Rbac};
+
+/// Policy change broadcast to the other instances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyChange {
+    pub instance_id: String,
+    pub sent_at_ms: i64,
+    pub event: PolicyEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PolicyEvent {
+    Add {
+        sec: String,
+        ptype: String,
+        rules: Vec<Vec<String>>,
+    },
+    Remove {
+        sec: String,
+        ptype: String,
+        rules: Vec<Vec<String>>,
+    },
+    // the whole policy was replaced, peers reload it from the database
+    Reload,
+}
+
+impl PolicyEvent {
+    fn from_event_data(data: EventData) -> Option<Self> {
+        match data {
+            EventData::AddPolicy(sec, ptype, rule) => Some(PolicyEvent::Add {
+                sec,
+                ptype,
+                rules: vec![rule],
+            }),
+            EventData::AddPolicies(sec, ptype, rules) => {
+                Some(PolicyEvent::Add { sec, ptype, rules })
+            }
+            EventData::RemovePolicy(sec, ptype, rule) => Some(PolicyEvent::Remove {
+                sec,
+                ptype,
+                rules: vec![rule],
+            }),
+            EventData::RemovePolicies(sec, ptype, rules)
+            | EventData::RemoveFilteredPolicy(sec, ptype, rules) => {
+                Some(PolicyEvent::Remove { sec, ptype, rules })
+            }
+            EventData::SavePolicy(_) | EventData::ClearPolicy => Some(PolicyEvent::Reload),
+            EventData::ClearCache => None,
+        }
+    }
+}
+
+/// Counters describing how far this instance's enforcer trails its peers.
+#[derive(Debug, Default)]
+pub struct PolicySyncMetrics {
+    pub published_total: AtomicU64,
+    pub applied_total: AtomicU64,
+    pub errors_total: AtomicU64,
+    pub full_reloads_total: AtomicU64,
+    pub last_lag_ms: AtomicI64,
+    pub max_lag_ms: AtomicI64,
+    pub last_full_reload_at: AtomicI64,
+}
+
+impl PolicySyncMetrics {
+    fn record_applied(&self, lag_ms: i64) {
+        self.applied_total.fetch_add(1, Ordering::Relaxed);
+        self.last_lag_ms.store(lag_ms, Ordering::Relaxed);
+        self.max_lag_ms.fetch_max(lag_ms, Ordering::Relaxed);
+    }
+
+    fn record_full_reload(&self) {
+        self.full_reloads_total.fetch_add(1, Ordering::Relaxed);
+        self.last_full_reload_at
+            .store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    // Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let metrics = [
+            (
+                "casbin_policy_sync_published_total",
+                "counter",
+                "Policy changes published to peers",
+                self.published_total.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "casbin_policy_sync_applied_total",
+                "counter",
+                "Policy changes received from peers and applied",
+                self.applied_total.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "casbin_policy_sync_errors_total",
+                "counter",
+                "Failures while publishing, receiving or applying policy changes",
+                self.errors_total.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "casbin_policy_sync_full_reloads_total",
+                "counter",
+                "Full policy reloads from the database",
+                self.full_reloads_total.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "casbin_policy_sync_lag_ms",
+                "gauge",
+                "Delay between a peer publishing the last change and this instance applying it",
+                self.last_lag_ms.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_policy_sync_lag_ms_max",
+                "gauge",
+                "Largest sync delay observed since startup",
+                self.max_lag_ms.load(Ordering::Relaxed),
+            ),
+            (
+                "casbin_policy_last_full_reload_timestamp_seconds",
+                "gauge",
+                "Unix time of the last full policy reload",
+                self.last_full_reload_at.load(Ordering::Relaxed),
+            ),
+        ];
+
+        metrics
+            .iter()
+            .map(|(name, kind, help, value)| {
+                format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n")
+            })
+            .collect()
+    }
+}
+
+/// Casbin watcher handing local policy changes to the publisher task.
+struct RedisPolicyWatcher {
+    instance_id: String,
+    sender: UnboundedSender<PolicyChange>,
+    callback: Option<Box<dyn FnMut(String) + Send + Sync>>,
+}
+
+impl Watcher for RedisPolicyWatcher {
+    fn set_update_callback(&mut self, cb: Box<dyn FnMut(String) + Send + Sync>) {
+        self.callback = Some(cb);
+    }
+
+    // called while the enforcer write lock is held, so only queue the change here
+    fn update(&mut self, data: EventData) {
+        if let Some(callback) = self.callback.as_mut() {
+            callback(data.to_string());
+        }
+
+        let Some(event) = PolicyEvent::from_event_data(data) else {
+            return;
+        };
+
+        let change = PolicyChange {
+            instance_id: self.instance_id.clone(),
+            sent_at_ms: chrono::Utc::now().timestamp_millis(),
+            event,
+        };
+
+        if self.sender.send(change).is_err() {
+            warn!("[RbacSync] publisher stopped, policy change not broadcast");
+        }
+    }
+}
+
+/// Keeps the enforcers of all instances in line: local changes are published over Redis,
+/// peer changes are applied in memory, and a periodic full reload catches anything missed.
+pub struct PolicySync {
+    instance_id: String,
+    channel: String,
+    redis_url: String,
+    reload_interval: Duration,
+    redis_pool: Pool<RedisConnectionManager>,
+    rbac: Arc<Rbac>,
+}
+
+impl PolicySync {
+    pub fn new(cfg: &AppConfig, redis_pool: Pool<RedisConnectionManager>, rbac: Arc<Rbac>) -> Self {
+        Self {
+            instance_id: uuid::Uuid::new_v4().to_string(),
+            channel: cfg.policy_sync_channel.clone(),
+            redis_url: cfg.redis_url.clone(),
+            reload_interval: Duration::from_secs(cfg.policy_reload_interval_secs),
+            redis_pool,
+            rbac,
+        }
+    }
+
+    pub async fn start(self) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        self.rbac.enforcer.write().await.set_watcher(Box::new(RedisPolicyWatcher {
+            instance_id: self.instance_id.clone(),
+            sender,
+            callback: None,
+        }));
+
+        info!("[RbacSync] instance {} syncing policies on {}", self.instance_id, self.channel);
+
+        let sync = Arc::new(self);
+        tokio::spawn(sync.clone().publish(receiver));
+        tokio::spawn(sync.clone().subscribe());
+        tokio::spawn(sync.reload_periodically());
+    }
+
+    async fn publish(self: Arc<Self>, mut receiver: UnboundedReceiver<PolicyChange>) {
+        let metrics = &self.rbac.sync_metrics;
+
+        while let Some(change) = receiver.recv().await {
+            let result = async {
+                let payload = serde_json::to_string(&change)
+                    .map_err(|err| err.to_string())?;
+                let mut conn = self.redis_pool.get().await.map_err(|err| err.to_string())?;
+                let _: i64 = conn
+                    .publish(&self.channel, payload)
+                    .await
+                    .map_err(|err| err.to_string())?;
+
+                Ok::<(), String>(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => {
+                    metrics.published_total.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                    error!("[RbacSync] failed to publish policy change: {}", err);
+                }
+            }
+        }
+    }
+
+    async fn subscribe(self: Arc<Self>) {
+        loop {
+            if let Err(err) = self.listen().await {
+                self.rbac.sync_metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                error!("[RbacSync] subscription lost: {}", err);
+            }
+
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    async fn listen(&self) -> Result<(), String> {
+        let client = redis::Client::open(self.redis_url.as_str()).map_err(|err| err.to_string())?;
+        let mut pubsub = client.get_async_pubsub().await.map_err(|err| err.to_string())?;
+        pubsub.subscribe(&self.channel).await.map_err(|err| err.to_string())?;
+
+        // changes published while we were not subscribed are only in the database
+        self.reload().await;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(err) => {
+                    self.rbac.sync_metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                    warn!("[RbacSync] unreadable policy change: {}", err);
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<PolicyChange>(&payload) {
+                Ok(change) if change.instance_id == self.instance_id => {}
+                Ok(change) => self.apply(change).await,
+                Err(err) => {
+                    self.rbac.sync_metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                    warn!("[RbacSync] malformed policy change: {}", err);
+                }
+            }
+        }
+
+        Err("pub/sub stream closed".to_string())
+    }
+
+    // peers already persisted the change, so only the in-memory model is touched
+    async fn apply(&self, change: PolicyChange) {
+        let mut enforcer = self.rbac.enforcer.write().await;
+
+        let result = match change.event {
+            PolicyEvent::Add { sec, ptype, rules } => {
+                enforcer
+                    .get_mut_model()
+                    .add_policies(&sec, &ptype, rules.clone());
+                enforcer.build_incremental_role_links(EventData::AddPolicies(sec, ptype, rules))
+            }
+            PolicyEvent::Remove { sec, ptype, rules } => {
+                enforcer
+                    .get_mut_model()
+                    .remove_policies(&sec, &ptype, rules.clone());
+                // removed links would survive in domains matched through `*`
+                if sec == "g" {
+                    enforcer.build_role_links()
+                } else {
+                    Ok(())
+                }
+            }
+            PolicyEvent::Reload => {
+                let result = enforcer.load_policy().await;
+                if result.is_ok() {
+                    self.rbac.sync_metrics.record_full_reload();
+                }
+                result
+            }
+        };
+        let result = match result {
+            Ok(()) => self.rbac.publish_snapshot(&enforcer).await,
+            Err(err) => Err(err),
+        };
+        drop(enforcer);
+
+        match result {
+            Ok(()) => {
+                let lag_ms = chrono::Utc::now().timestamp_millis() - change.sent_at_ms;
+                self.rbac.sync_metrics.record_applied(lag_ms.max(0));
+            }
+            Err(err) => {
+                self.rbac.sync_metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                error!("[RbacSync] failed to apply policy change, reloading: {}", err);
+                self.reload().await;
+            }
+        }
+    }
+
+    async fn reload_periodically(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.reload_interval);
+        // the first tick completes immediately and the policy was just loaded
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            self.reload().await;
+        }
+    }
+
+    async fn reload(&self) {
+        let mut enforcer = self.rbac.enforcer.write().await;
+        let result = match enforcer.load_policy().await {
+            Ok(()) => self.rbac.publish_snapshot(&enforcer).await,
+            Err(err) => Err(err),
+        };
+        drop(enforcer);
+
+        match result {
+            Ok(()) => self.rbac.sync_metrics.record_full_reload(),
+            Err(err) => {
+                self.rbac.sync_metrics.errors_total.fetch_add(1, Ordering::Relaxed);
+                error!("[RbacSync] full policy reload failed: {}", err);
+            }
+        }
+    }
+}
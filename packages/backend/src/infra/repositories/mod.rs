@@ -1,6 +1,19 @@
+pub mod pg_access_request_repo;
+pub mod pg_access_review_repo;
+pub mod pg_app_setup_repo;
+pub mod pg_email_change_repo;
+pub mod pg_invite_repo;
 pub mod pg_oauth_provider;
+pub mod pg_permission_repo;
+pub mod pg_policy_repo;
+pub mod pg_role_constraint_repo;
+pub mod pg_role_policy_change_repo;
 pub mod pg_role_repo;
+pub mod pg_role_template_repo;
+pub mod pg_saml_provider_repo;
 pub mod pg_user_repo;
+pub mod pg_user_role_repo;
 pub mod pg_user_session;
 pub mod pg_project_repo;
 pub mod redis_repo_impl;
+pub mod smtp_mail_repo_impl;
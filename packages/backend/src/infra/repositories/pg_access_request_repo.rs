@@ -0,0 +1,219 @@
+use crate::{
+    domain::{
+        entities::access_request::{
+            AccessRequest, AccessRequestDecision, AccessRequestRevocation,
+        },
+        repositories::access_request_repo::AccessRequestRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgAccessRequestRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgAccessRequestRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AccessRequestRepository for PgAccessRequestRepository {
+    async fn find_by_id(&self, id: &str) -> Result<AccessRequest, AppError> {
+        let request = sqlx::query_as!(
+            AccessRequest,
+            "SELECT * FROM access_requests WHERE id = $1",
+            id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn find_by_requester(&self, requester_id: &str) -> Result<Vec<AccessRequest>, AppError> {
+        let requests = sqlx::query_as!(
+            AccessRequest,
+            "SELECT * FROM access_requests WHERE requester_id = $1 ORDER BY created_at DESC",
+            requester_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn find_by_domain(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessRequest>, AppError> {
+        let requests = sqlx::query_as!(
+            AccessRequest,
+            "SELECT * FROM access_requests WHERE ($1 = '*' OR domain = $1) AND ($2::text IS NULL OR status = $2) ORDER BY created_at DESC",
+            domain,
+            status
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn has_open_request(&self, entity: &AccessRequest) -> Result<bool, AppError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM access_requests
+                WHERE requester_id = $1 AND domain = $2
+                AND role_id IS NOT DISTINCT FROM $3 AND permission IS NOT DISTINCT FROM $4
+                AND status IN ('pending', 'approved')
+            ) AS "exists!""#,
+            entity.requester_id,
+            entity.domain,
+            entity.role_id,
+            entity.permission
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn find_expired(&self, limit: i64) -> Result<Vec<AccessRequest>, AppError> {
+        let requests = sqlx::query_as!(
+            AccessRequest,
+            "SELECT * FROM access_requests WHERE status = 'approved' AND expires_at <= NOW() ORDER BY expires_at LIMIT $1",
+            limit
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn find_decisions(&self, request_id: &str) -> Result<Vec<AccessRequestDecision>, AppError> {
+        let decisions = sqlx::query_as!(
+            AccessRequestDecision,
+            "SELECT * FROM access_request_decisions WHERE request_id = $1 ORDER BY created_at",
+            request_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(decisions)
+    }
+
+    async fn find_revocations(
+        &self,
+        request_id: &str,
+    ) -> Result<Vec<AccessRequestRevocation>, AppError> {
+        let revocations = sqlx::query_as!(
+            AccessRequestRevocation,
+            "SELECT * FROM access_request_revocations WHERE request_id = $1 ORDER BY created_at",
+            request_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(revocations)
+    }
+
+    async fn create(&self, entity: &AccessRequest) -> Result<AccessRequest, AppError> {
+        let request = sqlx::query_as!(
+            AccessRequest,
+            "INSERT INTO access_requests (id, requester_id, domain, role_id, permission, justification, duration_secs, status, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+            entity.id,
+            entity.requester_id,
+            entity.domain,
+            entity.role_id,
+            entity.permission,
+            entity.justification,
+            entity.duration_secs,
+            entity.status,
+            entity.created_at,
+            entity.updated_at
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<AccessRequest, AppError> {
+        let request = sqlx::query_as!(
+            AccessRequest,
+            "SELECT * FROM access_requests WHERE id = $1 FOR UPDATE",
+            id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequest,
+    ) -> Result<AccessRequest, AppError> {
+        let request = sqlx::query_as!(
+            AccessRequest,
+            "UPDATE access_requests SET status = $2, granted_role_id = $3, starts_at = $4, expires_at = $5, updated_at = $6 WHERE id = $1 RETURNING *",
+            entity.id,
+            entity.status,
+            entity.granted_role_id,
+            entity.starts_at,
+            entity.expires_at,
+            entity.updated_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_create_decision(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequestDecision,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO access_request_decisions (id, request_id, approver_id, decision, comment, created_at) VALUES ($1, $2, $3, $4, $5, $6)",
+            entity.id,
+            entity.request_id,
+            entity.approver_id,
+            entity.decision,
+            entity.comment,
+            entity.created_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn tx_create_revocation(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessRequestRevocation,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO access_request_revocations (id, request_id, revoked_by, reason, created_at) VALUES ($1, $2, $3, $4, $5)",
+            entity.id,
+            entity.request_id,
+            entity.revoked_by,
+            entity.reason,
+            entity.created_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    domain::{
+        entities::access_review::{AccessReview, AccessReviewItem},
+        repositories::access_review_repo::AccessReviewRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgAccessReviewRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgAccessReviewRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AccessReviewRepository for PgAccessReviewRepository {
+    async fn find_by_id(&self, id: &str) -> Result<AccessReview, AppError> {
+        let review = sqlx::query_as!(
+            AccessReview,
+            "SELECT * FROM access_reviews WHERE id = $1",
+            id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn find_by_domain(
+        &self,
+        domain: &str,
+        status: Option<&str>,
+    ) -> Result<Vec<AccessReview>, AppError> {
+        let reviews = sqlx::query_as!(
+            AccessReview,
+            "SELECT * FROM access_reviews WHERE ($1 = '*' OR domain = $1) AND ($2::text IS NULL OR status = $2) ORDER BY created_at DESC",
+            domain,
+            status
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    async fn find_open_by_reviewer(&self, reviewer_id: &str) -> Result<Vec<AccessReview>, AppError> {
+        let reviews = sqlx::query_as!(
+            AccessReview,
+            "SELECT * FROM access_reviews WHERE status = 'open' AND $1 = ANY(reviewer_ids) ORDER BY deadline",
+            reviewer_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    async fn find_overdue(&self, limit: i64) -> Result<Vec<AccessReview>, AppError> {
+        let reviews = sqlx::query_as!(
+            AccessReview,
+            "SELECT * FROM access_reviews WHERE status = 'open' AND deadline <= NOW() ORDER BY deadline LIMIT $1",
+            limit
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    async fn find_items(&self, review_id: &str) -> Result<Vec<AccessReviewItem>, AppError> {
+        let items = sqlx::query_as!(
+            AccessReviewItem,
+            "SELECT * FROM access_review_items WHERE review_id = $1 ORDER BY role_name, email, domain",
+            review_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<AccessReview, AppError> {
+        let review = sqlx::query_as!(
+            AccessReview,
+            "INSERT INTO access_reviews (id, name, description, domain, role_ids, reviewer_ids, default_action, status, deadline, created_by, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *",
+            entity.id,
+            entity.name,
+            entity.description,
+            entity.domain,
+            &entity.role_ids,
+            &entity.reviewer_ids,
+            entity.default_action,
+            entity.status,
+            entity.deadline,
+            entity.created_by,
+            entity.created_at,
+            entity.updated_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn tx_create_items(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"INSERT INTO access_review_items (id, review_id, user_id, email, role_id, role_name, domain, assigned_at, expires_at)
+            SELECT gen_random_uuid()::text, $1, users.id, users.email, roles.id, roles.name, user_roles.domain, user_roles.created_at, user_roles.expires_at
+            FROM user_roles
+            INNER JOIN users ON users.id = user_roles.user_id
+            INNER JOIN roles ON roles.id = user_roles.role_id
+            WHERE user_roles.role_id = ANY($2) AND ($3 = '*' OR user_roles.domain = $3)
+            AND users.deleted_at IS NULL AND roles.deleted_at IS NULL
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())"#,
+            entity.id,
+            &entity.role_ids,
+            entity.domain
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<AccessReview, AppError> {
+        let review = sqlx::query_as!(
+            AccessReview,
+            "SELECT * FROM access_reviews WHERE id = $1 FOR UPDATE",
+            id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReview,
+    ) -> Result<AccessReview, AppError> {
+        let review = sqlx::query_as!(
+            AccessReview,
+            "UPDATE access_reviews SET status = $2, updated_at = $3, completed_at = $4 WHERE id = $1 RETURNING *",
+            entity.id,
+            entity.status,
+            entity.updated_at,
+            entity.completed_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(review)
+    }
+
+    async fn tx_lock_item(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        review_id: &str,
+        id: &str,
+    ) -> Result<AccessReviewItem, AppError> {
+        let item = sqlx::query_as!(
+            AccessReviewItem,
+            "SELECT * FROM access_review_items WHERE review_id = $1 AND id = $2 FOR UPDATE",
+            review_id,
+            id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(item)
+    }
+
+    async fn tx_lock_pending_items(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        review_id: &str,
+    ) -> Result<Vec<AccessReviewItem>, AppError> {
+        let items = sqlx::query_as!(
+            AccessReviewItem,
+            "SELECT * FROM access_review_items WHERE review_id = $1 AND decision = 'pending' ORDER BY role_name, email FOR UPDATE",
+            review_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(items)
+    }
+
+    async fn tx_update_item(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &AccessReviewItem,
+    ) -> Result<AccessReviewItem, AppError> {
+        let item = sqlx::query_as!(
+            AccessReviewItem,
+            "UPDATE access_review_items SET decision = $2, by_default = $3, decided_by = $4, decided_at = $5, comment = $6 WHERE id = $1 RETURNING *",
+            entity.id,
+            entity.decision,
+            entity.by_default,
+            entity.decided_by,
+            entity.decided_at,
+            entity.comment
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(item)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    domain::{entities::app_setup::AppSetup, repositories::app_setup_repo::AppSetupRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgAppSetupRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgAppSetupRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AppSetupRepository for PgAppSetupRepository {
+    async fn is_completed(&self) -> Result<bool, AppError> {
+        let completed = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM app_setup) AS "completed!""#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(completed)
+    }
+
+    // the single row primary key makes sure only one caller can ever complete the setup
+    async fn tx_complete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        super_admin_id: &str,
+    ) -> Result<AppSetup, AppError> {
+        let setup = sqlx::query_as!(
+            AppSetup,
+            "INSERT INTO app_setup (id, super_admin_id, completed_at) VALUES (1, $1, $2) ON CONFLICT (id) DO NOTHING RETURNING *",
+            super_admin_id,
+            chrono::Utc::now()
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        setup.ok_or(AppError::SetupCompleted)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    domain::{
+        entities::email_change_request::{
+            EmailChangeRequest, EMAIL_CHANGE_CANCELLED, EMAIL_CHANGE_CONFIRMED, EMAIL_CHANGE_PENDING,
+        },
+        repositories::email_change_repo::EmailChangeRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgEmailChangeRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgEmailChangeRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailChangeRepository for PgEmailChangeRepository {
+    async fn find_pending_by_user_id(&self, user_id: &str) -> Result<EmailChangeRequest, AppError> {
+        let request = sqlx::query_as!(
+            EmailChangeRequest,
+            "SELECT * FROM email_change_requests WHERE user_id = $1 AND status = $2 AND expires_at > NOW()",
+            user_id,
+            EMAIL_CHANGE_PENDING
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn find_by_confirm_token_hash(&self, token_hash: &str) -> Result<EmailChangeRequest, AppError> {
+        let request = sqlx::query_as!(
+            EmailChangeRequest,
+            "SELECT * FROM email_change_requests WHERE confirm_token_hash = $1",
+            token_hash
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn find_by_revert_token_hash(&self, token_hash: &str) -> Result<EmailChangeRequest, AppError> {
+        let request = sqlx::query_as!(
+            EmailChangeRequest,
+            "SELECT * FROM email_change_requests WHERE revert_token_hash = $1",
+            token_hash
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_lock_by_revert_token_hash(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        token_hash: &str,
+    ) -> Result<EmailChangeRequest, AppError> {
+        let request = sqlx::query_as!(
+            EmailChangeRequest,
+            "SELECT * FROM email_change_requests WHERE revert_token_hash = $1 FOR UPDATE",
+            token_hash
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_replace_pending(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &EmailChangeRequest,
+    ) -> Result<EmailChangeRequest, AppError> {
+        // a new request supersedes the previous one, its links stop working
+        sqlx::query!(
+            "UPDATE email_change_requests SET status = $1 WHERE user_id = $2 AND status = $3",
+            EMAIL_CHANGE_CANCELLED,
+            entity.user_id,
+            EMAIL_CHANGE_PENDING
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let request = sqlx::query_as!(
+            EmailChangeRequest,
+            "INSERT INTO email_change_requests (id, user_id, old_email, new_email, confirm_token_hash, revert_token_hash, status, expires_at, revert_expires_at, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+            entity.id,
+            entity.user_id,
+            entity.old_email,
+            entity.new_email,
+            entity.confirm_token_hash,
+            entity.revert_token_hash,
+            entity.status,
+            entity.expires_at,
+            entity.revert_expires_at,
+            entity.created_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn tx_update_status(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &EmailChangeRequest,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE email_change_requests SET status = $1, confirmed_at = $2, reverted_at = $3 WHERE id = $4",
+            entity.status,
+            entity.confirmed_at,
+            entity.reverted_at,
+            entity.id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn tx_cancel_revertable(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        except_id: &str,
+    ) -> Result<(), AppError> {
+        // a later change of the address makes the revert links of earlier ones stale
+        sqlx::query!(
+            "UPDATE email_change_requests SET status = $1 WHERE user_id = $2 AND id <> $3 AND status IN ($4, $5)",
+            EMAIL_CHANGE_CANCELLED,
+            user_id,
+            except_id,
+            EMAIL_CHANGE_PENDING,
+            EMAIL_CHANGE_CONFIRMED
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    domain::{entities::invite::Invite, repositories::invite_repo::InviteRepository},
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgInviteRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgInviteRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl InviteRepository for PgInviteRepository {
+    async fn find_all(&self) -> Result<Vec<Invite>, AppError> {
+        let invites = sqlx::query_as!(Invite, "SELECT * FROM invites ORDER BY created_at DESC")
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(invites)
+    }
+
+    async fn find_pending_by_email(&self, email: &str) -> Result<Invite, AppError> {
+        let invite = sqlx::query_as!(
+            Invite,
+            "SELECT * FROM invites WHERE LOWER(email) = LOWER($1) AND accepted_at IS NULL AND expires_at > NOW() ORDER BY created_at DESC LIMIT 1",
+            email
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Invite, AppError> {
+        let invite = sqlx::query_as!(
+            Invite,
+            "SELECT * FROM invites WHERE token_hash = $1",
+            token_hash
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    async fn create(&self, entity: &Invite) -> Result<Invite, AppError> {
+        let invite = sqlx::query_as!(
+            Invite,
+            "INSERT INTO invites (id, email, token_hash, invited_by, expires_at, created_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+            entity.id,
+            entity.email,
+            entity.token_hash,
+            entity.invited_by,
+            entity.expires_at,
+            entity.created_at
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    async fn tx_accept(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &Invite,
+    ) -> Result<(), AppError> {
+        // guarded on accepted_at so two signups racing on one invite cannot both succeed
+        let result = sqlx::query!(
+            "UPDATE invites SET accepted_at = $1, accepted_user_id = $2 WHERE id = $3 AND accepted_at IS NULL",
+            entity.accepted_at,
+            entity.accepted_user_id,
+            entity.id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::RegistrationNotAllowed(
+                "invitation has already been used".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), AppError> {
+        sqlx::query!("DELETE FROM invites WHERE id = $1", id)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+}
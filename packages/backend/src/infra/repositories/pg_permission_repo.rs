@@ -0,0 +1,133 @@
+use crate::{
+    domain::{
+        entities::permission::Permission, repositories::permission_repo::PermissionRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgPermissionRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgPermissionRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PermissionRepository for PgPermissionRepository {
+    async fn find_all(&self) -> Result<Vec<Permission>, AppError> {
+        let permissions = sqlx::query_as!(
+            Permission,
+            "SELECT * FROM permissions WHERE deleted_at IS NULL ORDER BY group_name, name, action"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(permissions)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Permission, AppError> {
+        let permission = sqlx::query_as!(
+            Permission,
+            "SELECT * FROM permissions WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(permission)
+    }
+
+    async fn exists(&self, name: &str, action: &str) -> Result<bool, AppError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM permissions WHERE name = $1 AND action = $2 AND deleted_at IS NULL
+            ) AS "exists!""#,
+            name,
+            action
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn find_unknown(&self, keys: &[String]) -> Result<Vec<String>, AppError> {
+        let unknown = sqlx::query_scalar!(
+            r#"SELECT requested.key AS "key!"
+            FROM UNNEST($1::text[]) AS requested(key)
+            WHERE NOT EXISTS (
+                SELECT 1 FROM permissions
+                WHERE deleted_at IS NULL
+                AND split_part(requested.key, ':', 1) IN ('*', name)
+                AND substring(requested.key FROM position(':' IN requested.key) + 1) IN ('*', action)
+            )"#,
+            keys
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(unknown)
+    }
+
+    async fn is_in_use(&self, name: &str, action: &str) -> Result<bool, AppError> {
+        let in_use = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM casbin_rule WHERE ptype = 'p' AND v2 = $1 AND v3 = $2
+            ) AS "exists!""#,
+            name,
+            action
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(in_use)
+    }
+
+    async fn create(&self, entity: &Permission) -> Result<Permission, AppError> {
+        let permission = sqlx::query_as!(
+            Permission,
+            "INSERT INTO permissions (id, name, action, description, group_name) VALUES ($1, $2, $3, $4, $5) RETURNING *",
+            entity.id,
+            entity.name,
+            entity.action,
+            entity.description,
+            entity.group_name
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(permission)
+    }
+
+    async fn update(&self, entity: &Permission) -> Result<Permission, AppError> {
+        let permission = sqlx::query_as!(
+            Permission,
+            "UPDATE permissions SET description = $1, group_name = $2, updated_at = $3 WHERE id = $4 AND deleted_at IS NULL RETURNING *",
+            entity.description,
+            entity.group_name,
+            entity.updated_at,
+            entity.id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(permission)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        sqlx::query!(
+            "UPDATE permissions SET deleted_at = $2 WHERE id = $1",
+            id,
+            now
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    domain::repositories::policy_repo::PolicyRepository, infra::errors::app_error::AppError,
+};
+
+#[derive(Debug, Clone)]
+pub struct PgPolicyRepository {
+    pub db_pool: sqlx::PgPool,
+}
+
+impl PgPolicyRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+// the casbin adapter stores unused fields as empty strings, rows are written the same way
+fn rule_field(rule: &[String], index: usize) -> &str {
+    rule.get(index).map(String::as_str).unwrap_or("")
+}
+
+#[async_trait::async_trait]
+impl PolicyRepository for PgPolicyRepository {
+    async fn tx_replace_role_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_ids: &[String],
+        removed_role_ids: &[String],
+        policies: &[Vec<String>],
+        groupings: &[Vec<String>],
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM casbin_rule WHERE (ptype = 'p' AND v0 = ANY($1)) OR (ptype = 'g' AND (v0 = ANY($1) OR v1 = ANY($2)))",
+            role_ids,
+            removed_role_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        self.tx_add_rules(tx, "p", policies).await?;
+        self.tx_add_rules(tx, "g", groupings).await?;
+
+        Ok(())
+    }
+
+    async fn find_all_rules(&self) -> Result<Vec<(String, Vec<String>)>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT ptype, COALESCE(v0, '') AS "v0!", COALESCE(v1, '') AS "v1!", COALESCE(v2, '') AS "v2!",
+                COALESCE(v3, '') AS "v3!", COALESCE(v4, '') AS "v4!", COALESCE(v5, '') AS "v5!"
+            FROM casbin_rule WHERE ptype IN ('p', 'g') ORDER BY id"#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let mut rule = vec![row.v0, row.v1, row.v2, row.v3, row.v4, row.v5];
+                while rule.last().is_some_and(String::is_empty) {
+                    rule.pop();
+                }
+                (row.ptype, rule)
+            })
+            .collect())
+    }
+
+    async fn tx_add_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ptype: &str,
+        rules: &[Vec<String>],
+    ) -> Result<(), AppError> {
+        for rule in rules {
+            sqlx::query!(
+                "INSERT INTO casbin_rule (ptype, v0, v1, v2, v3, v4, v5) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                ptype,
+                rule_field(rule, 0),
+                rule_field(rule, 1),
+                rule_field(rule, 2),
+                rule_field(rule, 3),
+                rule_field(rule, 4),
+                rule_field(rule, 5)
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn tx_remove_rules(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ptype: &str,
+        rules: &[Vec<String>],
+    ) -> Result<(), AppError> {
+        for rule in rules {
+            sqlx::query!(
+                r#"DELETE FROM casbin_rule WHERE ptype = $1
+                AND COALESCE(v0, '') = $2 AND COALESCE(v1, '') = $3 AND COALESCE(v2, '') = $4
+                AND COALESCE(v3, '') = $5 AND COALESCE(v4, '') = $6 AND COALESCE(v5, '') = $7"#,
+                ptype,
+                rule_field(rule, 0),
+                rule_field(rule, 1),
+                rule_field(rule, 2),
+                rule_field(rule, 3),
+                rule_field(rule, 4),
+                rule_field(rule, 5)
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,17 @@
 use crate::{
     domain::{ entities::project::{Project, ProjectWithOwnerEmail}, repositories::project_repo::ProjectRepository },
-    infra::errors::app_error::AppError,
+    infra::{errors::app_error::AppError, rbac::ResourceGrant},
 };
 
+// the id of the project the grant was given for
+fn granted_id(grant: &ResourceGrant) -> Result<&str, AppError> {
+    if grant.object() != "projects" {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(grant.resource_id())
+}
+
 #[derive(Clone, Debug)]
 pub struct PgProjectRepository {
     pool: sqlx::PgPool,
@@ -24,14 +33,18 @@ impl ProjectRepository for PgProjectRepository {
         Ok(projects)
     }
 
-    async fn find_by_id_and_user_id(&self, id: &str, user_id: &str) -> Result<ProjectWithOwnerEmail, AppError> {
-      let project = sqlx::query_as!(ProjectWithOwnerEmail, "SELECT p.id, p.user_id, p.name, p.description, p.created_at, p.updated_at, p.deleted_at, u.email as user_email FROM projects p LEFT JOIN users u ON p.user_id = u.id WHERE p.id = $1 AND p.user_id = $2 AND p.deleted_at IS NULL", id, user_id)
+    async fn find_by_id(&self, id: &str) -> Result<ProjectWithOwnerEmail, AppError> {
+      let project = sqlx::query_as!(ProjectWithOwnerEmail, "SELECT p.id, p.user_id, p.name, p.description, p.created_at, p.updated_at, p.deleted_at, u.email as user_email FROM projects p LEFT JOIN users u ON p.user_id = u.id WHERE p.id = $1 AND p.deleted_at IS NULL", id)
           .fetch_one(&self.pool)
           .await?;
 
       Ok(project)
     }
-    async fn create(&self, entity: Project) -> Result<Project, AppError> {
+    async fn create(&self, grant: &ResourceGrant, entity: Project) -> Result<Project, AppError> {
+        if granted_id(grant)? != entity.id {
+            return Err(AppError::Forbidden);
+        }
+
         let project = sqlx::query_as!(
             Project,
             "INSERT INTO projects (id, user_id, name, description, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
@@ -48,14 +61,15 @@ impl ProjectRepository for PgProjectRepository {
         Ok(project)
     }
 
-    async fn update(&self, id: &str, user_id: &str, entity: Project) -> Result<ProjectWithOwnerEmail, AppError> {
+    async fn update(&self, grant: &ResourceGrant, entity: Project) -> Result<ProjectWithOwnerEmail, AppError> {
+        let id = granted_id(grant)?;
         let project = sqlx::query_as!(
             ProjectWithOwnerEmail,
-            "UPDATE projects p SET name = $1, description = $2, updated_at = NOW() FROM users u WHERE p.id = $3 AND p.user_id = $4 AND u.id = p.user_id RETURNING p.id, p.user_id, p.name, p.description, p.created_at, p.updated_at, p.deleted_at, u.email as user_email",
+            "UPDATE projects p SET name = $1, description = $2, user_id = $4, updated_at = NOW() FROM users u WHERE p.id = $3 AND p.deleted_at IS NULL AND u.id = $4 RETURNING p.id, p.user_id, p.name, p.description, p.created_at, p.updated_at, p.deleted_at, u.email as user_email",
             entity.name,
             entity.description,
             id,
-            user_id
+            entity.user_id
         )
         .fetch_one(&self.pool)
         .await?;
@@ -63,13 +77,10 @@ impl ProjectRepository for PgProjectRepository {
         Ok(project)
     }
 
-    async fn delete(
-        &self,
-        id: &str,
-        user_id: &str
-    ) -> Result<(), AppError> {
+    async fn delete(&self, grant: &ResourceGrant) -> Result<(), AppError> {
+        let id = granted_id(grant)?;
         sqlx
-            ::query!("UPDATE projects SET deleted_at = NOW() WHERE id = $1 AND user_id = $2", id, user_id)
+            ::query!("UPDATE projects SET deleted_at = NOW() WHERE id = $1", id)
             .execute(&self.pool)
             .await?;
 
@@ -0,0 +1,87 @@
+use crate::{
+    domain::{
+        entities::role_constraint::RoleConstraint,
+        repositories::role_constraint_repo::RoleConstraintRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgRoleConstraintRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgRoleConstraintRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RoleConstraintRepository for PgRoleConstraintRepository {
+    async fn find_all(&self, domain: &str) -> Result<Vec<RoleConstraint>, AppError> {
+        let constraints = sqlx::query_as!(
+            RoleConstraint,
+            "SELECT * FROM role_constraints WHERE $1 = '*' OR domain IN ('*', $1) ORDER BY domain, name",
+            domain
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(constraints)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<RoleConstraint, AppError> {
+        let constraint = sqlx::query_as!(
+            RoleConstraint,
+            "SELECT * FROM role_constraints WHERE id = $1",
+            id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(constraint)
+    }
+
+    async fn create(&self, entity: &RoleConstraint) -> Result<RoleConstraint, AppError> {
+        let constraint = sqlx::query_as!(
+            RoleConstraint,
+            "INSERT INTO role_constraints (id, name, description, domain, role_ids, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+            entity.id,
+            entity.name,
+            entity.description,
+            entity.domain,
+            &entity.role_ids,
+            entity.created_at,
+            entity.updated_at
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(constraint)
+    }
+
+    async fn update(&self, entity: &RoleConstraint) -> Result<RoleConstraint, AppError> {
+        let constraint = sqlx::query_as!(
+            RoleConstraint,
+            "UPDATE role_constraints SET name = $1, description = $2, role_ids = $3, updated_at = $4 WHERE id = $5 RETURNING *",
+            entity.name,
+            entity.description,
+            &entity.role_ids,
+            entity.updated_at,
+            entity.id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(constraint)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), AppError> {
+        sqlx::query!("DELETE FROM role_constraints WHERE id = $1", id)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+}
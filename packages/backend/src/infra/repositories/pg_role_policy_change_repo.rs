@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    domain::{
+        entities::role_policy_change::{RolePolicyChange, RolePolicySnapshot},
+        repositories::role_policy_change_repo::RolePolicyChangeRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgRolePolicyChangeRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgRolePolicyChangeRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+struct RolePolicyChangeRow {
+    id: String,
+    role_id: String,
+    role_name: String,
+    domain: String,
+    version: i32,
+    action: String,
+    actor_id: Option<String>,
+    before_permissions: Option<Vec<String>>,
+    before_denied_permissions: Option<Vec<String>>,
+    before_parents: Option<Vec<String>>,
+    after_permissions: Option<Vec<String>>,
+    after_denied_permissions: Option<Vec<String>>,
+    after_parents: Option<Vec<String>>,
+    rolled_back_to: Option<i32>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    before_conditions: Option<Vec<String>>,
+    after_conditions: Option<Vec<String>>,
+}
+
+// a snapshot is stored as arrays that are either all set or all NULL,
+// conditions as `permission=condition` entries
+fn snapshot(
+    permissions: Option<Vec<String>>,
+    denied_permissions: Option<Vec<String>>,
+    parents: Option<Vec<String>>,
+    conditions: Option<Vec<String>>,
+) -> Option<RolePolicySnapshot> {
+    permissions.map(|permissions| RolePolicySnapshot {
+        permissions,
+        denied_permissions: denied_permissions.unwrap_or_default(),
+        parents: parents.unwrap_or_default(),
+        conditions: conditions
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(permission, condition)| (permission.to_string(), condition.to_string()))
+            .collect(),
+    })
+}
+
+fn condition_entries(conditions: &BTreeMap<String, String>) -> Vec<String> {
+    conditions
+        .iter()
+        .map(|(permission, condition)| format!("{}={}", permission, condition))
+        .collect()
+}
+
+impl From<RolePolicyChangeRow> for RolePolicyChange {
+    fn from(row: RolePolicyChangeRow) -> Self {
+        Self {
+            id: row.id,
+            role_id: row.role_id,
+            role_name: row.role_name,
+            domain: row.domain,
+            version: row.version,
+            action: row.action,
+            actor_id: row.actor_id,
+            before: snapshot(
+                row.before_permissions,
+                row.before_denied_permissions,
+                row.before_parents,
+                row.before_conditions,
+            ),
+            after: snapshot(
+                row.after_permissions,
+                row.after_denied_permissions,
+                row.after_parents,
+                row.after_conditions,
+            ),
+            rolled_back_to: row.rolled_back_to,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RolePolicyChangeRepository for PgRolePolicyChangeRepository {
+    async fn paginate_by_role(
+        &self,
+        role_id: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<RolePolicyChange>, i64), AppError> {
+        let offset = (page - 1) * limit;
+
+        let rows = sqlx::query_as!(
+            RolePolicyChangeRow,
+            "SELECT * FROM role_policy_changes WHERE role_id = $1 ORDER BY version DESC LIMIT $2 OFFSET $3",
+            role_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let total_items = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM role_policy_changes WHERE role_id = $1"#,
+            role_id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok((rows.into_iter().map(RolePolicyChange::from).collect(), total_items))
+    }
+
+    async fn find_by_version(&self, role_id: &str, version: i32) -> Result<RolePolicyChange, AppError> {
+        let row = sqlx::query_as!(
+            RolePolicyChangeRow,
+            "SELECT * FROM role_policy_changes WHERE role_id = $1 AND version = $2",
+            role_id,
+            version
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn create(&self, entity: &RolePolicyChange) -> Result<RolePolicyChange, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+        let change = self.tx_create(&mut tx, entity).await?;
+        tx.commit().await?;
+
+        Ok(change)
+    }
+
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &RolePolicyChange,
+    ) -> Result<RolePolicyChange, AppError> {
+        let before = entity.before.as_ref();
+        let after = entity.after.as_ref();
+        let before_conditions = before.map(|snapshot| condition_entries(&snapshot.conditions));
+        let after_conditions = after.map(|snapshot| condition_entries(&snapshot.conditions));
+
+        let row = sqlx::query_as!(
+            RolePolicyChangeRow,
+            r#"INSERT INTO role_policy_changes (
+                id, role_id, role_name, domain, version, action, actor_id,
+                before_permissions, before_denied_permissions, before_parents,
+                after_permissions, after_denied_permissions, after_parents,
+                rolled_back_to, created_at, before_conditions, after_conditions
+            )
+            VALUES (
+                $1, $2::VARCHAR, $3, $4,
+                COALESCE((SELECT MAX(version) FROM role_policy_changes WHERE role_id = $2::VARCHAR), 0) + 1,
+                $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16
+            )
+            RETURNING *"#,
+            entity.id,
+            entity.role_id,
+            entity.role_name,
+            entity.domain,
+            entity.action,
+            entity.actor_id,
+            before.map(|snapshot| snapshot.permissions.as_slice()),
+            before.map(|snapshot| snapshot.denied_permissions.as_slice()),
+            before.map(|snapshot| snapshot.parents.as_slice()),
+            after.map(|snapshot| snapshot.permissions.as_slice()),
+            after.map(|snapshot| snapshot.denied_permissions.as_slice()),
+            after.map(|snapshot| snapshot.parents.as_slice()),
+            entity.rolled_back_to,
+            entity.created_at,
+            before_conditions.as_deref(),
+            after_conditions.as_deref()
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(row.into())
+    }
+}
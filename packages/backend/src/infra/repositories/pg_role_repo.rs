@@ -1,6 +1,6 @@
 use crate::{
     domain::{
-        entities::role::{Role, RoleCount},
+        entities::role::{AssignedRole, Role, RoleCount},
         repositories::role_repo::RoleRepository,
     },
     infra::errors::app_error::AppError,
@@ -19,12 +19,18 @@ impl PgRoleRepository {
 
 #[async_trait::async_trait]
 impl RoleRepository for PgRoleRepository {
-    async fn paginate(&self, page: i64, limit: i64) -> Result<(Vec<Role>, i64), AppError> {
+    async fn paginate(
+        &self,
+        domain: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<Role>, i64), AppError> {
         let offset = (page - 1) * limit;
 
         let roles = sqlx::query_as!(
             Role,
-            "SELECT * FROM roles WHERE deleted_at IS NULL LIMIT $1 OFFSET $2",
+            "SELECT * FROM roles WHERE deleted_at IS NULL AND domain IN ('*', $1) LIMIT $2 OFFSET $3",
+            domain,
             limit,
             offset
         )
@@ -33,7 +39,8 @@ impl RoleRepository for PgRoleRepository {
 
         let count = sqlx::query_as!(
             RoleCount,
-            "SELECT COUNT(*) AS total_items FROM roles WHERE deleted_at IS NULL"
+            "SELECT COUNT(*) AS total_items FROM roles WHERE deleted_at IS NULL AND domain IN ('*', $1)",
+            domain
         )
         .fetch_one(&self.db_pool)
         .await?;
@@ -41,10 +48,25 @@ impl RoleRepository for PgRoleRepository {
         Ok((roles, count.total_items.unwrap_or(0)))
     }
 
-    async fn find_all(&self) -> Result<Vec<Role>, AppError> {
-        let roles = sqlx::query_as!(Role, "SELECT * FROM roles WHERE deleted_at IS NULL")
-            .fetch_all(&self.db_pool)
-            .await?;
+    async fn find_all(&self, domain: &str) -> Result<Vec<Role>, AppError> {
+        let roles = sqlx::query_as!(
+            Role,
+            "SELECT * FROM roles WHERE deleted_at IS NULL AND domain IN ('*', $1)",
+            domain
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(roles)
+    }
+
+    async fn find_all_across_domains(&self) -> Result<Vec<Role>, AppError> {
+        let roles = sqlx::query_as!(
+            Role,
+            "SELECT * FROM roles WHERE deleted_at IS NULL ORDER BY domain, name"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
 
         Ok(roles)
     }
@@ -64,7 +86,7 @@ impl RoleRepository for PgRoleRepository {
     async fn find_default(&self) -> Result<Role, AppError> {
         let role = sqlx::query_as!(
             Role,
-            "SELECT * FROM roles WHERE is_default = true AND deleted_at IS NULL"
+            "SELECT * FROM roles WHERE is_default = true AND domain = '*' AND deleted_at IS NULL"
         )
         .fetch_one(&self.db_pool)
         .await?;
@@ -72,11 +94,12 @@ impl RoleRepository for PgRoleRepository {
         Ok(role)
     }
 
-    async fn find_by_name(&self, role_name: &str) -> Result<Role, AppError> {
+    async fn find_by_name(&self, role_name: &str, domain: &str) -> Result<Role, AppError> {
         let role = sqlx::query_as!(
             Role,
-            "SELECT * FROM roles WHERE name = $1 AND deleted_at IS NULL",
-            role_name
+            "SELECT * FROM roles WHERE name = $1 AND domain = $2 AND deleted_at IS NULL",
+            role_name,
+            domain
         )
         .fetch_one(&self.db_pool)
         .await?;
@@ -87,10 +110,11 @@ impl RoleRepository for PgRoleRepository {
     async fn create(&self, entity: Role) -> Result<Role, AppError> {
         let role = sqlx::query_as!(
             Role,
-            "INSERT INTO roles (id, name, is_default) VALUES ($1, $2, $3) RETURNING *",
+            "INSERT INTO roles (id, name, is_default, domain) VALUES ($1, $2, $3, $4) RETURNING *",
             entity.id,
             entity.name,
-            entity.is_default
+            entity.is_default,
+            entity.domain
         )
         .fetch_one(&self.db_pool)
         .await?;
@@ -105,10 +129,11 @@ impl RoleRepository for PgRoleRepository {
     ) -> Result<Role, AppError> {
         let role = sqlx::query_as!(
             Role,
-            "INSERT INTO roles (id, name, is_default) VALUES ($1, $2, $3) RETURNING *",
+            "INSERT INTO roles (id, name, is_default, domain) VALUES ($1, $2, $3, $4) RETURNING *",
             entity.id,
             entity.name,
-            entity.is_default
+            entity.is_default,
+            entity.domain
         )
         .fetch_one(&mut **tx)
         .await?;
@@ -138,15 +163,81 @@ impl RoleRepository for PgRoleRepository {
         Ok(())
     }
 
-    async fn get_roles_by_user_id(&self, user_id: &str) -> Result<Vec<Role>, AppError> {
-        let roles = sqlx::query_as!(
-            Role,
-            "SELECT roles.* FROM roles INNER JOIN user_roles ON roles.id = user_roles.role_id WHERE user_roles.user_id = $1 AND roles.deleted_at IS NULL",
+    async fn tx_update(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &Role,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE roles SET name = $1, is_default = $2, updated_at = $3 WHERE id = $4",
+            entity.name,
+            entity.is_default,
+            entity.updated_at,
+            entity.id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn tx_delete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        sqlx::query!("UPDATE roles SET deleted_at = $2 WHERE id = $1", id, now)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn tx_release_deleted_name(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_name: &str,
+        domain: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE roles SET name = LEFT(name, 200) || '#' || id WHERE name = $1 AND domain = $2 AND deleted_at IS NOT NULL",
+            role_name,
+            domain
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_roles_by_user_id(&self, user_id: &str) -> Result<Vec<AssignedRole>, AppError> {
+        let rows = sqlx::query!(
+            // grants that have not started yet are kept so they apply without a cache refresh
+            "SELECT roles.*, user_roles.domain AS assigned_domain, user_roles.starts_at, user_roles.expires_at FROM roles INNER JOIN user_roles ON roles.id = user_roles.role_id WHERE user_roles.user_id = $1 AND roles.deleted_at IS NULL AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())",
             user_id
         )
         .fetch_all(&self.db_pool)
         .await?;
 
+        let roles = rows
+            .into_iter()
+            .map(|row| AssignedRole {
+                role: Role {
+                    id: row.id,
+                    name: row.name,
+                    is_default: row.is_default,
+                    domain: row.domain,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                    deleted_at: row.deleted_at,
+                },
+                assigned_domain: row.assigned_domain,
+                starts_at: row.starts_at,
+                expires_at: row.expires_at,
+            })
+            .collect();
+
         Ok(roles)
     }
 }
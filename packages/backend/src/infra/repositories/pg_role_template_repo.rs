@@ -0,0 +1,71 @@
+use crate::{
+    domain::{
+        entities::role_template::RoleTemplateLink,
+        repositories::role_template_repo::RoleTemplateRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgRoleTemplateRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgRoleTemplateRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RoleTemplateRepository for PgRoleTemplateRepository {
+    async fn find_by_template(&self, template_key: &str) -> Result<Vec<RoleTemplateLink>, AppError> {
+        let links = sqlx::query_as!(
+            RoleTemplateLink,
+            "SELECT l.role_id, l.template_key, l.template_version, l.permissions, l.synced_at
+            FROM role_template_links l JOIN roles r ON r.id = l.role_id
+            WHERE l.template_key = $1 AND r.deleted_at IS NULL ORDER BY r.name",
+            template_key
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    async fn find_all(&self) -> Result<Vec<RoleTemplateLink>, AppError> {
+        let links = sqlx::query_as!(
+            RoleTemplateLink,
+            "SELECT l.role_id, l.template_key, l.template_version, l.permissions, l.synced_at
+            FROM role_template_links l JOIN roles r ON r.id = l.role_id
+            WHERE r.deleted_at IS NULL ORDER BY r.name"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    async fn tx_save(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &RoleTemplateLink,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO role_template_links (role_id, template_key, template_version, permissions, synced_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (role_id) DO UPDATE SET template_key = EXCLUDED.template_key,
+                template_version = EXCLUDED.template_version, permissions = EXCLUDED.permissions,
+                synced_at = EXCLUDED.synced_at",
+            entity.role_id,
+            entity.template_key,
+            entity.template_version,
+            &entity.permissions,
+            entity.synced_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    domain::{
+        entities::saml_identity_provider::SamlIdentityProvider,
+        repositories::saml_provider_repo::SamlProviderRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgSamlProviderRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgSamlProviderRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SamlProviderRepository for PgSamlProviderRepository {
+    async fn find_all(&self) -> Result<Vec<SamlIdentityProvider>, AppError> {
+        let providers = sqlx::query_as!(
+            SamlIdentityProvider,
+            "SELECT * FROM saml_identity_providers WHERE deleted_at IS NULL ORDER BY name"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(providers)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<SamlIdentityProvider, AppError> {
+        let provider = sqlx::query_as!(
+            SamlIdentityProvider,
+            "SELECT * FROM saml_identity_providers WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(provider)
+    }
+
+    async fn find_active_by_slug(&self, slug: &str) -> Result<SamlIdentityProvider, AppError> {
+        let provider = sqlx::query_as!(
+            SamlIdentityProvider,
+            "SELECT * FROM saml_identity_providers WHERE slug = $1 AND is_active = true AND deleted_at IS NULL",
+            slug
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(provider)
+    }
+
+    async fn create(&self, entity: SamlIdentityProvider) -> Result<SamlIdentityProvider, AppError> {
+        let provider = sqlx::query_as!(
+            SamlIdentityProvider,
+            "INSERT INTO saml_identity_providers (id, slug, name, entity_id, sso_url, x509_cert, email_attribute, name_attribute, allow_idp_initiated, is_active) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+            entity.id,
+            entity.slug,
+            entity.name,
+            entity.entity_id,
+            entity.sso_url,
+            entity.x509_cert,
+            entity.email_attribute,
+            entity.name_attribute,
+            entity.allow_idp_initiated,
+            entity.is_active
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(provider)
+    }
+
+    async fn update(&self, entity: &SamlIdentityProvider) -> Result<SamlIdentityProvider, AppError> {
+        let provider = sqlx::query_as!(
+            SamlIdentityProvider,
+            "UPDATE saml_identity_providers SET slug = $1, name = $2, entity_id = $3, sso_url = $4, x509_cert = $5, email_attribute = $6, name_attribute = $7, allow_idp_initiated = $8, is_active = $9, updated_at = $10 WHERE id = $11 AND deleted_at IS NULL RETURNING *",
+            entity.slug,
+            entity.name,
+            entity.entity_id,
+            entity.sso_url,
+            entity.x509_cert,
+            entity.email_attribute,
+            entity.name_attribute,
+            entity.allow_idp_initiated,
+            entity.is_active,
+            entity.updated_at,
+            entity.id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(provider)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), AppError> {
+        let now = chrono::Utc::now();
+        sqlx::query!(
+            "UPDATE saml_identity_providers SET deleted_at = $2, is_active = false WHERE id = $1",
+            id,
+            now
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -43,6 +43,18 @@ impl UserRepository for PgUserRepository {
         Ok(provider)
     }
 
+    async fn tx_lock(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: &str,
+    ) -> Result<User, AppError> {
+        let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1 FOR UPDATE", id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        Ok(user)
+    }
+
     async fn tx_create(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -116,9 +128,10 @@ impl UserRepository for PgUserRepository {
 
         let user_role = sqlx::query_as!(
             UserRole,
-            "INSERT INTO user_roles (user_id, role_id, created_at, updated_at) VALUES ($1, $2, $3, $4) RETURNING *",
+            "INSERT INTO user_roles (user_id, role_id, domain, created_at, updated_at) VALUES ($1, $2, $3, $4, $5) RETURNING *",
             user_role.user_id,
             user_role.role_id,
+            user_role.domain,
             user_role.created_at,
             user_role.updated_at,
         )
@@ -127,4 +140,26 @@ impl UserRepository for PgUserRepository {
 
         Ok((user, user_oauth_provider, user_role))
     }
+
+    async fn tx_add_role(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_role: &UserRole,
+    ) -> Result<UserRole, AppError> {
+        let user_role = sqlx::query_as!(
+            UserRole,
+            "INSERT INTO user_roles (user_id, role_id, domain, created_at, updated_at, starts_at, expires_at) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (user_id, role_id, domain) DO UPDATE SET updated_at = EXCLUDED.updated_at, starts_at = EXCLUDED.starts_at, expires_at = EXCLUDED.expires_at RETURNING *",
+            user_role.user_id,
+            user_role.role_id,
+            user_role.domain,
+            user_role.created_at,
+            user_role.updated_at,
+            user_role.starts_at,
+            user_role.expires_at,
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(user_role)
+    }
 }
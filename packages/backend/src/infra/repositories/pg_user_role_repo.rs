@@ -0,0 +1,226 @@
+use crate::{
+    domain::{
+        entities::user_role::{RoleMember, UserRole, UserRoleExpiration},
+        repositories::user_role_repo::UserRoleRepository,
+    },
+    infra::errors::app_error::AppError,
+};
+
+#[derive(Clone, Debug)]
+pub struct PgUserRoleRepository {
+    db_pool: sqlx::PgPool,
+}
+
+impl PgUserRoleRepository {
+    pub fn new(db_pool: sqlx::PgPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRoleRepository for PgUserRoleRepository {
+    async fn paginate_members(
+        &self,
+        role_id: &str,
+        domain: &str,
+        page: i64,
+        limit: i64,
+    ) -> Result<(Vec<RoleMember>, i64), AppError> {
+        let offset = (page - 1) * limit;
+
+        let members = sqlx::query_as!(
+            RoleMember,
+            r#"SELECT users.id AS user_id, users.email, users.fullname, user_roles.domain, user_roles.created_at AS assigned_at, user_roles.starts_at, user_roles.expires_at
+            FROM user_roles INNER JOIN users ON users.id = user_roles.user_id
+            WHERE user_roles.role_id = $1 AND ($2 = '*' OR user_roles.domain = $2) AND users.deleted_at IS NULL
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())
+            ORDER BY user_roles.created_at, users.email
+            LIMIT $3 OFFSET $4"#,
+            role_id,
+            domain,
+            limit,
+            offset
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let total_items = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "total_items!"
+            FROM user_roles INNER JOIN users ON users.id = user_roles.user_id
+            WHERE user_roles.role_id = $1 AND ($2 = '*' OR user_roles.domain = $2) AND users.deleted_at IS NULL
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())"#,
+            role_id,
+            domain
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok((members, total_items))
+    }
+
+    async fn find_members_by_role_ids(
+        &self,
+        role_ids: &[String],
+        domain: &str,
+    ) -> Result<Vec<RoleMember>, AppError> {
+        let members = sqlx::query_as!(
+            RoleMember,
+            r#"SELECT users.id AS user_id, users.email, users.fullname, user_roles.domain, user_roles.created_at AS assigned_at, user_roles.starts_at, user_roles.expires_at
+            FROM user_roles INNER JOIN users ON users.id = user_roles.user_id
+            WHERE user_roles.role_id = ANY($1) AND (user_roles.domain = '*' OR user_roles.domain = $2) AND users.deleted_at IS NULL
+            AND (user_roles.starts_at IS NULL OR user_roles.starts_at <= NOW())
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())
+            ORDER BY users.email"#,
+            role_ids,
+            domain
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    async fn find_unexpired(&self) -> Result<Vec<UserRole>, AppError> {
+        let user_roles = sqlx::query_as!(
+            UserRole,
+            r#"SELECT user_roles.* FROM user_roles
+            INNER JOIN users ON users.id = user_roles.user_id
+            INNER JOIN roles ON roles.id = user_roles.role_id
+            WHERE users.deleted_at IS NULL AND roles.deleted_at IS NULL
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())
+            ORDER BY user_roles.user_id, user_roles.created_at"#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(user_roles)
+    }
+
+    async fn tx_create(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &UserRole,
+    ) -> Result<UserRole, AppError> {
+        let user_role = sqlx::query_as!(
+            UserRole,
+            "INSERT INTO user_roles (user_id, role_id, domain, created_at, updated_at, starts_at, expires_at) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (user_id, role_id, domain) DO UPDATE SET updated_at = EXCLUDED.updated_at, starts_at = EXCLUDED.starts_at, expires_at = EXCLUDED.expires_at RETURNING *",
+            entity.user_id,
+            entity.role_id,
+            entity.domain,
+            entity.created_at,
+            entity.updated_at,
+            entity.starts_at,
+            entity.expires_at,
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(user_role)
+    }
+
+    async fn tx_lock_by_user_id(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+    ) -> Result<Vec<UserRole>, AppError> {
+        // the user's row rather than their grants, a user without any has no rows to lock. A user
+        // still being registered has no row yet, nobody else can grant them roles either.
+        sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        let user_roles = sqlx::query_as!(
+            UserRole,
+            r#"SELECT user_roles.* FROM user_roles
+            INNER JOIN roles ON roles.id = user_roles.role_id
+            WHERE user_roles.user_id = $1 AND roles.deleted_at IS NULL
+            AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW())"#,
+            user_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(user_roles)
+    }
+
+    async fn tx_lock_holders(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_id: &str,
+    ) -> Result<i64, AppError> {
+        let mut holders = sqlx::query_scalar!(
+            "SELECT user_roles.user_id FROM user_roles INNER JOIN users ON users.id = user_roles.user_id WHERE user_roles.role_id = $1 AND users.deleted_at IS NULL AND (user_roles.starts_at IS NULL OR user_roles.starts_at <= NOW()) AND (user_roles.expires_at IS NULL OR user_roles.expires_at > NOW()) FOR UPDATE OF user_roles",
+            role_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        // a user may hold the role in several domains
+        holders.sort();
+        holders.dedup();
+
+        Ok(holders.len() as i64)
+    }
+
+    async fn tx_delete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: &str,
+        role_id: &str,
+        domain: &str,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM user_roles WHERE user_id = $1 AND role_id = $2 AND domain = $3",
+            user_id,
+            role_id,
+            domain
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn tx_delete_expired(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        limit: i64,
+    ) -> Result<Vec<UserRole>, AppError> {
+        let expired = sqlx::query_as!(
+            UserRole,
+            r#"DELETE FROM user_roles WHERE (user_id, role_id, domain) IN (
+                SELECT user_id, role_id, domain FROM user_roles
+                WHERE expires_at <= NOW()
+                ORDER BY expires_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            ) RETURNING *"#,
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(expired)
+    }
+
+    async fn tx_record_expiration(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity: &UserRoleExpiration,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO user_role_expirations (id, user_id, role_id, domain, starts_at, expires_at, expired_at) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            entity.id,
+            entity.user_id,
+            entity.role_id,
+            entity.domain,
+            entity.starts_at,
+            entity.expires_at,
+            entity.expired_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -45,12 +45,13 @@ impl UserSessionRepository for PgUserSessionRepository {
     async fn create(&self, entity: UserSession) -> Result<UserSession, AppError> {
         let session = sqlx::query_as!(
             UserSession,
-            "INSERT INTO user_sessions (id, user_id, access_token, refresh_token, created_at) VALUES ($1, $2, $3, $4, $5) RETURNING *",
+            "INSERT INTO user_sessions (id, user_id, access_token, refresh_token, created_at, authenticated_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
             entity.id,
             entity.user_id,
             entity.access_token,
             entity.refresh_token,
-            entity.created_at
+            entity.created_at,
+            entity.authenticated_at
         )
         .fetch_one(&self.pool)
         .await?;
@@ -71,6 +72,18 @@ impl UserSessionRepository for PgUserSessionRepository {
         Ok(())
     }
 
+    async fn update_authenticated_at(&self, session: &UserSession) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE user_sessions SET authenticated_at = $1 WHERE user_id = $2",
+            session.authenticated_at,
+            session.user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     async fn delete_by_id(&self, session_id: &str) -> Result<(), AppError> {
         sqlx::query!("DELETE FROM user_sessions WHERE id = $1", session_id)
             .execute(&self.pool)
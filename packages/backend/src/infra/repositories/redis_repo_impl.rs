@@ -1,4 +1,4 @@
-use bb8_redis::{ bb8::Pool, redis::AsyncCommands, RedisConnectionManager };
+use bb8_redis::{ bb8::Pool, redis::{ AsyncCommands, SetExpiry, SetOptions, ExistenceCheck }, RedisConnectionManager };
 
 use crate::{
     domain::repositories::redis_repo::RedisRepository,
@@ -47,6 +47,34 @@ impl RedisRepository for RedisRepositoryImpl {
         Ok(())
     }
 
+    async fn set_value_if_absent_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        expiry: u64
+    ) -> Result<bool, AppError> {
+        let mut conn = self.pool.get().await?;
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(expiry));
+        let result: Option<String> = conn.set_options(key, value, options).await?;
+
+        Ok(result.is_some())
+    }
+
+    // fixed window counter, the expiry is only set by the first increment
+    async fn increment_with_expiry(&self, key: &str, expiry: i64) -> Result<i64, AppError> {
+        let mut conn = self.pool.get().await?;
+
+        let count: i64 = conn.incr(key, 1).await?;
+        if count == 1 {
+            let _: () = conn.expire(key, expiry).await?;
+        }
+
+        Ok(count)
+    }
+
     async fn delete_value(&self, key: &str) -> Result<(), AppError> {
         let mut conn = self.pool.get().await.unwrap();
 
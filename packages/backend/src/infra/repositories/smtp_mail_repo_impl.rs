@@ -0,0 +1,56 @@
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+
+use crate::{domain::repositories::mail_repo::MailRepository, infra::errors::app_error::AppError};
+
+#[derive(Clone)]
+pub struct SmtpMailRepositoryImpl {
+    from: String,
+    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+}
+
+impl SmtpMailRepositoryImpl {
+    pub fn new(from: String, transport: Option<AsyncSmtpTransport<Tokio1Executor>>) -> Self {
+        Self { from, transport }
+    }
+}
+
+#[async_trait::async_trait]
+impl MailRepository for SmtpMailRepositoryImpl {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError> {
+        let Some(transport) = &self.transport else {
+            tracing::info!(
+                "[Mail] SMTP is not configured, mail to {} with subject {:?}:\n{}",
+                to,
+                subject,
+                body
+            );
+            return Ok(());
+        };
+
+        let from = self
+            .from
+            .parse::<Mailbox>()
+            .map_err(|err| AppError::ProcessError(format!("Invalid MAIL_FROM: {}", err)))?;
+        let to = to
+            .parse::<Mailbox>()
+            .map_err(|err| AppError::ProcessError(format!("Invalid recipient: {}", err)))?;
+
+        let message = Message::builder()
+            .from(from)
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())
+            .map_err(|err| AppError::ProcessError(err.to_string()))?;
+
+        transport.send(message).await.map_err(|err| {
+            tracing::error!("[Mail] failed to send mail: {}", err);
+            AppError::ProcessError("Failed to send email".to_string())
+        })?;
+
+        Ok(())
+    }
+}
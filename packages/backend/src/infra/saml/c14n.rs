@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use roxmltree::{Node, NodeId, NodeType};
+
+/// Exclusive XML Canonicalization 1.0 (without comments) of the subtree rooted at `node`.
+///
+/// `exclude` is skipped while rendering, which is how the enveloped-signature transform
+/// is applied. `inclusive_prefixes` is the `InclusiveNamespaces PrefixList` of the
+/// transform, `#default` standing for the default namespace.
+pub fn canonicalize(node: Node, exclude: Option<NodeId>, inclusive_prefixes: &[String]) -> String {
+    let mut out = String::new();
+    render_element(&mut out, node, exclude, inclusive_prefixes, &BTreeMap::new());
+    out
+}
+
+fn render_element(
+    out: &mut String,
+    node: Node,
+    exclude: Option<NodeId>,
+    inclusive_prefixes: &[String],
+    rendered: &BTreeMap<String, String>,
+) {
+    let qname = element_qname(node);
+    let prefix = qname.split_once(':').map(|(prefix, _)| prefix).unwrap_or("");
+
+    // prefixes visibly utilized by this element and its attributes
+    let mut utilized = vec![prefix.to_string()];
+    for attr in node.attributes() {
+        if let Some((attr_prefix, _)) = attribute_qname(node, &attr).split_once(':') {
+            utilized.push(attr_prefix.to_string());
+        }
+    }
+    for inclusive in inclusive_prefixes {
+        if inclusive == "#default" {
+            utilized.push(String::new());
+        } else if node.lookup_namespace_uri(Some(inclusive)).is_some() {
+            utilized.push(inclusive.clone());
+        }
+    }
+
+    let mut declarations = BTreeMap::new();
+    for utilized_prefix in utilized {
+        if utilized_prefix == "xml" {
+            continue;
+        }
+
+        let uri = if utilized_prefix.is_empty() {
+            node.default_namespace().unwrap_or_default()
+        } else {
+            match node.lookup_namespace_uri(Some(&utilized_prefix)) {
+                Some(uri) => uri,
+                None => continue,
+            }
+        };
+
+        let already_rendered = match rendered.get(&utilized_prefix) {
+            Some(rendered_uri) => rendered_uri == uri,
+            // an empty default namespace never needs to be declared at the top
+            None => utilized_prefix.is_empty() && uri.is_empty(),
+        };
+
+        if !already_rendered {
+            declarations.insert(utilized_prefix, uri.to_string());
+        }
+    }
+
+    out.push('<');
+    out.push_str(&qname);
+
+    for (ns_prefix, uri) in &declarations {
+        if ns_prefix.is_empty() {
+            out.push_str(" xmlns=\"");
+        } else {
+            out.push_str(" xmlns:");
+            out.push_str(ns_prefix);
+            out.push_str("=\"");
+        }
+        out.push_str(&escape_attribute(uri));
+        out.push('"');
+    }
+
+    let mut attributes = node
+        .attributes()
+        .map(|attr| {
+            (
+                attr.namespace().unwrap_or_default().to_string(),
+                attr.name().to_string(),
+                attribute_qname(node, &attr),
+                attr.value().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    attributes.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+    for (_, _, attr_qname, value) in attributes {
+        out.push(' ');
+        out.push_str(&attr_qname);
+        out.push_str("=\"");
+        out.push_str(&escape_attribute(&value));
+        out.push('"');
+    }
+    out.push('>');
+
+    let mut child_rendered = rendered.clone();
+    child_rendered.extend(declarations);
+
+    for child in node.children() {
+        if Some(child.id()) == exclude {
+            continue;
+        }
+
+        match child.node_type() {
+            NodeType::Element => {
+                render_element(out, child, exclude, inclusive_prefixes, &child_rendered)
+            }
+            NodeType::Text => out.push_str(&escape_text(child.text().unwrap_or_default())),
+            NodeType::PI => {
+                if let Some(pi) = child.pi() {
+                    out.push_str("<?");
+                    out.push_str(pi.target);
+                    if let Some(value) = pi.value {
+                        out.push(' ');
+                        out.push_str(value);
+                    }
+                    out.push_str("?>");
+                }
+            }
+            NodeType::Comment | NodeType::Root => {}
+        }
+    }
+
+    out.push_str("</");
+    out.push_str(&qname);
+    out.push('>');
+}
+
+// roxmltree resolves namespaces but does not keep prefixes, so read them back from the input
+fn element_qname(node: Node) -> String {
+    let input = node.document().input_text();
+    input[node.range().start + 1..]
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn attribute_qname(node: Node, attr: &roxmltree::Attribute) -> String {
+    node.document().input_text()[attr.range_qname()].to_string()
+}
+
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#x9;"),
+            '\n' => escaped.push_str("&#xA;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
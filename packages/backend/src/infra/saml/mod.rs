@@ -0,0 +1,4 @@
+pub mod c14n;
+pub mod constants;
+pub mod request;
+pub mod response;
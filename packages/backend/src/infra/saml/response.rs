@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use rsa::{
+    pkcs1v15::{Signature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+    signature::Verifier,
+    RsaPublicKey,
+};
+use sha2::{Digest, Sha256};
+use x509_cert::der::{Decode, Encode};
+
+use crate::infra::errors::app_error::AppError;
+
+use super::{
+    c14n::canonicalize,
+    constants::{
+        ENVELOPED_SIGNATURE, EXC_C14N, RSA_SHA256, SAML_ASSERTION_NS, SAML_BEARER_METHOD,
+        SAML_PROTOCOL_NS, SAML_STATUS_SUCCESS, SHA256, XMLDSIG_NS,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct SamlAssertion {
+    pub id: String,
+    pub issuer: String,
+    pub name_id: String,
+    pub name_id_format: Option<String>,
+    pub in_response_to: Option<String>,
+    pub not_on_or_after: DateTime<Utc>,
+    pub attributes: HashMap<String, Vec<String>>,
+}
+
+impl SamlAssertion {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .get(name)
+            .and_then(|values| values.first())
+            .map(|value| value.as_str())
+    }
+}
+
+pub struct SamlValidation<'a> {
+    pub idp_entity_id: &'a str,
+    pub idp_certificate: &'a str,
+    pub sp_entity_id: &'a str,
+    pub acs_url: &'a str,
+    pub clock_skew: chrono::Duration,
+}
+
+/// Verifies a base64-decoded `samlp:Response` and returns its single signed assertion.
+///
+/// Only the configured IdP certificate is trusted, the signature must cover either the
+/// assertion or the whole response, and everything read afterwards comes from that
+/// signed element, so wrapped or injected assertions are rejected.
+pub fn verify_response(xml: &str, validation: &SamlValidation) -> Result<SamlAssertion, AppError> {
+    verify_response_at(xml, validation, Utc::now())
+}
+
+// validity windows are checked against `now`
+fn verify_response_at(
+    xml: &str,
+    validation: &SamlValidation,
+    now: DateTime<Utc>,
+) -> Result<SamlAssertion, AppError> {
+    let doc = Document::parse(xml).map_err(|err| invalid(&format!("malformed XML: {}", err)))?;
+    let response = doc.root_element();
+
+    if !response.has_tag_name((SAML_PROTOCOL_NS, "Response")) {
+        return Err(invalid("root element is not a SAML Response"));
+    }
+
+    let status = child(response, SAML_PROTOCOL_NS, "Status")
+        .and_then(|status| child(status, SAML_PROTOCOL_NS, "StatusCode"))
+        .and_then(|code| code.attribute("Value"))
+        .unwrap_or_default();
+    if status != SAML_STATUS_SUCCESS {
+        return Err(invalid(&format!("IdP returned status {}", status)));
+    }
+
+    if doc
+        .descendants()
+        .any(|node| node.has_tag_name((SAML_ASSERTION_NS, "EncryptedAssertion")))
+    {
+        return Err(invalid("encrypted assertions are not supported"));
+    }
+
+    let assertions = doc
+        .descendants()
+        .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "Assertion")))
+        .collect::<Vec<_>>();
+    let assertion = match assertions.as_slice() {
+        [assertion] if assertion.parent_element() == Some(response) => *assertion,
+        _ => return Err(invalid("response must contain exactly one assertion")),
+    };
+
+    // the ID keys the replay cache, assertions without one would all share a single entry
+    let assertion_id = assertion.attribute("ID").unwrap_or_default().trim().to_string();
+    if assertion_id.is_empty() {
+        return Err(invalid("assertion has no ID"));
+    }
+
+    let certificate = parse_certificate(validation.idp_certificate)?;
+
+    // the assertion signature wins, a signed response also covers its assertion
+    let signed = if let Some(signature) = child(assertion, XMLDSIG_NS, "Signature") {
+        verify_signature(&doc, assertion, signature, &certificate)?;
+        assertion
+    } else if let Some(signature) = child(response, XMLDSIG_NS, "Signature") {
+        verify_signature(&doc, response, signature, &certificate)?;
+        response
+    } else {
+        return Err(invalid("neither the response nor the assertion is signed"));
+    };
+
+    if signed == response
+        && let Some(issuer) = child(response, SAML_ASSERTION_NS, "Issuer")
+        && text(issuer) != validation.idp_entity_id
+    {
+        return Err(invalid("response issuer does not match the IdP"));
+    }
+
+    let skew = validation.clock_skew;
+
+    let issuer = child(assertion, SAML_ASSERTION_NS, "Issuer")
+        .map(text)
+        .unwrap_or_default();
+    if issuer != validation.idp_entity_id {
+        return Err(invalid("assertion issuer does not match the IdP"));
+    }
+
+    let subject = child(assertion, SAML_ASSERTION_NS, "Subject")
+        .ok_or_else(|| invalid("assertion has no subject"))?;
+    let name_id = child(subject, SAML_ASSERTION_NS, "NameID")
+        .ok_or_else(|| invalid("assertion has no NameID"))?;
+
+    let confirmation_data = subject
+        .children()
+        .filter(|node| {
+            node.has_tag_name((SAML_ASSERTION_NS, "SubjectConfirmation"))
+                && node.attribute("Method") == Some(SAML_BEARER_METHOD)
+        })
+        .find_map(|node| child(node, SAML_ASSERTION_NS, "SubjectConfirmationData"))
+        .ok_or_else(|| invalid("assertion has no bearer subject confirmation"))?;
+
+    if confirmation_data.attribute("Recipient") != Some(validation.acs_url) {
+        return Err(invalid("assertion recipient does not match this service provider"));
+    }
+
+    let confirmation_expiry = parse_instant(confirmation_data.attribute("NotOnOrAfter"))?
+        .ok_or_else(|| invalid("subject confirmation has no expiry"))?;
+    if confirmation_expiry + skew <= now {
+        return Err(invalid("subject confirmation has expired"));
+    }
+
+    let conditions = child(assertion, SAML_ASSERTION_NS, "Conditions")
+        .ok_or_else(|| invalid("assertion has no conditions"))?;
+
+    if let Some(not_before) = parse_instant(conditions.attribute("NotBefore"))?
+        && not_before - skew > now
+    {
+        return Err(invalid("assertion is not yet valid"));
+    }
+
+    let not_on_or_after = parse_instant(conditions.attribute("NotOnOrAfter"))?
+        .unwrap_or(confirmation_expiry);
+    if not_on_or_after + skew <= now {
+        return Err(invalid("assertion has expired"));
+    }
+
+    let audience_allowed = conditions
+        .children()
+        .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "AudienceRestriction")))
+        .all(|restriction| {
+            restriction
+                .children()
+                .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "Audience")))
+                .any(|audience| text(audience) == validation.sp_entity_id)
+        });
+    if !audience_allowed {
+        return Err(invalid("assertion audience does not include this service provider"));
+    }
+
+    let mut attributes: HashMap<String, Vec<String>> = HashMap::new();
+    for statement in assertion
+        .children()
+        .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "AttributeStatement")))
+    {
+        for attribute in statement
+            .children()
+            .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "Attribute")))
+        {
+            let name = attribute.attribute("Name").unwrap_or_default().to_string();
+            let values = attribute
+                .children()
+                .filter(|node| node.has_tag_name((SAML_ASSERTION_NS, "AttributeValue")))
+                .map(text);
+            attributes.entry(name).or_default().extend(values);
+        }
+    }
+
+    Ok(SamlAssertion {
+        id: assertion_id,
+        issuer,
+        name_id: text(name_id),
+        name_id_format: name_id.attribute("Format").map(|format| format.to_string()),
+        in_response_to: confirmation_data
+            .attribute("InResponseTo")
+            .map(|id| id.to_string()),
+        not_on_or_after,
+        attributes,
+    })
+}
+
+fn verify_signature(
+    doc: &Document,
+    signed: Node,
+    signature: Node,
+    certificate: &RsaPublicKey,
+) -> Result<(), AppError> {
+    let signed_id = signed
+        .attribute("ID")
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| invalid("signed element has no ID"))?;
+
+    // duplicated IDs are the classic signature wrapping vector
+    if doc
+        .descendants()
+        .filter(|node| node.attribute("ID") == Some(signed_id))
+        .count()
+        != 1
+    {
+        return Err(invalid("signed element ID is not unique"));
+    }
+
+    let signed_info = child(signature, XMLDSIG_NS, "SignedInfo")
+        .ok_or_else(|| invalid("signature has no SignedInfo"))?;
+
+    let c14n_method = child(signed_info, XMLDSIG_NS, "CanonicalizationMethod")
+        .ok_or_else(|| invalid("signature has no canonicalization method"))?;
+    if c14n_method.attribute("Algorithm") != Some(EXC_C14N) {
+        return Err(invalid("unsupported canonicalization method"));
+    }
+
+    let signature_method = child(signed_info, XMLDSIG_NS, "SignatureMethod")
+        .and_then(|method| method.attribute("Algorithm"));
+    if signature_method != Some(RSA_SHA256) {
+        return Err(invalid("unsupported signature method"));
+    }
+
+    let references = signed_info
+        .children()
+        .filter(|node| node.has_tag_name((XMLDSIG_NS, "Reference")))
+        .collect::<Vec<_>>();
+    let reference = match references.as_slice() {
+        [reference] => *reference,
+        _ => return Err(invalid("signature must have exactly one reference")),
+    };
+
+    if reference.attribute("URI") != Some(format!("#{}", signed_id).as_str()) {
+        return Err(invalid("signature does not reference the signed element"));
+    }
+
+    let mut enveloped = false;
+    let mut reference_prefixes = vec![];
+    if let Some(transforms) = child(reference, XMLDSIG_NS, "Transforms") {
+        for transform in transforms
+            .children()
+            .filter(|node| node.has_tag_name((XMLDSIG_NS, "Transform")))
+        {
+            match transform.attribute("Algorithm") {
+                Some(ENVELOPED_SIGNATURE) => enveloped = true,
+                Some(EXC_C14N) => reference_prefixes = inclusive_prefixes(transform),
+                _ => return Err(invalid("unsupported reference transform")),
+            }
+        }
+    }
+
+    let digest_method = child(reference, XMLDSIG_NS, "DigestMethod")
+        .and_then(|method| method.attribute("Algorithm"));
+    if digest_method != Some(SHA256) {
+        return Err(invalid("unsupported digest method"));
+    }
+
+    let expected_digest = decode_base64(
+        &child(reference, XMLDSIG_NS, "DigestValue")
+            .map(text)
+            .unwrap_or_default(),
+    )?;
+
+    let excluded = if enveloped { Some(signature.id()) } else { None };
+    let canonical = canonicalize(signed, excluded, &reference_prefixes);
+    let digest = Sha256::digest(canonical.as_bytes());
+
+    if digest.as_slice() != expected_digest.as_slice() {
+        return Err(invalid("digest does not match the signed content"));
+    }
+
+    let signature_value = decode_base64(
+        &child(signature, XMLDSIG_NS, "SignatureValue")
+            .map(text)
+            .unwrap_or_default(),
+    )?;
+    let signature_value = Signature::try_from(signature_value.as_slice())
+        .map_err(|_| invalid("malformed signature value"))?;
+
+    let canonical_signed_info = canonicalize(signed_info, None, &inclusive_prefixes(c14n_method));
+
+    VerifyingKey::<Sha256>::new(certificate.clone())
+        .verify(canonical_signed_info.as_bytes(), &signature_value)
+        .map_err(|_| invalid("signature verification failed"))
+}
+
+/// Accepts either a PEM certificate or the bare base64 body found in IdP metadata.
+pub fn parse_certificate(certificate: &str) -> Result<RsaPublicKey, AppError> {
+    let body = certificate
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+
+    let der = decode_base64(&body)?;
+    let certificate = x509_cert::Certificate::from_der(&der)
+        .map_err(|err| invalid(&format!("invalid IdP certificate: {}", err)))?;
+
+    let spki = certificate
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+        .map_err(|err| invalid(&format!("invalid IdP certificate: {}", err)))?;
+
+    RsaPublicKey::from_public_key_der(&spki)
+        .map_err(|err| invalid(&format!("IdP certificate is not an RSA key: {}", err)))
+}
+
+fn inclusive_prefixes(transform: Node) -> Vec<String> {
+    transform
+        .children()
+        .find(|node| node.has_tag_name((EXC_C14N, "InclusiveNamespaces")))
+        .and_then(|node| node.attribute("PrefixList"))
+        .map(|list| list.split_whitespace().map(|prefix| prefix.to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, ns: &str, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name((ns, name)))
+}
+
+fn text(node: Node) -> String {
+    node.descendants()
+        .filter(|node| node.is_text())
+        .filter_map(|node| node.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, AppError> {
+    let compact = value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+
+    base64::engine::general_purpose::STANDARD
+        .decode(compact)
+        .map_err(|_| invalid("malformed base64 value"))
+}
+
+fn parse_instant(value: Option<&str>) -> Result<Option<DateTime<Utc>>, AppError> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|instant| instant.with_timezone(&Utc))
+                .map_err(|_| invalid("malformed timestamp"))
+        })
+        .transpose()
+}
+
+fn invalid(reason: &str) -> AppError {
+    AppError::SamlValidationError(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDP_CERTIFICATE: &str = include_str!("../../../etc/saml/fixtures/idp.crt");
+    const SIGNED_RESPONSE: &str =
+        include_str!("../../../etc/saml/fixtures/idp_initiated_response.b64");
+    const TAMPERED_RESPONSE: &str = include_str!("../../../etc/saml/fixtures/tampered_response.b64");
+
+    fn validation() -> SamlValidation<'static> {
+        SamlValidation {
+            idp_entity_id: "http://localhost:8081/idp",
+            idp_certificate: IDP_CERTIFICATE,
+            sp_entity_id: "http://localhost:8000/oauth/saml",
+            acs_url: "http://localhost:8000/oauth/saml/local-idp/acs",
+            clock_skew: chrono::Duration::seconds(120),
+        }
+    }
+
+    fn decode(response: &str) -> String {
+        String::from_utf8(decode_base64(response).unwrap()).unwrap()
+    }
+
+    fn rejection(result: Result<SamlAssertion, AppError>) -> String {
+        match result {
+            Err(AppError::SamlValidationError(reason)) => reason,
+            Err(err) => panic!("unexpected error: {:?}", err),
+            Ok(assertion) => panic!("accepted assertion for {}", assertion.name_id),
+        }
+    }
+
+    #[test]
+    fn accepts_signed_fixture() {
+        let assertion = verify_response(&decode(SIGNED_RESPONSE), &validation()).unwrap();
+
+        assert_eq!(assertion.name_id, "alice@example.com");
+        assert_eq!(assertion.issuer, "http://localhost:8081/idp");
+        assert_eq!(assertion.attribute("email"), Some("alice@example.com"));
+        assert_eq!(assertion.attribute("displayName"), Some("Alice Example"));
+    }
+
+    #[test]
+    fn rejects_tampered_fixture() {
+        let reason = rejection(verify_response(&decode(TAMPERED_RESPONSE), &validation()));
+
+        assert!(reason.contains("digest"), "{}", reason);
+    }
+
+    #[test]
+    fn rejects_wrong_audience() {
+        let validation = SamlValidation {
+            sp_entity_id: "https://other.example.com/saml",
+            ..validation()
+        };
+
+        let reason = rejection(verify_response(&decode(SIGNED_RESPONSE), &validation));
+
+        assert!(reason.contains("audience"), "{}", reason);
+    }
+
+    #[test]
+    fn rejects_wrong_recipient() {
+        let validation = SamlValidation {
+            acs_url: "https://other.example.com/oauth/saml/local-idp/acs",
+            ..validation()
+        };
+
+        let reason = rejection(verify_response(&decode(SIGNED_RESPONSE), &validation));
+
+        assert!(reason.contains("recipient"), "{}", reason);
+    }
+
+    #[test]
+    fn rejects_assertion_without_id() {
+        let xml = decode(SIGNED_RESPONSE).replace(r#"ID="_assert_8f2e6a4c1b9d4e7f0a3c" "#, "");
+
+        let reason = rejection(verify_response(&xml, &validation()));
+
+        assert!(reason.contains("no ID"), "{}", reason);
+    }
+
+    #[test]
+    fn rejects_expired_response() {
+        let after_expiry = DateTime::parse_from_rfc3339("2099-01-01T00:05:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let reason = rejection(verify_response_at(
+            &decode(SIGNED_RESPONSE),
+            &validation(),
+            after_expiry,
+        ));
+
+        assert!(reason.contains("expired"), "{}", reason);
+    }
+}
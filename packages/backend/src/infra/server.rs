@@ -1,35 +1,57 @@
-use std::sync::Arc;
+use std::{ net::SocketAddr, sync::Arc, time::Duration };
 use axum::{
     http::{
         header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderName},
         HeaderValue, Method
     },
     extract::Request,
-    Router,
     ServiceExt,
 };
 
 use casbin::{ CoreApi, DefaultModel, Enforcer };
 use sqlx_adapter::SqlxAdapter;
-use tokio::sync::RwLock;
 use tower_http::cors::{ AllowOrigin, CorsLayer };
-use tracing::{ debug, info };
+use tracing::{ debug, error, info, warn };
 
 use crate::{
-    application::state::AppState,
-    infra::{ graceful::shutdown_signal, rbac::Rbac },
-    interface::api::{
+    application::{
+        state::AppState,
+        usecases::policy::check_policy_consistency::CheckPolicyConsistency,
+    },
+    infra::{
+        graceful::shutdown_signal,
+        rbac::Rbac,
+        rbac_sync::PolicySync,
+        repositories::{
+            pg_policy_repo::PgPolicyRepository,
+            pg_role_policy_change_repo::PgRolePolicyChangeRepository,
+            pg_role_repo::PgRoleRepository,
+        },
+    },
+    interface::{ api::{
+        access_request_handler::setup_access_request_routes,
+        access_review_handler::setup_access_review_routes,
         auth_handler::setup_auth_routes,
+        authz_handler::setup_authz_routes,
+        invite_handler::setup_invite_routes,
+        metrics_handler::setup_metrics_routes,
         permission_handler::setup_permission_handler,
+        policy_handler::setup_policy_routes,
         public_oauth_handler::setup_public_oauth_handler,
+        role_constraint_handler::setup_role_constraint_routes,
         role_handler::setup_role_routes,
+        saml_provider_handler::setup_saml_provider_routes,
         super_handler::setup_super_handler,
         project_handler::setup_project_routes,
         user_handler::setup_user_routes,
-    },
+        user_role_handler::setup_user_role_routes,
+    }, middleware::permission::GuardedRouter },
 };
 
-use super::{ config::AppConfig, data::{ postgres::establish_connection, redis::get_redis_con } };
+use super::{
+    config::AppConfig,
+    data::{ mail::get_mail_transport, postgres::establish_connection, redis::get_redis_con },
+};
 
 #[derive(Debug)]
 pub struct ServerBuilder {
@@ -54,19 +76,80 @@ impl ServerBuilder {
 
         let redis_pool = get_redis_con(&self.cfg.redis_url).await;
 
+        let mail_transport = get_mail_transport(&self.cfg.smtp_url);
+
         // casbin enforcer
-        let enforcer = Arc::new(RwLock::new(self.setup_casbin().await));
+        let rbac = Arc::new(self.setup_rbac().await);
 
-        // setup roles & permissions casbin rbac
-        let rbac = Arc::new(Rbac::new(enforcer));
-        // rbac.setup_roles_and_permissions().await; // not used anymore
+        // keep the policy of every replica in sync through redis pub/sub
+        PolicySync::new(&self.cfg, redis_pool.clone(), rbac.clone()).start().await;
 
-        let app_state = Arc::new(AppState::new(self.cfg.clone(), db_pool, redis_pool, rbac));
+        let app_state = Arc::new(AppState::new(
+            self.cfg.clone(),
+            db_pool,
+            redis_pool,
+            mail_transport,
+            rbac
+        ));
+
+        // roles declared in the bootstrap file, a bad file keeps the server from starting
+        let reconciled = app_state.uc.policy.reconcile_roles
+            .execute(&app_state.db_pool, None, None, false).await
+            .expect("Failed to reconcile roles");
+        if reconciled.is_none() {
+            info!("No role bootstrap file configured, roles are not reconciled");
+        }
+
+        // until the first super admin exists, print a single-use token for /v1/super/seed-super-user
+        let bootstrap_token = app_state.uc.auth.issue_bootstrap_token
+            .execute().await
+            .expect("Failed to issue bootstrap token");
+        if let Some(token) = bootstrap_token {
+            warn!(
+                "No super admin exists yet. Create one with POST /v1/super/seed-super-user and header x-bootstrap-token: {} (valid for {}s)",
+                token,
+                self.cfg.bootstrap_token_ttl_secs
+            );
+        }
+
+        // time-bound role grants stop counting at expiry, the sweeper removes and records them
+        let sweeper_state = app_state.clone();
+        let sweep_interval = Duration::from_secs(self.cfg.role_grant_sweep_interval_secs);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sweep_interval);
+            loop {
+                interval.tick().await;
+                if let Err(err) = sweeper_state.uc.user_role.sweep_expired_grants
+                    .execute(&sweeper_state.db_pool).await
+                {
+                    error!("Failed to sweep expired role grants: {}", err);
+                }
+                // grants from access requests end with them
+                if let Err(err) = sweeper_state.uc.access_request.expire_access_requests
+                    .execute(&sweeper_state.db_pool).await
+                {
+                    error!("Failed to expire access requests: {}", err);
+                }
+                // unreviewed assignments get the campaign's default action at the deadline
+                if let Err(err) = sweeper_state.uc.access_review.complete_overdue_access_reviews
+                    .execute(&sweeper_state.db_pool).await
+                {
+                    error!("Failed to complete overdue access reviews: {}", err);
+                }
+            }
+        });
 
         let api_routes = self.setup_api_router(app_state.clone());
+        self.verify_route_permissions(&app_state, &api_routes).await;
 
-        let app = api_routes
-            .nest("/oauth", setup_public_oauth_handler())
+        let mut app = api_routes
+            .into_router()
+            .nest("/oauth", setup_public_oauth_handler());
+        // metrics are only served to a scraper holding METRICS_TOKEN
+        if !self.cfg.metrics_token.is_empty() {
+            app = app.nest("/metrics", setup_metrics_routes());
+        }
+        let app = app
             .layer(self.setup_cors())
             .with_state(app_state);
 
@@ -75,18 +158,42 @@ impl ServerBuilder {
         let listener = tokio::net::TcpListener::bind(&addr).await.expect("Failed to bind address");
 
         debug!("🚀 API Started on {}", addr);
-        axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).with_graceful_shutdown(shutdown_signal()).await
+        axum::serve(listener, ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(app)).with_graceful_shutdown(shutdown_signal()).await
         .expect("API Server Error");
     }
 
-    fn setup_api_router(&self, app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-        Router::new()
-            .nest("/v1/permissions", setup_permission_handler())
+    fn setup_api_router(&self, app_state: Arc<AppState>) -> GuardedRouter {
+        GuardedRouter::new(app_state.clone())
+            .nest("/v1/permissions", setup_permission_handler(app_state.clone()))
             .nest("/v1/roles", setup_role_routes(app_state.clone()))
+            .nest("/v1/role-constraints", setup_role_constraint_routes(app_state.clone()))
+            .nest("/v1/policies", setup_policy_routes(app_state.clone()))
+            .nest("/v1/saml-providers", setup_saml_provider_routes(app_state.clone()))
+            .nest("/v1/access-requests", setup_access_request_routes(app_state.clone()))
+            .nest("/v1/access-reviews", setup_access_review_routes(app_state.clone()))
             .nest("/v1/auth", setup_auth_routes(app_state.clone()))
+            .nest("/v1/authz", setup_authz_routes(app_state.clone()))
+            .nest("/v1/invites", setup_invite_routes(app_state.clone()))
             .nest("/v1/super", setup_super_handler(app_state.clone()))
             .nest("/v1/projects", setup_project_routes(app_state.clone()))
             .nest("/v1/user", setup_user_routes(app_state.clone()))
+            .nest("/v1/users", setup_user_role_routes(app_state.clone()))
+    }
+
+    // a route guarded by a permission missing from the registry could never be granted
+    async fn verify_route_permissions(&self, app_state: &AppState, api_routes: &GuardedRouter) {
+        let registry = app_state.uc.permission.get_permission_list
+            .execute().await
+            .expect("Failed to load the permission registry");
+
+        let unregistered = api_routes.unregistered_permissions(&registry);
+        if !unregistered.is_empty() {
+            panic!("Routes require unregistered permissions: {}", unregistered.join(", "));
+        }
+
+        for route in api_routes.routes() {
+            debug!("{} {} requires {:?}", route.method, route.path, route.access);
+        }
     }
 
     fn setup_cors(&self) -> CorsLayer {
@@ -111,15 +218,49 @@ impl ServerBuilder {
                 Method::OPTIONS,
             ])
             .allow_credentials(true)
-            .allow_headers([AUTHORIZATION, ACCEPT, CONTENT_TYPE, HeaderName::from_static("x-csrf-token")])
+            .allow_headers([
+                AUTHORIZATION,
+                ACCEPT,
+                CONTENT_TYPE,
+                HeaderName::from_static("x-csrf-token"),
+                HeaderName::from_static("x-organization-id"),
+            ])
             .expose_headers([HeaderName::from_static("set-cookie")])
     }
 
-    async fn setup_casbin(&self) -> Enforcer {
+    // one-off check of roles against the stored casbin rules, running instances pick up a repair
+    // with their next periodic reload; false when drift is left unrepaired
+    pub async fn check_policies(&self, repair: bool) -> bool {
+        let db_pool = establish_connection(&self.cfg.db_url).await;
+        let rbac = Arc::new(self.setup_rbac().await);
+
+        let check_policy_consistency = CheckPolicyConsistency::new(
+            Arc::new(PgRoleRepository::new(db_pool.clone())),
+            Arc::new(PgPolicyRepository::new(db_pool.clone())),
+            Arc::new(PgRolePolicyChangeRepository::new(db_pool.clone())),
+            rbac
+        );
+        let report = check_policy_consistency
+            .execute(&db_pool, repair).await
+            .expect("Failed to check policy consistency");
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize consistency report")
+        );
+
+        report.is_consistent() || report.repaired
+    }
+
+    async fn setup_rbac(&self) -> Rbac {
         // casbin config initialization
         let model = DefaultModel::from_file("etc/rbac_model.conf").await.unwrap();
         let adapter = SqlxAdapter::new(&self.cfg.db_url, 8).await.unwrap();
 
-        Enforcer::new(model, adapter).await.unwrap()
+        let mut enforcer = Enforcer::new(model.clone(), adapter).await.unwrap();
+        Rbac::configure_enforcer(&mut enforcer).unwrap();
+
+        // access checks run against an in-memory snapshot built from the same model
+        Rbac::new(enforcer, model).await.unwrap()
     }
 }
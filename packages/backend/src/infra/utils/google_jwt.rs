@@ -73,10 +73,10 @@ impl GoogleJwtMaker {
                 .read()
                 .map_err(|err| AppError::ProcessError(err.to_string()))?;
 
-            if let Some(cached) = &*cache {
-                if cached.expires_at > Instant::now() {
-                    return Ok(cached.jwks.clone());
-                }
+            if let Some(cached) = &*cache
+                && cached.expires_at > Instant::now()
+            {
+                return Ok(cached.jwks.clone());
             }
         }
 
@@ -3,3 +3,4 @@ pub mod jwt_maker;
 pub mod pagination;
 pub mod password;
 pub mod response;
+pub mod token;
@@ -0,0 +1,11 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// opaque single-use token for links sent by mail, only its hash is stored
+pub fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{ Path, Query, State },
+    Extension,
+    Json,
+};
+
+use crate::{
+    application::{
+        dto::access_request::{
+            create_access_request_request::CreateAccessRequestRequest,
+            decide_access_request_request::{
+                AccessRequestListQuery,
+                DecideAccessRequestRequest,
+                RevokeAccessRequestRequest,
+            },
+        },
+        state::AppState,
+    },
+    domain::entities::{ access_context::AccessContext, access_request::{ AccessRequest, AccessRequestDetail }, user::UserFull },
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::{
+        client_ip::ClientIp,
+        domain::Domain,
+        permission::{ Access, GuardedRouter },
+        session_token::SessionToken,
+    },
+};
+
+const APPROVE: Access = Access::domain("access-requests", "approve");
+
+// any signed in user can ask for access, approvers decide
+pub fn setup_access_request_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_access_requests, APPROVE)
+        .post("/", create_access_request, Access::Authenticated)
+        .get("/mine", get_my_access_requests, Access::Authenticated)
+        // requesters see their own requests, approvers those of the domain
+        .get("/{id}", get_access_request_by_id, Access::Authenticated)
+        .post("/{id}/approve", approve_access_request, APPROVE)
+        .post("/{id}/deny", deny_access_request, APPROVE)
+        .post("/{id}/cancel", cancel_access_request, Access::Authenticated)
+        .post("/{id}/revoke", revoke_access_request, APPROVE)
+}
+
+async fn create_access_request(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Json(req): Json<CreateAccessRequestRequest>
+) -> Result<SuccessResponse<AccessRequest>, AppError> {
+    let request = state.uc.access_request.create_access_request.execute(
+        &current_user,
+        &domain,
+        req
+    ).await?;
+
+    Ok(SuccessResponse::with_data(201, request))
+}
+
+async fn get_my_access_requests(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<Vec<AccessRequest>>, AppError> {
+    let requests = state.uc.access_request.get_my_access_requests.execute(
+        &current_user.user.id
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, requests))
+}
+
+async fn get_access_requests(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Query(query): Query<AccessRequestListQuery>
+) -> Result<SuccessResponse<Vec<AccessRequest>>, AppError> {
+    let requests = state.uc.access_request.get_access_requests.execute(
+        &domain,
+        query.status.as_deref()
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, requests))
+}
+
+async fn get_access_request_by_id(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    ClientIp(client_ip): ClientIp,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<AccessRequestDetail>, AppError> {
+    // requesters always see their own requests
+    let is_approver = state.rbac.check_access(
+        &current_user.roles,
+        &domain,
+        "access-requests",
+        "approve",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    let request = state.uc.access_request.get_access_request_by_id.execute(
+        &domain,
+        &id,
+        &current_user.user.id,
+        is_approver
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, request))
+}
+
+async fn approve_access_request(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    req: Option<Json<DecideAccessRequestRequest>>
+) -> Result<SuccessResponse<AccessRequest>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let request = state.uc.access_request.approve_access_request.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        req.map(|Json(req)| req).unwrap_or_default()
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, request))
+}
+
+async fn deny_access_request(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    req: Option<Json<DecideAccessRequestRequest>>
+) -> Result<SuccessResponse<AccessRequest>, AppError> {
+    let request = state.uc.access_request.deny_access_request.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        req.map(|Json(req)| req).unwrap_or_default()
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, request))
+}
+
+async fn cancel_access_request(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<AccessRequest>, AppError> {
+    let request = state.uc.access_request.cancel_access_request.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &id
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, request))
+}
+
+async fn revoke_access_request(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    req: Option<Json<RevokeAccessRequestRequest>>
+) -> Result<SuccessResponse<AccessRequest>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let request = state.uc.access_request.revoke_access_request.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        req.map(|Json(req)| req).unwrap_or_default()
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, request))
+}
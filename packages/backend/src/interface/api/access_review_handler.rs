@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{ Path, Query, State },
+    http::header,
+    response::{ IntoResponse, Response },
+    Extension,
+    Json,
+};
+
+use crate::{
+    application::{
+        dto::{
+            access_review::{
+                create_access_review_request::CreateAccessReviewRequest,
+                decide_access_review_item_request::{
+                    AccessReviewExportQuery,
+                    AccessReviewListQuery,
+                    DecideAccessReviewItemRequest,
+                },
+            },
+            policy::policy_export_query::PolicyFormat,
+        },
+        state::AppState,
+    },
+    domain::entities::{
+        access_context::AccessContext,
+        access_review::{ AccessReview, AccessReviewDetail, AccessReviewItem, AccessReviewReport },
+        user::UserFull,
+    },
+    infra::{ errors::app_error::AppError, rbac::GLOBAL_DOMAIN, utils::response::SuccessResponse },
+    interface::middleware::{
+        client_ip::ClientIp,
+        domain::Domain,
+        permission::{ Access, GuardedRouter },
+        session_token::SessionToken,
+    },
+};
+
+const MANAGE: Access = Access::domain("access-reviews", "manage");
+
+// managers open and complete campaigns, the reviewers named on a campaign decide its assignments
+pub fn setup_access_review_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_access_reviews, MANAGE)
+        .post("/", create_access_review, MANAGE)
+        .get("/mine", get_my_access_reviews, Access::Authenticated)
+        .get("/{id}", get_access_review_by_id, Access::Authenticated)
+        .post("/{id}/items/{item_id}/decision", decide_access_review_item, Access::Authenticated)
+        .post("/{id}/complete", complete_access_review, MANAGE)
+        .get("/{id}/export", export_access_review, MANAGE)
+}
+
+async fn get_access_reviews(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Query(query): Query<AccessReviewListQuery>
+) -> Result<SuccessResponse<Vec<AccessReview>>, AppError> {
+    let reviews = state.uc.access_review.get_access_reviews.execute(
+        &domain,
+        query.status.as_deref()
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, reviews))
+}
+
+async fn create_access_review(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    ClientIp(client_ip): ClientIp,
+    Json(req): Json<CreateAccessReviewRequest>
+) -> Result<SuccessResponse<AccessReviewDetail>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    // reviewing super admins needs the same right as /v1/super/admins
+    let manages_super_admins = state.rbac.check_access(
+        &current_user.roles,
+        GLOBAL_DOMAIN,
+        "super-admin-management",
+        "write",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    let review = state.uc.access_review.create_access_review.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        req,
+        manages_super_admins
+    ).await?;
+
+    Ok(SuccessResponse::with_data(201, review))
+}
+
+async fn get_my_access_reviews(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<Vec<AccessReview>>, AppError> {
+    let reviews = state.uc.access_review.get_my_access_reviews.execute(
+        &current_user.user.id
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, reviews))
+}
+
+async fn get_access_review_by_id(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    ClientIp(client_ip): ClientIp,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<AccessReviewDetail>, AppError> {
+    // reviewers always see the campaigns they review
+    let is_manager = state.rbac.check_access(
+        &current_user.roles,
+        &domain,
+        "access-reviews",
+        "manage",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    let review = state.uc.access_review.get_access_review_by_id.execute(
+        &domain,
+        &id,
+        &current_user.user.id,
+        is_manager
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, review))
+}
+
+async fn decide_access_review_item(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
+    Path((id, item_id)): Path<(String, String)>,
+    Json(req): Json<DecideAccessReviewItemRequest>
+) -> Result<SuccessResponse<AccessReviewItem>, AppError> {
+    let manages_super_admins = state.rbac.check_access(
+        &current_user.roles,
+        GLOBAL_DOMAIN,
+        "super-admin-management",
+        "write",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    let item = state.uc.access_review.decide_access_review_item.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &id,
+        &item_id,
+        req,
+        manages_super_admins
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, item))
+}
+
+async fn complete_access_review(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<AccessReviewReport>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let report = state.uc.access_review.complete_access_review.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, report))
+}
+
+async fn export_access_review(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    Query(query): Query<AccessReviewExportQuery>
+) -> Result<Response, AppError> {
+    let report = state.uc.access_review.export_access_review.execute(&domain, &id).await?;
+
+    let response = match query.format {
+        PolicyFormat::Json =>
+            (
+                [
+                    (header::CONTENT_TYPE, "application/json".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"access-review-{}.json\"", report.review.id),
+                    ),
+                ],
+                Json(report),
+            ).into_response(),
+        PolicyFormat::Csv =>
+            (
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"access-review-{}.csv\"", report.review.id),
+                    ),
+                ],
+                report.to_csv(),
+            ).into_response(),
+    };
+
+    Ok(response)
+}
@@ -3,32 +3,62 @@ use std::sync::Arc;
 use axum::{
     extract::State,
     http::header,
-    middleware::from_fn_with_state,
     response::IntoResponse,
-    routing::{delete, get},
-    Extension, Router,
+    Extension, Json,
 };
 use axum_extra::extract::cookie::{self, Cookie, Expiration};
 use time::OffsetDateTime;
 
 use crate::{
-    application::state::AppState,
-    domain::entities::user::UserFull,
-    infra::{errors::app_error::AppError, utils::response::SuccessResponse},
-    interface::middleware::auth_mw::is_authorized,
+    application::{
+        dto::auth::reauth_request::{ReauthRequest, ReauthResponse},
+        state::AppState,
+    },
+    domain::entities::{access_context::AccessContext, field_access::Redacted, user::UserFull},
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN, utils::response::SuccessResponse},
+    interface::middleware::{
+        client_ip::ClientIp,
+        permission::{Access, GuardedRouter},
+        session_token::SessionToken,
+    },
 };
 
-pub fn setup_auth_routes(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/current-user", get(current_user))
-        .route("/logout", delete(logout))
-        .layer(from_fn_with_state(app_state, is_authorized))
+pub fn setup_auth_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/current-user", current_user, Access::Authenticated)
+        .delete("/logout", logout, Access::Authenticated)
+        .post("/reauth", reauth, Access::Authenticated)
 }
 
 pub async fn current_user(
     Extension(current_user): Extension<UserFull>,
-) -> Result<SuccessResponse<UserFull>, AppError> {
-    Ok(SuccessResponse::with_data(200, current_user))
+    State(app_state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
+) -> Result<SuccessResponse<Redacted<UserFull>>, AppError> {
+    let ctx = AccessContext::new(&current_user.user.id, Some(&client_ip));
+    let roles = current_user.roles.clone();
+    let user = app_state
+        .rbac
+        .redact(&roles, GLOBAL_DOMAIN, current_user, &ctx)
+        .await?;
+
+    Ok(SuccessResponse::with_data(200, user))
+}
+
+pub async fn reauth(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(app_state): State<Arc<AppState>>,
+    Json(req): Json<ReauthRequest>,
+) -> Result<SuccessResponse<ReauthResponse>, AppError> {
+    let resp = app_state
+        .uc
+        .auth
+        .reauthenticate
+        .execute(&current_user, &session_token, req)
+        .await?;
+
+    Ok(SuccessResponse::with_data(200, resp))
 }
 
 pub async fn logout(
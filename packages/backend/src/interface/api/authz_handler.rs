@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use axum::{ extract::State, Json };
+
+use crate::{
+    application::{
+        dto::authz::explain_access_request::{ AccessMatrixRequest, ExplainAccessRequest },
+        state::AppState,
+    },
+    domain::entities::access_decision::{ AccessDecision, AccessMatrix },
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::{ domain::Domain, permission::{ Access, GuardedRouter } },
+};
+
+pub fn setup_authz_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .post("/explain", explain_access, Access::domain("role-management", "read"))
+        .post("/matrix", get_access_matrix, Access::domain("role-management", "read"))
+}
+
+// dry run of an authorization check, explains why a subject is allowed or denied
+async fn explain_access(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Json(req): Json<ExplainAccessRequest>
+) -> Result<SuccessResponse<AccessDecision>, AppError> {
+    let decision = state.uc.authz.explain_access.execute(&domain, req).await?;
+
+    Ok(SuccessResponse::with_data(200, decision))
+}
+
+async fn get_access_matrix(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Json(req): Json<AccessMatrixRequest>
+) -> Result<SuccessResponse<AccessMatrix>, AppError> {
+    let matrix = state.uc.authz.get_access_matrix.execute(&domain, req).await?;
+
+    Ok(SuccessResponse::with_data(200, matrix))
+}
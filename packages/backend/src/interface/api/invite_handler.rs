@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::{ extract::{ Path, State }, Extension, Json };
+
+use crate::{
+    application::{ dto::invite::create_invite_request::CreateInviteRequest, state::AppState },
+    domain::entities::{ invite::Invite, user::UserFull },
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::permission::{ Access, GuardedRouter },
+};
+
+pub fn setup_invite_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_all_invites, Access::global("user-management", "read"))
+        .post("/", create_invite, Access::global("user-management", "write"))
+        .delete("/{id}", delete_invite, Access::global("user-management", "write"))
+}
+
+async fn get_all_invites(
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<Vec<Invite>>, AppError> {
+    let invites = state.uc.invite.get_all_invites.execute().await?;
+
+    Ok(SuccessResponse::with_data(200, invites))
+}
+
+async fn create_invite(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateInviteRequest>
+) -> Result<SuccessResponse<Invite>, AppError> {
+    let invite = state.uc.invite.create_invite.execute(&current_user.user.id, req).await?;
+
+    Ok(SuccessResponse::with_data(201, invite))
+}
+
+async fn delete_invite(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.invite.delete_invite.execute(&id).await?;
+
+    Ok(SuccessResponse::with_data(200, id))
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+
+use crate::{application::state::AppState, infra::utils::token::hash_token};
+
+pub fn setup_metrics_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(get_metrics))
+}
+
+// scraped by prometheus, reports how far this instance's policy trails its peers and how well
+// access decisions are cached
+async fn get_metrics(State(app_state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    // hashes are compared so the check does not leak how much of the token matched
+    let expected = &app_state.cfg.metrics_token;
+    if expected.is_empty() || hash_token(presented) != hash_token(expected) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        format!(
+            "{}{}",
+            app_state.rbac.sync_metrics.render(),
+            app_state.rbac.decision_metrics.render()
+        ),
+    )
+        .into_response()
+}
@@ -1,7 +1,17 @@
+pub mod access_request_handler;
+pub mod access_review_handler;
 pub mod auth_handler;
+pub mod authz_handler;
+pub mod invite_handler;
+pub mod metrics_handler;
 pub mod permission_handler;
+pub mod policy_handler;
 pub mod public_oauth_handler;
+pub mod role_constraint_handler;
 pub mod role_handler;
+pub mod saml_handler;
+pub mod saml_provider_handler;
 pub mod super_handler;
 pub mod project_handler;
 pub mod user_handler;
+pub mod user_role_handler;
@@ -1,33 +1,85 @@
 use std::{collections::HashMap, sync::Arc};
 
-use axum::{extract::State, Router};
-use tokio::fs;
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json,
+};
 
 use crate::{
-    application::state::AppState,
+    application::{
+        dto::permission::create_update_permission_request::{
+            CreatePermissionRequest, UpdatePermissionRequest,
+        },
+        state::AppState,
+    },
+    domain::entities::permission::{Permission, PermissionGroup},
     infra::{errors::app_error::AppError, utils::response::SuccessResponse},
+    interface::middleware::permission::{Access, GuardedRouter},
 };
 
-pub fn setup_permission_handler() -> Router<Arc<AppState>> {
-    Router::new().route("/list", axum::routing::get(get_permission_list))
+const READ: Access = Access::global("permission-management", "read");
+const WRITE: Access = Access::global("permission-management", "write");
+
+pub fn setup_permission_handler(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .public_route("/list", get(get_permission_list))
+        .get("/", get_all_permissions, READ)
+        .post("/", create_permission, WRITE)
+        .get("/{id}", get_permission_by_id, READ)
+        .put("/{id}", update_permission, WRITE)
+        .delete("/{id}", delete_permission, WRITE)
 }
 
 async fn get_permission_list(
     State(app_state): State<Arc<AppState>>,
 ) -> Result<SuccessResponse<HashMap<String, Vec<String>>>, AppError> {
-    if let Ok(permissions) = app_state.svc.redis.get_permissions_list().await {
-        return Ok(SuccessResponse::with_data(200, permissions));
-    }
+    let permission_list = app_state.uc.permission.get_permission_list.execute().await?;
+
+    Ok(SuccessResponse::with_data(200, permission_list))
+}
 
-    let permission_list = fs::read_to_string("etc/permissions.json").await?;
+async fn get_all_permissions(
+    State(state): State<Arc<AppState>>,
+) -> Result<SuccessResponse<Vec<PermissionGroup>>, AppError> {
+    let permissions = state.uc.permission.get_all_permission.execute().await?;
 
-    let permission_list: HashMap<String, Vec<String>> = serde_json::from_str(&permission_list)?;
+    Ok(SuccessResponse::with_data(200, permissions))
+}
 
-    app_state
-        .svc
-        .redis
-        .set_permissions_list(permission_list.clone())
-        .await?;
+async fn get_permission_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<SuccessResponse<Permission>, AppError> {
+    let permission = state.uc.permission.get_permission_by_id.execute(&id).await?;
 
-    Ok(SuccessResponse::with_data(200, permission_list))
+    Ok(SuccessResponse::with_data(200, permission))
+}
+
+async fn create_permission(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreatePermissionRequest>,
+) -> Result<SuccessResponse<Permission>, AppError> {
+    let permission = state.uc.permission.create_permission.execute(req).await?;
+
+    Ok(SuccessResponse::with_data(201, permission))
+}
+
+async fn update_permission(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdatePermissionRequest>,
+) -> Result<SuccessResponse<Permission>, AppError> {
+    let permission = state.uc.permission.update_permission.execute(&id, req).await?;
+
+    Ok(SuccessResponse::with_data(200, permission))
+}
+
+async fn delete_permission(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.permission.delete_permission.execute(&id).await?;
+
+    Ok(SuccessResponse::with_data(200, id))
 }
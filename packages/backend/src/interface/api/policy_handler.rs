@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{ Query, State },
+    http::{ header, HeaderMap },
+    response::{ IntoResponse, Response },
+    Extension,
+    Json,
+};
+
+use crate::{
+    application::{
+        dto::policy::{
+            policy_export_query::{ PolicyExportQuery, PolicyFormat },
+            policy_reconcile_query::PolicyReconcileQuery,
+        },
+        usecases::policy::import_policies::ImportMode,
+        state::AppState,
+    },
+    domain::entities::{
+        policy_consistency::PolicyConsistencyReport,
+        policy_document::{ PolicyDiff, PolicyDocument },
+        user::UserFull,
+    },
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::{ permission::{ Access, GuardedRouter }, session_token::SessionToken },
+};
+
+const READ: Access = Access::global("role-management", "read");
+const WRITE: Access = Access::global("role-management", "write");
+
+// the document covers every domain, so it is only managed globally
+pub fn setup_policy_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/export", export_policies, READ)
+        .post("/import/preview", preview_policy_import, READ)
+        .post("/import", import_policies, WRITE)
+        .post("/reconcile/preview", preview_role_reconcile, READ)
+        .post("/reconcile", reconcile_roles, WRITE)
+        .get("/consistency", check_policy_consistency, READ)
+        .post("/consistency/repair", repair_policy_consistency, WRITE)
+}
+
+async fn export_policies(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PolicyExportQuery>
+) -> Result<Response, AppError> {
+    let document = state.uc.policy.export_policies.execute().await?;
+
+    let response = match query.format {
+        PolicyFormat::Json =>
+            (
+                [
+                    (header::CONTENT_TYPE, "application/json"),
+                    (header::CONTENT_DISPOSITION, "attachment; filename=\"policies.json\""),
+                ],
+                Json(document),
+            ).into_response(),
+        PolicyFormat::Csv =>
+            (
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (header::CONTENT_DISPOSITION, "attachment; filename=\"policies.csv\""),
+                ],
+                document.to_csv(),
+            ).into_response(),
+    };
+
+    Ok(response)
+}
+
+async fn preview_policy_import(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: String
+) -> Result<SuccessResponse<PolicyDiff>, AppError> {
+    let document = parse_document(&headers, &body)?;
+
+    let diff = state.uc.policy.import_policies.execute(
+        &state.db_pool,
+        Some(&current_user.user.id),
+        document,
+        ImportMode::Replace,
+        true
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, diff))
+}
+
+async fn import_policies(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: String
+) -> Result<SuccessResponse<PolicyDiff>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let document = parse_document(&headers, &body)?;
+
+    let diff = state.uc.policy.import_policies.execute(
+        &state.db_pool,
+        Some(&current_user.user.id),
+        document,
+        ImportMode::Replace,
+        false
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, diff))
+}
+
+async fn preview_role_reconcile(
+    Extension(current_user): Extension<UserFull>,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PolicyReconcileQuery>
+) -> Result<SuccessResponse<PolicyDiff>, AppError> {
+    let diff = state.uc.policy.reconcile_roles
+        .execute(&state.db_pool, Some(&current_user.user.id), query.never_delete, true).await?
+        .ok_or_else(|| AppError::NotFound("No role bootstrap file configured".to_owned()))?;
+
+    Ok(SuccessResponse::with_data(200, diff))
+}
+
+async fn reconcile_roles(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PolicyReconcileQuery>
+) -> Result<SuccessResponse<PolicyDiff>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let diff = state.uc.policy.reconcile_roles
+        .execute(&state.db_pool, Some(&current_user.user.id), query.never_delete, false).await?
+        .ok_or_else(|| AppError::NotFound("No role bootstrap file configured".to_owned()))?;
+
+    Ok(SuccessResponse::with_data(200, diff))
+}
+
+async fn check_policy_consistency(
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<PolicyConsistencyReport>, AppError> {
+    let report = state.uc.policy.check_policy_consistency.execute(&state.db_pool, false).await?;
+
+    Ok(SuccessResponse::with_data(200, report))
+}
+
+async fn repair_policy_consistency(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<PolicyConsistencyReport>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let report = state.uc.policy.check_policy_consistency.execute(&state.db_pool, true).await?;
+
+    Ok(SuccessResponse::with_data(200, report))
+}
+
+// documents come back in the format they were exported in, told apart by content type
+fn parse_document(headers: &HeaderMap, body: &str) -> Result<PolicyDocument, AppError> {
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/csv"));
+
+    if is_csv {
+        PolicyDocument::from_csv(body)
+    } else {
+        Ok(serde_json::from_str(body)?)
+    }
+}
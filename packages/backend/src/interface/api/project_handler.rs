@@ -1,112 +1,96 @@
 use std::sync::Arc;
 
-use axum::{ Extension, Json, Router, extract::{Path, State}, middleware, routing::get};
+use axum::{ Extension, Json, extract::{Path, State}};
 
 use crate::{
     application::{dto::project::create_update_project_request::CreateOrUpdateProject, state::AppState},
-    domain::entities::{project::{Project, ProjectWithOwnerEmail}, user::UserFull},
+    domain::entities::{
+        access_context::ResourceCheck,
+        field_access::Redacted,
+        project::{Project, ProjectWithOwnerEmail},
+        user::UserFull,
+    },
     infra::{
         errors::app_error::AppError,
         utils::response::SuccessResponse,
     },
-    interface::middleware::auth_mw::is_authorized,
+    interface::middleware::{
+        client_ip::ClientIp,
+        permission::{Access, GuardedRouter},
+        session_token::SessionToken,
+    },
 };
 
-pub fn setup_project_routes(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/", get(get_all_projects_by_user_id).post(create_project))
-        .route("/{id}", get(get_project_by_id_and_user_id).put(update_project).delete(delete_project))
-        // Authentication layer (runs first)
-        .layer(middleware::from_fn_with_state(app_state.clone(), is_authorized))
+// project policies can depend on the owner, the usecases check them against the loaded project
+// with the route's ResourceCheck
+pub fn setup_project_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_all_projects_by_user_id, Access::resource_global("projects", "read"))
+        .post("/", create_project, Access::resource_global("projects", "write"))
+        .get("/{id}", get_project_by_id, Access::resource_global("projects", "read"))
+        .put("/{id}", update_project, Access::resource_global("projects", "write"))
+        .delete("/{id}", delete_project, Access::resource_global("projects", "delete"))
 }
 
 async fn get_all_projects_by_user_id(
     Extension(current_user): Extension<UserFull>,
-    State(state): State<Arc<AppState>>
+    Extension(check): Extension<ResourceCheck>,
+    State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp
 ) -> Result<SuccessResponse<Vec<Project>>, AppError> {
-    // Check authorization
-    state.rbac.check_access(&current_user.roles, "projects", "read").await?;
-    
-    let user_id = &current_user.user.id;
-
-    let projects = state.uc.project.get_all_by_user_id.execute(user_id).await?;
+    let projects = state.uc.project.get_all_by_user_id.execute(&check, &current_user, &client_ip).await?;
 
     Ok(SuccessResponse::with_data(200, projects))
 }
 
-async fn get_project_by_id_and_user_id(
+async fn get_project_by_id(
     Extension(current_user): Extension<UserFull>,
+    Extension(check): Extension<ResourceCheck>,
     State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
     Path(project_id): Path<String>
-) -> Result<SuccessResponse<ProjectWithOwnerEmail>, AppError> {
-    // Check authorization
-    state.rbac.check_access(&current_user.roles, "projects", "read").await?;
-    
-    let user_id = &current_user.user.id;
-
-    let project = state.uc.project.get_by_id_and_user_id.execute(&project_id, user_id).await?;
-
-    if project.user_id != Some(user_id.to_string()) {
-        return Ok(SuccessResponse::with_message(403, "You don't have access to this project"));
-    }
+) -> Result<SuccessResponse<Redacted<ProjectWithOwnerEmail>>, AppError> {
+    let project = state.uc.project.get_by_id.execute(&project_id, &check, &current_user, &client_ip).await?;
 
     Ok(SuccessResponse::with_data(200, project))
 }
 
 async fn create_project(
     Extension(current_user): Extension<UserFull>,
+    Extension(check): Extension<ResourceCheck>,
     State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
     Json(req): Json<CreateOrUpdateProject>
 ) -> Result<SuccessResponse<Project>, AppError> {
-    // Check authorization
-    state.rbac.check_access(&current_user.roles, "projects", "write").await?;
-    
-    let user_id = &current_user.user.id;
-
-    let project = state.uc.project.create_project.execute(user_id, req).await?;
+    let project = state.uc.project.create_project.execute(&check, &current_user, &client_ip, req).await?;
 
     Ok(SuccessResponse::with_data(201, project))
 }
 
 async fn update_project(
     Extension(current_user): Extension<UserFull>,
+    Extension(check): Extension<ResourceCheck>,
     State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
     Path(project_id): Path<String>,
     Json(req): Json<CreateOrUpdateProject>
-) -> Result<SuccessResponse<ProjectWithOwnerEmail>, AppError> {
-    // Check authorization
-    state.rbac.check_access(&current_user.roles, "projects", "write").await?;
-    
-    let user_id = &current_user.user.id;
-
-    let project = state.uc.project.get_by_id_and_user_id.execute(&project_id, user_id).await?;
-
-    if project.user_id != Some(user_id.to_string()) {
-        return Ok(SuccessResponse::with_message(403, "You don't have access to this project"));
-    }
-
-    let project = state.uc.project.update_project.execute(&project_id, user_id, req).await?;
+) -> Result<SuccessResponse<Redacted<ProjectWithOwnerEmail>>, AppError> {
+    let project = state.uc.project.update_project.execute(&project_id, &check, &current_user, &client_ip, req).await?;
 
     Ok(SuccessResponse::with_data(200, project))
 }
 
 async fn delete_project(
     Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    Extension(check): Extension<ResourceCheck>,
     State(state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
     Path(project_id): Path<String>,
 ) -> Result<SuccessResponse<Project>, AppError> {
-    // Check authorization
-    state.rbac.check_access(&current_user.roles, "projects", "delete").await?;
-    
-    let user_id = &current_user.user.id;
-
-    let project = state.uc.project.get_by_id_and_user_id.execute(&project_id, user_id).await?;
-
-    if project.user_id != Some(user_id.to_string()) {
-        return Ok(SuccessResponse::with_message(403, "You don't have access to this project"));
-    }
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
 
-    state.uc.project.delete_project.execute(&project_id, user_id).await?;
+    state.uc.project.delete_project.execute(&project_id, &check, &current_user, &client_ip).await?;
 
     Ok(SuccessResponse::with_code(200))
 }
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use axum::{
     extract::{ Path, Query, State },
     http::header,
-    response::IntoResponse,
+    response::{ Html, IntoResponse, Redirect },
     routing::{ get, post },
+    Form,
     Json,
     Router,
 };
@@ -16,6 +17,7 @@ use crate::{
             email_request::{ EmailLoginRequest, EmailRegisterRequest },
             oauth2_request::Oauth2Request,
             token_response::TokenResponse,
+            user_settings_dto::{ EmailChangeTokenQuery, RevertEmailChangeRequest },
         },
         state::AppState,
     },
@@ -24,6 +26,7 @@ use crate::{
         oauth2::constants::{ EMAIL_PROVIDER, GOOGLE_PROVIDER },
         utils::response::SuccessResponse,
     },
+    interface::{ api::saml_handler::setup_saml_routes, middleware::client_ip::ClientIp },
 };
 
 pub fn setup_public_oauth_handler() -> Router<Arc<AppState>> {
@@ -33,7 +36,10 @@ pub fn setup_public_oauth_handler() -> Router<Arc<AppState>> {
         .route("/{provider}/intercept", get(intercept_oauth_code))
         .route("/email/register", post(register_with_email))
         .route("/email/login", post(login_with_email))
+        .route("/email/change/confirm", get(confirm_email_change_page).post(confirm_email_change))
+        .route("/email/change/revert", get(revert_email_change_page).post(revert_email_change))
         .route("/refresh-token", get(refresh_token))
+        .nest("/saml", setup_saml_routes())
 }
 
 pub async fn get_oauth_url(
@@ -52,12 +58,14 @@ pub async fn get_oauth_url(
 pub async fn handle_oauth2_callback(
     State(app_state): State<Arc<AppState>>,
     Path(provider): Path<String>,
+    ClientIp(client_ip): ClientIp,
     Query(req): Query<Oauth2Request>
 ) -> Result<impl IntoResponse, AppError> {
     let (access_token, refresh_token) = app_state.uc.auth.oauth2_login.execute(
         &app_state.db_pool,
         provider.clone(),
-        req
+        req,
+        &client_ip
     ).await?;
 
     let mut access_cookie = Cookie::build(("access_token", access_token.clone()))
@@ -120,9 +128,14 @@ pub async fn intercept_oauth_code(Query(req): Query<Oauth2Request>) -> Result<
 
 pub async fn register_with_email(
     State(app_state): State<Arc<AppState>>,
+    ClientIp(client_ip): ClientIp,
     Json(req): Json<EmailRegisterRequest>
 ) -> Result<SuccessResponse<String>, AppError> {
-    let user = app_state.uc.auth.email_register.execute(&app_state.db_pool, req).await?;
+    let user = app_state.uc.auth.email_register.execute(
+        &app_state.db_pool,
+        req,
+        &client_ip
+    ).await?;
 
     Ok(SuccessResponse::with_data(200, user.id))
 }
@@ -226,3 +239,108 @@ pub async fn refresh_token(
 
     Ok(resp)
 }
+
+/*
+ * links from the email change mails open a page, only its form changes anything, so mail
+ * scanners and link prefetchers cannot confirm or revert on their own
+ */
+pub async fn confirm_email_change_page(Query(query): Query<EmailChangeTokenQuery>) -> Html<String> {
+    email_change_page(
+        "Confirm your new email address",
+        "Use this address to sign in from now on.",
+        "confirm",
+        &query.token,
+        false
+    )
+}
+
+pub async fn confirm_email_change(
+    State(app_state): State<Arc<AppState>>,
+    Form(req): Form<EmailChangeTokenQuery>
+) -> impl IntoResponse {
+    let outcome = match app_state.uc.user.confirm_email_change.execute(&req.token).await {
+        Ok(()) => "confirmed",
+        Err(AppError::UserEmailAlreadyExist) => "email_taken",
+        Err(err) => {
+            tracing::info!("[API:Oauth->confirm_email_change] {}", err);
+            "invalid"
+        }
+    };
+
+    Redirect::to(
+        &format!(
+            "{}/user/settings?email_change={}",
+            app_state.cfg.public_app_url.trim_end_matches('/'),
+            outcome
+        )
+    )
+}
+
+pub async fn revert_email_change_page(Query(query): Query<EmailChangeTokenQuery>) -> Html<String> {
+    email_change_page(
+        "Undo the email change",
+        "Your account keeps its previous address, every session is signed out and the password is replaced with the one below.",
+        "revert",
+        &query.token,
+        true
+    )
+}
+
+pub async fn revert_email_change(
+    State(app_state): State<Arc<AppState>>,
+    Form(req): Form<RevertEmailChangeRequest>
+) -> impl IntoResponse {
+    // every session was just revoked, so send the owner to the login page
+    let outcome = match app_state.uc.user.revert_email_change.execute(req).await {
+        Ok(()) => "reverted",
+        Err(AppError::ValidationError(_)) => "weak_password",
+        Err(err) => {
+            tracing::info!("[API:Oauth->revert_email_change] {}", err);
+            "invalid"
+        }
+    };
+
+    Redirect::to(
+        &format!(
+            "{}/login?email_change={}",
+            app_state.cfg.public_app_url.trim_end_matches('/'),
+            outcome
+        )
+    )
+}
+
+fn email_change_page(
+    title: &str,
+    description: &str,
+    action: &str,
+    token: &str,
+    asks_password: bool
+) -> Html<String> {
+    let password = if asks_password {
+        "<label>New password <input type=\"password\" name=\"new_password\" minlength=\"8\" required autocomplete=\"new-password\"></label>"
+    } else {
+        ""
+    };
+
+    Html(
+        format!(
+            "<!doctype html><html><head><meta charset=\"utf-8\"><meta name=\"robots\" content=\"noindex\"><title>{title}</title></head>\
+             <body><h1>{title}</h1><p>{description}</p>\
+             <form method=\"post\" action=\"{action}\"><input type=\"hidden\" name=\"token\" value=\"{token}\">{password}\
+             <button type=\"submit\">{title}</button></form></body></html>",
+            title = title,
+            description = description,
+            action = action,
+            token = escape_html(token),
+            password = password
+        )
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
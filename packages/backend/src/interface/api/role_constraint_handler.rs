@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use axum::{ extract::{ Path, State }, Extension, Json };
+
+use crate::{
+    application::{
+        dto::role_constraint::create_update_role_constraint_request::CreateOrUpdateRoleConstraint,
+        state::AppState,
+    },
+    domain::entities::{
+        role_constraint::{ RoleConstraint, RoleConstraintViolation },
+        user::UserFull,
+    },
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::{
+        domain::Domain,
+        permission::{ Access, GuardedRouter },
+        session_token::SessionToken,
+    },
+};
+
+const READ: Access = Access::domain("role-management", "read");
+const WRITE: Access = Access::domain("role-management", "write");
+
+pub fn setup_role_constraint_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_role_constraints, READ)
+        .post("/", create_role_constraint, WRITE)
+        .get("/violations", get_role_constraint_violations, READ)
+        .put("/{id}", update_role_constraint, WRITE)
+        .delete("/{id}", delete_role_constraint, WRITE)
+}
+
+async fn get_role_constraints(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain
+) -> Result<SuccessResponse<Vec<RoleConstraint>>, AppError> {
+    let constraints = state.uc.role_constraint.get_role_constraints.execute(&domain).await?;
+
+    Ok(SuccessResponse::with_data(200, constraints))
+}
+
+async fn get_role_constraint_violations(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain
+) -> Result<SuccessResponse<Vec<RoleConstraintViolation>>, AppError> {
+    let violations = state.uc.role_constraint.get_role_constraint_violations.execute(&domain).await?;
+
+    Ok(SuccessResponse::with_data(200, violations))
+}
+
+async fn create_role_constraint(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Json(req): Json<CreateOrUpdateRoleConstraint>
+) -> Result<SuccessResponse<RoleConstraint>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let constraint = state.uc.role_constraint.create_role_constraint.execute(&domain, req).await?;
+
+    Ok(SuccessResponse::with_data(201, constraint))
+}
+
+async fn update_role_constraint(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    Json(req): Json<CreateOrUpdateRoleConstraint>
+) -> Result<SuccessResponse<RoleConstraint>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let constraint = state.uc.role_constraint.update_role_constraint.execute(&domain, &id, req).await?;
+
+    Ok(SuccessResponse::with_data(200, constraint))
+}
+
+async fn delete_role_constraint(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    state.uc.role_constraint.delete_role_constraint.execute(&domain, &id).await?;
+
+    Ok(SuccessResponse::with_data(200, id))
+}
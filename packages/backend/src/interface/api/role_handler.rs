@@ -1,138 +1,281 @@
 use std::sync::Arc;
 
-use axum::{ extract::{ Path, Query, State }, middleware, routing::get, Extension, Json, Router };
+use axum::{ extract::{ Path, Query, State }, Extension, Json };
 
 use crate::{
     application::{
         dto::role::{
+            clone_role_request::CloneRoleRequest,
             create_update_role_request::CreateOrUpdateRole,
             get_role_request::RoleWithPermission,
+            role_template_request::InstantiateRoleTemplateRequest,
+            role_template_response::RoleTemplateWithInstances,
         },
         state::AppState,
     },
-    domain::entities::{ role::Role, user::UserFull },
+    domain::entities::{
+        role::Role,
+        role_policy_change::RolePolicyChange,
+        user::UserFull,
+        user_role::RoleMember,
+    },
     infra::{
         errors::app_error::AppError,
         utils::{ pagination::{ PaginatedResponse, PaginationQuery }, response::SuccessResponse },
     },
-    interface::middleware::auth_mw::is_authorized,
+    interface::middleware::{
+        domain::Domain,
+        permission::{ Access, GuardedRouter },
+        session_token::SessionToken,
+    },
 };
 
-pub fn setup_role_routes(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/all", get(get_all_roles))
-        .route("/", get(get_paginated_roles).post(create_role))
-        .route("/{id}", get(get_role_by_id).put(update_role).delete(delete_role))
-        .layer(middleware::from_fn_with_state(app_state.clone(), is_authorized))
+const READ: Access = Access::domain("role-management", "read");
+const WRITE: Access = Access::domain("role-management", "write");
+
+pub fn setup_role_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/all", get_all_roles, READ)
+        .get("/", get_paginated_roles, READ)
+        .post("/", create_role, WRITE)
+        .get("/templates", get_role_templates, READ)
+        .post("/templates/{key}", instantiate_role_template, WRITE)
+        .post("/templates/{key}/sync", sync_role_template, WRITE)
+        .get("/{id}", get_role_by_id, READ)
+        .put("/{id}", update_role, WRITE)
+        .delete("/{id}", delete_role, WRITE)
+        .post("/{id}/clone", clone_role, WRITE)
+        .get("/{id}/members", get_role_members, Access::domain("user-management", "read"))
+        .put("/{id}/parents/{parent_id}", add_role_parent, WRITE)
+        .delete("/{id}/parents/{parent_id}", remove_role_parent, WRITE)
+        .get("/{id}/history", get_role_policy_history, READ)
+        .post("/{id}/history/{version}/rollback", rollback_role_policy, WRITE)
 }
 
 async fn get_paginated_roles(
-    Extension(current_user): Extension<UserFull>,
     State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
     Query(query): Query<PaginationQuery>
 ) -> Result<SuccessResponse<PaginatedResponse<Role>>, AppError> {
-    let has_access = state.rbac.check_access(&current_user.roles, "role-management", "read").await?;
-
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
-
     let roles = state.uc.role.get_paginated_role.execute(
-        query.page.unwrap_or(1 as i64),
-        query.limit.unwrap_or(15 as i64)
+        &domain,
+        query.page.unwrap_or(1_i64),
+        query.limit.unwrap_or(15_i64)
     ).await?;
 
     Ok(SuccessResponse::with_data(200, roles))
 }
 
 async fn get_all_roles(
-    Extension(current_user): Extension<UserFull>,
-    State(state): State<Arc<AppState>>
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain
 ) -> Result<SuccessResponse<Vec<Role>>, AppError> {
-    let has_access = state.rbac.check_access(&current_user.roles, "role-management", "read").await?;
-
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
-
-    let roles = state.uc.role.get_all_role.execute().await?;
+    let roles = state.uc.role.get_all_role.execute(&domain).await?;
 
     Ok(SuccessResponse::with_data(200, roles))
 }
 
 async fn get_role_by_id(
-    Extension(current_user): Extension<UserFull>,
     State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
     Path(id): Path<String>
 ) -> Result<SuccessResponse<RoleWithPermission>, AppError> {
-    let has_access = state.rbac.check_access(&current_user.roles, "role-management", "read").await?;
-
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
-
-    let role = state.uc.role.get_role_by_id.execute(&id).await?;
+    let role = state.uc.role.get_role_by_id.execute(&domain, &id).await?;
 
     Ok(SuccessResponse::with_data(200, role))
 }
 
 async fn create_role(
     Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
     State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
     Json(req): Json<CreateOrUpdateRole>
 ) -> Result<SuccessResponse<String>, AppError> {
-    let has_access = state.rbac.check_access(
-        &current_user.roles,
-        "role-management",
-        "write"
-    ).await?;
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
 
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
+    let role = state.uc.role.create_role.execute(&state.db_pool, &current_user.user.id, &domain, req).await?;
+
+    Ok(SuccessResponse::with_data(200, role.id))
+}
 
-    let role = state.uc.role.create_role.execute(req).await?;
+async fn clone_role(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    Json(req): Json<CloneRoleRequest>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let role = state.uc.role.clone_role.execute(&state.db_pool, &current_user.user.id, &domain, &id, req).await?;
+
+    Ok(SuccessResponse::with_data(200, role.id))
+}
+
+async fn get_role_templates(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain
+) -> Result<SuccessResponse<Vec<RoleTemplateWithInstances>>, AppError> {
+    let templates = state.uc.role.get_role_templates.execute(&domain).await?;
+
+    Ok(SuccessResponse::with_data(200, templates))
+}
+
+async fn instantiate_role_template(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(key): Path<String>,
+    Json(req): Json<InstantiateRoleTemplateRequest>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let role = state.uc.role.instantiate_role_template.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &key,
+        req
+    ).await?;
 
     Ok(SuccessResponse::with_data(200, role.id))
 }
 
+// updates roles created from an earlier version of the template, returns the ones that changed
+async fn sync_role_template(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(key): Path<String>
+) -> Result<SuccessResponse<Vec<Role>>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let roles = state.uc.role.sync_role_template.execute(&state.db_pool, &current_user.user.id, &domain, &key).await?;
+
+    Ok(SuccessResponse::with_data(200, roles))
+}
+
 async fn update_role(
     Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
     State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
     Path(id): Path<String>,
     Json(req): Json<CreateOrUpdateRole>
 ) -> Result<SuccessResponse<String>, AppError> {
-    let has_access = state.rbac.check_access(
-        &current_user.roles,
-        "role-management",
-        "write"
-    ).await?;
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
 
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
-
-    state.uc.role.update_role_by_id.execute(&id, req).await?;
+    state.uc.role.update_role_by_id.execute(&state.db_pool, &current_user.user.id, &domain, &id, req).await?;
 
     Ok(SuccessResponse::with_data(200, id))
 }
 
 async fn delete_role(
     Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
     State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
     Path(id): Path<String>
 ) -> Result<SuccessResponse<String>, AppError> {
-    let has_access = state.rbac.check_access(
-        &current_user.roles,
-        "role-management",
-        "write"
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    state.uc.role.delete_role_by_id.execute(&state.db_pool, &current_user.user.id, &domain, &id).await?;
+
+    Ok(SuccessResponse::with_data(200, id))
+}
+
+async fn add_role_parent(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path((id, parent_id)): Path<(String, String)>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    state.uc.role.add_role_parent.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        &parent_id
     ).await?;
 
-    if !has_access {
-        return Err(AppError::Forbidden);
-    }
+    Ok(SuccessResponse::with_data(200, id))
+}
+
+async fn remove_role_parent(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path((id, parent_id)): Path<(String, String)>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
 
-    state.uc.role.delete_role_by_id.execute(&id).await?;
+    state.uc.role.remove_role_parent.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        &parent_id
+    ).await?;
 
     Ok(SuccessResponse::with_data(200, id))
 }
+
+async fn get_role_members(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    Query(query): Query<PaginationQuery>
+) -> Result<SuccessResponse<PaginatedResponse<RoleMember>>, AppError> {
+    let members = state.uc.user_role.get_role_members.execute(
+        &domain,
+        &id,
+        query.page.unwrap_or(1_i64),
+        query.limit.unwrap_or(15_i64)
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, members))
+}
+
+async fn get_role_policy_history(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>,
+    Query(query): Query<PaginationQuery>
+) -> Result<SuccessResponse<PaginatedResponse<RolePolicyChange>>, AppError> {
+    let history = state.uc.role.get_role_policy_history.execute(
+        &domain,
+        &id,
+        query.page.unwrap_or(1_i64),
+        query.limit.unwrap_or(15_i64)
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, history))
+}
+
+async fn rollback_role_policy(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path((id, version)): Path<(String, i32)>
+) -> Result<SuccessResponse<RolePolicyChange>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let change = state.uc.role.rollback_role_policy.execute(
+        &state.db_pool,
+        &current_user.user.id,
+        &domain,
+        &id,
+        version
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, change))
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{ Path, Query, State },
+    http::header,
+    response::{ IntoResponse, Redirect },
+    routing::{ get, post },
+    Form,
+    Router,
+};
+use axum_extra::extract::cookie::{ self, Cookie };
+
+use crate::{
+    application::{
+        dto::saml::saml_acs_request::{ SamlAcsRequest, SamlLoginQuery },
+        state::AppState,
+    },
+    infra::{
+        errors::app_error::AppError,
+        oauth2::constants::SAML_PROVIDER,
+        utils::response::SuccessResponse,
+    },
+    interface::middleware::client_ip::ClientIp,
+};
+
+pub fn setup_saml_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/{slug}/metadata", get(get_sp_metadata))
+        .route("/{slug}/get-url", get(get_saml_login_url))
+        .route("/{slug}/acs", post(handle_saml_acs))
+}
+
+pub async fn get_sp_metadata(
+    State(app_state): State<Arc<AppState>>,
+    Path(slug): Path<String>
+) -> Result<impl IntoResponse, AppError> {
+    let metadata = app_state.uc.saml.get_saml_metadata.execute(&slug).await?;
+
+    Ok(([(header::CONTENT_TYPE, "application/samlmetadata+xml")], metadata))
+}
+
+pub async fn get_saml_login_url(
+    State(app_state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Query(query): Query<SamlLoginQuery>
+) -> Result<SuccessResponse<String>, AppError> {
+    let url = app_state.uc.saml.get_saml_login_url.execute(&slug, query.redirect_to).await?;
+
+    Ok(SuccessResponse::with_data(200, url))
+}
+
+/*
+ * Assertion Consumer Service, the IdP makes the browser POST the SAMLResponse here
+ * for both SP-initiated and IdP-initiated logins
+ */
+pub async fn handle_saml_acs(
+    State(app_state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    ClientIp(client_ip): ClientIp,
+    Form(req): Form<SamlAcsRequest>
+) -> Result<impl IntoResponse, AppError> {
+    let (access_token, refresh_token, redirect_path) = app_state.uc.saml.saml_login.execute(
+        &app_state.db_pool,
+        &slug,
+        req,
+        &client_ip
+    ).await?;
+
+    let mut access_cookie = Cookie::build(("access_token", access_token))
+        .path("/")
+        .http_only(true)
+        .same_site(cookie::SameSite::Lax);
+
+    let mut refresh_cookie = Cookie::build(("refresh_token", refresh_token))
+        .path("/")
+        .http_only(true)
+        .same_site(cookie::SameSite::Lax);
+
+    let mut provider_cookie = Cookie::build(("provider", SAML_PROVIDER.to_string()))
+        .path("/")
+        .http_only(true)
+        .same_site(cookie::SameSite::Lax);
+
+    if &app_state.cfg.app_env != "local" {
+        access_cookie = access_cookie.secure(true);
+        refresh_cookie = refresh_cookie.secure(true);
+        provider_cookie = provider_cookie.secure(true);
+    }
+
+    let redirect_url = format!(
+        "{}{}",
+        app_state.cfg.public_app_url.trim_end_matches('/'),
+        redirect_path
+    );
+    let mut resp = Redirect::to(&redirect_url).into_response();
+
+    resp.headers_mut().append(header::SET_COOKIE, access_cookie.to_string().parse()?);
+    resp.headers_mut().append(header::SET_COOKIE, refresh_cookie.to_string().parse()?);
+    resp.headers_mut().append(header::SET_COOKIE, provider_cookie.to_string().parse()?);
+
+    Ok(resp)
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{ extract::{ Path, State }, Json };
+
+use crate::{
+    application::{
+        dto::saml::create_update_saml_provider_request::CreateOrUpdateSamlProvider,
+        state::AppState,
+    },
+    domain::entities::saml_identity_provider::SamlIdentityProvider,
+    infra::{ errors::app_error::AppError, utils::response::SuccessResponse },
+    interface::middleware::permission::{ Access, GuardedRouter },
+};
+
+const READ: Access = Access::global("saml-management", "read");
+const WRITE: Access = Access::global("saml-management", "write");
+
+pub fn setup_saml_provider_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/", get_all_saml_providers, READ)
+        .post("/", create_saml_provider, WRITE)
+        .get("/{id}", get_saml_provider_by_id, READ)
+        .put("/{id}", update_saml_provider, WRITE)
+        .delete("/{id}", delete_saml_provider, WRITE)
+}
+
+async fn get_all_saml_providers(
+    State(state): State<Arc<AppState>>
+) -> Result<SuccessResponse<Vec<SamlIdentityProvider>>, AppError> {
+    let providers = state.uc.saml.get_all_saml_provider.execute().await?;
+
+    Ok(SuccessResponse::with_data(200, providers))
+}
+
+async fn get_saml_provider_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<SamlIdentityProvider>, AppError> {
+    let provider = state.uc.saml.get_saml_provider_by_id.execute(&id).await?;
+
+    Ok(SuccessResponse::with_data(200, provider))
+}
+
+async fn create_saml_provider(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateOrUpdateSamlProvider>
+) -> Result<SuccessResponse<SamlIdentityProvider>, AppError> {
+    let provider = state.uc.saml.create_saml_provider.execute(req).await?;
+
+    Ok(SuccessResponse::with_data(201, provider))
+}
+
+async fn update_saml_provider(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateOrUpdateSamlProvider>
+) -> Result<SuccessResponse<SamlIdentityProvider>, AppError> {
+    let provider = state.uc.saml.update_saml_provider.execute(&id, req).await?;
+
+    Ok(SuccessResponse::with_data(200, provider))
+}
+
+async fn delete_saml_provider(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.saml.delete_saml_provider.execute(&id).await?;
+
+    Ok(SuccessResponse::with_data(200, id))
+}
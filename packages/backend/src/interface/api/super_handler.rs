@@ -1,32 +1,73 @@
 use std::sync::Arc;
 
-use axum::{extract::State, middleware, routing::post, Json, Router};
+use axum::{extract::State, http::HeaderMap, routing::post, Extension, Json};
+use validator::Validate;
 
 use crate::{
-    application::{dto::auth::email_request::EmailRegisterRequest, state::AppState},
+    application::{
+        dto::auth::{email_request::EmailRegisterRequest, super_admin_request::GrantSuperAdminRequest},
+        state::AppState,
+    },
+    domain::entities::user::UserFull,
     infra::{errors::app_error::AppError, utils::response::SuccessResponse},
-    interface::middleware::super_mw::is_super_user,
+    interface::middleware::{
+        permission::{Access, GuardedRouter},
+        session_token::SessionToken,
+    },
 };
 
-pub fn setup_super_handler(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/seed-super-user", post(seed_super_admin))
-        .layer(middleware::from_fn_with_state(
-            app_state.clone(),
-            is_super_user,
-        ))
+pub fn setup_super_handler(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        // first-run setup, only answers while no super admin exists
+        .public_route("/seed-super-user", post(seed_super_admin))
+        .post(
+            "/admins",
+            grant_super_admin,
+            Access::global("super-admin-management", "write"),
+        )
 }
 
 pub async fn seed_super_admin(
     State(app_state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(req): Json<EmailRegisterRequest>,
 ) -> Result<SuccessResponse<()>, AppError> {
+    let bootstrap_token = headers
+        .get("x-bootstrap-token")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
     app_state
         .uc
         .auth
         .seed_super_admin
-        .execute(&app_state.db_pool, req)
+        .execute(&app_state.db_pool, bootstrap_token, req)
         .await?;
 
     Ok(SuccessResponse::with_data(200, ()))
 }
+
+pub async fn grant_super_admin(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(app_state): State<Arc<AppState>>,
+    Json(req): Json<GrantSuperAdminRequest>,
+) -> Result<SuccessResponse<String>, AppError> {
+    req.validate()?;
+
+    app_state
+        .uc
+        .auth
+        .check_recent_auth
+        .execute(&current_user.user.id, &session_token)
+        .await?;
+
+    app_state
+        .uc
+        .auth
+        .grant_super_admin
+        .execute(&app_state.db_pool, &req.user_id)
+        .await?;
+
+    Ok(SuccessResponse::with_data(200, req.user_id))
+}
@@ -3,9 +3,7 @@ use std::sync::Arc;
 use axum::{
     extract::State,
     http::StatusCode,
-    routing::get,
-    middleware,
-    Extension, Json, Router,
+    Extension, Json,
 };
 
 use crate::{
@@ -15,24 +13,29 @@ use crate::{
     },
     domain::entities::user::UserFull,
     infra::{errors::app_error::AppError, utils::response::SuccessResponse},
-    interface::middleware::auth_mw::is_authorized,
+    interface::middleware::{
+        permission::{Access, GuardedRouter},
+        session_token::SessionToken,
+    },
 };
 
-pub fn setup_user_routes(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/settings", get(get_user_settings).put(update_user_settings))
-        // Authentication layer (runs first)
-        .layer(middleware::from_fn_with_state(app_state.clone(), is_authorized))
+// users always manage their own account, no permission is involved
+pub fn setup_user_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/settings", get_user_settings, Access::Authenticated)
+        .put("/settings", update_user_settings, Access::Authenticated)
 }
 
 pub async fn update_user_settings(
     State(app_state): State<Arc<AppState>>,
     Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
     Json(update_dto): Json<UserSettingsUpdateDto>,
 ) -> Result<SuccessResponse<UserSettingsDto>, AppError> {
-    // Check authorization
-    app_state.rbac.check_access(&current_user.roles, "user_settings", "write").await?;
-    
+    if update_dto.email.is_some() || update_dto.new_password.is_some() {
+        app_state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+    }
+
     let updated_user = app_state
         .uc
         .user
@@ -47,9 +50,6 @@ pub async fn get_user_settings(
     State(app_state): State<Arc<AppState>>,
     Extension(current_user): Extension<UserFull>,
 ) -> Result<SuccessResponse<UserSettingsDto>, AppError> {
-    // Check authorization
-    app_state.rbac.check_access(&current_user.roles, "user_settings", "read").await?;
-    
     let user_settings = app_state
         .uc
         .user
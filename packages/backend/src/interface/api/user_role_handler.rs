@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::{ extract::{ Path, State }, Extension, Json };
+
+use crate::{
+    application::{
+        dto::user_role::assign_user_role_request::AssignUserRoleRequest,
+        state::AppState,
+    },
+    domain::entities::{ access_context::AccessContext, role::AssignedRole, user::UserFull, user_role::UserRole },
+    infra::{ errors::app_error::AppError, rbac::GLOBAL_DOMAIN, utils::response::SuccessResponse },
+    interface::middleware::{
+        client_ip::ClientIp,
+        domain::Domain,
+        permission::{ Access, GuardedRouter },
+        session_token::SessionToken,
+    },
+};
+
+pub fn setup_user_role_routes(app_state: Arc<AppState>) -> GuardedRouter {
+    GuardedRouter::new(app_state)
+        .get("/{id}/roles", get_user_roles, Access::domain("user-management", "read"))
+        .put("/{id}/roles/{role_id}", assign_user_role, Access::domain("user-management", "write"))
+        .delete("/{id}/roles/{role_id}", unassign_user_role, Access::domain("user-management", "write"))
+}
+
+async fn get_user_roles(
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    Path(id): Path<String>
+) -> Result<SuccessResponse<Vec<AssignedRole>>, AppError> {
+    let roles = state.uc.user_role.get_user_roles.execute(&domain, &id).await?;
+
+    Ok(SuccessResponse::with_data(200, roles))
+}
+
+async fn assign_user_role(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    ClientIp(client_ip): ClientIp,
+    Path((id, role_id)): Path<(String, String)>,
+    req: Option<Json<AssignUserRoleRequest>>
+) -> Result<SuccessResponse<UserRole>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    // granting or revoking super admin needs the same right as /v1/super/admins
+    let manages_super_admins = state.rbac.check_access(
+        &current_user.roles,
+        GLOBAL_DOMAIN,
+        "super-admin-management",
+        "write",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    let user_role = state.uc.user_role.assign_user_role.execute(
+        &state.db_pool,
+        &domain,
+        &id,
+        &role_id,
+        req.map(|Json(req)| req).unwrap_or_default(),
+        manages_super_admins
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, user_role))
+}
+
+async fn unassign_user_role(
+    Extension(current_user): Extension<UserFull>,
+    SessionToken(session_token): SessionToken,
+    State(state): State<Arc<AppState>>,
+    Domain(domain): Domain,
+    ClientIp(client_ip): ClientIp,
+    Path((id, role_id)): Path<(String, String)>
+) -> Result<SuccessResponse<String>, AppError> {
+    state.uc.auth.check_recent_auth.execute(&current_user.user.id, &session_token).await?;
+
+    let manages_super_admins = state.rbac.check_access(
+        &current_user.roles,
+        GLOBAL_DOMAIN,
+        "super-admin-management",
+        "write",
+        &AccessContext::new(&current_user.user.id, Some(&client_ip))
+    ).await?;
+
+    state.uc.user_role.unassign_user_role.execute(
+        &state.db_pool,
+        &domain,
+        &id,
+        &role_id,
+        manages_super_admins
+    ).await?;
+
+    Ok(SuccessResponse::with_data(200, role_id))
+}
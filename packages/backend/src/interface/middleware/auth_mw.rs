@@ -11,7 +11,7 @@ use crate::{
     application::state::AppState,
     infra::{
         errors::app_error::AppError,
-        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER},
+        oauth2::constants::{EMAIL_PROVIDER, GOOGLE_PROVIDER, SAML_PROVIDER},
     },
 };
 
@@ -94,6 +94,32 @@ pub async fn is_authorized(
                 }
             }
         }
+        SAML_PROVIDER => {
+            let claims = app_state
+                .jwt_maker
+                .verify_access_token(&token.unwrap_or_default())
+                .map_err(|err| {
+                    tracing::info!(
+                        "[Middleware:Auth->is_authorized->SAML_PROVIDER] User is not authorized with error: {}",
+                        err
+                    );
+                    AppError::SessionExpired
+                })?;
+
+            match app_state.svc.redis.get_current_user(&claims.sub).await {
+                Ok(existing_current_user) => (true, existing_current_user),
+                Err(_) => {
+                    let current_user = app_state
+                        .svc
+                        .oauth
+                        .get_current_oauth_user_by_user_id(&provider, &claims.sub)
+                        .await
+                        .map_err(|err| AppError::UnauthorizedError(err.to_string()))?;
+
+                    (false, current_user)
+                }
+            }
+        }
         _ => {
             tracing::info!("[Middleware:Auth->is_authorized] User is not authorized because of Invalid Oauth Provider");
             return Err(AppError::UnauthorizedError(
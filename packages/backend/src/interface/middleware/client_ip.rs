@@ -0,0 +1,75 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+
+use crate::{
+    application::state::AppState,
+    infra::{errors::app_error::AppError, rbac::ip_in_range},
+};
+
+/// Address of the caller, used for per-IP limits.
+pub struct ClientIp(pub String);
+
+impl FromRequestParts<Arc<AppState>> for ClientIp {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let Some(peer) = peer else {
+            return Ok(ClientIp("unknown".to_string()));
+        };
+
+        // anyone can send these headers, they only count when a known proxy set them
+        let trusted = |ip: &str| is_trusted_proxy(ip, &state.cfg.trusted_proxies);
+        if state.cfg.trust_proxy_headers && trusted(&peer.to_string()) {
+            // proxies append the address they saw, the right-most one not added by a trusted
+            // proxy is the client
+            let forwarded = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| {
+                    value
+                        .rsplit(',')
+                        .map(str::trim)
+                        .filter(|ip| ip.parse::<IpAddr>().is_ok())
+                        .find(|ip| !trusted(ip))
+                })
+                .or_else(|| {
+                    parts
+                        .headers
+                        .get("x-real-ip")
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::trim)
+                        .filter(|ip| ip.parse::<IpAddr>().is_ok())
+                });
+
+            if let Some(ip) = forwarded {
+                return Ok(ClientIp(ip.to_string()));
+            }
+        }
+
+        Ok(ClientIp(peer.to_string()))
+    }
+}
+
+fn is_trusted_proxy(ip: &str, trusted_proxies: &str) -> bool {
+    ip.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+        || trusted_proxies
+            .split(',')
+            .map(str::trim)
+            .filter(|range| !range.is_empty())
+            .any(|range| ip_in_range(ip, range))
+}
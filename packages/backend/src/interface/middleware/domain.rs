@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+
+use crate::{
+    application::state::AppState,
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+/// Organization the request acts in, taken from `x-organization-id`, global when absent.
+pub struct Domain(pub String);
+
+impl Domain {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let domain = headers
+            .get("x-organization-id")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| GLOBAL_DOMAIN.to_string());
+
+        Domain(domain)
+    }
+}
+
+impl FromRequestParts<Arc<AppState>> for Domain {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Domain::from_headers(&parts.headers))
+    }
+}
@@ -1,2 +1,5 @@
 pub mod auth_mw;
-pub mod super_mw;
+pub mod client_ip;
+pub mod domain;
+pub mod permission;
+pub mod session_token;
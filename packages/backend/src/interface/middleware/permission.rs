@@ -0,0 +1,281 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    handler::Handler,
+    http::{request::Parts, Method},
+    middleware::{self, Next},
+    response::Response,
+    routing::{on, MethodFilter, MethodRouter},
+    Router,
+};
+use tracing::error;
+
+use crate::{
+    application::state::AppState,
+    domain::entities::{
+        access_context::{AccessContext, ResourceCheck},
+        user::UserFull,
+    },
+    infra::{errors::app_error::AppError, rbac::GLOBAL_DOMAIN},
+};
+
+use super::{auth_mw::is_authorized, client_ip::ClientIp, domain::Domain};
+
+/// Domain a required permission is checked in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// The organization from `x-organization-id`, global when absent.
+    Domain,
+    /// Always the global domain.
+    Global,
+}
+
+/// What an authenticated route requires on top of a signed in user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Self-service routes that only act on the caller's own data.
+    Authenticated,
+    Permission {
+        object: &'static str,
+        action: &'static str,
+        scope: Scope,
+    },
+    /// Permission whose policies depend on the resource, such as its owner. The handler receives
+    /// a `ResourceCheck` to check with once the resource is loaded, writes to the resource need
+    /// the `ResourceGrant` the check hands out, and a successful response from a handler that
+    /// never checked is refused.
+    Resource {
+        object: &'static str,
+        action: &'static str,
+        scope: Scope,
+    },
+}
+
+impl Access {
+    pub const fn domain(object: &'static str, action: &'static str) -> Self {
+        Access::Permission {
+            object,
+            action,
+            scope: Scope::Domain,
+        }
+    }
+
+    pub const fn global(object: &'static str, action: &'static str) -> Self {
+        Access::Permission {
+            object,
+            action,
+            scope: Scope::Global,
+        }
+    }
+
+    pub const fn resource_global(object: &'static str, action: &'static str) -> Self {
+        Access::Resource {
+            object,
+            action,
+            scope: Scope::Global,
+        }
+    }
+}
+
+/// A route and the access it declared.
+#[derive(Clone, Debug)]
+pub struct RouteAccess {
+    pub method: Method,
+    pub path: String,
+    pub access: Access,
+}
+
+/// Router where every authenticated route states its required access, checked before the handler
+/// runs. Public routes are added separately and skip authentication.
+pub struct GuardedRouter {
+    app_state: Arc<AppState>,
+    protected: Router<Arc<AppState>>,
+    public: Router<Arc<AppState>>,
+    has_public: bool,
+    routes: Vec<RouteAccess>,
+}
+
+impl GuardedRouter {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self {
+            app_state,
+            protected: Router::new(),
+            public: Router::new(),
+            has_public: false,
+            routes: Vec::new(),
+        }
+    }
+
+    pub fn route<H, T>(mut self, method: Method, path: &str, handler: H, access: Access) -> Self
+    where
+        H: Handler<T, Arc<AppState>>,
+        T: 'static,
+    {
+        let filter = MethodFilter::try_from(method.clone())
+            .unwrap_or_else(|_| panic!("Unsupported method {} for {}", method, path));
+        let method_router = on(filter, handler).route_layer(middleware::from_fn_with_state(
+            (self.app_state.clone(), access),
+            require_access,
+        ));
+
+        self.protected = self.protected.route(path, method_router);
+        self.routes.push(RouteAccess {
+            method,
+            path: path.to_string(),
+            access,
+        });
+        self
+    }
+
+    pub fn get<H, T>(self, path: &str, handler: H, access: Access) -> Self
+    where
+        H: Handler<T, Arc<AppState>>,
+        T: 'static,
+    {
+        self.route(Method::GET, path, handler, access)
+    }
+
+    pub fn post<H, T>(self, path: &str, handler: H, access: Access) -> Self
+    where
+        H: Handler<T, Arc<AppState>>,
+        T: 'static,
+    {
+        self.route(Method::POST, path, handler, access)
+    }
+
+    pub fn put<H, T>(self, path: &str, handler: H, access: Access) -> Self
+    where
+        H: Handler<T, Arc<AppState>>,
+        T: 'static,
+    {
+        self.route(Method::PUT, path, handler, access)
+    }
+
+    pub fn delete<H, T>(self, path: &str, handler: H, access: Access) -> Self
+    where
+        H: Handler<T, Arc<AppState>>,
+        T: 'static,
+    {
+        self.route(Method::DELETE, path, handler, access)
+    }
+
+    // reachable without signing in, such as login or first-run setup
+    pub fn public_route(mut self, path: &str, method_router: MethodRouter<Arc<AppState>>) -> Self {
+        self.public = self.public.route(path, method_router);
+        self.has_public = true;
+        self
+    }
+
+    pub fn nest(mut self, prefix: &str, other: GuardedRouter) -> Self {
+        if !other.routes.is_empty() {
+            self.protected = self.protected.nest(prefix, other.protected);
+        }
+        if other.has_public {
+            self.public = self.public.nest(prefix, other.public);
+            self.has_public = true;
+        }
+
+        self.routes.extend(other.routes.into_iter().map(|route| RouteAccess {
+            path: match route.path.as_str() {
+                "/" => prefix.to_string(),
+                path => format!("{}{}", prefix, path),
+            },
+            ..route
+        }));
+        self
+    }
+
+    pub fn routes(&self) -> &[RouteAccess] {
+        &self.routes
+    }
+
+    // `resource:action` permissions named by routes but missing from the registry
+    pub fn unregistered_permissions(&self, registry: &HashMap<String, Vec<String>>) -> Vec<String> {
+        let mut unknown = self
+            .routes
+            .iter()
+            .filter_map(|route| match route.access {
+                Access::Permission { object, action, .. } | Access::Resource { object, action, .. }
+                    if !registry
+                        .get(object)
+                        .is_some_and(|actions| actions.iter().any(|known| known == action)) =>
+                {
+                    Some(format!("{}:{} ({} {})", object, action, route.method, route.path))
+                }
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        unknown.sort();
+        unknown
+    }
+
+    pub fn into_router(self) -> Router<Arc<AppState>> {
+        self.protected
+            .layer(middleware::from_fn_with_state(self.app_state, is_authorized))
+            .merge(self.public)
+    }
+}
+
+// denies unless the route's access is met, including when no user was authenticated
+async fn require_access(
+    State((state, access)): State<(Arc<AppState>, Access)>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let (mut parts, body) = req.into_parts();
+    let Some(current_user) = parts.extensions.get::<UserFull>() else {
+        return Err(AppError::Unauthorized);
+    };
+
+    if let Access::Resource {
+        object,
+        action,
+        scope,
+    } = access
+    {
+        let check = ResourceCheck::new(object, action, scope_domain(scope, &parts));
+        parts.extensions.insert(check.clone());
+
+        let response = next.run(Request::from_parts(parts, body)).await;
+        if response.status().is_success() && !check.is_checked() {
+            error!(
+                "Handler for {}:{} answered without checking the resource, refusing the response",
+                object, action
+            );
+            return Err(AppError::Forbidden);
+        }
+
+        return Ok(response);
+    }
+
+    if let Access::Permission {
+        object,
+        action,
+        scope,
+    } = access
+    {
+        let domain = scope_domain(scope, &parts);
+        let roles = current_user.roles.clone();
+        let user_id = current_user.user.id.clone();
+        let ClientIp(ip) = ClientIp::from_request_parts(&mut parts, &state).await?;
+
+        let has_access = state
+            .rbac
+            .check_access(&roles, &domain, object, action, &AccessContext::new(&user_id, Some(&ip)))
+            .await?;
+
+        if !has_access {
+            return Err(AppError::Forbidden);
+        }
+    }
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+fn scope_domain(scope: Scope, parts: &Parts) -> String {
+    match scope {
+        Scope::Global => GLOBAL_DOMAIN.to_string(),
+        Scope::Domain => Domain::from_headers(&parts.headers).0,
+    }
+}
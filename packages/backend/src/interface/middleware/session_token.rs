@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use axum_extra::extract::CookieJar;
+
+use crate::{application::state::AppState, infra::errors::app_error::AppError};
+
+/// Refresh token of the caller's session, tells apart sessions of the same user.
+pub struct SessionToken(pub String);
+
+impl FromRequestParts<Arc<AppState>> for SessionToken {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let jar = CookieJar::from_request_parts(parts, state)
+            .await
+            .unwrap_or_default();
+
+        Ok(SessionToken(
+            jar.get("refresh_token")
+                .map(|cookie| cookie.value().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+}
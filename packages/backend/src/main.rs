@@ -23,5 +23,12 @@ async fn main() {
 
     let server = ServerBuilder::new(Arc::new(cfg));
 
+    // `check-policies [--repair]` reports drift between roles and casbin rules instead of serving
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.get(1).map(String::as_str) == Some("check-policies") {
+        let consistent = server.check_policies(args.iter().any(|arg| arg == "--repair")).await;
+        std::process::exit(if consistent { 0 } else { 1 });
+    }
+
     server.run().await;
 }
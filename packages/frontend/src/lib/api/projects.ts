@@ -2,7 +2,8 @@ import { makeRequest } from "./request";
 
 export interface Project {
   id?: string;
-  user_email: string;
+  // left out when the caller may not see the owner's email
+  user_email?: string;
   name: string;
   description?: string;
   created_at?: string;